@@ -1,6 +1,7 @@
 use crate::image::ImageGenerationModel;
 use crate::{completion::CompletionModel, embedding::EmbeddingModel};
 use aws_config::{BehaviorVersion, Region};
+use aws_sdk_bedrockruntime::config::Credentials;
 use rig::impl_conversion_traits;
 use rig::prelude::*;
 
@@ -60,11 +61,41 @@ impl From<aws_sdk_bedrockruntime::Client> for Client {
 impl Client {}
 
 impl ProviderClient for Client {
+    /// Create a new Bedrock client from the `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY` and
+    /// `AWS_REGION` environment variables (`AWS_SESSION_TOKEN` is used if set).
+    /// `AWS_DEFAULT_REGION` is used as a fallback for the region, then [DEFAULT_AWS_REGION].
+    /// Panics if the credentials environment variables are not set.
+    ///
+    /// To use the full AWS credentials chain (profiles, SSO, IMDS, etc.), use [ClientBuilder] instead.
     fn from_env() -> Self
     where
         Self: Sized,
     {
-        panic!("You should not call from_env to build a Bedrock client");
+        let access_key_id = std::env::var("AWS_ACCESS_KEY_ID").expect("AWS_ACCESS_KEY_ID not set");
+        let secret_access_key =
+            std::env::var("AWS_SECRET_ACCESS_KEY").expect("AWS_SECRET_ACCESS_KEY not set");
+        let session_token = std::env::var("AWS_SESSION_TOKEN").ok();
+        let region = std::env::var("AWS_REGION")
+            .or_else(|_| std::env::var("AWS_DEFAULT_REGION"))
+            .unwrap_or_else(|_| DEFAULT_AWS_REGION.to_string());
+
+        let credentials = Credentials::new(
+            access_key_id,
+            secret_access_key,
+            session_token,
+            None,
+            "rig-bedrock-env",
+        );
+
+        let config = aws_sdk_bedrockruntime::Config::builder()
+            .behavior_version(BehaviorVersion::latest())
+            .region(Region::new(region))
+            .credentials_provider(credentials)
+            .build();
+
+        Client {
+            aws_client: aws_sdk_bedrockruntime::Client::from_conf(config),
+        }
     }
 }
 
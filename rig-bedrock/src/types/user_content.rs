@@ -31,10 +31,13 @@ impl TryFrom<aws_bedrock::ContentBlock> for RigUserContent {
                 let tool_results = OneOrMany::many(tool_result_contents).map_err(|_| {
                     CompletionError::ProviderError("ToolResult returned invalid response".into())
                 })?;
-                Ok(RigUserContent(UserContent::ToolResult(ToolResult {
-                    id: tool_result.tool_use_id,
-                    content: tool_results,
-                })))
+                let tool_result = match tool_result.status {
+                    Some(aws_bedrock::ToolResultStatus::Error) => {
+                        ToolResult::error(tool_result.tool_use_id, tool_results)
+                    }
+                    _ => ToolResult::new(tool_result.tool_use_id, tool_results),
+                };
+                Ok(RigUserContent(UserContent::ToolResult(tool_result)))
             }
             aws_bedrock::ContentBlock::Document(document) => {
                 let doc: RigDocument = document.try_into()?;
@@ -60,6 +63,11 @@ impl TryFrom<RigUserContent> for Vec<aws_bedrock::ContentBlock> {
             UserContent::ToolResult(tool_result) => {
                 let builder = aws_bedrock::ToolResultBlock::builder()
                     .tool_use_id(tool_result.id)
+                    .set_status(
+                        tool_result
+                            .is_error
+                            .then_some(aws_bedrock::ToolResultStatus::Error),
+                    )
                     .set_content(Some(
                         tool_result
                             .content
@@ -79,6 +79,17 @@ where
     <M as CompletionModel>::StreamingResponse: std::marker::Send,
 {
     let prompt: Message = prompt.into();
+    // The text of the prompt, which determines the tools available to the model
+    let rag_text = match &prompt {
+        Message::User { content } => content
+            .iter()
+            .find_map(|content| match content {
+                UserContent::Text(text) => Some(text.text.clone()),
+                _ => None,
+            })
+            .unwrap_or_default(),
+        Message::Assistant { .. } => String::new(),
+    };
 
     (Box::pin(async_stream::stream! {
         let mut current_prompt = prompt;
@@ -104,7 +115,7 @@ where
                     },
                     Ok(AssistantContent::ToolCall(tool_call)) => {
                         let tool_result =
-                            agent.tools.call(&tool_call.function.name, tool_call.function.arguments.to_string()).await?;
+                            agent.tools.call_with_prompt(&tool_call.function.name, tool_call.function.arguments.to_string(), &rag_text).await?;
 
                         let tool_call_msg = AssistantContent::ToolCall(tool_call.clone());
 
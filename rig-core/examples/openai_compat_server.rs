@@ -0,0 +1,25 @@
+use rig::prelude::*;
+use rig::{providers::openai, server::OpenAICompatHandler};
+
+#[tokio::main]
+async fn main() -> Result<(), anyhow::Error> {
+    // Create OpenAI client
+    let client = openai::Client::from_env();
+
+    // Create the agent to serve
+    let comedian_agent = client
+        .agent(openai::GPT_4O)
+        .preamble("You are a comedian here to entertain the user using humour and jokes.")
+        .build();
+
+    // Serve the agent at http://localhost:3000/v1/chat/completions, e.g.:
+    // curl http://localhost:3000/v1/chat/completions -H "Content-Type: application/json" \
+    //     -d '{"messages": [{"role": "user", "content": "Entertain me!"}], "stream": true}'
+    let app = OpenAICompatHandler::new(comedian_agent).router();
+
+    let listener = tokio::net::TcpListener::bind("0.0.0.0:3000").await?;
+    println!("Listening on {}", listener.local_addr()?);
+    axum::serve(listener, app).await?;
+
+    Ok(())
+}
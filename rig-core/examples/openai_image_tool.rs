@@ -0,0 +1,95 @@
+use rig::prelude::*;
+use rig::{
+    completion::{Prompt, ToolDefinition},
+    image_generation::ImageGenerationError,
+    providers::openai::{
+        self,
+        images::{ImageClient, ImageGenerationConfig, ImageStyle},
+    },
+    tool::Tool,
+};
+use serde::Deserialize;
+use serde_json::json;
+
+#[derive(Deserialize)]
+struct GenerateImageArgs {
+    prompt: String,
+    #[serde(default)]
+    natural: bool,
+}
+
+/// A tool generating images with DALL-E 3, returning the URL of the generated image.
+struct GenerateImage {
+    images: ImageClient,
+}
+
+impl Tool for GenerateImage {
+    const NAME: &'static str = "generate_image";
+    type Error = ImageGenerationError;
+    type Args = GenerateImageArgs;
+    type Output = String;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: "Generate an image from a detailed description, and return its URL"
+                .to_string(),
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "prompt": {
+                        "type": "string",
+                        "description": "Detailed description of the image"
+                    },
+                    "natural": {
+                        "type": "boolean",
+                        "description": "Whether the image should look natural rather than dramatic"
+                    }
+                },
+                "required": ["prompt"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let style = if args.natural {
+            ImageStyle::Natural
+        } else {
+            ImageStyle::Vivid
+        };
+
+        let response = self
+            .images
+            .generate(&args.prompt, ImageGenerationConfig::default().style(style))
+            .await?;
+        println!("[tool-call] Revised prompt: {:?}", response.revised_prompt);
+
+        response
+            .url
+            .ok_or_else(|| ImageGenerationError::ResponseError("No image URL".to_string()))
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), anyhow::Error> {
+    let openai = openai::Client::from_env();
+
+    let illustrator = openai
+        .agent(openai::GPT_4O)
+        .preamble(
+            "You are an illustrator. When asked for an illustration, generate it with the \
+             `generate_image` tool and answer with the URL of the image.",
+        )
+        .tool(GenerateImage {
+            images: ImageClient::new(&openai),
+        })
+        .build();
+
+    let response = illustrator
+        .prompt("Illustrate a castle sitting upon a large mountain, overlooking the water.")
+        .multi_turn(2)
+        .await?;
+    println!("Illustrator: {response}");
+
+    Ok(())
+}
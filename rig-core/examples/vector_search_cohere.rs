@@ -22,9 +22,10 @@ async fn main() -> Result<(), anyhow::Error> {
     // Create Cohere client
     let cohere_api_key = env::var("COHERE_API_KEY").expect("COHERE_API_KEY not set");
     let cohere_client = Client::new(&cohere_api_key);
-    let document_model = cohere_client.embedding_model(EMBED_ENGLISH_V3, "search_document");
-    let search_model = cohere_client.embedding_model(EMBED_ENGLISH_V3, "search_query");
-    let embeddings = EmbeddingsBuilder::new(document_model.clone())
+    // The input type is routed automatically: `search_document` when embedding the documents
+    // and `search_query` when embedding the query of the vector store index
+    let model = cohere_client.embedding_model(EMBED_ENGLISH_V3, None);
+    let embeddings = EmbeddingsBuilder::new(model.clone())
         .documents(vec![
             WordDefinition {
                 id: "doc0".to_string(),
@@ -59,7 +60,7 @@ async fn main() -> Result<(), anyhow::Error> {
         InMemoryVectorStore::from_documents_with_id_f(embeddings, |doc| doc.id.clone());
 
     // Create vector store index
-    let index = vector_store.index(search_model);
+    let index = vector_store.index(model);
     let results = index
         .top_n::<WordDefinition>(
             "Which instrument is found in the Nebulon Mountain Ranges?",
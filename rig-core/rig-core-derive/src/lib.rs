@@ -140,8 +140,98 @@ fn get_json_type(ty: &Type) -> proc_macro2::TokenStream {
     }
 }
 
+/// Returns the inner type of `Option<T>`, or `None` if the type is not an `Option`.
+fn option_inner_type(ty: &Type) -> Option<&Type> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) => match args.args.first() {
+            Some(syn::GenericArgument::Type(inner_type)) => Some(inner_type),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Whether the JSON schema of the type can be written directly (i.e.: primitive types and
+/// vectors of primitive types), rather than generated with `schemars`.
+fn is_primitive_type(ty: &Type) -> bool {
+    let Type::Path(type_path) = ty else {
+        return false;
+    };
+    let segment = &type_path.path.segments[0];
+    match segment.ident.to_string().as_str() {
+        "i8" | "i16" | "i32" | "i64" | "u8" | "u16" | "u32" | "u64" | "f32" | "f64" | "String"
+        | "str" | "bool" => true,
+        "Vec" => match &segment.arguments {
+            PathArguments::AngleBracketed(args) => match args.args.first() {
+                Some(syn::GenericArgument::Type(inner_type)) => is_primitive_type(inner_type),
+                _ => true,
+            },
+            _ => true,
+        },
+        _ => false,
+    }
+}
+
+/// Generates the expression building the JSON schema of a parameter.
+/// Non-primitive types (e.g.: structs deriving `serde::Deserialize`) must implement
+/// `schemars::JsonSchema`: their schema is generated by the `generator` of the tool definition,
+/// which collects the definitions they refer to (added to the schema of the parameters).
+fn get_json_schema(ty: &Type, description: &str) -> proc_macro2::TokenStream {
+    if is_primitive_type(ty) {
+        let json_type = get_json_type(ty);
+        quote! {
+            serde_json::json!({
+                #json_type,
+                "description": #description
+            })
+        }
+    } else {
+        quote! {
+            {
+                let mut schema = serde_json::to_value(generator.subschema_for::<#ty>())
+                    .expect("JSON schemas should be serializable");
+                schema["description"] = serde_json::json!(#description);
+                schema
+            }
+        }
+    }
+}
+
+/// Extracts the doc comments of a function, joined by newlines.
+fn get_doc_comments(attrs: &[syn::Attribute]) -> Option<String> {
+    let lines = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(nv) => match &nv.value {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(lit_str),
+                    ..
+                }) => Some(lit_str.value().trim().to_string()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    let doc = lines.join("\n").trim().to_string();
+    (!doc.is_empty()).then_some(doc)
+}
+
 /// A procedural macro that transforms a function into a `rig::tool::Tool` that can be used with a `rig::agent::Agent`.
 ///
+/// The description of the tool is taken from the `description` argument if provided, or from the
+/// doc comments of the function otherwise. Parameters of non-primitive types (e.g.: structs
+/// deriving `serde::Deserialize`) must also derive `schemars::JsonSchema`, which is used to
+/// generate their JSON schema. `Option` parameters are not required.
+///
 /// # Examples
 ///
 /// Basic usage:
@@ -170,6 +260,23 @@ fn get_json_type(ty: &Type) -> proc_macro2::TokenStream {
 /// }
 /// ```
 ///
+/// With doc comments and a struct parameter:
+/// ```rust
+/// use rig_derive::rig_tool;
+///
+/// #[derive(serde::Deserialize, schemars::JsonSchema)]
+/// struct Point {
+///     x: f64,
+///     y: f64,
+/// }
+///
+/// /// Compute the distance between two points
+/// #[rig_tool]
+/// fn distance(a: Point, b: Point) -> Result<f64, rig::tool::ToolError> {
+///     Ok(((a.x - b.x).powi(2) + (a.y - b.y).powi(2)).sqrt())
+/// }
+/// ```
+///
 /// With parameter descriptions:
 /// ```rust
 /// use rig_derive::rig_tool;
@@ -241,8 +348,11 @@ pub fn rig_tool(args: TokenStream, input: TokenStream) -> TokenStream {
     // Generate PascalCase struct name from the function name
     let struct_name = format_ident!("{}", { fn_name_str.to_case(Case::Pascal) });
 
-    // Use provided description or generate a default one
-    let tool_description = match args.description {
+    // Use provided description, or the doc comments of the function, or generate a default one
+    let tool_description = match args
+        .description
+        .or_else(|| get_doc_comments(&input_fn.attrs))
+    {
         Some(desc) => quote! { #desc.to_string() },
         None => quote! { format!("Function to {}", Self::NAME) },
     };
@@ -250,8 +360,8 @@ pub fn rig_tool(args: TokenStream, input: TokenStream) -> TokenStream {
     // Extract parameter names, types, and descriptions
     let mut param_names = Vec::new();
     let mut param_types = Vec::new();
-    let mut param_descriptions = Vec::new();
-    let mut json_types = Vec::new();
+    let mut json_schemas = Vec::new();
+    let mut required_params = Vec::new();
 
     for arg in input_fn.sig.inputs.iter() {
         if let syn::FnArg::Typed(pat_type) = arg {
@@ -266,10 +376,18 @@ pub fn rig_tool(args: TokenStream, input: TokenStream) -> TokenStream {
                     .map(|s| s.to_owned())
                     .unwrap_or(default_parameter_description);
 
+                // Optional parameters are not required, and described by their inner type
+                let schema_type = match option_inner_type(ty) {
+                    Some(inner_type) => inner_type,
+                    None => {
+                        required_params.push(param_name_str.clone());
+                        ty
+                    }
+                };
+
                 param_names.push(param_name);
                 param_types.push(ty);
-                param_descriptions.push(description);
-                json_types.push(get_json_type(ty));
+                json_schemas.push(get_json_schema(schema_type, &description));
             }
         }
     }
@@ -315,18 +433,25 @@ pub fn rig_tool(args: TokenStream, input: TokenStream) -> TokenStream {
             }
 
             async fn definition(&self, _prompt: String) -> rig::completion::ToolDefinition {
-                let parameters = serde_json::json!({
+                let mut generator = rig::schemars::gen::SchemaGenerator::default();
+                let mut properties = serde_json::Map::new();
+                #(
+                    properties.insert(stringify!(#param_names).to_string(), #json_schemas);
+                )*
+
+                let mut parameters = serde_json::json!({
                     "type": "object",
-                    "properties": {
-                        #(
-                            stringify!(#param_names): {
-                                #json_types,
-                                "description": #param_descriptions
-                            }
-                        ),*
-                    }
+                    "properties": properties,
+                    "required": [#(#required_params),*]
                 });
 
+                // The `$ref`s of the schemas of the parameters point to these definitions
+                let definitions = generator.take_definitions();
+                if !definitions.is_empty() {
+                    parameters["definitions"] = serde_json::to_value(definitions)
+                        .expect("JSON schemas should be serializable");
+                }
+
                 rig::completion::ToolDefinition {
                     name: #fn_name_str.to_string(),
                     description: #tool_description.to_string(),
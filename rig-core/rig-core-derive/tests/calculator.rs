@@ -143,3 +143,103 @@ async fn test_calculator_tool() {
 
     assert_eq!(result, serde_json::json!(8));
 }
+
+#[derive(serde::Deserialize, schemars::JsonSchema)]
+struct Point {
+    x: f64,
+    y: f64,
+}
+
+#[derive(serde::Deserialize, schemars::JsonSchema)]
+struct Segment {
+    start: Point,
+    end: Point,
+}
+
+/// Compute the length of a segment
+#[rig_tool]
+fn length(segment: Segment) -> Result<f64, rig::tool::ToolError> {
+    distance(segment.start, segment.end, None)
+}
+
+/// Compute the distance between two points,
+/// optionally rounded to the given number of decimals
+#[rig_tool(params(a = "First point", b = "Second point"))]
+fn distance(a: Point, b: Point, decimals: Option<u32>) -> Result<f64, rig::tool::ToolError> {
+    let distance = ((a.x - b.x).powi(2) + (a.y - b.y).powi(2)).sqrt();
+    Ok(match decimals {
+        Some(decimals) => {
+            let factor = 10f64.powi(decimals as i32);
+            (distance * factor).round() / factor
+        }
+        None => distance,
+    })
+}
+
+#[tokio::test]
+async fn test_doc_comments_and_schemas() {
+    let definition = Distance.definition(String::default()).await;
+
+    assert_eq!(
+        definition.description,
+        "Compute the distance between two points,\noptionally rounded to the given number of decimals"
+    );
+    assert_eq!(
+        definition.parameters["required"],
+        serde_json::json!(["a", "b"])
+    );
+    assert_eq!(
+        definition.parameters["properties"]["a"]["description"],
+        "First point"
+    );
+    assert_eq!(
+        definition.parameters["properties"]["a"]["$ref"],
+        "#/definitions/Point"
+    );
+    assert_eq!(
+        definition.parameters["definitions"]["Point"]["properties"]["x"]["type"],
+        "number"
+    );
+    assert_eq!(
+        definition.parameters["properties"]["decimals"]["type"],
+        "number"
+    );
+
+    let result = Distance
+        .call(DistanceParameters {
+            a: Point { x: 0.0, y: 0.0 },
+            b: Point { x: 1.0, y: 1.0 },
+            decimals: Some(2),
+        })
+        .await
+        .unwrap();
+    assert_eq!(result, serde_json::json!(1.41));
+}
+
+#[tokio::test]
+async fn test_nested_schemas() {
+    let definition = Length.definition(String::default()).await;
+
+    // Every `$ref` points to a definition of the parameters
+    assert_eq!(
+        definition.parameters["properties"]["segment"]["$ref"],
+        "#/definitions/Segment"
+    );
+    let segment = &definition.parameters["definitions"]["Segment"];
+    assert_eq!(
+        segment["properties"]["start"]["$ref"],
+        "#/definitions/Point"
+    );
+    assert!(definition.parameters["definitions"]["Point"].is_object());
+
+    let result = Length
+        .call(LengthParameters {
+            segment: Segment {
+                start: Point { x: 0.0, y: 0.0 },
+                end: Point { x: 3.0, y: 4.0 },
+            },
+        })
+        .await
+        .unwrap();
+    assert_eq!(result, serde_json::json!(5.0));
+}
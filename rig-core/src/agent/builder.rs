@@ -1,15 +1,42 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, future::Future, path::Path};
+
+use futures::FutureExt;
 
 use crate::{
-    completion::{CompletionModel, Document},
-    tool::{Tool, ToolSet},
+    completion::{CompletionError, CompletionModel, Document, Message, Prompt, PromptError},
+    loaders::{DocumentLoader, LoadError},
+    prompt::PromptTemplate,
+    tokens::TokenCounter,
+    tool::{ConditionalTool, Tool, ToolError, ToolSet},
     vector_store::VectorStoreIndexDyn,
 };
 
 #[cfg(feature = "mcp")]
 use crate::tool::McpTool;
 
-use super::Agent;
+use super::{
+    Agent, AgentCallContext, ContextStrategy, DynamicPreamble, PostPromptHook, PrePromptHook,
+    ResponseValidator, SummarizingMemory, TruncationStrategy,
+};
+
+/// Error of an instruction check of [AgentBuilder::try_build_checked].
+#[derive(Debug, thiserror::Error)]
+pub enum InstructionCheckError {
+    #[error("Instruction check failed for prompt {prompt:?}: {error}")]
+    PromptError { prompt: String, error: PromptError },
+
+    #[error("Instruction check failed for prompt {prompt:?}: expected response to contain {expected:?}, got {response:?}")]
+    Mismatch {
+        prompt: String,
+        expected: String,
+        response: String,
+    },
+}
+
+/// Default maximum number of model/tool cycles of a single prompt
+const DEFAULT_MAX_TOOL_ITERATIONS: usize = 10;
+/// Default maximum number of retries of a prompt whose response is rejected by the validator
+const DEFAULT_MAX_RETRIES: usize = 2;
 
 /// A builder for creating an agent
 ///
@@ -37,14 +64,39 @@ pub struct AgentBuilder<M: CompletionModel> {
     model: M,
     /// System prompt
     preamble: Option<String>,
+    /// System prompt template, rendered at prompt-time
+    preamble_template: Option<PromptTemplate>,
+    /// Function generating the system prompt of each call
+    dynamic_preamble: Option<DynamicPreamble>,
+    /// Timezone of the current date and time prepended to the system prompt, if enabled
+    #[cfg(feature = "date-time")]
+    date_time_tz: Option<chrono_tz::Tz>,
+    /// Variables used to render the system prompt template
+    preamble_vars: HashMap<String, String>,
     /// Context documents always available to the agent
     static_context: Vec<Document>,
+    /// Messages sent before the chat history of each call
+    initial_messages: Vec<Message>,
     /// Tools that are always available to the agent (by name)
     static_tools: Vec<String>,
     /// Additional parameters to be passed to the model
     additional_params: Option<serde_json::Value>,
     /// Maximum number of tokens for the completion
     max_tokens: Option<u64>,
+    /// Maximum (estimated) number of tokens of the prompt
+    max_prompt_tokens: Option<usize>,
+    /// Maximum number of model/tool cycles of a single prompt
+    max_tool_iterations: usize,
+    /// Token counter used to estimate the size of the prompt
+    token_counter: TokenCounter,
+    /// Maximum (estimated) number of tokens of the preamble, chat history and prompt
+    context_window_size: Option<usize>,
+    /// How the chat history is shortened when it doesn't fit in the context window
+    truncation_strategy: TruncationStrategy,
+    /// How the context documents are injected in the completion request
+    context_strategy: ContextStrategy,
+    /// Agent used to summarize the oldest messages of the chat history
+    summarizer: Option<SummarizingMemory>,
     /// List of vector store, with the sample number
     dynamic_context: Vec<(usize, Box<dyn VectorStoreIndexDyn>)>,
     /// Dynamic tools
@@ -57,6 +109,16 @@ pub struct AgentBuilder<M: CompletionModel> {
     include_reason_in_content: bool,
     /// Tag to wrap reasoning content when including it in main content
     include_reason_in_content_tag: String,
+    /// Test prompts (with their expected substring) checked by `build_checked`
+    instruction_assertions: Vec<(String, String)>,
+    /// Function checking the final response of each prompt
+    response_validator: Option<ResponseValidator>,
+    /// Maximum number of retries of a prompt whose response is rejected by the validator
+    max_retries: usize,
+    /// Hooks run on the prompt of each call
+    pre_prompt_hooks: Vec<PrePromptHook>,
+    /// Hooks run on the final response of each call
+    post_prompt_hooks: Vec<PostPromptHook>,
 }
 
 impl<M: CompletionModel> AgentBuilder<M> {
@@ -64,16 +126,34 @@ impl<M: CompletionModel> AgentBuilder<M> {
         Self {
             model,
             preamble: None,
+            preamble_template: None,
+            dynamic_preamble: None,
+            #[cfg(feature = "date-time")]
+            date_time_tz: None,
+            preamble_vars: HashMap::new(),
             static_context: vec![],
+            initial_messages: vec![],
             static_tools: vec![],
             temperature: None,
             max_tokens: None,
+            max_prompt_tokens: None,
+            max_tool_iterations: DEFAULT_MAX_TOOL_ITERATIONS,
+            token_counter: TokenCounter::default(),
+            context_window_size: None,
+            truncation_strategy: TruncationStrategy::default(),
+            context_strategy: ContextStrategy::default(),
+            summarizer: None,
             additional_params: None,
             dynamic_context: vec![],
             dynamic_tools: vec![],
             tools: ToolSet::default(),
             include_reason_in_content: true,
             include_reason_in_content_tag: "think".to_string(),
+            instruction_assertions: vec![],
+            response_validator: None,
+            max_retries: DEFAULT_MAX_RETRIES,
+            pre_prompt_hooks: vec![],
+            post_prompt_hooks: vec![],
         }
     }
 
@@ -83,6 +163,21 @@ impl<M: CompletionModel> AgentBuilder<M> {
         self
     }
 
+    /// Set the system prompt from the content of the file at `path`.
+    /// The file is read immediately (i.e.: not on each prompt), and an error is returned
+    /// if it cannot be read.
+    pub fn preamble_file(self, path: impl AsRef<Path>) -> Result<Self, std::io::Error> {
+        let preamble = std::fs::read_to_string(path)?;
+        Ok(self.preamble(&preamble))
+    }
+
+    /// Same as [AgentBuilder::preamble_file], but reads the file without blocking the async runtime.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn preamble_file_async(self, path: impl AsRef<Path>) -> Result<Self, std::io::Error> {
+        let preamble = tokio::fs::read_to_string(path).await?;
+        Ok(self.preamble(&preamble))
+    }
+
     /// Append to the preamble of the agent
     pub fn append_preamble(mut self, doc: &str) -> Self {
         self.preamble = Some(format!(
@@ -93,6 +188,89 @@ impl<M: CompletionModel> AgentBuilder<M> {
         self
     }
 
+    /// Set the system prompt from a template with `{variable}` placeholders.
+    /// The template is rendered on each prompt and, if set, takes precedence over the preamble.
+    pub fn preamble_template(mut self, template: impl Into<PromptTemplate>) -> Self {
+        self.preamble_template = Some(template.into());
+        self
+    }
+
+    /// Set a function generating the system prompt of each call, from the prompt, chat history
+    /// and metadata of the call (see [PromptRequest::metadata](super::PromptRequest::metadata)).
+    /// If set, it takes precedence over the preamble and preamble template.
+    ///
+    /// # Example
+    /// ```rust
+    /// let agent = openai.agent("gpt-4o")
+    ///     .dynamic_preamble(|context| {
+    ///         let tenant = context.metadata.get("tenant").map_or("Acme", String::as_str);
+    ///         format!("You are the support assistant of {tenant}.")
+    ///     })
+    ///     .build();
+    ///
+    /// let response = agent
+    ///     .prompt("How do I reset my password?")
+    ///     .metadata("tenant", "Globex")
+    ///     .await?;
+    /// ```
+    pub fn dynamic_preamble(
+        mut self,
+        f: impl Fn(&AgentCallContext) -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.dynamic_preamble = Some(Box::new(f));
+        self
+    }
+
+    /// Prepend the current date and time (e.g.: `Current date and time: 2025-01-01T12:00:00+00:00`)
+    /// to the preamble of each call, so that the model always knows it. The time is in UTC unless
+    /// a timezone is set with [AgentBuilder::inject_date_time_tz].
+    ///
+    /// Note: This requires the `date-time` feature to be enabled in the `Cargo.toml` file.
+    #[cfg(feature = "date-time")]
+    pub fn inject_date_time(mut self, inject: bool) -> Self {
+        self.date_time_tz = if inject {
+            Some(self.date_time_tz.unwrap_or(chrono_tz::Tz::UTC))
+        } else {
+            None
+        };
+        self
+    }
+
+    /// Prepend the current date and time in the given timezone to the preamble of each call
+    /// (see [AgentBuilder::inject_date_time]).
+    #[cfg(feature = "date-time")]
+    pub fn inject_date_time_tz(mut self, tz: chrono_tz::Tz) -> Self {
+        self.date_time_tz = Some(tz);
+        self
+    }
+
+    /// Set the variables used to render the system prompt template
+    pub fn preamble_vars(mut self, vars: HashMap<String, String>) -> Self {
+        self.preamble_vars.extend(vars);
+        self
+    }
+
+    /// Set messages sent before the chat history of each call, after the preamble (e.g.: few-shot
+    /// examples of user prompts and the expected assistant responses). Unlike the chat history,
+    /// they are never dropped to fit the context window.
+    ///
+    /// # Example
+    /// ```rust
+    /// let agent = openai.agent("gpt-4o")
+    ///     .preamble("Classify the sentiment of the user's message.")
+    ///     .initial_messages(vec![
+    ///         Message::user("I love this product!"),
+    ///         Message::assistant("positive"),
+    ///         Message::user("It broke after a day."),
+    ///         Message::assistant("negative"),
+    ///     ])
+    ///     .build();
+    /// ```
+    pub fn initial_messages(mut self, messages: Vec<Message>) -> Self {
+        self.initial_messages = messages;
+        self
+    }
+
     /// Add a static context document to the agent
     pub fn context(mut self, doc: &str) -> Self {
         self.static_context.push(Document {
@@ -103,6 +281,27 @@ impl<M: CompletionModel> AgentBuilder<M> {
         self
     }
 
+    /// Load documents with the given loader (e.g.: all the markdown files matching `docs/*.md`)
+    /// and add them to the agent's static context.
+    ///
+    /// # Example
+    /// ```rust
+    /// use rig::loaders::MarkdownLoader;
+    ///
+    /// let agent = openai.agent("gpt-4o")
+    ///     .load_context(MarkdownLoader, "docs/*.md")
+    ///     .await?
+    ///     .build();
+    /// ```
+    pub async fn load_context(
+        mut self,
+        loader: impl DocumentLoader,
+        source: &str,
+    ) -> Result<Self, LoadError> {
+        self.static_context.extend(loader.load(source).await?);
+        Ok(self)
+    }
+
     /// Add a static tool to the agent
     pub fn tool(mut self, tool: impl Tool + 'static) -> Self {
         let toolname = tool.name();
@@ -111,6 +310,19 @@ impl<M: CompletionModel> AgentBuilder<M> {
         self
     }
 
+    /// Add a static tool to the agent, only included in the requests whose prompt satisfies
+    /// `predicate` (see [ConditionalTool]).
+    pub fn conditional_tool(
+        mut self,
+        tool: impl Tool + 'static,
+        predicate: impl Fn(&str) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        let toolname = tool.name();
+        self.tools.add_tool(ConditionalTool::new(tool, predicate));
+        self.static_tools.push(toolname);
+        self
+    }
+
     // Add an MCP tool to the agent
     #[cfg(feature = "mcp")]
     pub fn mcp_tool<T: mcp_core::transport::Transport>(
@@ -149,6 +361,38 @@ impl<M: CompletionModel> AgentBuilder<M> {
         self
     }
 
+    /// Once the preamble, chat history and prompt exceed `threshold_tokens`, replace the oldest
+    /// messages of the chat history with a summary generated by `summarizer`. The summarizer
+    /// can use a different (e.g.: cheaper) model than the agent.
+    ///
+    /// This is a shorthand for [AgentBuilder::context_window_size] (set to `threshold_tokens`)
+    /// and [AgentBuilder::truncation_strategy] (set to [TruncationStrategy::SummarizeOldest]):
+    /// it replaces the values set by previous calls to these methods, and is overridden by later
+    /// ones.
+    pub fn summarize_with<S: CompletionModel + 'static>(
+        mut self,
+        summarizer: Agent<S>,
+        threshold_tokens: usize,
+    ) -> Self {
+        self.context_window_size = Some(threshold_tokens);
+        self.truncation_strategy = TruncationStrategy::SummarizeOldest;
+        self.summarizer = Some(SummarizingMemory::new(summarizer));
+        self
+    }
+
+    /// Add an interceptor called before any of the agent's tools is executed, e.g.: to log all
+    /// tool invocations or sanitize their input (see [ToolSet::intercept_all]).
+    pub fn tool_interceptor(
+        mut self,
+        interceptor: impl Fn(&str, &serde_json::Value) -> Result<serde_json::Value, ToolError>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.tools.intercept_all(interceptor);
+        self
+    }
+
     /// Set the temperature of the model
     pub fn temperature(mut self, temperature: f64) -> Self {
         self.temperature = Some(temperature);
@@ -161,6 +405,129 @@ impl<M: CompletionModel> AgentBuilder<M> {
         self
     }
 
+    /// Set the maximum (estimated) number of tokens of the prompt. If the preamble, static context,
+    /// chat history and prompt exceed this limit, the agent returns a
+    /// [CompletionError::ContextWindowExceeded](crate::completion::CompletionError::ContextWindowExceeded)
+    /// without sending the request.
+    pub fn max_prompt_tokens(mut self, max_prompt_tokens: usize) -> Self {
+        self.max_prompt_tokens = Some(max_prompt_tokens);
+        self
+    }
+
+    /// Set the maximum number of model/tool cycles (i.e.: the model calling tools and receiving their
+    /// results) in a single prompt, after which the agent returns a
+    /// [CompletionError::MaxIterationsExceeded](crate::completion::CompletionError::MaxIterationsExceeded)
+    /// error. This prevents endless loops when the model keeps calling tools. Defaults to 10.
+    pub fn max_tool_iterations(mut self, max_tool_iterations: usize) -> Self {
+        self.max_tool_iterations = max_tool_iterations;
+        self
+    }
+
+    /// Set the token counter used to estimate the size of the prompt (e.g.: `TokenCounter::new("gpt-4o")`).
+    /// Defaults to a character based heuristic.
+    pub fn token_counter(mut self, token_counter: TokenCounter) -> Self {
+        self.token_counter = token_counter;
+        self
+    }
+
+    /// Set the size (in tokens) of the model's context window. When the preamble, chat history and
+    /// prompt don't fit, the oldest messages of the chat history are dropped (or summarized, see
+    /// [AgentBuilder::truncation_strategy]) until they do. The preamble is always preserved.
+    pub fn context_window_size(mut self, context_window_size: usize) -> Self {
+        self.context_window_size = Some(context_window_size);
+        self
+    }
+
+    /// Set how the chat history is shortened when it doesn't fit in the context window.
+    /// Defaults to [TruncationStrategy::TruncateOldest].
+    pub fn truncation_strategy(mut self, truncation_strategy: TruncationStrategy) -> Self {
+        self.truncation_strategy = truncation_strategy;
+        self
+    }
+
+    /// Set how the context documents (static and dynamic) are injected in the completion request.
+    /// Defaults to [ContextStrategy::DocumentsField], which lets the provider format them; some
+    /// models follow the documents better when they are part of the preamble or of the prompt.
+    pub fn context_strategy(mut self, context_strategy: ContextStrategy) -> Self {
+        self.context_strategy = context_strategy;
+        self
+    }
+
+    /// Set a function checking the final response of each prompt (e.g.: that it is valid JSON
+    /// when JSON mode is unavailable). When it returns `Err(feedback)`, the feedback is sent to
+    /// the model as a correction message and the model is prompted again, up to
+    /// [AgentBuilder::max_retries] times, after which the prompt fails with a
+    /// [CompletionError::ResponseError](crate::completion::CompletionError::ResponseError).
+    ///
+    /// # Example
+    /// ```rust
+    /// let agent = openai.agent("gpt-4o")
+    ///     .preamble("Answer with a JSON object with a `city` field.")
+    ///     .response_validator(|response| {
+    ///         serde_json::from_str::<serde_json::Value>(response)
+    ///             .map(|_| ())
+    ///             .map_err(|e| format!("Your response is not valid JSON ({e})."))
+    ///     })
+    ///     .build();
+    /// ```
+    pub fn response_validator(
+        mut self,
+        validator: impl Fn(&str) -> Result<(), String> + Send + Sync + 'static,
+    ) -> Self {
+        self.response_validator = Some(Box::new(validator));
+        self
+    }
+
+    /// Set the maximum number of retries of a prompt whose response is rejected by the
+    /// [response validator](AgentBuilder::response_validator). Defaults to 2.
+    pub fn max_retries(mut self, max_retries: usize) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Add an async hook run on the prompt of each call (via [Prompt] or [Chat]), before the
+    /// completion request is built (e.g.: to log or scrub the prompt). The hook returns the
+    /// (possibly modified) prompt, or an error to reject it. Hooks are run in the order they
+    /// are added.
+    ///
+    /// # Example
+    /// ```rust
+    /// let agent = openai.agent("gpt-4o")
+    ///     .pre_prompt_hook(|prompt| async move {
+    ///         tracing::info!("Prompt: {prompt:?}");
+    ///         Ok(prompt)
+    ///     })
+    ///     .build();
+    /// ```
+    ///
+    /// [Prompt]: crate::completion::Prompt
+    /// [Chat]: crate::completion::Chat
+    pub fn pre_prompt_hook<F, Fut>(mut self, hook: F) -> Self
+    where
+        F: Fn(Message) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<Message, CompletionError>> + Send + 'static,
+    {
+        self.pre_prompt_hooks
+            .push(Box::new(move |prompt| hook(prompt).boxed()));
+        self
+    }
+
+    /// Add an async hook run on the final response of each call (via [Prompt] or [Chat]). The
+    /// hook returns the (possibly modified) response, or an error to reject it. Hooks are run
+    /// in the order they are added.
+    ///
+    /// [Prompt]: crate::completion::Prompt
+    /// [Chat]: crate::completion::Chat
+    pub fn post_prompt_hook<F, Fut>(mut self, hook: F) -> Self
+    where
+        F: Fn(String) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<String, PromptError>> + Send + 'static,
+    {
+        self.post_prompt_hooks
+            .push(Box::new(move |response| hook(response).boxed()));
+        self
+    }
+
     /// Set additional parameters to be passed to the model
     pub fn additional_params(mut self, params: serde_json::Value) -> Self {
         self.additional_params = Some(params);
@@ -181,21 +548,95 @@ impl<M: CompletionModel> AgentBuilder<M> {
         self
     }
 
+    /// Register a sanity check for the agent's instructions: when the agent is built with
+    /// [AgentBuilder::build_checked], `prompt` is sent to the model and the response must
+    /// contain `expected_substring`.
+    ///
+    /// Checks are only run by [AgentBuilder::build_checked] when `debug_assertions` are enabled
+    /// (and always by [AgentBuilder::try_build_checked]).
+    pub fn assert_instruction(mut self, prompt: &str, expected_substring: &str) -> Self {
+        self.instruction_assertions
+            .push((prompt.to_string(), expected_substring.to_string()));
+        self
+    }
+
+    /// Build the agent and, when `debug_assertions` are enabled, run the instruction
+    /// checks registered with [AgentBuilder::assert_instruction].
+    ///
+    /// # Panics
+    /// Panics if prompting the model fails or if a response does not contain the expected substring
+    /// (see [AgentBuilder::try_build_checked] to handle the failures instead).
+    pub async fn build_checked(self) -> Agent<M> {
+        #[cfg(debug_assertions)]
+        return self
+            .try_build_checked()
+            .await
+            .unwrap_or_else(|err| panic!("{err}"));
+
+        #[cfg(not(debug_assertions))]
+        self.build()
+    }
+
+    /// Build the agent and run the instruction checks registered with
+    /// [AgentBuilder::assert_instruction], whether `debug_assertions` are enabled or not.
+    pub async fn try_build_checked(mut self) -> Result<Agent<M>, InstructionCheckError> {
+        let assertions = std::mem::take(&mut self.instruction_assertions);
+        let agent = self.build();
+
+        for (prompt, expected) in assertions {
+            let response = agent.prompt(prompt.as_str()).await.map_err(|error| {
+                InstructionCheckError::PromptError {
+                    prompt: prompt.clone(),
+                    error,
+                }
+            })?;
+
+            if !response.contains(&expected) {
+                return Err(InstructionCheckError::Mismatch {
+                    prompt,
+                    expected,
+                    response,
+                });
+            }
+        }
+
+        Ok(agent)
+    }
+
     /// Build the agent
     pub fn build(self) -> Agent<M> {
         Agent {
             model: self.model,
             preamble: self.preamble.unwrap_or_default(),
+            preamble_template: self
+                .preamble_template
+                .map(|template| template.vars(self.preamble_vars)),
+            dynamic_preamble: self.dynamic_preamble,
+            #[cfg(feature = "date-time")]
+            date_time_tz: self.date_time_tz,
             static_context: self.static_context,
+            initial_messages: self.initial_messages,
             static_tools: self.static_tools,
             temperature: self.temperature,
             max_tokens: self.max_tokens,
+            max_prompt_tokens: self.max_prompt_tokens,
+            max_tool_iterations: self.max_tool_iterations,
+            token_counter: self.token_counter,
+            context_window_size: self.context_window_size,
+            truncation_strategy: self.truncation_strategy,
+            context_strategy: self.context_strategy,
+            summarizer: self.summarizer,
             additional_params: self.additional_params,
             dynamic_context: self.dynamic_context,
             dynamic_tools: self.dynamic_tools,
             tools: self.tools,
             include_reason_in_content: self.include_reason_in_content,
             include_reason_in_content_tag: self.include_reason_in_content_tag,
+            response_validator: self.response_validator,
+            max_retries: self.max_retries,
+            pre_prompt_hooks: self.pre_prompt_hooks,
+            post_prompt_hooks: self.post_prompt_hooks,
+            last_trace: std::sync::Mutex::new(None),
         }
     }
 }
@@ -0,0 +1,145 @@
+//! This module contains the implementation of the [ChainOfThoughtAgent] struct, which makes an
+//! agent reason explicitly before answering.
+//!
+//! Each prompt is answered in two completion calls: the agent is first asked to think step by
+//! step about the prompt, then to give its final answer given its own reasoning.
+//!
+//! # Example
+//! ```rust
+//! use rig::{agent::ChainOfThoughtAgent, completion::Prompt, providers::openai};
+//!
+//! let openai = openai::Client::from_env();
+//!
+//! let agent = ChainOfThoughtAgent::new(openai.agent("gpt-4o-mini").build()).steps(3);
+//!
+//! let response = agent
+//!     .reason("A bat and a ball cost $1.10. The bat costs $1 more than the ball. How much is the ball?")
+//!     .await
+//!     .expect("Failed to prompt the agent");
+//!
+//! println!("Reasoning: {}", response.reasoning);
+//! println!("Answer: {}", response.answer);
+//! ```
+use serde::{Deserialize, Serialize};
+
+use crate::completion::{CompletionModel, Message, Prompt, PromptError};
+
+use super::Agent;
+
+/// Separator between the reasoning and the request of the final answer, by default.
+const DEFAULT_SEPARATOR: &str = "\n\nGiven the reasoning above, give your final answer:\n";
+
+/// The reasoning of a [ChainOfThoughtAgent] and its final answer.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ChainOfThoughtResponse {
+    pub reasoning: String,
+    pub answer: String,
+}
+
+/// An agent reasoning step by step about each prompt before answering it.
+pub struct ChainOfThoughtAgent<M: CompletionModel> {
+    agent: Agent<M>,
+    /// Number of reasoning steps requested to the agent, if any
+    steps: Option<usize>,
+    /// Separator between the reasoning and the request of the final answer
+    separator: String,
+}
+
+impl<M: CompletionModel> ChainOfThoughtAgent<M> {
+    pub fn new(agent: Agent<M>) -> Self {
+        Self {
+            agent,
+            steps: None,
+            separator: DEFAULT_SEPARATOR.to_string(),
+        }
+    }
+
+    /// Ask the agent to reason in `steps` steps (by default, the agent chooses).
+    pub fn steps(mut self, steps: usize) -> Self {
+        self.steps = Some(steps);
+        self
+    }
+
+    /// Set the separator between the reasoning and the request of the final answer, in the
+    /// prompt of the answer phase.
+    pub fn separator(mut self, separator: &str) -> Self {
+        self.separator = separator.to_string();
+        self
+    }
+
+    /// Returns the prompt of the reasoning phase.
+    fn reasoning_prompt(&self, prompt: &str) -> String {
+        match self.steps {
+            Some(steps) => format!(
+                "Before answering, think step by step:\n\
+                (Reason in exactly {steps} numbered steps, without giving the final answer yet.)\n\
+                {prompt}"
+            ),
+            None => format!("Before answering, think step by step:\n{prompt}"),
+        }
+    }
+
+    /// Reason about `prompt`, then answer it.
+    pub async fn reason(&self, prompt: &str) -> Result<ChainOfThoughtResponse, PromptError> {
+        let reasoning = self.agent.prompt(self.reasoning_prompt(prompt)).await?;
+
+        let answer = self
+            .agent
+            .prompt(format!(
+                "{prompt}\n\nReasoning:\n{reasoning}{}",
+                self.separator
+            ))
+            .await?;
+
+        Ok(ChainOfThoughtResponse { reasoning, answer })
+    }
+}
+
+#[allow(refining_impl_trait)]
+impl<M: CompletionModel> Prompt for ChainOfThoughtAgent<M> {
+    async fn prompt(&self, prompt: impl Into<Message> + Send) -> Result<String, PromptError> {
+        let prompt = prompt.into().rag_text().unwrap_or_default();
+        Ok(self.reason(&prompt).await?.answer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{agent::AgentBuilder, testing::MockCompletionModel};
+
+    #[tokio::test]
+    async fn test_chain_of_thought() {
+        let model = MockCompletionModel::sequence(["1. 2 + 2 is 4", "4"]);
+        let agent = ChainOfThoughtAgent::new(AgentBuilder::new(model.clone()).build())
+            .steps(1)
+            .separator("\nAnswer:");
+
+        let response = agent.reason("What is 2 + 2?").await.unwrap();
+        assert_eq!(
+            response,
+            ChainOfThoughtResponse {
+                reasoning: "1. 2 + 2 is 4".to_string(),
+                answer: "4".to_string(),
+            }
+        );
+
+        let prompts = model
+            .requests()
+            .into_iter()
+            .map(|request| request.chat_history.iter().last().unwrap().rag_text())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            prompts,
+            vec![
+                Some(
+                    "Before answering, think step by step:\n\
+                    (Reason in exactly 1 numbered steps, without giving the final answer yet.)\n\
+                    What is 2 + 2?"
+                        .to_string()
+                ),
+                Some("What is 2 + 2?\n\nReasoning:\n1. 2 + 2 is 4\nAnswer:".to_string()),
+            ]
+        );
+    }
+}
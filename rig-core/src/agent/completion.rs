@@ -1,15 +1,22 @@
-use super::prompt_request::PromptRequest;
+use super::{
+    memory::{summary_prompt, SummarizingMemory},
+    prompt_request::PromptRequest,
+};
 use crate::{
     completion::{
-        Chat, Completion, CompletionError, CompletionModel, CompletionRequestBuilder, Document,
-        Message, Prompt, PromptError,
+        AssistantContent, Chat, Completion, CompletionError, CompletionModel, CompletionRequest,
+        CompletionRequestBuilder, Document, Message, Prompt, PromptError,
     },
+    message::UserContent,
+    prompt::PromptTemplate,
     streaming::{StreamingChat, StreamingCompletion, StreamingCompletionResponse, StreamingPrompt},
+    tokens::TokenCounter,
     tool::ToolSet,
     vector_store::VectorStoreError,
+    OneOrMany,
 };
-use futures::{stream, StreamExt, TryStreamExt};
-use std::collections::HashMap;
+use futures::{future::BoxFuture, stream, StreamExt, TryStreamExt};
+use std::{collections::HashMap, sync::Mutex, time::Duration};
 
 /// Struct representing an LLM agent. An agent is an LLM model combined with a preamble
 /// (i.e.: system prompt) and a static set of context documents and tools.
@@ -36,14 +43,38 @@ pub struct Agent<M: CompletionModel> {
     pub model: M,
     /// System prompt
     pub preamble: String,
+    /// System prompt template, rendered at prompt-time (takes precedence over `preamble`)
+    pub preamble_template: Option<PromptTemplate>,
+    /// Function generating the system prompt of each call (takes precedence over `preamble`
+    /// and `preamble_template`)
+    pub dynamic_preamble: Option<DynamicPreamble>,
+    /// Timezone of the current date and time prepended to the preamble of each call, if enabled
+    #[cfg(feature = "date-time")]
+    pub date_time_tz: Option<chrono_tz::Tz>,
     /// Context documents always available to the agent
     pub static_context: Vec<Document>,
+    /// Messages (e.g.: few-shot examples) sent before the chat history of each call
+    pub initial_messages: Vec<Message>,
     /// Tools that are always available to the agent (identified by their name)
     pub static_tools: Vec<String>,
     /// Temperature of the model
     pub temperature: Option<f64>,
     /// Maximum number of tokens for the completion
     pub max_tokens: Option<u64>,
+    /// Maximum (estimated) number of tokens of the prompt
+    pub max_prompt_tokens: Option<usize>,
+    /// Maximum number of model/tool cycles of a single prompt
+    pub max_tool_iterations: usize,
+    /// Token counter used to estimate the size of the prompt
+    pub token_counter: TokenCounter,
+    /// Maximum (estimated) number of tokens of the preamble, chat history and prompt
+    pub context_window_size: Option<usize>,
+    /// How the chat history is shortened when it doesn't fit in the context window
+    pub truncation_strategy: TruncationStrategy,
+    /// How the context documents are injected in the completion request
+    pub context_strategy: ContextStrategy,
+    /// Summarizer used by [TruncationStrategy::SummarizeOldest] (defaults to the agent's own model)
+    pub summarizer: Option<SummarizingMemory>,
     /// Additional parameters to be passed to the model
     pub additional_params: Option<serde_json::Value>,
     /// List of vector store, with the sample number
@@ -56,6 +87,229 @@ pub struct Agent<M: CompletionModel> {
     pub include_reason_in_content: bool,
     /// Tag to wrap reasoning content when including it in main content
     pub include_reason_in_content_tag: String,
+    /// Function checking the final response of each prompt (see
+    /// [AgentBuilder::response_validator](super::AgentBuilder::response_validator))
+    pub response_validator: Option<ResponseValidator>,
+    /// Maximum number of retries of a prompt whose response is rejected by the response validator
+    pub max_retries: usize,
+    /// Hooks run, in order, on the prompt of each call
+    pub pre_prompt_hooks: Vec<PrePromptHook>,
+    /// Hooks run, in order, on the final response of each call
+    pub post_prompt_hooks: Vec<PostPromptHook>,
+    /// Trace of the last prompt sent to the agent (see [Agent::last_trace])
+    pub last_trace: Mutex<Option<AgentTrace>>,
+}
+
+/// Function generating the system prompt of an agent for each call
+/// (see [AgentBuilder::dynamic_preamble](super::AgentBuilder::dynamic_preamble)).
+pub type DynamicPreamble = Box<dyn Fn(&AgentCallContext) -> String + Send + Sync>;
+
+/// Function checking the final response of an agent, returning feedback on invalid responses
+/// (see [AgentBuilder::response_validator](super::AgentBuilder::response_validator)).
+pub type ResponseValidator = Box<dyn Fn(&str) -> Result<(), String> + Send + Sync>;
+
+/// Async function run on the prompt of each call, before the completion request is built
+/// (see [AgentBuilder::pre_prompt_hook](super::AgentBuilder::pre_prompt_hook)).
+pub type PrePromptHook =
+    Box<dyn Fn(Message) -> BoxFuture<'static, Result<Message, CompletionError>> + Send + Sync>;
+
+/// Async function run on the final response of each call
+/// (see [AgentBuilder::post_prompt_hook](super::AgentBuilder::post_prompt_hook)).
+pub type PostPromptHook =
+    Box<dyn Fn(String) -> BoxFuture<'static, Result<String, PromptError>> + Send + Sync>;
+
+/// Information about the current call of an agent, used to generate its preamble.
+pub struct AgentCallContext<'a> {
+    /// The message sent to the model (i.e.: the user's prompt, or tool results in multi-turn prompts)
+    pub prompt: &'a Message,
+    /// The chat history preceding the prompt
+    pub chat_history: &'a [Message],
+    /// Metadata attached by the caller (see [PromptRequest::metadata])
+    pub metadata: &'a HashMap<String, String>,
+}
+
+/// Trace of the last prompt sent to an [Agent] (see [Agent::last_trace]).
+#[derive(Clone, Debug)]
+pub struct AgentTrace {
+    /// The rendered preamble sent to the model
+    pub preamble: Option<String>,
+    /// The documents sent to the model, with their similarity score
+    /// (`None` for static context documents)
+    pub documents: Vec<(Option<f64>, Document)>,
+    /// The names of the tools made available to the model
+    pub tools: Vec<String>,
+    /// The full completion request sent to the model (the last one for multi-turn prompts)
+    pub request: CompletionRequest,
+    /// The content of the model's response to the request
+    pub response: OneOrMany<AssistantContent>,
+    /// The time elapsed from the start of the prompt to the model's response
+    /// (including the previous turns and tool calls of multi-turn prompts)
+    pub latency: Duration,
+}
+
+/// Strategy used by an [Agent] to shorten the chat history when it doesn't fit in the
+/// agent's context window (see [AgentBuilder::context_window_size](super::AgentBuilder::context_window_size)).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TruncationStrategy {
+    /// Drop the oldest messages of the chat history
+    #[default]
+    TruncateOldest,
+    /// Drop the oldest messages of the chat history and send a summary of them (generated by the
+    /// agent's model, or its summarizer if any) as a system message following the preamble
+    /// (see [CompletionRequestBuilder::system_messages](crate::completion::CompletionRequestBuilder::system_messages)
+    /// for the providers supporting it)
+    SummarizeOldest,
+}
+
+/// How an [Agent] injects its context documents (static and dynamic) in the completion request
+/// (see [AgentBuilder::context_strategy](super::AgentBuilder::context_strategy)).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ContextStrategy {
+    /// Send the documents in the `documents` field of the request, formatted by the provider
+    #[default]
+    DocumentsField,
+    /// Append the documents to the preamble
+    SystemPromptAppend,
+    /// Prepend the documents to the content of the prompt message
+    UserMessagePrepend,
+}
+
+impl<M: CompletionModel> Agent<M> {
+    /// Drop the oldest messages of `chat_history` until the preamble, chat history and prompt fit in
+    /// the context window. Returns the truncated chat history and the summary of the dropped
+    /// messages (with [TruncationStrategy::SummarizeOldest]).
+    async fn fit_context_window(
+        &self,
+        preamble: &str,
+        prompt: &Message,
+        mut chat_history: Vec<Message>,
+    ) -> Result<(Vec<Message>, Option<String>), CompletionError> {
+        let Some(limit) = self.context_window_size else {
+            return Ok((chat_history, None));
+        };
+
+        let estimate = |history: &[Message]| {
+            self.token_counter.estimate(
+                &[
+                    self.initial_messages.as_slice(),
+                    history,
+                    std::slice::from_ref(prompt),
+                ]
+                .concat(),
+                preamble,
+            )
+        };
+
+        let mut dropped = vec![];
+        while !chat_history.is_empty() && estimate(&chat_history) > limit {
+            dropped.push(chat_history.remove(0));
+
+            // Never start the history with an assistant message or a dangling tool result
+            while chat_history
+                .first()
+                .is_some_and(|message| !is_user_prompt(message))
+            {
+                dropped.push(chat_history.remove(0));
+            }
+        }
+
+        if dropped.is_empty() || self.truncation_strategy == TruncationStrategy::TruncateOldest {
+            return Ok((chat_history, None));
+        }
+
+        let summary = match &self.summarizer {
+            Some(memory) => memory.summarize(&dropped).await.map_err(|err| match err {
+                PromptError::CompletionError(err) => err,
+                err => CompletionError::RequestError(Box::new(err)),
+            })?,
+            None => self
+                .model
+                .completion_request(summary_prompt(&dropped))
+                .send()
+                .await?
+                .choice
+                .into_iter()
+                .filter_map(|content| match content {
+                    AssistantContent::Text(text) => Some(text.text),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+        };
+
+        Ok((
+            chat_history,
+            Some(format!("Summary of the earlier conversation: {summary}")),
+        ))
+    }
+}
+
+/// Whether the message was written by the user (as opposed to a tool result).
+fn is_user_prompt(message: &Message) -> bool {
+    match message {
+        Message::User { content } => !content
+            .iter()
+            .any(|content| matches!(content, UserContent::ToolResult(_))),
+        Message::Assistant { .. } => false,
+    }
+}
+
+/// Inject the context documents in the preamble or prompt, as configured by `strategy`.
+/// Returns the preamble, prompt and the documents left for the `documents` field of the request.
+fn inject_documents(
+    strategy: ContextStrategy,
+    preamble: String,
+    prompt: Message,
+    documents: Vec<Document>,
+) -> (String, Message, Vec<Document>) {
+    if documents.is_empty() {
+        return (preamble, prompt, documents);
+    }
+
+    let text = documents
+        .iter()
+        .map(|doc| doc.to_string())
+        .collect::<String>();
+
+    match (strategy, prompt) {
+        (ContextStrategy::SystemPromptAppend, prompt) => {
+            let preamble = if preamble.is_empty() {
+                text
+            } else {
+                format!("{preamble}\n\n{text}")
+            };
+            (preamble, prompt, vec![])
+        }
+        (ContextStrategy::UserMessagePrepend, Message::User { content }) => {
+            let is_tool_result = content
+                .iter()
+                .any(|content| matches!(content, UserContent::ToolResult(_)));
+            let mut content = content.into_iter().collect::<Vec<_>>();
+            // Tool results must come first in the message, so the documents follow them
+            if is_tool_result {
+                content.push(UserContent::text(text));
+            } else {
+                content.insert(0, UserContent::text(text));
+            }
+
+            let prompt = Message::User {
+                content: OneOrMany::many(content).expect("There is at least one content"),
+            };
+            (preamble, prompt, vec![])
+        }
+        (_, prompt) => (preamble, prompt, documents),
+    }
+}
+
+/// Returns the RAG text of a request (used to retrieve its dynamic context and tools): the text
+/// of the prompt, or of the latest message in the chat history that contains text.
+pub(crate) fn rag_text(prompt: &Message, chat_history: &[Message]) -> Option<String> {
+    prompt.rag_text().or_else(|| {
+        chat_history
+            .iter()
+            .rev()
+            .find_map(|message| message.rag_text())
+    })
 }
 
 impl<M: CompletionModel> Completion<M> for Agent<M> {
@@ -64,16 +318,33 @@ impl<M: CompletionModel> Completion<M> for Agent<M> {
         prompt: impl Into<Message> + Send,
         chat_history: Vec<Message>,
     ) -> Result<CompletionRequestBuilder<M>, CompletionError> {
-        let prompt = prompt.into();
+        self.completion_with_metadata(prompt.into(), chat_history, &HashMap::new())
+            .await
+    }
+}
 
-        // Find the latest message in the chat history that contains RAG text
-        let rag_text = prompt.rag_text();
-        let rag_text = rag_text.or_else(|| {
-            chat_history
-                .iter()
-                .rev()
-                .find_map(|message| message.rag_text())
-        });
+impl<M: CompletionModel> Agent<M> {
+    /// Same as [Completion::completion], with metadata passed to the agent's dynamic preamble (if any).
+    pub(crate) async fn completion_with_metadata(
+        &self,
+        prompt: Message,
+        chat_history: Vec<Message>,
+        metadata: &HashMap<String, String>,
+    ) -> Result<CompletionRequestBuilder<M>, CompletionError> {
+        self.completion_with_scores(prompt, chat_history, metadata)
+            .await
+            .map(|(builder, _)| builder)
+    }
+
+    /// Same as [Agent::completion_with_metadata], also returning the similarity scores of the
+    /// dynamic context documents (by document id).
+    pub(crate) async fn completion_with_scores(
+        &self,
+        prompt: Message,
+        chat_history: Vec<Message>,
+        metadata: &HashMap<String, String>,
+    ) -> Result<(CompletionRequestBuilder<M>, HashMap<String, f64>), CompletionError> {
+        let rag_text = rag_text(&prompt, &chat_history);
 
         // Merge reasoning configuration with existing additional_params
         let reasoning_params = serde_json::json!({
@@ -87,18 +358,55 @@ impl<M: CompletionModel> Completion<M> for Agent<M> {
             reasoning_params
         };
 
-        let completion_request = self
-            .model
-            .completion_request(prompt)
-            .preamble(self.preamble.clone())
-            .messages(chat_history)
-            .temperature_opt(self.temperature)
-            .max_tokens_opt(self.max_tokens)
-            .additional_params(merged_params)
-            .documents(self.static_context.clone());
+        let preamble = match (&self.dynamic_preamble, &self.preamble_template) {
+            (Some(dynamic_preamble), _) => dynamic_preamble(&AgentCallContext {
+                prompt: &prompt,
+                chat_history: &chat_history,
+                metadata,
+            }),
+            (None, Some(template)) => template
+                .render()
+                .map_err(|e| CompletionError::RequestError(Box::new(e)))?,
+            (None, None) => self.preamble.clone(),
+        };
+
+        // The date and time are added on each call so that they are always up to date
+        #[cfg(feature = "date-time")]
+        let preamble = match self.date_time_tz {
+            Some(tz) => format!(
+                "Current date and time: {}\n{preamble}",
+                chrono::Utc::now().with_timezone(&tz).to_rfc3339()
+            ),
+            None => preamble,
+        };
+
+        let (chat_history, summary) = self
+            .fit_context_window(&preamble, &prompt, chat_history)
+            .await?;
+
+        // The initial messages are never truncated
+        let chat_history = [self.initial_messages.clone(), chat_history].concat();
+
+        // Estimate the size of the prompt before making any call (dynamic context is not known yet)
+        if let Some(limit) = self.max_prompt_tokens {
+            let static_context = self
+                .static_context
+                .iter()
+                .map(|doc| doc.to_string())
+                .collect::<String>();
+            let messages = [chat_history.as_slice(), std::slice::from_ref(&prompt)].concat();
+
+            let estimated = self
+                .token_counter
+                .estimate(&messages, &format!("{preamble}{static_context}"));
+
+            if estimated > limit {
+                return Err(CompletionError::ContextWindowExceeded { estimated, limit });
+            }
+        }
 
         // If the agent has RAG text, we need to fetch the dynamic context and tools
-        let agent = match &rag_text {
+        let (dynamic_context, tools, scores) = match &rag_text {
             Some(text) => {
                 let dynamic_context = stream::iter(self.dynamic_context.iter())
                     .then(|(num_sample, index)| async {
@@ -107,16 +415,19 @@ impl<M: CompletionModel> Completion<M> for Agent<M> {
                                 .top_n(text, *num_sample)
                                 .await?
                                 .into_iter()
-                                .map(|(_, id, doc)| {
+                                .map(|(score, id, doc)| {
                                     // Pretty print the document if possible for better readability
                                     let text = serde_json::to_string_pretty(&doc)
                                         .unwrap_or_else(|_| doc.to_string());
 
-                                    Document {
-                                        id,
-                                        text,
-                                        additional_props: HashMap::new(),
-                                    }
+                                    (
+                                        score,
+                                        Document {
+                                            id,
+                                            text,
+                                            additional_props: HashMap::new(),
+                                        },
+                                    )
                                 })
                                 .collect::<Vec<_>>(),
                         )
@@ -139,61 +450,109 @@ impl<M: CompletionModel> Completion<M> for Agent<M> {
                                 .collect::<Vec<_>>(),
                         )
                     })
-                    .try_fold(vec![], |mut acc, docs| async {
-                        for doc in docs {
-                            if let Some(tool) = self.tools.get(&doc) {
-                                acc.push(tool.definition(text.into()).await)
-                            } else {
-                                tracing::warn!("Tool implementation not found in toolset: {}", doc);
-                            }
-                        }
+                    .try_fold(vec![], |mut acc, docs| async move {
+                        acc.extend(self.tools.available_definitions(&docs, text).await);
                         Ok(acc)
                     })
                     .await
                     .map_err(|e| CompletionError::RequestError(Box::new(e)))?;
 
-                let static_tools = stream::iter(self.static_tools.iter())
-                    .filter_map(|toolname| async move {
-                        if let Some(tool) = self.tools.get(toolname) {
-                            // TODO: tool definitions should likely take an `Option<String>`
-                            Some(tool.definition(text.into()).await)
-                        } else {
-                            tracing::warn!(
-                                "Tool implementation not found in toolset: {}",
-                                toolname
-                            );
-                            None
-                        }
-                    })
-                    .collect::<Vec<_>>()
+                let scores = dynamic_context
+                    .iter()
+                    .map(|(score, doc)| (doc.id.clone(), *score))
+                    .collect();
+                let dynamic_context = dynamic_context
+                    .into_iter()
+                    .map(|(_, doc)| doc)
+                    .collect::<Vec<_>>();
+
+                let static_tools = self
+                    .tools
+                    .available_definitions(&self.static_tools, text)
                     .await;
 
-                completion_request
-                    .documents(dynamic_context)
-                    .tools([static_tools.clone(), dynamic_tools].concat())
+                (
+                    dynamic_context,
+                    [static_tools, dynamic_tools].concat(),
+                    scores,
+                )
             }
             None => {
-                let static_tools = stream::iter(self.static_tools.iter())
-                    .filter_map(|toolname| async move {
-                        if let Some(tool) = self.tools.get(toolname) {
-                            // TODO: tool definitions should likely take an `Option<String>`
-                            Some(tool.definition("".into()).await)
-                        } else {
-                            tracing::warn!(
-                                "Tool implementation not found in toolset: {}",
-                                toolname
-                            );
-                            None
-                        }
-                    })
-                    .collect::<Vec<_>>()
+                let static_tools = self
+                    .tools
+                    .available_definitions(&self.static_tools, "")
                     .await;
 
-                completion_request.tools(static_tools)
+                (vec![], static_tools, HashMap::new())
             }
         };
 
-        Ok(agent)
+        let documents = [self.static_context.clone(), dynamic_context].concat();
+        let (preamble, prompt, documents) =
+            inject_documents(self.context_strategy, preamble, prompt, documents);
+
+        let completion_request = self
+            .model
+            .completion_request(prompt)
+            .preamble(preamble)
+            .system_messages(summary.into_iter().collect())
+            .messages(chat_history)
+            .temperature_opt(self.temperature)
+            .max_tokens_opt(self.max_tokens)
+            .additional_params(merged_params)
+            .documents(documents)
+            .tools(tools);
+
+        Ok((completion_request, scores))
+    }
+
+    /// Returns the trace of the last prompt sent to the agent (via [Prompt] or [Chat]),
+    /// describing the request sent to the model and its response, for debugging purposes.
+    ///
+    /// # Example
+    /// ```rust
+    /// let response = agent.prompt("What is the capital of France?").await?;
+    ///
+    /// if let Some(trace) = agent.last_trace() {
+    ///     println!("Preamble: {:?}", trace.preamble);
+    ///     for (score, doc) in &trace.documents {
+    ///         println!("Document {} (score: {score:?})", doc.id);
+    ///     }
+    ///     println!("Tools: {:?}, latency: {:?}", trace.tools, trace.latency);
+    /// }
+    /// ```
+    pub fn last_trace(&self) -> Option<AgentTrace> {
+        self.last_trace
+            .lock()
+            .expect("Trace lock should not be poisoned")
+            .clone()
+    }
+
+    /// Record the trace of a request sent to the agent's model and its response.
+    pub(crate) fn record_trace(
+        &self,
+        request: CompletionRequest,
+        scores: &HashMap<String, f64>,
+        response: OneOrMany<AssistantContent>,
+        latency: Duration,
+    ) {
+        let trace = AgentTrace {
+            preamble: request.preamble.clone(),
+            documents: request
+                .documents
+                .iter()
+                .map(|doc| (scores.get(&doc.id).copied(), doc.clone()))
+                .collect(),
+            tools: request.tools.iter().map(|tool| tool.name.clone()).collect(),
+            request,
+            response,
+            latency,
+        };
+
+        *self
+            .last_trace
+            .lock()
+            .expect("Trace lock should not be poisoned") = Some(trace);
     }
 }
 
@@ -244,6 +603,26 @@ impl<M: CompletionModel> StreamingCompletion<M> for Agent<M> {
     }
 }
 
+impl<M: CompletionModel> Agent<M> {
+    /// Stream a prompt to the agent, only yielding the text chunks of the response
+    /// (see [StreamingCompletionResponse::into_text_stream]).
+    ///
+    /// # Example
+    /// ```rust
+    /// let mut stream = agent.stream_text("Tell me a story").await?;
+    ///
+    /// while let Some(text) = stream.next().await {
+    ///     print!("{}", text?);
+    /// }
+    /// ```
+    pub async fn stream_text(
+        &self,
+        prompt: impl Into<Message> + Send,
+    ) -> Result<impl futures::Stream<Item = Result<String, CompletionError>>, CompletionError> {
+        Ok(self.stream_prompt(prompt).await?.into_text_stream())
+    }
+}
+
 impl<M: CompletionModel> StreamingPrompt<M::StreamingResponse> for Agent<M> {
     async fn stream_prompt(
         &self,
@@ -259,60 +638,23 @@ impl<M: CompletionModel> StreamingChat<M::StreamingResponse> for Agent<M> {
         prompt: impl Into<Message> + Send,
         chat_history: Vec<Message>,
     ) -> Result<StreamingCompletionResponse<M::StreamingResponse>, CompletionError> {
-        self.stream_completion(prompt, chat_history)
+        let prompt: Message = prompt.into();
+        // Kept so that the tools offered for the prompt can be called (see `stream_to_stdout`)
+        let rag_text = rag_text(&prompt, &chat_history);
+        let response = self
+            .stream_completion(prompt, chat_history)
             .await?
             .stream()
-            .await
+            .await?;
+        Ok(response.with_rag_text(rag_text))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{
-        completion::{CompletionModel, CompletionRequest, CompletionResponse},
-        streaming::StreamingCompletionResponse,
-        OneOrMany,
-    };
+    use crate::testing::MockCompletionModel;
     use serde_json::json;
-    // Mock completion model for testing
-    #[derive(Clone)]
-    struct MockCompletionModel {
-        response: String,
-    }
-
-    impl MockCompletionModel {
-        fn new(response: &str) -> Self {
-            Self {
-                response: response.to_string(),
-            }
-        }
-    }
-
-    #[derive(Clone)]
-    struct MockStreamingResponse;
-
-    impl CompletionModel for MockCompletionModel {
-        type Response = String;
-        type StreamingResponse = MockStreamingResponse;
-
-        async fn completion(
-            &self,
-            _request: CompletionRequest,
-        ) -> Result<CompletionResponse<Self::Response>, CompletionError> {
-            Ok(CompletionResponse {
-                choice: OneOrMany::one(crate::completion::AssistantContent::text(&self.response)),
-                raw_response: self.response.clone(),
-            })
-        }
-
-        async fn stream(
-            &self,
-            _request: CompletionRequest,
-        ) -> Result<StreamingCompletionResponse<Self::StreamingResponse>, CompletionError> {
-            todo!()
-        }
-    }
 
     #[test]
     fn test_agent_include_reason_in_content_default() {
@@ -419,6 +761,500 @@ mod tests {
         assert_eq!(params["custom_field"], "custom_value");
     }
 
+    #[tokio::test]
+    async fn test_agent_completion_renders_preamble_template() {
+        let model = MockCompletionModel::new("Test response");
+        let agent = crate::agent::AgentBuilder::new(model)
+            .preamble_template("You are assisting {user}.")
+            .preamble_vars(HashMap::from([("user".to_string(), "Alice".to_string())]))
+            .build();
+
+        let request = agent
+            .completion("Test prompt", vec![])
+            .await
+            .expect("Should create completion builder")
+            .build();
+
+        assert_eq!(
+            request.preamble.as_deref(),
+            Some("You are assisting Alice.")
+        );
+    }
+
+    #[cfg(feature = "date-time")]
+    #[tokio::test]
+    async fn test_agent_completion_injects_date_time() {
+        let model = MockCompletionModel::new("Test response");
+        let agent = crate::agent::AgentBuilder::new(model)
+            .preamble("Test preamble")
+            .inject_date_time_tz(chrono_tz::Europe::Paris)
+            .build();
+
+        let request = agent
+            .completion("Test prompt", vec![])
+            .await
+            .expect("Should create completion builder")
+            .build();
+
+        let preamble = request.preamble.unwrap();
+        let (date_time, preamble) = preamble
+            .strip_prefix("Current date and time: ")
+            .and_then(|preamble| preamble.split_once('\n'))
+            .expect("Date and time should be prepended to the preamble");
+        assert!(chrono::DateTime::parse_from_rfc3339(date_time).is_ok());
+        assert_eq!(preamble, "Test preamble");
+    }
+
+    #[tokio::test]
+    async fn test_agent_completion_missing_preamble_variable() {
+        let model = MockCompletionModel::new("Test response");
+        let agent = crate::agent::AgentBuilder::new(model)
+            .preamble_template("You are assisting {user}.")
+            .build();
+
+        let result = agent.completion("Test prompt", vec![]).await;
+
+        assert!(matches!(result, Err(CompletionError::RequestError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_agent_completion_context_window_exceeded() {
+        let model = MockCompletionModel::new("Test response");
+        let agent = crate::agent::AgentBuilder::new(model)
+            .preamble("Test preamble")
+            .max_prompt_tokens(20)
+            .build();
+
+        let result = agent.completion("A".repeat(100), vec![]).await;
+
+        // The preamble (4 + 4 tokens), prompt (25 + 4 tokens) and reply priming (3 tokens)
+        assert!(matches!(
+            result,
+            Err(CompletionError::ContextWindowExceeded {
+                estimated: 40,
+                limit: 20
+            })
+        ));
+        // 8 + (1 + 4) + 3 tokens
+        assert!(agent.completion("Hi", vec![]).await.is_ok());
+    }
+
+    fn long_chat_history() -> Vec<Message> {
+        (0..3)
+            .flat_map(|_| {
+                [
+                    Message::user("x".repeat(40)),
+                    Message::assistant("x".repeat(40)),
+                ]
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_agent_context_window_truncates_oldest() {
+        let model = MockCompletionModel::new("Test response");
+        let agent = crate::agent::AgentBuilder::new(model)
+            .context_window_size(40)
+            .build();
+
+        let request = agent
+            .completion("Hi", long_chat_history())
+            .await
+            .expect("Should create completion builder")
+            .build();
+
+        // Only the last (user, assistant) exchange fits, followed by the prompt
+        assert_eq!(request.chat_history.len(), 3);
+        assert_eq!(request.chat_history.first(), Message::user("x".repeat(40)));
+    }
+
+    #[tokio::test]
+    async fn test_agent_context_window_summarizes_oldest() {
+        let model = MockCompletionModel::new("Test response");
+        let agent = crate::agent::AgentBuilder::new(model)
+            .preamble("Test preamble")
+            .context_window_size(50)
+            .truncation_strategy(crate::agent::TruncationStrategy::SummarizeOldest)
+            .build();
+
+        let request = agent
+            .completion("Hi", long_chat_history())
+            .await
+            .expect("Should create completion builder")
+            .build();
+
+        assert_eq!(request.chat_history.len(), 3);
+        assert_eq!(request.preamble.as_deref(), Some("Test preamble"));
+        assert_eq!(
+            request.system_messages,
+            vec!["Summary of the earlier conversation: Test response"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_agent_summarize_with() {
+        let summarizer =
+            crate::agent::AgentBuilder::new(MockCompletionModel::new("Summary")).build();
+        let agent = crate::agent::AgentBuilder::new(MockCompletionModel::new("Test response"))
+            .summarize_with(summarizer, 40)
+            .build();
+
+        let request = agent
+            .completion("Hi", long_chat_history())
+            .await
+            .expect("Should create completion builder")
+            .build();
+
+        assert_eq!(request.chat_history.len(), 3);
+        assert_eq!(
+            request.system_messages,
+            vec!["Summary of the earlier conversation: Summary"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_agent_build_checked_passes() {
+        let model = MockCompletionModel::new("Ahoy, matey!");
+        let agent = crate::agent::AgentBuilder::new(model)
+            .preamble("You are a pirate.")
+            .assert_instruction("Greet me", "Ahoy")
+            .build_checked()
+            .await;
+
+        assert_eq!(agent.preamble, "You are a pirate.");
+    }
+
+    #[tokio::test]
+    async fn test_agent_build_checked_fails_on_mismatch() {
+        let model = MockCompletionModel::new("Hello there.");
+        let result = crate::agent::AgentBuilder::new(model)
+            .preamble("You are a pirate.")
+            .assert_instruction("Greet me", "Ahoy")
+            .try_build_checked()
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(crate::agent::InstructionCheckError::Mismatch { response, .. })
+                if response == "Hello there."
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_agent_stream_text() {
+        let agent = crate::agent::AgentBuilder::new(crate::testing::MockStreamingModel::new(
+            "Once upon a time",
+        ))
+        .build();
+
+        let chunks = agent
+            .stream_text("Tell me a story")
+            .await
+            .unwrap()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+
+        assert_eq!(chunks.len(), "Once upon a time".len());
+        assert_eq!(chunks.concat(), "Once upon a time");
+    }
+
+    #[tokio::test]
+    async fn test_agent_stream_rag_text() {
+        let agent =
+            crate::agent::AgentBuilder::new(crate::testing::MockStreamingModel::new("Hey")).build();
+
+        let stream = agent.stream_prompt("Please echo this").await.unwrap();
+        assert_eq!(stream.rag_text(), Some("Please echo this"));
+    }
+
+    #[tokio::test]
+    async fn test_agent_dynamic_preamble() {
+        let agent = crate::agent::AgentBuilder::new(MockCompletionModel::new("Bonjour !"))
+            .preamble("Static preamble")
+            .dynamic_preamble(|context| {
+                format!(
+                    "You are assisting {} ({} previous messages).",
+                    context
+                        .metadata
+                        .get("user")
+                        .map_or("a guest", String::as_str),
+                    context.chat_history.len()
+                )
+            })
+            .build();
+
+        let request = agent
+            .completion_with_metadata(
+                "Hello".into(),
+                vec![Message::user("Hi"), Message::assistant("Hi!")],
+                &HashMap::from([("user".to_string(), "Alice".to_string())]),
+            )
+            .await
+            .unwrap()
+            .build();
+        assert_eq!(
+            request.preamble.as_deref(),
+            Some("You are assisting Alice (2 previous messages).")
+        );
+
+        let request = agent.completion("Hello", vec![]).await.unwrap().build();
+        assert_eq!(
+            request.preamble.as_deref(),
+            Some("You are assisting a guest (0 previous messages).")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_agent_last_trace() {
+        let agent = crate::agent::AgentBuilder::new(MockCompletionModel::new("Paris."))
+            .preamble("You are a geography expert.")
+            .context("France is a country in Europe.")
+            .build();
+        assert!(agent.last_trace().is_none());
+
+        agent
+            .prompt("What is the capital of France?")
+            .await
+            .unwrap();
+
+        let trace = agent.last_trace().unwrap();
+        assert_eq!(
+            trace.preamble.as_deref(),
+            Some("You are a geography expert.")
+        );
+        assert_eq!(trace.documents.len(), 1);
+        assert_eq!(trace.documents[0].0, None);
+        assert!(trace.tools.is_empty());
+        assert_eq!(
+            trace.response,
+            OneOrMany::one(AssistantContent::text("Paris."))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_agent_max_tool_iterations() {
+        use crate::{
+            completion::{CompletionResponse, ToolDefinition},
+            tool::{Tool, ToolError},
+        };
+
+        struct Ping;
+
+        impl Tool for Ping {
+            const NAME: &'static str = "ping";
+
+            type Error = ToolError;
+            type Args = serde_json::Value;
+            type Output = String;
+
+            async fn definition(&self, _prompt: String) -> ToolDefinition {
+                ToolDefinition {
+                    name: Self::NAME.to_string(),
+                    description: "Returns pong".to_string(),
+                    parameters: json!({}),
+                }
+            }
+
+            async fn call(&self, _args: Self::Args) -> Result<Self::Output, Self::Error> {
+                Ok("pong".to_string())
+            }
+        }
+
+        // The model never stops calling the tool
+        let model = MockCompletionModel::from_fn(|_| CompletionResponse {
+            choice: OneOrMany::one(AssistantContent::tool_call("call_1", "ping", json!({}))),
+            raw_response: (),
+        });
+        let agent = crate::agent::AgentBuilder::new(model.clone())
+            .tool(Ping)
+            .max_tool_iterations(3)
+            .build();
+
+        let result = agent.prompt("Ping").multi_turn(20).await;
+
+        assert!(matches!(
+            result,
+            Err(PromptError::CompletionError(
+                CompletionError::MaxIterationsExceeded(3)
+            ))
+        ));
+        assert_eq!(model.requests().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_agent_response_validator() {
+        let validator = |response: &str| {
+            serde_json::from_str::<serde_json::Value>(response)
+                .map(|_| ())
+                .map_err(|_| "Not JSON".to_string())
+        };
+
+        let model = MockCompletionModel::sequence(["Paris", r#"{"city": "Paris"}"#]);
+        let agent = crate::agent::AgentBuilder::new(model.clone())
+            .response_validator(validator)
+            .build();
+
+        let response = agent.prompt("Capital of France?").await.unwrap();
+        assert_eq!(response, r#"{"city": "Paris"}"#);
+
+        let requests = model.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(
+            requests[1].chat_history.iter().last().unwrap().rag_text(),
+            Some("Your previous response is invalid: Not JSON\nPlease correct it.".to_string())
+        );
+
+        let agent = crate::agent::AgentBuilder::new(MockCompletionModel::new("Paris"))
+            .response_validator(validator)
+            .max_retries(1)
+            .build();
+
+        let result = agent.prompt("Capital of France?").await;
+        assert!(matches!(
+            result,
+            Err(PromptError::CompletionError(
+                CompletionError::ResponseError(_)
+            ))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_agent_prompt_hooks() {
+        let model = MockCompletionModel::new("My email is jane@example.com");
+        let agent = crate::agent::AgentBuilder::new(model.clone())
+            .pre_prompt_hook(|prompt| async move {
+                match prompt.rag_text() {
+                    Some(text) if text.contains("password") => Err(
+                        CompletionError::SecurityViolation("Prompt contains a password".into()),
+                    ),
+                    Some(text) => Ok(Message::user(text.to_uppercase())),
+                    None => Ok(prompt),
+                }
+            })
+            .post_prompt_hook(|response| async move {
+                Ok(response.replace("jane@example.com", "[REDACTED]"))
+            })
+            .build();
+
+        let response = agent.prompt("What is my email?").await.unwrap();
+        assert_eq!(response, "My email is [REDACTED]");
+        assert_eq!(
+            model.requests()[0]
+                .chat_history
+                .iter()
+                .last()
+                .unwrap()
+                .rag_text(),
+            Some("WHAT IS MY EMAIL?".to_string())
+        );
+
+        let result = agent.prompt("My password is hunter2").await;
+        assert!(matches!(
+            result,
+            Err(PromptError::CompletionError(
+                CompletionError::SecurityViolation(_)
+            ))
+        ));
+        assert_eq!(model.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_agent_preamble_file() {
+        use assert_fs::prelude::{FileWriteStr, PathChild};
+
+        let temp = assert_fs::TempDir::new().expect("Failed to create temp dir");
+        let file = temp.child("persona.txt");
+        file.write_str("You are a pirate.")
+            .expect("Failed to write to persona.txt");
+
+        let agent = crate::agent::AgentBuilder::new(MockCompletionModel::new("Ahoy!"))
+            .preamble_file(file.path())
+            .unwrap()
+            .build();
+        assert_eq!(agent.preamble, "You are a pirate.");
+
+        let agent = crate::agent::AgentBuilder::new(MockCompletionModel::new("Ahoy!"))
+            .preamble_file_async(file.path())
+            .await
+            .unwrap()
+            .build();
+        assert_eq!(agent.preamble, "You are a pirate.");
+
+        let result = crate::agent::AgentBuilder::new(MockCompletionModel::new("Ahoy!"))
+            .preamble_file(temp.path().join("missing.txt"));
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_agent_initial_messages() {
+        let agent = crate::agent::AgentBuilder::new(MockCompletionModel::new("negative"))
+            .preamble("Classify the sentiment of the user's message.")
+            .initial_messages(vec![
+                Message::user("I love this product!"),
+                Message::assistant("positive"),
+            ])
+            .build();
+
+        let request = agent
+            .completion(
+                "It broke after a day.",
+                vec![Message::user("Hello"), Message::assistant("Hi!")],
+            )
+            .await
+            .expect("Should create completion builder")
+            .build();
+
+        assert_eq!(
+            request.chat_history.into_iter().collect::<Vec<_>>(),
+            vec![
+                Message::user("I love this product!"),
+                Message::assistant("positive"),
+                Message::user("Hello"),
+                Message::assistant("Hi!"),
+                Message::user("It broke after a day."),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_agent_context_strategy() {
+        let build_request = |context_strategy| async move {
+            crate::agent::AgentBuilder::new(MockCompletionModel::new("Test response"))
+                .preamble("Test preamble")
+                .context("Rig is a Rust library.")
+                .context_strategy(context_strategy)
+                .build()
+                .completion("What is Rig?", vec![])
+                .await
+                .expect("Should create completion builder")
+                .build()
+        };
+        let doc = "<file id: static_doc_0>\nRig is a Rust library.\n</file>\n";
+
+        let request = build_request(ContextStrategy::DocumentsField).await;
+        assert_eq!(request.documents.len(), 1);
+        assert_eq!(request.preamble.as_deref(), Some("Test preamble"));
+
+        let request = build_request(ContextStrategy::SystemPromptAppend).await;
+        assert!(request.documents.is_empty());
+        assert_eq!(request.preamble, Some(format!("Test preamble\n\n{doc}")));
+
+        let request = build_request(ContextStrategy::UserMessagePrepend).await;
+        assert!(request.documents.is_empty());
+        assert_eq!(request.preamble.as_deref(), Some("Test preamble"));
+        assert_eq!(
+            request.chat_history.iter().last().unwrap(),
+            &Message::User {
+                content: OneOrMany::many([
+                    UserContent::text(doc),
+                    UserContent::text("What is Rig?")
+                ])
+                .unwrap()
+            }
+        );
+    }
+
     // Integration tests that require real DeepSeek API
     // Note: Run with DEEPSEEK_API_KEY set and --ignored flag
     mod integration_tests {
@@ -0,0 +1,156 @@
+//! This module contains the implementation of the [DebateAgent] struct, which explores a claim
+//! through a structured debate between two agents before a third agent issues a verdict.
+//!
+//! On each round, the proponent argues in favor of the claim and the opponent argues against it.
+//! Both agents see the full transcript of the previous rounds so they can rebut each other.
+//! Once all rounds are over, the judge reads the transcript and issues a verdict.
+//!
+//! # Example
+//! ```rust
+//! use rig::{agent::debate::DebateAgent, providers::openai};
+//!
+//! let openai = openai::Client::from_env();
+//!
+//! let debate = DebateAgent::new(
+//!     openai.agent("gpt-4o").preamble("You argue in favor of the claim.").build(),
+//!     openai.agent("gpt-4o").preamble("You argue against the claim.").build(),
+//!     openai.agent("gpt-4o").preamble("You are an impartial judge.").build(),
+//! );
+//!
+//! let transcript = debate
+//!     .debate("Remote work is more productive than office work", 3)
+//!     .await
+//!     .expect("Failed to run the debate");
+//!
+//! println!("Verdict: {}", transcript.verdict);
+//! ```
+use serde::{Deserialize, Serialize};
+
+use crate::completion::{CompletionModel, Prompt, PromptError};
+
+use super::Agent;
+
+/// The result of a debate.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct DebateTranscript {
+    /// The (proponent, opponent) arguments of each round, in order
+    pub rounds: Vec<(String, String)>,
+    /// The judge's verdict
+    pub verdict: String,
+}
+
+impl std::fmt::Display for DebateTranscript {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, (proponent, opponent)) in self.rounds.iter().enumerate() {
+            writeln!(f, "Round {}", i + 1)?;
+            writeln!(f, "Proponent: {proponent}")?;
+            writeln!(f, "Opponent: {opponent}")?;
+        }
+        write!(f, "Verdict: {}", self.verdict)
+    }
+}
+
+/// Two agents debating a claim, and a third one judging the debate.
+pub struct DebateAgent<M: CompletionModel> {
+    /// Agent arguing in favor of the claim
+    pub proponent: Agent<M>,
+    /// Agent arguing against the claim
+    pub opponent: Agent<M>,
+    /// Agent issuing the verdict once the debate is over
+    pub judge: Agent<M>,
+}
+
+impl<M: CompletionModel> DebateAgent<M> {
+    pub fn new(proponent: Agent<M>, opponent: Agent<M>, judge: Agent<M>) -> Self {
+        Self {
+            proponent,
+            opponent,
+            judge,
+        }
+    }
+
+    /// Run a debate of `rounds` rounds on `claim` and return the transcript with the judge's verdict.
+    pub async fn debate(
+        &self,
+        claim: &str,
+        rounds: usize,
+    ) -> Result<DebateTranscript, PromptError> {
+        let mut transcript = DebateTranscript::default();
+
+        for round in 1..=rounds {
+            let proponent = self
+                .proponent
+                .prompt(format!(
+                    "Claim: {claim}\n\n{}Round {round} of {rounds}: argue IN FAVOR of the claim.",
+                    history(&transcript)
+                ))
+                .await?;
+
+            let opponent = self
+                .opponent
+                .prompt(format!(
+                    "Claim: {claim}\n\n{}Round {round} of {rounds}, proponent: {proponent}\n\n\
+                    Argue AGAINST the claim and rebut the proponent.",
+                    history(&transcript)
+                ))
+                .await?;
+
+            transcript.rounds.push((proponent, opponent));
+        }
+
+        transcript.verdict = self
+            .judge
+            .prompt(format!(
+                "Claim: {claim}\n\n{}The debate is over. Weigh the arguments of both sides \
+                and issue a verdict on the claim.",
+                history(&transcript)
+            ))
+            .await?;
+
+        Ok(transcript)
+    }
+}
+
+/// Format the previous rounds of the debate so they can be included in a prompt.
+fn history(transcript: &DebateTranscript) -> String {
+    transcript
+        .rounds
+        .iter()
+        .enumerate()
+        .map(|(i, (proponent, opponent))| {
+            format!(
+                "Round {}, proponent: {proponent}\n\nRound {}, opponent: {opponent}\n\n",
+                i + 1,
+                i + 1
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{agent::AgentBuilder, testing::MockCompletionModel};
+
+    fn agent(response: &str) -> Agent<MockCompletionModel> {
+        AgentBuilder::new(MockCompletionModel::new(response)).build()
+    }
+
+    #[tokio::test]
+    async fn test_debate() {
+        let debate = DebateAgent::new(agent("for"), agent("against"), agent("undecided"));
+
+        let transcript = debate.debate("The earth is flat", 2).await.unwrap();
+
+        assert_eq!(
+            transcript,
+            DebateTranscript {
+                rounds: vec![
+                    ("for".to_string(), "against".to_string()),
+                    ("for".to_string(), "against".to_string())
+                ],
+                verdict: "undecided".to_string(),
+            }
+        );
+    }
+}
@@ -0,0 +1,80 @@
+//! This module contains the [SummarizingMemory] struct, which compresses the oldest messages
+//! of an agent's chat history into a summary once the history grows too large.
+//!
+//! The summary is generated by a separate summarizer [Agent], which allows using a fast and
+//! cheap model for summarization while the main agent uses a more capable one. It is sent to the
+//! model as a system message following the preamble.
+//!
+//! # Example
+//! ```rust
+//! use rig::providers::openai;
+//!
+//! let openai = openai::Client::from_env();
+//!
+//! let summarizer = openai.agent("gpt-4o-mini")
+//!     .preamble("You summarize conversations concisely.")
+//!     .build();
+//!
+//! let agent = openai.agent("gpt-4o")
+//!     .preamble("You are a helpful assistant.")
+//!     .summarize_with(summarizer, 8000)
+//!     .build();
+//! ```
+use std::sync::Arc;
+
+use futures::future::BoxFuture;
+
+use crate::{
+    completion::{CompletionModel, Message, Prompt, PromptError},
+    tokens,
+};
+
+use super::Agent;
+
+type SummarizeFn = dyn Fn(String) -> BoxFuture<'static, Result<String, PromptError>> + Send + Sync;
+
+/// Summarizes the oldest messages of a chat history with a (possibly different) agent.
+#[derive(Clone)]
+/// When the messages are summarized is set by the context window of the agent
+/// (see [AgentBuilder::summarize_with](super::AgentBuilder::summarize_with)).
+pub struct SummarizingMemory {
+    summarizer: Arc<SummarizeFn>,
+}
+
+impl SummarizingMemory {
+    /// Create a memory that summarizes the oldest messages with `summarizer`.
+    pub fn new<S: CompletionModel + 'static>(summarizer: Agent<S>) -> Self {
+        let summarizer = Arc::new(summarizer);
+
+        Self {
+            summarizer: Arc::new(move |prompt: String| {
+                let summarizer = summarizer.clone();
+                Box::pin(async move { summarizer.prompt(prompt).await })
+            }),
+        }
+    }
+
+    /// Summarize the given messages.
+    pub async fn summarize(&self, messages: &[Message]) -> Result<String, PromptError> {
+        (self.summarizer)(summary_prompt(messages)).await
+    }
+}
+
+/// The prompt used to ask a model for a summary of `messages`.
+pub(crate) fn summary_prompt(messages: &[Message]) -> String {
+    let transcript = messages
+        .iter()
+        .map(|message| match message {
+            Message::User { .. } => format!("user: {}", tokens::message_text(message)),
+            Message::Assistant { .. } => {
+                format!("assistant: {}", tokens::message_text(message))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    format!(
+        "Summarize the following conversation in a few sentences, keeping any \
+        information that may be needed to continue it:\n\n{transcript}"
+    )
+}
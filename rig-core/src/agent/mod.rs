@@ -108,9 +108,25 @@
 //! ```
 
 mod builder;
+mod chain_of_thought;
 mod completion;
+pub mod debate;
+mod memory;
 mod prompt_request;
+pub mod react;
+mod router;
+pub mod self_refinement;
+pub mod supervisor;
+pub mod tree_of_thought;
 
-pub use builder::AgentBuilder;
-pub use completion::Agent;
+pub use builder::{AgentBuilder, InstructionCheckError};
+pub use chain_of_thought::{ChainOfThoughtAgent, ChainOfThoughtResponse};
+pub use completion::{
+    Agent, AgentCallContext, AgentTrace, ContextStrategy, DynamicPreamble, PostPromptHook,
+    PrePromptHook, ResponseValidator, TruncationStrategy,
+};
+pub use memory::SummarizingMemory;
+#[cfg(feature = "server")]
+pub(crate) use prompt_request::append_preamble;
 pub use prompt_request::PromptRequest;
+pub use router::FunctionRouter;
@@ -1,15 +1,16 @@
-use std::future::IntoFuture;
+use std::{collections::HashMap, future::IntoFuture, time::Instant};
 
-use futures::{future::BoxFuture, stream, FutureExt, StreamExt};
+use futures::{future::BoxFuture, FutureExt};
+use tracing::Instrument;
 
 use crate::{
-    completion::{Completion, CompletionError, CompletionModel, Message, PromptError},
+    completion::{CompletionError, CompletionModel, Message, PromptError},
     message::{AssistantContent, UserContent},
     tool::ToolSetError,
     OneOrMany,
 };
 
-use super::Agent;
+use super::{completion::rag_text, Agent};
 
 /// A builder for creating prompt requests with customizable options.
 /// Uses generics to track which options have been set during the build process.
@@ -21,6 +22,10 @@ pub struct PromptRequest<'a, M: CompletionModel> {
     chat_history: Option<&'a mut Vec<Message>>,
     /// Maximum depth for multi-turn conversations (0 means no multi-turn)
     max_depth: usize,
+    /// Metadata passed to the agent's dynamic preamble
+    metadata: HashMap<String, String>,
+    /// Instructions appended to the agent's preamble for this request only
+    additional_preamble: Option<String>,
     /// The agent to use for execution
     agent: &'a Agent<M>,
 }
@@ -32,6 +37,8 @@ impl<'a, M: CompletionModel> PromptRequest<'a, M> {
             prompt: prompt.into(),
             chat_history: None,
             max_depth: 0,
+            metadata: HashMap::new(),
+            additional_preamble: None,
             agent,
         }
     }
@@ -44,6 +51,8 @@ impl<'a, M: CompletionModel> PromptRequest<'a, M> {
             prompt: self.prompt,
             chat_history: self.chat_history,
             max_depth: depth,
+            metadata: self.metadata,
+            additional_preamble: self.additional_preamble,
             agent: self.agent,
         }
     }
@@ -54,9 +63,25 @@ impl<'a, M: CompletionModel> PromptRequest<'a, M> {
             prompt: self.prompt,
             chat_history: Some(history),
             max_depth: self.max_depth,
+            metadata: self.metadata,
+            additional_preamble: self.additional_preamble,
             agent: self.agent,
         }
     }
+
+    /// Attach metadata to the request (e.g.: the id of the user), which is passed to the
+    /// agent's [dynamic preamble](crate::agent::AgentBuilder::dynamic_preamble).
+    pub fn metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+
+    /// Append `preamble` to the agent's preamble for this request only (e.g.: the system
+    /// messages of a client of the agent).
+    pub fn additional_preamble(mut self, preamble: impl Into<String>) -> Self {
+        self.additional_preamble = Some(preamble.into());
+        self
+    }
 }
 
 /// Due to: [RFC 2515](https://github.com/rust-lang/rust/issues/63063), we have to use a `BoxFuture`
@@ -67,7 +92,10 @@ impl<'a, M: CompletionModel> IntoFuture for PromptRequest<'a, M> {
     type IntoFuture = BoxFuture<'a, Self::Output>; // This future should not outlive the agent
 
     fn into_future(self) -> Self::IntoFuture {
-        self.send().boxed()
+        // The completion spans of the prompt (see [crate::tracing::OtelInstrumented]) are nested
+        // in this span, itself a child of the current span of the caller
+        let span = tracing::info_span!(target: "rig", "prompt", max_depth = self.max_depth);
+        self.send().instrument(span).boxed()
     }
 }
 
@@ -75,15 +103,22 @@ impl<M: CompletionModel> PromptRequest<'_, M> {
     async fn send(self) -> Result<String, PromptError> {
         let agent = self.agent;
         let mut prompt = self.prompt;
+        for hook in &agent.pre_prompt_hooks {
+            prompt = hook(prompt).await?;
+        }
         let chat_history = if let Some(history) = self.chat_history {
             history
         } else {
             &mut Vec::new()
         };
 
+        let start = Instant::now();
+        let mut tool_iterations = 0;
+        let mut retries = 0;
         let mut current_max_depth = 0;
         // We need to do atleast 2 loops for 1 roundtrip (user expects normal message)
-        while current_max_depth <= self.max_depth + 1 {
+        // Retries of invalid responses do not count towards the depth
+        while current_max_depth <= self.max_depth + 1 + retries {
             current_max_depth += 1;
 
             if self.max_depth > 1 {
@@ -94,11 +129,17 @@ impl<M: CompletionModel> PromptRequest<'_, M> {
                 );
             }
 
-            let resp = agent
-                .completion(prompt.clone(), chat_history.to_vec())
-                .await?
-                .send()
+            // The conditional tools available for the request are the ones which can be called
+            let rag_text = rag_text(&prompt, chat_history).unwrap_or_default();
+            let (builder, scores) = agent
+                .completion_with_scores(prompt.clone(), chat_history.to_vec(), &self.metadata)
                 .await?;
+            let mut request = builder.build();
+            if let Some(additional_preamble) = &self.additional_preamble {
+                request.preamble = Some(append_preamble(request.preamble, additional_preamble));
+            }
+            let resp = agent.model.completion(request.clone()).await?;
+            agent.record_trace(request, &scores, resp.choice.clone(), start.elapsed());
 
             chat_history.push(prompt);
 
@@ -124,38 +165,65 @@ impl<M: CompletionModel> PromptRequest<'_, M> {
                     .collect::<Vec<_>>()
                     .join("\n");
 
+                if let Some(Err(feedback)) = agent
+                    .response_validator
+                    .as_ref()
+                    .map(|validator| validator(&merged_texts))
+                {
+                    if retries == agent.max_retries {
+                        return Err(CompletionError::ResponseError(format!(
+                            "Invalid response after {retries} retries: {feedback}"
+                        ))
+                        .into());
+                    }
+                    retries += 1;
+                    tracing::info!(
+                        target: "rig",
+                        "Invalid response, retrying ({retries}/{}): {feedback}",
+                        agent.max_retries
+                    );
+
+                    prompt = Message::user(format!(
+                        "Your previous response is invalid: {feedback}\nPlease correct it."
+                    ));
+                    continue;
+                }
+
                 if self.max_depth > 1 {
                     tracing::info!("Depth reached: {}/{}", current_max_depth, self.max_depth);
                 }
 
                 // If there are no tool calls, depth is not relevant, we can just return the merged text.
-                return Ok(merged_texts);
+                let mut response = merged_texts;
+                for hook in &agent.post_prompt_hooks {
+                    response = hook(response).await?;
+                }
+                return Ok(response);
             }
 
-            let tool_content = stream::iter(tool_calls)
-                .then(|choice| async move {
-                    if let AssistantContent::ToolCall(tool_call) = choice {
-                        let output = agent
-                            .tools
-                            .call(
-                                &tool_call.function.name,
-                                tool_call.function.arguments.to_string(),
-                            )
-                            .await?;
-                        Ok(UserContent::tool_result(
-                            tool_call.id.clone(),
-                            OneOrMany::one(output.into()),
-                        ))
-                    } else {
-                        unreachable!(
-                            "This should never happen as we already filtered for `ToolCall`"
-                        )
-                    }
+            if tool_iterations == agent.max_tool_iterations {
+                return Err(
+                    CompletionError::MaxIterationsExceeded(agent.max_tool_iterations).into(),
+                );
+            }
+            tool_iterations += 1;
+
+            let tool_calls = tool_calls
+                .into_iter()
+                .filter_map(|choice| match choice {
+                    AssistantContent::ToolCall(tool_call) => Some(tool_call.clone()),
+                    _ => None,
                 })
-                .collect::<Vec<Result<UserContent, ToolSetError>>>()
+                .collect();
+
+            // Tool calls are executed concurrently since they are independent from each other
+            let tool_content = agent
+                .tools
+                .execute_all(tool_calls, &rag_text)
                 .await
                 .into_iter()
-                .collect::<Result<Vec<_>, _>>()
+                .map(|result| result.map(UserContent::ToolResult))
+                .collect::<Result<Vec<_>, ToolSetError>>()
                 .map_err(|e| CompletionError::RequestError(Box::new(e)))?;
 
             prompt = Message::User {
@@ -171,3 +239,11 @@ impl<M: CompletionModel> PromptRequest<'_, M> {
         })
     }
 }
+
+/// Append `additional_preamble` to `preamble` (e.g.: the system messages of a client).
+pub(crate) fn append_preamble(preamble: Option<String>, additional_preamble: &str) -> String {
+    match preamble {
+        Some(preamble) if !preamble.is_empty() => format!("{preamble}\n\n{additional_preamble}"),
+        _ => additional_preamble.to_string(),
+    }
+}
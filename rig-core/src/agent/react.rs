@@ -0,0 +1,347 @@
+//! This module contains the implementation of the [ReactAgent] struct, which runs the ReAct
+//! (Reason + Act) loop explicitly, rather than relying on the native tool calling of the model.
+//!
+//! On each iteration, the model is prompted with the question and the transcript of the previous
+//! iterations, and continues it with a thought and an action of the form
+//! `Action: tool_name(arguments)`. The tool is called with the arguments (usually a JSON object),
+//! and its output is appended to the transcript as an observation. The loop stops when the model
+//! gives its answer with `Action: Final Answer(answer)`.
+//!
+//! The tools are the static tools of the wrapped agent.
+//!
+//! # Example
+//! ```rust
+//! use rig::{agent::react::ReactAgent, providers::openai};
+//!
+//! let openai = openai::Client::from_env();
+//!
+//! let agent = ReactAgent::new(
+//!     openai
+//!         .agent("gpt-4o")
+//!         .preamble("You are a helpful assistant.")
+//!         .tool(Adder)
+//!         .build(),
+//! )
+//! .max_iterations(5);
+//!
+//! let response = agent.react("What is 2 + 3?").await.expect("Failed to run the agent");
+//!
+//! println!("Answer: {}", response.answer);
+//! ```
+use serde::{Deserialize, Serialize};
+
+use crate::completion::{AssistantContent, CompletionModel, Message, Prompt, PromptError};
+
+use super::Agent;
+
+/// Maximum number of iterations of the loop, by default.
+const DEFAULT_MAX_ITERATIONS: usize = 10;
+/// Name of the action giving the final answer
+const FINAL_ANSWER: &str = "Final Answer";
+
+/// An iteration of the ReAct loop.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReactStep {
+    pub thought: String,
+    /// Name of the tool called
+    pub action: String,
+    /// Arguments of the tool call
+    pub action_input: String,
+    /// Output of the tool call (or the error it returned)
+    pub observation: String,
+}
+
+/// The iterations of a [ReactAgent] and its final answer.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReactResponse {
+    pub steps: Vec<ReactStep>,
+    pub answer: String,
+}
+
+/// An agent answering prompts with the ReAct loop (think → act → observe → repeat).
+pub struct ReactAgent<M: CompletionModel> {
+    agent: Agent<M>,
+    max_iterations: usize,
+}
+
+impl<M: CompletionModel> ReactAgent<M> {
+    pub fn new(agent: Agent<M>) -> Self {
+        Self {
+            agent,
+            max_iterations: DEFAULT_MAX_ITERATIONS,
+        }
+    }
+
+    /// Set the maximum number of iterations of the loop (10 by default).
+    pub fn max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Returns the preamble of the agent, followed by the instructions of the ReAct format and
+    /// the descriptions of the tools.
+    async fn preamble(&self, prompt: &str) -> String {
+        let tools = self
+            .agent
+            .tools
+            .available_definitions(&self.agent.static_tools, prompt)
+            .await
+            .into_iter()
+            .map(|tool| {
+                format!(
+                    "- {}: {} Arguments: {}",
+                    tool.name, tool.description, tool.parameters
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        format!(
+            "{}\n\n\
+            Answer the question by alternating thoughts and actions, in the following format:\n\
+            Thought: your reasoning about what to do next\n\
+            Action: tool_name(arguments as a JSON object)\n\
+            Observation: the output of the tool (given to you, do not write it)\n\
+            ... (Thought/Action/Observation can repeat several times)\n\
+            Thought: I now know the final answer\n\
+            Action: {FINAL_ANSWER}(the final answer)\n\n\
+            The available tools are:\n{tools}",
+            self.agent.preamble
+        )
+    }
+
+    /// Run the ReAct loop on `prompt` until the model gives its final answer.
+    /// Fails with [PromptError::MaxDepthError] if there is no final answer after the maximum
+    /// number of iterations.
+    pub async fn react(&self, prompt: &str) -> Result<ReactResponse, PromptError> {
+        let preamble = self.preamble(prompt).await;
+        let mut steps: Vec<ReactStep> = vec![];
+
+        for _ in 0..self.max_iterations {
+            let transcript = steps
+                .iter()
+                .map(|step| match step.action.as_str() {
+                    // The response of the model could not be parsed
+                    "" => format!(
+                        "Thought: {}\nObservation: {}\n",
+                        step.thought, step.observation
+                    ),
+                    _ => format!(
+                        "Thought: {}\nAction: {}({})\nObservation: {}\n",
+                        step.thought, step.action, step.action_input, step.observation
+                    ),
+                })
+                .collect::<String>();
+
+            let response = CompletionModel::completion_request(
+                &self.agent.model,
+                format!("Question: {prompt}\n{transcript}Thought:"),
+            )
+            .preamble(preamble.clone())
+            .temperature_opt(self.agent.temperature)
+            .max_tokens_opt(self.agent.max_tokens)
+            .send()
+            .await?;
+            let text = response
+                .choice
+                .iter()
+                .filter_map(|content| match content {
+                    AssistantContent::Text(text) => Some(text.text.clone()),
+                    AssistantContent::ToolCall(_) => None,
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            let Some((thought, action, action_input)) = parse_action(&text) else {
+                steps.push(ReactStep {
+                    thought: text.trim().to_string(),
+                    observation: "Invalid format: the thought should be followed by \
+                        `Action: tool_name(arguments)`"
+                        .to_string(),
+                    ..Default::default()
+                });
+                continue;
+            };
+
+            if action.eq_ignore_ascii_case(FINAL_ANSWER) {
+                return Ok(ReactResponse {
+                    steps,
+                    answer: action_input,
+                });
+            }
+
+            tracing::debug!(target: "rig", "ReAct action: {action}({action_input})");
+            // Errors are observations, so that the model can recover from them
+            let observation = match self
+                .agent
+                .tools
+                .call_with_prompt(&action, action_input.clone(), prompt)
+                .await
+            {
+                Ok(output) => output,
+                Err(e) => format!("Error: {e}"),
+            };
+
+            steps.push(ReactStep {
+                thought,
+                action,
+                action_input,
+                observation,
+            });
+        }
+
+        Err(PromptError::MaxDepthError {
+            max_depth: self.max_iterations,
+            chat_history: vec![],
+            prompt: prompt.into(),
+        })
+    }
+}
+
+/// Parse the response of the model into its thought, action and action input. Anything after
+/// the action (e.g.: an observation made up by the model) is ignored.
+fn parse_action(response: &str) -> Option<(String, String, String)> {
+    let (thought, action) = response.split_once("Action:")?;
+    let action = action.split("\nObservation:").next()?.trim();
+    let (name, input) = action.split_once('(')?;
+    let input = input.rsplit_once(')')?.0;
+
+    Some((
+        thought
+            .trim()
+            .trim_start_matches("Thought:")
+            .trim()
+            .to_string(),
+        name.trim().to_string(),
+        input.trim().to_string(),
+    ))
+}
+
+#[allow(refining_impl_trait)]
+impl<M: CompletionModel> Prompt for ReactAgent<M> {
+    async fn prompt(&self, prompt: impl Into<Message> + Send) -> Result<String, PromptError> {
+        let prompt = prompt.into().rag_text().unwrap_or_default();
+        Ok(self.react(&prompt).await?.answer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        agent::AgentBuilder,
+        completion::ToolDefinition,
+        testing::MockCompletionModel,
+        tool::{Tool, ToolError},
+    };
+
+    #[derive(Deserialize)]
+    struct AddArgs {
+        x: i32,
+        y: i32,
+    }
+
+    struct Adder;
+
+    impl Tool for Adder {
+        const NAME: &'static str = "add";
+
+        type Error = ToolError;
+        type Args = AddArgs;
+        type Output = i32;
+
+        async fn definition(&self, _prompt: String) -> ToolDefinition {
+            ToolDefinition {
+                name: Self::NAME.to_string(),
+                description: "Add x and y together.".to_string(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {"x": {"type": "number"}, "y": {"type": "number"}}
+                }),
+            }
+        }
+
+        async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+            Ok(args.x + args.y)
+        }
+    }
+
+    #[test]
+    fn test_parse_action() {
+        assert_eq!(
+            parse_action(
+                " I should add the numbers.\nAction: add({\"x\": 2, \"y\": 3})\nObservation: 5"
+            ),
+            Some((
+                "I should add the numbers.".to_string(),
+                "add".to_string(),
+                "{\"x\": 2, \"y\": 3}".to_string()
+            ))
+        );
+        assert_eq!(
+            parse_action("Thought: Done.\nAction: Final Answer(5 (five))"),
+            Some((
+                "Done.".to_string(),
+                "Final Answer".to_string(),
+                "5 (five)".to_string()
+            ))
+        );
+        assert_eq!(parse_action("The answer is 5."), None);
+    }
+
+    #[tokio::test]
+    async fn test_react_agent() {
+        let model = MockCompletionModel::sequence([
+            " I should add the numbers.\nAction: add({\"x\": 2, \"y\": 3})",
+            " I now know the final answer.\nAction: Final Answer(5)",
+        ]);
+        let agent = ReactAgent::new(AgentBuilder::new(model.clone()).tool(Adder).build());
+
+        let response = agent.react("What is 2 + 3?").await.unwrap();
+        assert_eq!(
+            response,
+            ReactResponse {
+                steps: vec![ReactStep {
+                    thought: "I should add the numbers.".to_string(),
+                    action: "add".to_string(),
+                    action_input: "{\"x\": 2, \"y\": 3}".to_string(),
+                    observation: "5".to_string(),
+                }],
+                answer: "5".to_string(),
+            }
+        );
+
+        let requests = model.requests();
+        assert!(requests[0]
+            .preamble
+            .as_ref()
+            .unwrap()
+            .contains("- add: Add x and y together."));
+        assert!(requests[0].tools.is_empty());
+        assert_eq!(
+            requests[1].chat_history.iter().last().unwrap().rag_text(),
+            Some(
+                "Question: What is 2 + 3?\n\
+                Thought: I should add the numbers.\n\
+                Action: add({\"x\": 2, \"y\": 3})\n\
+                Observation: 5\n\
+                Thought:"
+                    .to_string()
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_react_agent_max_iterations() {
+        let model = MockCompletionModel::new(" I should think more.");
+        let agent = ReactAgent::new(AgentBuilder::new(model.clone()).build()).max_iterations(2);
+
+        assert!(matches!(
+            agent.react("What is 2 + 3?").await,
+            Err(PromptError::MaxDepthError { max_depth: 2, .. })
+        ));
+        assert_eq!(model.requests().len(), 2);
+    }
+}
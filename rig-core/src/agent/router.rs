@@ -0,0 +1,264 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::{
+    completion::{
+        Chat, ChatDyn, Completion, CompletionError, CompletionModel, Message, Prompt, PromptError,
+        ToolDefinition,
+    },
+    message::{AssistantContent, ToolCall, ToolFunction},
+};
+
+use super::Agent;
+
+const ROUTE_TOOL_NAME: &str = "route";
+
+#[derive(Deserialize)]
+struct RouteArgs {
+    handler: String,
+}
+
+/// A handler of a [FunctionRouter], along with the description of the requests it handles.
+struct Route {
+    description: String,
+    handler: Box<dyn ChatDyn>,
+}
+
+/// A router dispatching each request to one of its handlers (e.g.: agents specialized in different
+/// tasks) based on the intent of the user.
+///
+/// On each call, the classifier agent selects the handler (by calling a `route` tool with the name
+/// of the handler), then the selected handler processes the original prompt and chat history.
+///
+/// # Example
+/// ```rust
+/// use rig::{agent::FunctionRouter, completion::Prompt, providers::openai};
+///
+/// let openai = openai::Client::from_env();
+///
+/// let router = FunctionRouter::new(
+///     openai.agent(openai::GPT_4O_MINI)
+///         .preamble("You route customer requests to the right department.")
+///         .build(),
+/// )
+/// .route(
+///     "billing",
+///     "Questions about invoices, payments and refunds",
+///     openai.agent(openai::GPT_4O).preamble("You are a billing assistant.").build(),
+/// )
+/// .route(
+///     "support",
+///     "Technical issues with the product",
+///     openai.agent(openai::GPT_4O).preamble("You are a technical support assistant.").build(),
+/// );
+///
+/// let response = router.prompt("I was charged twice this month").await?;
+/// ```
+pub struct FunctionRouter<M: CompletionModel> {
+    classifier: Agent<M>,
+    routes: HashMap<String, Route>,
+}
+
+impl<M: CompletionModel> FunctionRouter<M> {
+    /// Create a new router using `classifier` to select the handler of each request.
+    pub fn new(classifier: Agent<M>) -> Self {
+        Self {
+            classifier,
+            routes: HashMap::new(),
+        }
+    }
+
+    /// Add a handler to the router. The `description` of the requests it handles is shown to the
+    /// classifier to help it select the right handler.
+    pub fn route(
+        mut self,
+        name: impl Into<String>,
+        description: impl Into<String>,
+        handler: impl ChatDyn + 'static,
+    ) -> Self {
+        self.routes.insert(
+            name.into(),
+            Route {
+                description: description.into(),
+                handler: Box::new(handler),
+            },
+        );
+        self
+    }
+
+    /// Add a (boxed) handler to the router, e.g.: an agent using a different model than the others.
+    pub fn route_dyn(
+        mut self,
+        name: impl Into<String>,
+        description: impl Into<String>,
+        handler: Box<dyn ChatDyn>,
+    ) -> Self {
+        self.routes.insert(
+            name.into(),
+            Route {
+                description: description.into(),
+                handler,
+            },
+        );
+        self
+    }
+
+    fn route_definition(&self) -> ToolDefinition {
+        let mut names = self.routes.keys().cloned().collect::<Vec<_>>();
+        names.sort();
+
+        let handlers = names
+            .iter()
+            .map(|name| format!("- {name}: {}", self.routes[name].description))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        ToolDefinition {
+            name: ROUTE_TOOL_NAME.to_string(),
+            description: format!(
+                "Select the handler of the user's request. Available handlers:\n{handlers}"
+            ),
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "handler": {
+                        "type": "string",
+                        "enum": names,
+                        "description": "The name of the handler of the user's request",
+                    },
+                },
+                "required": ["handler"],
+            }),
+        }
+    }
+
+    /// Returns the name of the handler selected by the classifier for `prompt`.
+    pub async fn classify(
+        &self,
+        prompt: impl Into<Message> + Send,
+        chat_history: Vec<Message>,
+    ) -> Result<String, CompletionError> {
+        let response = self
+            .classifier
+            .completion(prompt, chat_history)
+            .await?
+            .tool(self.route_definition())
+            .send()
+            .await?;
+
+        let arguments = response
+            .choice
+            .into_iter()
+            .find_map(|content| match content {
+                AssistantContent::ToolCall(ToolCall {
+                    function: ToolFunction { name, arguments },
+                    ..
+                }) if name == ROUTE_TOOL_NAME => Some(arguments),
+                _ => None,
+            })
+            .ok_or_else(|| {
+                CompletionError::ResponseError("The classifier did not select a handler".into())
+            })?;
+
+        let RouteArgs { handler } = serde_json::from_value(arguments)?;
+        if !self.routes.contains_key(&handler) {
+            return Err(CompletionError::ResponseError(format!(
+                "The classifier selected an unknown handler: {handler}"
+            )));
+        }
+
+        Ok(handler)
+    }
+}
+
+#[allow(refining_impl_trait)]
+impl<M: CompletionModel> Prompt for FunctionRouter<M> {
+    async fn prompt(&self, prompt: impl Into<Message> + Send) -> Result<String, PromptError> {
+        self.chat(prompt, vec![]).await
+    }
+}
+
+#[allow(refining_impl_trait)]
+impl<M: CompletionModel> Chat for FunctionRouter<M> {
+    async fn chat(
+        &self,
+        prompt: impl Into<Message> + Send,
+        chat_history: Vec<Message>,
+    ) -> Result<String, PromptError> {
+        let prompt = prompt.into();
+        let handler = self.classify(prompt.clone(), chat_history.clone()).await?;
+        tracing::debug!(target: "rig", "Routing request to handler: {handler}");
+
+        self.routes[&handler]
+            .handler
+            .chat_dyn(prompt, chat_history)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        agent::AgentBuilder, completion::CompletionResponse, message::UserContent,
+        testing::MockCompletionModel, OneOrMany,
+    };
+
+    /// Mock classifier routing the requests mentioning an invoice to "billing"
+    fn classifier() -> MockCompletionModel {
+        MockCompletionModel::from_fn(|request| {
+            let prompt = match request.chat_history.iter().last() {
+                Some(Message::User { content }) => match content.first() {
+                    UserContent::Text(text) => text.text,
+                    _ => String::new(),
+                },
+                _ => String::new(),
+            };
+            let handler = if prompt.contains("invoice") {
+                "billing"
+            } else {
+                "support"
+            };
+            CompletionResponse {
+                choice: OneOrMany::one(AssistantContent::tool_call(
+                    "call_1",
+                    ROUTE_TOOL_NAME,
+                    json!({ "handler": handler }),
+                )),
+                raw_response: (),
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn test_function_router() {
+        let billing = MockCompletionModel::new("Billing here.");
+        let router = FunctionRouter::new(AgentBuilder::new(classifier()).build())
+            .route(
+                "billing",
+                "Invoices and payments",
+                AgentBuilder::new(billing.clone()).build(),
+            )
+            .route(
+                "support",
+                "Technical issues",
+                AgentBuilder::new(MockCompletionModel::new("Support here.")).build(),
+            );
+
+        let response = router
+            .chat(
+                "Where is my invoice?",
+                vec![Message::user("Hi"), Message::assistant("Hello!")],
+            )
+            .await
+            .unwrap();
+        assert_eq!(response, "Billing here.");
+        // The chat history is forwarded to the handler
+        assert_eq!(billing.requests()[0].chat_history.len(), 3);
+
+        let response = router.prompt("The app crashes").await.unwrap();
+        assert_eq!(response, "Support here.");
+    }
+}
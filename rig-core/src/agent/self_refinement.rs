@@ -0,0 +1,233 @@
+//! This module contains the implementation of the [SelfRefineAgent] struct, which iteratively
+//! improves a response with the feedback of a critic (Self-Refine).
+//!
+//! The generator agent first writes a draft. On each round, the critic agent reviews the draft,
+//! gives its feedback and rates it, and the generator revises the draft with the feedback. The
+//! loop stops once the critic rates the draft at least `threshold`, or after `max_rounds` rounds.
+//!
+//! # Example
+//! ```rust
+//! use rig::{agent::self_refinement::SelfRefineAgent, providers::openai};
+//!
+//! let openai = openai::Client::from_env();
+//!
+//! let agent = SelfRefineAgent::new(
+//!     openai.agent("gpt-4o").preamble("You are a technical writer.").build(),
+//!     openai.agent("gpt-4o").preamble("You are a demanding editor.").build(),
+//! )
+//! .max_rounds(3)
+//! .threshold(8.0);
+//!
+//! let response = agent
+//!     .refine("Explain how a hash map works in one paragraph.")
+//!     .await
+//!     .expect("Failed to run the agent");
+//!
+//! println!("Response: {}", response.response);
+//! ```
+use serde::{Deserialize, Serialize};
+
+use crate::completion::{CompletionModel, Message, Prompt, PromptError};
+
+use super::Agent;
+
+/// Maximum number of critique and revision rounds, by default.
+const DEFAULT_MAX_ROUNDS: usize = 3;
+/// Score (out of 10) above which a draft is accepted, by default.
+const DEFAULT_THRESHOLD: f64 = 8.0;
+
+/// A round of refinement: a draft, and the critic's review of it.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RefinementRound {
+    pub draft: String,
+    /// Feedback of the critic on the draft
+    pub feedback: String,
+    /// Score of the draft given by the critic, between 0 and 10
+    pub score: f64,
+}
+
+/// The rounds of a [SelfRefineAgent] and its final response.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SelfRefineResponse {
+    pub rounds: Vec<RefinementRound>,
+    /// The last draft (accepted by the critic, or revised in the last round)
+    pub response: String,
+}
+
+/// An agent refining the responses of a generator agent with the feedback of a critic agent.
+pub struct SelfRefineAgent<M: CompletionModel> {
+    /// Agent writing and revising the drafts
+    pub generator: Agent<M>,
+    /// Agent reviewing and rating the drafts
+    pub critic: Agent<M>,
+    max_rounds: usize,
+    threshold: f64,
+}
+
+impl<M: CompletionModel> SelfRefineAgent<M> {
+    pub fn new(generator: Agent<M>, critic: Agent<M>) -> Self {
+        Self {
+            generator,
+            critic,
+            max_rounds: DEFAULT_MAX_ROUNDS,
+            threshold: DEFAULT_THRESHOLD,
+        }
+    }
+
+    /// Set the maximum number of critique and revision rounds (3 by default).
+    pub fn max_rounds(mut self, max_rounds: usize) -> Self {
+        self.max_rounds = max_rounds;
+        self
+    }
+
+    /// Set the score, between 0 and 10, from which the critic accepts a draft (8 by default).
+    pub fn threshold(mut self, threshold: f64) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Have the critic review `draft`, returning its feedback and score.
+    async fn critique(&self, prompt: &str, draft: &str) -> Result<(String, f64), PromptError> {
+        let feedback = self
+            .critic
+            .prompt(format!(
+                "Prompt: {prompt}\n\nResponse: {draft}\n\n\
+                Review the response to the prompt: point out its errors, omissions and \
+                weaknesses, and how to fix them. End your review with a line \
+                `Score: N`, where N rates the response from 0 (useless) to 10 (perfect)."
+            ))
+            .await?;
+        let score = parse_score(&feedback);
+
+        Ok((feedback, score))
+    }
+
+    /// Write a response to `prompt`, then refine it until the critic accepts it or the maximum
+    /// number of rounds is reached.
+    pub async fn refine(&self, prompt: &str) -> Result<SelfRefineResponse, PromptError> {
+        let mut draft = self.generator.prompt(prompt).await?;
+        let mut rounds = vec![];
+
+        for _ in 0..self.max_rounds {
+            let (feedback, score) = self.critique(prompt, &draft).await?;
+            rounds.push(RefinementRound {
+                draft: draft.clone(),
+                feedback: feedback.clone(),
+                score,
+            });
+
+            if score >= self.threshold {
+                break;
+            }
+
+            tracing::debug!(target: "rig", "Self-refine: revising draft scored {score}");
+            draft = self
+                .generator
+                .prompt(format!(
+                    "Prompt: {prompt}\n\nDraft: {draft}\n\nFeedback: {feedback}\n\n\
+                    Revise the draft to address the feedback. Write only the improved response."
+                ))
+                .await?;
+        }
+
+        Ok(SelfRefineResponse {
+            rounds,
+            response: draft,
+        })
+    }
+}
+
+/// Returns the score following the last `Score:` of the feedback, clamped between 0 and 10
+/// (0 if there is none, so that the draft is revised).
+fn parse_score(feedback: &str) -> f64 {
+    feedback
+        .rsplit_once("Score:")
+        .and_then(|(_, score)| {
+            score
+                .split(|c: char| !(c.is_ascii_digit() || c == '.'))
+                .find_map(|token| token.trim_matches('.').parse::<f64>().ok())
+        })
+        .map_or(0.0, |score| score.clamp(0.0, 10.0))
+}
+
+#[allow(refining_impl_trait)]
+impl<M: CompletionModel> Prompt for SelfRefineAgent<M> {
+    async fn prompt(&self, prompt: impl Into<Message> + Send) -> Result<String, PromptError> {
+        let prompt = prompt.into().rag_text().unwrap_or_default();
+        Ok(self.refine(&prompt).await?.response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{agent::AgentBuilder, testing::MockCompletionModel};
+
+    #[test]
+    fn test_parse_score() {
+        assert_eq!(parse_score("Clear and correct.\nScore: 9"), 9.0);
+        assert_eq!(parse_score("Scores 3 points.\nScore: 7.5/10."), 7.5);
+        assert_eq!(parse_score("Score: 12"), 10.0);
+        assert_eq!(parse_score("Too vague."), 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_self_refine_agent() {
+        let generator = MockCompletionModel::sequence(["draft 1", "draft 2"]);
+        let critic = MockCompletionModel::sequence([
+            "Too vague, give an example.\nScore: 4",
+            "Clear and correct.\nScore: 9",
+        ]);
+        let agent = SelfRefineAgent::new(
+            AgentBuilder::new(generator.clone()).build(),
+            AgentBuilder::new(critic.clone()).build(),
+        );
+
+        let response = agent.refine("Explain hash maps.").await.unwrap();
+        assert_eq!(
+            response,
+            SelfRefineResponse {
+                rounds: vec![
+                    RefinementRound {
+                        draft: "draft 1".to_string(),
+                        feedback: "Too vague, give an example.\nScore: 4".to_string(),
+                        score: 4.0,
+                    },
+                    RefinementRound {
+                        draft: "draft 2".to_string(),
+                        feedback: "Clear and correct.\nScore: 9".to_string(),
+                        score: 9.0,
+                    },
+                ],
+                response: "draft 2".to_string(),
+            }
+        );
+
+        let revision = generator.requests()[1]
+            .chat_history
+            .iter()
+            .last()
+            .unwrap()
+            .rag_text()
+            .unwrap();
+        assert!(revision.starts_with("Prompt: Explain hash maps.\n\nDraft: draft 1"));
+        assert!(revision.contains("Feedback: Too vague, give an example."));
+        assert_eq!(critic.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_self_refine_agent_max_rounds() {
+        let generator = MockCompletionModel::new("draft");
+        let critic = MockCompletionModel::new("Wrong.\nScore: 2");
+        let agent = SelfRefineAgent::new(
+            AgentBuilder::new(generator.clone()).build(),
+            AgentBuilder::new(critic).build(),
+        )
+        .max_rounds(2);
+
+        let response = agent.refine("Explain hash maps.").await.unwrap();
+        assert_eq!(response.rounds.len(), 2);
+        // The initial draft, and a revision after each round
+        assert_eq!(generator.requests().len(), 3);
+    }
+}
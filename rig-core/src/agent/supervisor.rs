@@ -0,0 +1,458 @@
+//! This module contains the implementation of the [Supervisor] struct, which runs a multi-step
+//! workflow of agents and checkpoints its progress so that it can be resumed after a failure.
+//!
+//! Each [Step] prompts its agent with the output of the previous step (or the workflow input, for
+//! the first step), optionally transformed by an input transform, and checks the agent's response
+//! with an optional output validator.
+//!
+//! After each successful step, the state of the workflow is saved to a [CheckpointStore]. When a
+//! workflow is run again with the same id, it resumes from the step that failed instead of
+//! starting over. The checkpoint is cleared once the workflow completes.
+//!
+//! # Example
+//! ```rust
+//! use rig::{
+//!     agent::supervisor::{FileCheckpointStore, Step, Supervisor},
+//!     providers::openai,
+//! };
+//!
+//! let openai = openai::Client::from_env();
+//!
+//! let supervisor = Supervisor::new(FileCheckpointStore::new("checkpoints"))
+//!     .step(Step::new(
+//!         "research",
+//!         openai.agent("gpt-4o").preamble("Gather facts about the topic.").build(),
+//!     ))
+//!     .step(
+//!         Step::new(
+//!             "write",
+//!             openai.agent("gpt-4o").preamble("You write reports.").build(),
+//!         )
+//!         .input_transform(|facts| format!("Write a report from these facts:\n{facts}"))
+//!         .output_validator(|report| {
+//!             if report.len() > 200 {
+//!                 Ok(())
+//!             } else {
+//!                 Err("The report is too short".to_string())
+//!             }
+//!         }),
+//!     );
+//!
+//! let report = supervisor
+//!     .run("report-rust", "The Rust programming language")
+//!     .await
+//!     .expect("Failed to run the workflow");
+//! ```
+use std::{collections::HashMap, future::Future, sync::Mutex};
+
+use serde::{Deserialize, Serialize};
+
+use crate::completion::{CompletionModel, Prompt, PromptError};
+
+use super::Agent;
+
+#[derive(Debug, thiserror::Error)]
+pub enum SupervisorError {
+    #[error("PromptError: {0}")]
+    PromptError(#[from] PromptError),
+
+    /// The output of a step was rejected by its output validator
+    #[error("InvalidOutput: step `{step}`: {reason}")]
+    InvalidOutput { step: String, reason: String },
+
+    /// Error returned by the checkpoint store
+    #[error("CheckpointError: {0}")]
+    CheckpointError(#[from] Box<dyn std::error::Error + Send + Sync>),
+
+    #[error("JsonError: {0}")]
+    JsonError(#[from] serde_json::Error),
+}
+
+/// The state of a workflow, saved after each successful step.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    /// The input of the workflow
+    pub input: String,
+    /// The output of each completed step, in order
+    pub outputs: Vec<String>,
+}
+
+impl Checkpoint {
+    /// Index of the next step to run.
+    pub fn next_step(&self) -> usize {
+        self.outputs.len()
+    }
+}
+
+/// A storage of the checkpoints of workflows, identified by their id.
+pub trait CheckpointStore: Send + Sync {
+    /// Load the latest checkpoint of the workflow `workflow_id`, if any.
+    fn load(
+        &self,
+        workflow_id: &str,
+    ) -> impl Future<Output = Result<Option<Checkpoint>, SupervisorError>> + Send;
+
+    /// Save `checkpoint` as the latest checkpoint of the workflow `workflow_id`.
+    fn save(
+        &self,
+        workflow_id: &str,
+        checkpoint: &Checkpoint,
+    ) -> impl Future<Output = Result<(), SupervisorError>> + Send;
+
+    /// Remove the checkpoint of the workflow `workflow_id`.
+    fn clear(&self, workflow_id: &str) -> impl Future<Output = Result<(), SupervisorError>> + Send;
+}
+
+/// A [CheckpointStore] keeping the checkpoints in memory (i.e.: they are lost when the
+/// process exits).
+#[derive(Debug, Default)]
+pub struct InMemoryCheckpointStore {
+    checkpoints: Mutex<HashMap<String, Checkpoint>>,
+}
+
+impl InMemoryCheckpointStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl CheckpointStore for InMemoryCheckpointStore {
+    async fn load(&self, workflow_id: &str) -> Result<Option<Checkpoint>, SupervisorError> {
+        Ok(self
+            .checkpoints
+            .lock()
+            .expect("Checkpoints lock should not be poisoned")
+            .get(workflow_id)
+            .cloned())
+    }
+
+    async fn save(
+        &self,
+        workflow_id: &str,
+        checkpoint: &Checkpoint,
+    ) -> Result<(), SupervisorError> {
+        self.checkpoints
+            .lock()
+            .expect("Checkpoints lock should not be poisoned")
+            .insert(workflow_id.to_string(), checkpoint.clone());
+        Ok(())
+    }
+
+    async fn clear(&self, workflow_id: &str) -> Result<(), SupervisorError> {
+        self.checkpoints
+            .lock()
+            .expect("Checkpoints lock should not be poisoned")
+            .remove(workflow_id);
+        Ok(())
+    }
+}
+
+/// A [CheckpointStore] saving each checkpoint as a JSON file `<workflow_id>.json` in a directory.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Clone, Debug)]
+pub struct FileCheckpointStore {
+    dir: std::path::PathBuf,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl FileCheckpointStore {
+    /// Create a store saving the checkpoints in the directory `dir`, which is created if needed.
+    pub fn new(dir: impl Into<std::path::PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    fn path(&self, workflow_id: &str) -> std::path::PathBuf {
+        self.dir.join(format!("{workflow_id}.json"))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl CheckpointStore for FileCheckpointStore {
+    async fn load(&self, workflow_id: &str) -> Result<Option<Checkpoint>, SupervisorError> {
+        match tokio::fs::read(self.path(workflow_id)).await {
+            Ok(content) => Ok(Some(serde_json::from_slice(&content)?)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(SupervisorError::CheckpointError(e.into())),
+        }
+    }
+
+    async fn save(
+        &self,
+        workflow_id: &str,
+        checkpoint: &Checkpoint,
+    ) -> Result<(), SupervisorError> {
+        let content = serde_json::to_vec(checkpoint)?;
+        tokio::fs::create_dir_all(&self.dir)
+            .await
+            .map_err(|e| SupervisorError::CheckpointError(e.into()))?;
+
+        // Write to a temporary file first so that a crash never leaves a truncated checkpoint
+        let path = self.path(workflow_id);
+        let tmp_path = path.with_extension("json.tmp");
+        tokio::fs::write(&tmp_path, content)
+            .await
+            .map_err(|e| SupervisorError::CheckpointError(e.into()))?;
+        tokio::fs::rename(tmp_path, path)
+            .await
+            .map_err(|e| SupervisorError::CheckpointError(e.into()))
+    }
+
+    async fn clear(&self, workflow_id: &str) -> Result<(), SupervisorError> {
+        match tokio::fs::remove_file(self.path(workflow_id)).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(SupervisorError::CheckpointError(e.into()))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A [CheckpointStore] saving each checkpoint as a JSON string under the key
+/// `<prefix><workflow_id>` of a Redis database.
+///
+/// Note: requires the `redis-checkpoint` feature.
+#[cfg(feature = "redis-checkpoint")]
+#[derive(Clone)]
+pub struct RedisCheckpointStore {
+    connection: redis::aio::ConnectionManager,
+    prefix: String,
+}
+
+#[cfg(feature = "redis-checkpoint")]
+impl RedisCheckpointStore {
+    /// Create a store using the keys prefixed with `prefix` (e.g.: `checkpoint:`).
+    pub fn new(connection: redis::aio::ConnectionManager, prefix: &str) -> Self {
+        Self {
+            connection,
+            prefix: prefix.to_string(),
+        }
+    }
+
+    fn key(&self, workflow_id: &str) -> String {
+        format!("{}{workflow_id}", self.prefix)
+    }
+}
+
+#[cfg(feature = "redis-checkpoint")]
+impl CheckpointStore for RedisCheckpointStore {
+    async fn load(&self, workflow_id: &str) -> Result<Option<Checkpoint>, SupervisorError> {
+        let content: Option<String> = redis::cmd("GET")
+            .arg(self.key(workflow_id))
+            .query_async(&mut self.connection.clone())
+            .await
+            .map_err(|e| SupervisorError::CheckpointError(e.into()))?;
+
+        content
+            .map(|content| serde_json::from_str(&content))
+            .transpose()
+            .map_err(SupervisorError::from)
+    }
+
+    async fn save(
+        &self,
+        workflow_id: &str,
+        checkpoint: &Checkpoint,
+    ) -> Result<(), SupervisorError> {
+        redis::cmd("SET")
+            .arg(self.key(workflow_id))
+            .arg(serde_json::to_string(checkpoint)?)
+            .query_async::<()>(&mut self.connection.clone())
+            .await
+            .map_err(|e| SupervisorError::CheckpointError(e.into()))
+    }
+
+    async fn clear(&self, workflow_id: &str) -> Result<(), SupervisorError> {
+        redis::cmd("DEL")
+            .arg(self.key(workflow_id))
+            .query_async::<()>(&mut self.connection.clone())
+            .await
+            .map_err(|e| SupervisorError::CheckpointError(e.into()))
+    }
+}
+
+type InputTransform = Box<dyn Fn(&str) -> String + Send + Sync>;
+type OutputValidator = Box<dyn Fn(&str) -> Result<(), String> + Send + Sync>;
+
+/// A step of a workflow run by a [Supervisor].
+pub struct Step<M: CompletionModel> {
+    /// Name of the step, used in error messages
+    pub name: String,
+    /// Agent running the step
+    pub agent: Agent<M>,
+    input_transform: Option<InputTransform>,
+    output_validator: Option<OutputValidator>,
+}
+
+impl<M: CompletionModel> Step<M> {
+    pub fn new(name: &str, agent: Agent<M>) -> Self {
+        Self {
+            name: name.to_string(),
+            agent,
+            input_transform: None,
+            output_validator: None,
+        }
+    }
+
+    /// Set the function building the prompt of the agent from the output of the previous step
+    /// (or the input of the workflow, for the first step). By default, it is used as is.
+    pub fn input_transform(
+        mut self,
+        input_transform: impl Fn(&str) -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.input_transform = Some(Box::new(input_transform));
+        self
+    }
+
+    /// Set the function checking the output of the agent. If it returns an error, the
+    /// workflow stops and the step is run again when the workflow is resumed.
+    pub fn output_validator(
+        mut self,
+        output_validator: impl Fn(&str) -> Result<(), String> + Send + Sync + 'static,
+    ) -> Self {
+        self.output_validator = Some(Box::new(output_validator));
+        self
+    }
+}
+
+/// A sequence of agent steps, checkpointed after each step.
+pub struct Supervisor<M: CompletionModel, S: CheckpointStore> {
+    steps: Vec<Step<M>>,
+    store: S,
+}
+
+impl<M: CompletionModel, S: CheckpointStore> Supervisor<M, S> {
+    pub fn new(store: S) -> Self {
+        Self {
+            steps: Vec::new(),
+            store,
+        }
+    }
+
+    /// Add a step at the end of the workflow.
+    pub fn step(mut self, step: Step<M>) -> Self {
+        self.steps.push(step);
+        self
+    }
+
+    /// Run the workflow `workflow_id` on `input` and return the output of its last step.
+    ///
+    /// If a checkpoint of the workflow exists (i.e.: a previous run failed), the workflow resumes
+    /// from the step that failed, with the input of the previous run.
+    pub async fn run(&self, workflow_id: &str, input: &str) -> Result<String, SupervisorError> {
+        let mut checkpoint = match self.store.load(workflow_id).await? {
+            Some(checkpoint) => {
+                tracing::info!(target: "rig",
+                    "Resuming workflow {} from step {}",
+                    workflow_id,
+                    checkpoint.next_step()
+                );
+                checkpoint
+            }
+            None => Checkpoint {
+                input: input.to_string(),
+                outputs: Vec::new(),
+            },
+        };
+
+        for step in self.steps.iter().skip(checkpoint.next_step()) {
+            let previous = checkpoint.outputs.last().unwrap_or(&checkpoint.input);
+            let prompt = match &step.input_transform {
+                Some(input_transform) => input_transform(previous),
+                None => previous.clone(),
+            };
+
+            let output = step.agent.prompt(prompt).await?;
+
+            if let Some(output_validator) = &step.output_validator {
+                output_validator(&output).map_err(|reason| SupervisorError::InvalidOutput {
+                    step: step.name.clone(),
+                    reason,
+                })?;
+            }
+
+            checkpoint.outputs.push(output);
+            self.store.save(workflow_id, &checkpoint).await?;
+        }
+
+        self.store.clear(workflow_id).await?;
+
+        Ok(checkpoint.outputs.pop().unwrap_or(checkpoint.input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{agent::AgentBuilder, testing::MockCompletionModel};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_supervisor_resumes_from_failed_step() {
+        let researcher = MockCompletionModel::new("Rust is memory safe");
+        let writer = MockCompletionModel::sequence(["Too short", "Report: Rust is memory safe"]);
+
+        let supervisor = Supervisor::new(InMemoryCheckpointStore::new())
+            .step(Step::new(
+                "research",
+                AgentBuilder::new(researcher.clone()).build(),
+            ))
+            .step(
+                Step::new("write", AgentBuilder::new(writer.clone()).build())
+                    .input_transform(|facts| format!("Write a report from: {facts}"))
+                    .output_validator(|report| {
+                        if report.starts_with("Report:") {
+                            Ok(())
+                        } else {
+                            Err("Not a report".to_string())
+                        }
+                    }),
+            );
+
+        let result = supervisor.run("workflow", "Rust").await;
+        assert!(matches!(
+            result,
+            Err(SupervisorError::InvalidOutput { step, .. }) if step == "write"
+        ));
+        assert_eq!(
+            supervisor.store.load("workflow").await.unwrap(),
+            Some(Checkpoint {
+                input: "Rust".to_string(),
+                outputs: vec!["Rust is memory safe".to_string()],
+            })
+        );
+
+        let report = supervisor.run("workflow", "Rust").await.unwrap();
+        assert_eq!(report, "Report: Rust is memory safe");
+
+        // The research step is not run again
+        assert_eq!(researcher.requests().len(), 1);
+        assert_eq!(writer.requests().len(), 2);
+        assert_eq!(
+            writer.requests()[1]
+                .chat_history
+                .iter()
+                .last()
+                .unwrap()
+                .rag_text(),
+            Some("Write a report from: Rust is memory safe".to_string())
+        );
+        assert_eq!(supervisor.store.load("workflow").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_file_checkpoint_store() {
+        let temp = assert_fs::TempDir::new().expect("Failed to create temp dir");
+        let store = FileCheckpointStore::new(temp.path().join("checkpoints"));
+        let checkpoint = Checkpoint {
+            input: "Rust".to_string(),
+            outputs: vec!["Rust is memory safe".to_string()],
+        };
+
+        assert_eq!(store.load("workflow").await.unwrap(), None);
+        store.save("workflow", &checkpoint).await.unwrap();
+        assert_eq!(store.load("workflow").await.unwrap(), Some(checkpoint));
+        store.clear("workflow").await.unwrap();
+        assert_eq!(store.load("workflow").await.unwrap(), None);
+        store.clear("workflow").await.unwrap();
+    }
+}
@@ -0,0 +1,335 @@
+//! This module contains the implementation of the [TreeOfThoughtAgent] struct, which explores
+//! several reasoning paths in parallel and keeps the most promising ones (Tree of Thoughts).
+//!
+//! At each level of the tree, the agent generates `branch_factor` candidate next steps for each
+//! kept path (in a single request with `n` completions, when the provider supports it), scores
+//! each candidate with an evaluation prompt, and keeps the `top_k` best paths. At the last level,
+//! the candidate steps are final answers, and the answer of the highest-scoring path is returned.
+//!
+//! # Example
+//! ```rust
+//! use rig::{agent::tree_of_thought::TreeOfThoughtAgent, providers::openai};
+//!
+//! let openai = openai::Client::from_env();
+//!
+//! let agent = TreeOfThoughtAgent::new(openai.agent("gpt-4o").temperature(1.0).build())
+//!     .branch_factor(3)
+//!     .depth(3)
+//!     .top_k(2);
+//!
+//! let response = agent
+//!     .solve("Use the numbers 4, 9, 10 and 13 with +, -, * and / to obtain 24.")
+//!     .await
+//!     .expect("Failed to run the agent");
+//!
+//! println!("Steps: {:?}", response.steps);
+//! println!("Answer: {}", response.answer);
+//! ```
+use futures::future::try_join_all;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    completion::{AssistantContent, Completion, CompletionModel, Message, Prompt, PromptError},
+    providers::openai,
+};
+
+use super::Agent;
+
+/// Number of candidate steps generated for each path, by default.
+const DEFAULT_BRANCH_FACTOR: usize = 3;
+/// Number of levels of the tree (i.e.: steps of each path), by default.
+const DEFAULT_DEPTH: usize = 3;
+/// Number of paths kept at each level, by default.
+const DEFAULT_TOP_K: usize = 2;
+
+/// Raw completion responses which can contain several candidate completions (i.e.: when the
+/// request sets `n`, see
+/// [CompletionRequestBuilder::n_completions](crate::completion::CompletionRequestBuilder::n_completions)).
+///
+/// Responses without candidates return an empty list, in which case the [TreeOfThoughtAgent]
+/// makes one request per candidate.
+pub trait CandidateCompletions {
+    /// Returns the text of each candidate completion of the response.
+    fn candidate_texts(&self) -> Vec<String>;
+}
+
+impl CandidateCompletions for openai::CompletionResponse {
+    fn candidate_texts(&self) -> Vec<String> {
+        self.candidates()
+            .map(|candidates| candidates.into_iter().map(text).collect())
+            .unwrap_or_default()
+    }
+}
+
+impl CandidateCompletions for () {
+    fn candidate_texts(&self) -> Vec<String> {
+        vec![]
+    }
+}
+
+/// A path of the tree, with its score.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TreeOfThoughtResponse {
+    /// The steps of the highest-scoring path (the last one being the final answer)
+    pub steps: Vec<String>,
+    /// The score of the path, given by the evaluation prompt
+    pub score: f64,
+    /// The final answer
+    pub answer: String,
+}
+
+/// An agent exploring a tree of reasoning paths to solve a problem.
+pub struct TreeOfThoughtAgent<M: CompletionModel> {
+    agent: Agent<M>,
+    branch_factor: usize,
+    depth: usize,
+    top_k: usize,
+}
+
+impl<M> TreeOfThoughtAgent<M>
+where
+    M: CompletionModel,
+    M::Response: CandidateCompletions,
+{
+    pub fn new(agent: Agent<M>) -> Self {
+        Self {
+            agent,
+            branch_factor: DEFAULT_BRANCH_FACTOR,
+            depth: DEFAULT_DEPTH,
+            top_k: DEFAULT_TOP_K,
+        }
+    }
+
+    /// Set the number of candidate steps generated for each path (3 by default).
+    pub fn branch_factor(mut self, branch_factor: usize) -> Self {
+        self.branch_factor = branch_factor.max(1);
+        self
+    }
+
+    /// Set the number of levels of the tree, the last one being the final answer (3 by default).
+    pub fn depth(mut self, depth: usize) -> Self {
+        self.depth = depth.max(1);
+        self
+    }
+
+    /// Set the number of paths kept at each level (2 by default).
+    pub fn top_k(mut self, top_k: usize) -> Self {
+        self.top_k = top_k.max(1);
+        self
+    }
+
+    /// Returns the prompt generating the next step of `steps`.
+    fn step_prompt(&self, problem: &str, steps: &[String]) -> String {
+        let instruction = if steps.len() + 1 == self.depth {
+            "Given the steps above, give the final answer."
+        } else {
+            "Write only the next step of the reasoning, without giving the final answer yet."
+        };
+
+        format!("Problem: {problem}\n\n{}{instruction}", format_steps(steps))
+    }
+
+    /// Generate `branch_factor` candidate next steps of `steps`.
+    async fn expand(&self, problem: &str, steps: &[String]) -> Result<Vec<String>, PromptError> {
+        let prompt = self.step_prompt(problem, steps);
+
+        let response = self
+            .agent
+            .completion(prompt.as_str(), vec![])
+            .await?
+            .n_completions(u8::try_from(self.branch_factor).unwrap_or(u8::MAX))
+            .send()
+            .await?;
+
+        let mut candidates = response.raw_response.candidate_texts();
+        if candidates.is_empty() {
+            candidates.push(text(response.choice));
+        }
+
+        // The provider may not support `n`, in which case the candidates are generated one by one
+        let missing = self.branch_factor.saturating_sub(candidates.len());
+        let others = try_join_all((0..missing).map(|_| async {
+            let response = self
+                .agent
+                .completion(prompt.as_str(), vec![])
+                .await?
+                .send()
+                .await?;
+            Ok::<_, PromptError>(text(response.choice))
+        }))
+        .await?;
+        candidates.extend(others);
+        candidates.truncate(self.branch_factor);
+
+        Ok(candidates)
+    }
+
+    /// Score the path `steps` between 0 and 10 with the evaluation prompt.
+    async fn evaluate(&self, problem: &str, steps: &[String]) -> Result<f64, PromptError> {
+        let evaluation = self
+            .agent
+            .prompt(format!(
+                "Problem: {problem}\n\n{}How likely are these steps to lead to a correct \
+                solution of the problem? Answer with a single score from 0 (impossible) to 10 \
+                (certain), and nothing else.",
+                format_steps(steps)
+            ))
+            .await?;
+
+        Ok(parse_score(&evaluation))
+    }
+
+    /// Explore the tree of reasoning paths of `problem` and return the highest-scoring one.
+    pub async fn solve(&self, problem: &str) -> Result<TreeOfThoughtResponse, PromptError> {
+        let mut paths = vec![TreeOfThoughtResponse::default()];
+
+        for _ in 0..self.depth {
+            let candidates = try_join_all(paths.iter().map(|path| async move {
+                let steps = self.expand(problem, &path.steps).await?;
+                Ok::<_, PromptError>(
+                    steps
+                        .into_iter()
+                        .map(|step| [path.steps.clone(), vec![step]].concat())
+                        .collect::<Vec<_>>(),
+                )
+            }))
+            .await?
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+
+            paths = try_join_all(candidates.into_iter().map(|steps| async move {
+                let score = self.evaluate(problem, &steps).await?;
+                Ok::<_, PromptError>(TreeOfThoughtResponse {
+                    answer: steps.last().cloned().unwrap_or_default(),
+                    steps,
+                    score,
+                })
+            }))
+            .await?;
+
+            paths.sort_by(|a, b| b.score.total_cmp(&a.score));
+            paths.truncate(self.top_k);
+        }
+
+        Ok(paths.swap_remove(0))
+    }
+}
+
+/// Format the steps of a path so they can be included in a prompt.
+fn format_steps(steps: &[String]) -> String {
+    steps
+        .iter()
+        .enumerate()
+        .map(|(i, step)| format!("Step {}: {step}\n\n", i + 1))
+        .collect()
+}
+
+/// Returns the first number of the evaluation, clamped between 0 and 10 (0 if there is none).
+fn parse_score(evaluation: &str) -> f64 {
+    evaluation
+        .split(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .find_map(|token| token.trim_matches('.').parse::<f64>().ok())
+        .map_or(0.0, |score| score.clamp(0.0, 10.0))
+}
+
+fn text(content: crate::OneOrMany<AssistantContent>) -> String {
+    content
+        .into_iter()
+        .filter_map(|content| match content {
+            AssistantContent::Text(text) => Some(text.text),
+            AssistantContent::ToolCall(_) => None,
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[allow(refining_impl_trait)]
+impl<M> Prompt for TreeOfThoughtAgent<M>
+where
+    M: CompletionModel,
+    M::Response: CandidateCompletions,
+{
+    async fn prompt(&self, prompt: impl Into<Message> + Send) -> Result<String, PromptError> {
+        let prompt = prompt.into().rag_text().unwrap_or_default();
+        Ok(self.solve(&prompt).await?.answer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use super::*;
+    use crate::{
+        agent::AgentBuilder, completion::CompletionResponse, testing::MockCompletionModel,
+        OneOrMany,
+    };
+
+    #[test]
+    fn test_parse_score() {
+        assert_eq!(parse_score("7"), 7.0);
+        assert_eq!(parse_score("Score: 8.5/10."), 8.5);
+        assert_eq!(parse_score("42"), 10.0);
+        assert_eq!(parse_score("Unlikely."), 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_tree_of_thought() {
+        // Candidates are numbered in order, and the score of a path is its last candidate number
+        let counter = Arc::new(AtomicUsize::new(0));
+        let model = MockCompletionModel::from_fn(move |request| {
+            let prompt = request
+                .chat_history
+                .iter()
+                .last()
+                .unwrap()
+                .rag_text()
+                .unwrap();
+            let text = if prompt.contains("How likely") {
+                prompt
+                    .rsplit("candidate ")
+                    .next()
+                    .unwrap()
+                    .split_whitespace()
+                    .next()
+                    .unwrap()
+                    .to_string()
+            } else {
+                format!("candidate {}", counter.fetch_add(1, Ordering::SeqCst))
+            };
+            CompletionResponse {
+                choice: OneOrMany::one(AssistantContent::text(text)),
+                raw_response: (),
+            }
+        });
+        let agent = TreeOfThoughtAgent::new(AgentBuilder::new(model.clone()).build())
+            .branch_factor(3)
+            .depth(2)
+            .top_k(2);
+
+        let response = agent.solve("Make 24 with 4, 9, 10 and 13").await.unwrap();
+
+        // Level 1: candidates 0, 1 and 2, keeping 2 and 1
+        // Level 2: candidates 3, 4, 5 (from 2) and 6, 7, 8 (from 1), keeping 8
+        assert_eq!(
+            response,
+            TreeOfThoughtResponse {
+                steps: vec!["candidate 1".to_string(), "candidate 8".to_string()],
+                score: 8.0,
+                answer: "candidate 8".to_string(),
+            }
+        );
+
+        let requests = model.requests();
+        // 3 + 6 generations and 3 + 6 evaluations
+        assert_eq!(requests.len(), 18);
+        assert_eq!(
+            requests[0].additional_params.as_ref().unwrap()["n"],
+            serde_json::json!(3)
+        );
+    }
+}
@@ -2,8 +2,9 @@ use crate::agent::Agent;
 use crate::client::ProviderClient;
 use crate::embeddings::embedding::EmbeddingModelDyn;
 use crate::providers::{
-    anthropic, azure, cohere, deepseek, galadriel, gemini, groq, huggingface, hyperbolic, mira,
-    moonshot, ollama, openai, openrouter, perplexity, together, xai,
+    anthropic, azure, cerebras, cohere, deepseek, fireworks, galadriel, gemini, groq, huggingface,
+    hyperbolic, minimax, mira, moonshot, nvidia, ollama, openai, openrouter, perplexity, together,
+    xai,
 };
 use crate::transcription::TranscriptionModelDyn;
 use rig::completion::CompletionModelDyn;
@@ -94,7 +95,12 @@ impl<'a> DynClientBuilder {
             ClientFactory::new(DefaultProviders::TOGETHER, together::Client::from_env_boxed),
             ClientFactory::new(DefaultProviders::XAI, xai::Client::from_env_boxed),
             ClientFactory::new(DefaultProviders::AZURE, azure::Client::from_env_boxed),
+            ClientFactory::new(DefaultProviders::CEREBRAS, cerebras::Client::from_env_boxed),
             ClientFactory::new(DefaultProviders::DEEPSEEK, deepseek::Client::from_env_boxed),
+            ClientFactory::new(
+                DefaultProviders::FIREWORKS,
+                fireworks::Client::from_env_boxed,
+            ),
             ClientFactory::new(
                 DefaultProviders::GALADRIEL,
                 galadriel::Client::from_env_boxed,
@@ -104,9 +110,11 @@ impl<'a> DynClientBuilder {
                 DefaultProviders::HYPERBOLIC,
                 hyperbolic::Client::from_env_boxed,
             ),
+            ClientFactory::new(DefaultProviders::MINIMAX, minimax::Client::from_env_boxed),
             ClientFactory::new(DefaultProviders::MOONSHOT, moonshot::Client::from_env_boxed),
             ClientFactory::new(DefaultProviders::MIRA, mira::Client::from_env_boxed),
             ClientFactory::new(DefaultProviders::MISTRAL, mistral::Client::from_env_boxed),
+            ClientFactory::new(DefaultProviders::NVIDIA, nvidia::Client::from_env_boxed),
             ClientFactory::new(DefaultProviders::OLLAMA, ollama::Client::from_env_boxed),
             ClientFactory::new(
                 DefaultProviders::PERPLEXITY,
@@ -381,13 +389,17 @@ impl DefaultProviders {
     pub const TOGETHER: &'static str = "together";
     pub const XAI: &'static str = "xai";
     pub const AZURE: &'static str = "azure";
+    pub const CEREBRAS: &'static str = "cerebras";
     pub const DEEPSEEK: &'static str = "deepseek";
+    pub const FIREWORKS: &'static str = "fireworks";
     pub const GALADRIEL: &'static str = "galadriel";
     pub const GROQ: &'static str = "groq";
     pub const HYPERBOLIC: &'static str = "hyperbolic";
+    pub const MINIMAX: &'static str = "minimax";
     pub const MOONSHOT: &'static str = "moonshot";
     pub const MIRA: &'static str = "mira";
     pub const MISTRAL: &'static str = "mistral";
+    pub const NVIDIA: &'static str = "nvidia";
     pub const OLLAMA: &'static str = "ollama";
     pub const PERPLEXITY: &'static str = "perplexity";
 }
@@ -64,6 +64,22 @@ pub struct CompletionModelHandle<'a> {
     pub inner: Arc<dyn CompletionModelDyn + 'a>,
 }
 
+impl<'a> CompletionModelHandle<'a> {
+    pub fn new(model: impl CompletionModelDyn + 'a) -> Self {
+        Self {
+            inner: Arc::new(model),
+        }
+    }
+}
+
+impl<'a> From<Box<dyn CompletionModelDyn + 'a>> for CompletionModelHandle<'a> {
+    fn from(model: Box<dyn CompletionModelDyn + 'a>) -> Self {
+        Self {
+            inner: Arc::from(model),
+        }
+    }
+}
+
 impl CompletionModel for CompletionModelHandle<'_> {
     type Response = ();
     type StreamingResponse = ();
@@ -116,3 +132,25 @@ impl<T: CompletionClientDyn + Clone + 'static> AsCompletion for T {
         Some(Box::new(self.clone()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{completion::Prompt, testing::MockCompletionModel};
+
+    #[tokio::test]
+    async fn test_agents_from_boxed_models() {
+        let models: Vec<Box<dyn CompletionModelDyn>> = vec![
+            Box::new(MockCompletionModel::new("first")),
+            Box::new(MockCompletionModel::new("second")),
+        ];
+
+        let mut responses = vec![];
+        for model in models {
+            let agent = AgentBuilder::new(CompletionModelHandle::from(model)).build();
+            responses.push(agent.prompt("Hello").await.unwrap());
+        }
+
+        assert_eq!(responses, vec!["first", "second"]);
+    }
+}
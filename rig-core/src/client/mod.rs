@@ -173,8 +173,8 @@ mod tests {
     use crate::image_generation::ImageGenerationRequest;
     use crate::message::AssistantContent;
     use crate::providers::{
-        anthropic, azure, cohere, deepseek, galadriel, gemini, huggingface, hyperbolic, mira,
-        moonshot, openai, openrouter, together, xai,
+        anthropic, azure, cerebras, cohere, deepseek, fireworks, galadriel, gemini, huggingface,
+        hyperbolic, minimax, mira, moonshot, nvidia, openai, openrouter, together, xai,
     };
     use crate::streaming::StreamingCompletion;
     use crate::tool::Tool;
@@ -304,6 +304,13 @@ mod tests {
                 image_generation_model: Some("dalle-2"),
                 audio_generation_model: Some(("tts-1", "onyx")),
             },
+            ClientConfig {
+                name: "Cerebras",
+                factory: Box::new(cerebras::Client::from_env_boxed),
+                env_variable: "CEREBRAS_API_KEY",
+                completion_model: Some(cerebras::LLAMA3_1_8B),
+                ..Default::default()
+            },
             ClientConfig {
                 name: "Deepseek",
                 factory: Box::new(deepseek::Client::from_env_boxed),
@@ -311,6 +318,13 @@ mod tests {
                 completion_model: Some(deepseek::DEEPSEEK_CHAT),
                 ..Default::default()
             },
+            ClientConfig {
+                name: "Fireworks",
+                factory: Box::new(fireworks::Client::from_env_boxed),
+                env_variable: "FIREWORKS_API_KEY",
+                completion_model: Some(fireworks::LLAMA_V3P1_8B_INSTRUCT),
+                ..Default::default()
+            },
             ClientConfig {
                 name: "Galadriel",
                 factory: Box::new(galadriel::Client::from_env_boxed),
@@ -342,6 +356,13 @@ mod tests {
                 completion_model: Some("gpt-4o"),
                 ..Default::default()
             },
+            ClientConfig {
+                name: "MiniMax",
+                factory: Box::new(minimax::Client::from_env_boxed),
+                env_variable: "MINIMAX_API_KEY",
+                completion_model: Some(minimax::ABAB_6_5S_CHAT),
+                ..Default::default()
+            },
             ClientConfig {
                 name: "Moonshot",
                 factory: Box::new(moonshot::Client::from_env_boxed),
@@ -349,6 +370,13 @@ mod tests {
                 completion_model: Some(moonshot::MOONSHOT_CHAT),
                 ..Default::default()
             },
+            ClientConfig {
+                name: "NVIDIA NIM",
+                factory: Box::new(nvidia::Client::from_env_boxed),
+                env_variable: "NVIDIA_API_KEY",
+                completion_model: Some(nvidia::MISTRAL_7B_INSTRUCT),
+                ..Default::default()
+            },
             ClientConfig {
                 name: "Ollama",
                 factory: Box::new(ollama::Client::from_env_boxed),
@@ -503,6 +531,8 @@ mod tests {
             temperature: None,
             max_tokens: None,
             additional_params: None,
+            tool_choice: None,
+            system_messages: vec![],
             chat_history: OneOrMany::one(Message::user("What is the capital of France?")),
         });
 
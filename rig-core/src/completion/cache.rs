@@ -0,0 +1,329 @@
+//! This module contains the [CachingModel] struct, a [CompletionModel] wrapper that caches
+//! completion responses in memory so identical requests are only sent to the provider once.
+//!
+//! Caching is most useful for deterministic workloads (e.g.: `temperature` set to 0), since
+//! a cached response is returned for an identical request regardless of the temperature.
+//! Streaming requests are never cached.
+//!
+//! # Example
+//! ```rust
+//! use std::time::Duration;
+//! use rig::{client::CompletionClient, completion::CachingModel, providers::openai};
+//!
+//! let openai = openai::Client::from_env();
+//!
+//! let model = CachingModel::new(openai.completion_model(openai::GPT_4O), 1000)
+//!     .ttl(Duration::from_secs(3600));
+//!
+//! let agent = rig::agent::AgentBuilder::new(model.clone())
+//!     .temperature(0.0)
+//!     .build();
+//!
+//! // ... prompt the agent ...
+//!
+//! let (hits, misses) = model.cache_stats();
+//! ```
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use crate::{streaming::StreamingCompletionResponse, OneOrMany};
+
+use super::{
+    AssistantContent, CompletionError, CompletionModel, CompletionRequest, CompletionResponse,
+};
+
+struct CacheEntry<R> {
+    choice: OneOrMany<AssistantContent>,
+    raw_response: R,
+    inserted_at: Option<Instant>,
+    last_used: u64,
+}
+
+struct Cache<R> {
+    entries: HashMap<u64, CacheEntry<R>>,
+    /// Monotonic counter used to find the least recently used entry
+    clock: u64,
+    hits: u64,
+    misses: u64,
+}
+
+/// A completion model wrapper with an in-memory LRU cache of completion responses.
+/// Clones of a [CachingModel] share the same cache.
+pub struct CachingModel<M: CompletionModel> {
+    model: M,
+    capacity: usize,
+    ttl: Option<Duration>,
+    cache: Arc<Mutex<Cache<M::Response>>>,
+}
+
+impl<M: CompletionModel> Clone for CachingModel<M> {
+    fn clone(&self) -> Self {
+        Self {
+            model: self.model.clone(),
+            capacity: self.capacity,
+            ttl: self.ttl,
+            cache: self.cache.clone(),
+        }
+    }
+}
+
+impl<M: CompletionModel> CachingModel<M> {
+    /// Wrap `model` with a cache holding at most `capacity` responses.
+    pub fn new(model: M, capacity: usize) -> Self {
+        Self {
+            model,
+            capacity,
+            ttl: None,
+            cache: Arc::new(Mutex::new(Cache {
+                entries: HashMap::new(),
+                clock: 0,
+                hits: 0,
+                misses: 0,
+            })),
+        }
+    }
+
+    /// Set how long cached responses remain valid. By default, responses never expire
+    /// (they are only evicted when the cache is full).
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Returns the number of cache hits and misses so far.
+    pub fn cache_stats(&self) -> (u64, u64) {
+        let cache = self
+            .cache
+            .lock()
+            .expect("Cache lock should not be poisoned");
+        (cache.hits, cache.misses)
+    }
+
+    /// Remove all cached responses (the statistics are preserved).
+    pub fn clear(&self) {
+        self.cache
+            .lock()
+            .expect("Cache lock should not be poisoned")
+            .entries
+            .clear();
+    }
+}
+
+/// Hash all the fields of the request, since they can all influence the response.
+fn cache_key(request: &CompletionRequest) -> u64 {
+    let request =
+        serde_json::to_value(request).expect("Completion requests should be serializable");
+
+    let mut hasher = DefaultHasher::new();
+    canonicalize(request).to_string().hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Sort the keys of the objects of `value`, since the order of the keys of maps (e.g.: the
+/// `additional_props` of documents) is not deterministic.
+fn canonicalize(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut entries = map.into_iter().collect::<Vec<_>>();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            serde_json::Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key, canonicalize(value)))
+                    .collect(),
+            )
+        }
+        serde_json::Value::Array(values) => {
+            serde_json::Value::Array(values.into_iter().map(canonicalize).collect())
+        }
+        value => value,
+    }
+}
+
+impl<M> CompletionModel for CachingModel<M>
+where
+    M: CompletionModel,
+    M::Response: Clone,
+{
+    type Response = M::Response;
+    type StreamingResponse = M::StreamingResponse;
+
+    async fn completion(
+        &self,
+        request: CompletionRequest,
+    ) -> Result<CompletionResponse<Self::Response>, CompletionError> {
+        let key = cache_key(&request);
+
+        {
+            let mut cache = self
+                .cache
+                .lock()
+                .expect("Cache lock should not be poisoned");
+            cache.clock += 1;
+            let clock = cache.clock;
+
+            let expired = cache.entries.get(&key).is_some_and(|entry| {
+                entry
+                    .inserted_at
+                    .zip(self.ttl)
+                    .is_some_and(|(inserted_at, ttl)| inserted_at.elapsed() > ttl)
+            });
+            if expired {
+                cache.entries.remove(&key);
+            }
+
+            if let Some(entry) = cache.entries.get_mut(&key) {
+                entry.last_used = clock;
+                let response = CompletionResponse {
+                    choice: entry.choice.clone(),
+                    raw_response: entry.raw_response.clone(),
+                };
+                cache.hits += 1;
+                tracing::debug!(target: "rig", "Completion cache hit");
+                return Ok(response);
+            }
+
+            cache.misses += 1;
+        }
+
+        let response = self.model.completion(request).await?;
+
+        if self.capacity > 0 {
+            let mut cache = self
+                .cache
+                .lock()
+                .expect("Cache lock should not be poisoned");
+
+            if cache.entries.len() >= self.capacity && !cache.entries.contains_key(&key) {
+                if let Some(lru) = cache
+                    .entries
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.last_used)
+                    .map(|(key, _)| *key)
+                {
+                    cache.entries.remove(&lru);
+                }
+            }
+
+            let last_used = cache.clock;
+            cache.entries.insert(
+                key,
+                CacheEntry {
+                    choice: response.choice.clone(),
+                    raw_response: response.raw_response.clone(),
+                    inserted_at: self.ttl.map(|_| Instant::now()),
+                    last_used,
+                },
+            );
+        }
+
+        Ok(response)
+    }
+
+    async fn stream(
+        &self,
+        request: CompletionRequest,
+    ) -> Result<StreamingCompletionResponse<Self::StreamingResponse>, CompletionError> {
+        self.model.stream(request).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{completion::Document, testing::MockCompletionModel};
+
+    fn responses() -> MockCompletionModel {
+        MockCompletionModel::sequence(["Response 1", "Response 2", "Response 3", "Response 4"])
+    }
+
+    #[tokio::test]
+    async fn test_cache_hit() {
+        let mock = responses();
+        let model = CachingModel::new(mock.clone(), 10);
+
+        let first = model.completion_request("Hello").send().await.unwrap();
+        let second = model.completion_request("Hello").send().await.unwrap();
+        let other = model.completion_request("Goodbye").send().await.unwrap();
+
+        assert_eq!(
+            first.choice,
+            OneOrMany::one(AssistantContent::text("Response 1"))
+        );
+        assert_eq!(
+            second.choice,
+            OneOrMany::one(AssistantContent::text("Response 1"))
+        );
+        assert_eq!(
+            other.choice,
+            OneOrMany::one(AssistantContent::text("Response 2"))
+        );
+        assert_eq!(mock.requests().len(), 2);
+        assert_eq!(model.cache_stats(), (1, 2));
+    }
+
+    #[test]
+    fn test_cache_key_ignores_key_order() {
+        let props = (0..16)
+            .map(|i| (format!("key{i}"), i.to_string()))
+            .collect::<Vec<_>>();
+        let document = |props: Vec<(String, String)>| Document {
+            id: "doc".to_string(),
+            text: "text".to_string(),
+            additional_props: props.into_iter().collect(),
+        };
+        let request = |document: Document| {
+            MockCompletionModel::new("")
+                .completion_request("Hello")
+                .document(document)
+                .build()
+        };
+
+        let first = request(document(props.clone()));
+        let second = request(document(props.into_iter().rev().collect()));
+
+        assert_eq!(cache_key(&first), cache_key(&second));
+    }
+
+    #[tokio::test]
+    async fn test_cache_eviction() {
+        let model = CachingModel::new(responses(), 2);
+
+        model.completion_request("a").send().await.unwrap();
+        model.completion_request("b").send().await.unwrap();
+        // "a" is now more recently used than "b"
+        model.completion_request("a").send().await.unwrap();
+        // Evicts "b"
+        model.completion_request("c").send().await.unwrap();
+
+        assert_eq!(
+            model.completion_request("a").send().await.unwrap().choice,
+            OneOrMany::one(AssistantContent::text("Response 1"))
+        );
+        assert_eq!(
+            model.completion_request("b").send().await.unwrap().choice,
+            OneOrMany::one(AssistantContent::text("Response 4"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cache_ttl() {
+        let mock = responses();
+        let model = CachingModel::new(mock.clone(), 10).ttl(Duration::from_millis(10));
+
+        model.completion_request("Hello").send().await.unwrap();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let response = model.completion_request("Hello").send().await.unwrap();
+
+        assert_eq!(
+            response.choice,
+            OneOrMany::one(AssistantContent::text("Response 2"))
+        );
+        assert_eq!(mock.requests().len(), 2);
+        assert_eq!(model.cache_stats(), (0, 2));
+    }
+}
@@ -0,0 +1,254 @@
+//! This module contains the [FallbackModel] struct, a [CompletionModel] that automatically fails
+//! over to a secondary model when the primary one is unavailable.
+//!
+//! Fallback models are composable, which allows chaining as many providers as needed.
+//!
+//! # Example
+//! ```rust
+//! use rig::{
+//!     client::{CompletionClient, ProviderClient},
+//!     completion::FallbackModel,
+//!     providers::{anthropic, groq, openai},
+//! };
+//!
+//! let model = FallbackModel::new(
+//!     openai::Client::from_env().completion_model(openai::GPT_4O),
+//!     FallbackModel::new(
+//!         anthropic::Client::from_env().completion_model(anthropic::CLAUDE_3_5_SONNET),
+//!         groq::Client::from_env().completion_model("llama-3.3-70b-versatile"),
+//!     ),
+//! );
+//! ```
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+
+use crate::streaming::{RawStreamingChoice, StreamingCompletionResponse, StreamingResult};
+
+use super::{CompletionError, CompletionModel, CompletionRequest, CompletionResponse};
+
+/// The raw response of a [FallbackModel], indicating which of the two models produced it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum FallbackResponse<A, B> {
+    Primary(A),
+    Fallback(B),
+}
+
+/// A completion model that sends requests to `primary` and retries them on `fallback` when
+/// `primary` is unavailable, i.e.: it responds with a server error (5xx) or the request times out
+/// or cannot connect.
+///
+/// Other errors (e.g.: an invalid request or API key, or a message that cannot be converted to
+/// the provider's format) are returned as is, since the fallback would likely fail the same way.
+#[derive(Clone)]
+pub struct FallbackModel<A, B> {
+    pub primary: A,
+    pub fallback: B,
+}
+
+impl<A: CompletionModel, B: CompletionModel> FallbackModel<A, B> {
+    pub fn new(primary: A, fallback: B) -> Self {
+        Self { primary, fallback }
+    }
+}
+
+/// Whether the error returned by the primary model warrants retrying the request on the fallback model.
+fn should_fallback(error: &CompletionError) -> bool {
+    match error {
+        CompletionError::HttpError(err) => {
+            err.is_timeout() || err.is_connect() || err.status().is_some_and(is_retryable)
+        }
+        CompletionError::StatusError { status, .. } => is_retryable(*status),
+        _ => false,
+    }
+}
+
+fn is_retryable(status: reqwest::StatusCode) -> bool {
+    status.is_server_error() || status == reqwest::StatusCode::REQUEST_TIMEOUT
+}
+
+impl<A, B> CompletionModel for FallbackModel<A, B>
+where
+    A: CompletionModel,
+    B: CompletionModel,
+    A::StreamingResponse: 'static,
+    B::StreamingResponse: 'static,
+{
+    type Response = FallbackResponse<A::Response, B::Response>;
+    type StreamingResponse = FallbackResponse<A::StreamingResponse, B::StreamingResponse>;
+
+    async fn completion(
+        &self,
+        request: CompletionRequest,
+    ) -> Result<CompletionResponse<Self::Response>, CompletionError> {
+        match self.primary.completion(request.clone()).await {
+            Ok(response) => Ok(CompletionResponse {
+                choice: response.choice,
+                raw_response: FallbackResponse::Primary(response.raw_response),
+            }),
+            Err(err) if should_fallback(&err) => {
+                tracing::warn!(target: "rig", "Primary completion model failed, falling back: {err}");
+                let response = self.fallback.completion(request).await?;
+                Ok(CompletionResponse {
+                    choice: response.choice,
+                    raw_response: FallbackResponse::Fallback(response.raw_response),
+                })
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    async fn stream(
+        &self,
+        request: CompletionRequest,
+    ) -> Result<StreamingCompletionResponse<Self::StreamingResponse>, CompletionError> {
+        let inner: StreamingResult<Self::StreamingResponse> = match self
+            .primary
+            .stream(request.clone())
+            .await
+        {
+            Ok(response) => Box::pin(
+                response
+                    .inner
+                    .map(|chunk| chunk.map(|chunk| map_chunk(chunk, FallbackResponse::Primary))),
+            ),
+            Err(err) if should_fallback(&err) => {
+                tracing::warn!(target: "rig", "Primary completion model failed, falling back: {err}");
+                let response = self.fallback.stream(request).await?;
+                Box::pin(
+                    response.inner.map(|chunk| {
+                        chunk.map(|chunk| map_chunk(chunk, FallbackResponse::Fallback))
+                    }),
+                )
+            }
+            Err(err) => return Err(err),
+        };
+
+        Ok(StreamingCompletionResponse::stream(inner))
+    }
+}
+
+fn map_chunk<R: Clone, T: Clone>(
+    chunk: RawStreamingChoice<R>,
+    f: impl FnOnce(R) -> T,
+) -> RawStreamingChoice<T> {
+    match chunk {
+        RawStreamingChoice::Message(text) => RawStreamingChoice::Message(text),
+        RawStreamingChoice::Reasoning(reasoning) => RawStreamingChoice::Reasoning(reasoning),
+        RawStreamingChoice::ToolCall {
+            id,
+            name,
+            arguments,
+        } => RawStreamingChoice::ToolCall {
+            id,
+            name,
+            arguments,
+        },
+        RawStreamingChoice::FinalResponse(response) => {
+            RawStreamingChoice::FinalResponse(f(response))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{completion::AssistantContent, testing::MockCompletionModel, OneOrMany};
+
+    fn ok(text: &str) -> MockCompletionModel {
+        MockCompletionModel::new(text)
+    }
+
+    fn failing_with(status: reqwest::StatusCode) -> MockCompletionModel {
+        MockCompletionModel::try_from_fn(move |_| {
+            Err(CompletionError::StatusError {
+                status,
+                body: String::new(),
+            })
+        })
+    }
+
+    fn failing() -> MockCompletionModel {
+        failing_with(reqwest::StatusCode::SERVICE_UNAVAILABLE)
+    }
+
+    #[tokio::test]
+    async fn test_fallback_completion() {
+        let model = FallbackModel::new(failing(), FallbackModel::new(failing(), ok("tertiary")));
+
+        let response = model
+            .completion_request("Hello")
+            .send()
+            .await
+            .expect("Fallback should succeed");
+
+        assert_eq!(
+            response.choice,
+            OneOrMany::one(AssistantContent::text("tertiary"))
+        );
+        assert!(matches!(
+            response.raw_response,
+            FallbackResponse::Fallback(FallbackResponse::Fallback(()))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_primary_completion() {
+        let model = FallbackModel::new(ok("primary"), failing());
+
+        let response = model.completion_request("Hello").send().await.unwrap();
+
+        assert!(matches!(
+            response.raw_response,
+            FallbackResponse::Primary(())
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_all_models_fail() {
+        let model = FallbackModel::new(failing(), failing());
+
+        let result = model.completion_request("Hello").send().await;
+
+        assert!(matches!(result, Err(CompletionError::StatusError { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_no_fallback_on_client_error() {
+        let model = FallbackModel::new(
+            failing_with(reqwest::StatusCode::UNAUTHORIZED),
+            ok("secondary"),
+        );
+
+        let result = model.completion_request("Hello").send().await;
+
+        assert!(matches!(
+            result,
+            Err(CompletionError::StatusError { status, .. })
+                if status == reqwest::StatusCode::UNAUTHORIZED
+        ));
+        assert!(!should_fallback(&CompletionError::ProviderError(
+            "Invalid model".to_string()
+        )));
+        assert!(should_fallback(&CompletionError::StatusError {
+            status: reqwest::StatusCode::REQUEST_TIMEOUT,
+            body: String::new(),
+        }));
+    }
+
+    #[tokio::test]
+    async fn test_fallback_stream() {
+        let model = FallbackModel::new(failing(), ok("secondary"));
+
+        let mut stream = model.completion_request("Hello").stream().await.unwrap();
+        while stream.next().await.is_some() {}
+
+        assert_eq!(
+            stream.choice,
+            OneOrMany::one(AssistantContent::text("secondary"))
+        );
+        assert!(matches!(
+            stream.response,
+            Some(FallbackResponse::Fallback(()))
+        ));
+    }
+}
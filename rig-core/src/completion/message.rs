@@ -54,10 +54,19 @@ pub enum AssistantContent {
 }
 
 /// Tool result content containing information about a tool call and it's resulting content.
+///
+/// Use [ToolResult::new] or [ToolResult::error] to create one.
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 pub struct ToolResult {
     pub id: String,
     pub content: OneOrMany<ToolResultContent>,
+    /// Whether the content is an error returned by the tool (see
+    /// [ToolError::Recoverable](crate::tool::ToolError::Recoverable)), so that the model can
+    /// recover from it (e.g.: by calling the tool again with different arguments).
+    /// Only sent by the providers whose API has an error flag (Anthropic, Gemini and Bedrock):
+    /// the others ignore it and send the content as is.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub is_error: bool,
 }
 
 /// Describes the content of a tool result, which can be text or an image.
@@ -216,6 +225,14 @@ impl Message {
         }
     }
 
+    /// Returns the role of the author of the message.
+    pub fn role(&self) -> Role {
+        match self {
+            Message::User { .. } => Role::User,
+            Message::Assistant { .. } => Role::Assistant,
+        }
+    }
+
     /// Helper constructor to make creating user messages easier.
     pub fn user(text: impl Into<String>) -> Self {
         Message::User {
@@ -224,19 +241,53 @@ impl Message {
     }
 
     /// Helper constructor to make creating assistant messages easier.
+    ///
+    /// Note: there is no system message constructor, as system instructions are not part of
+    /// the messages but set with the preamble of the completion request (see
+    /// [CompletionRequestBuilder::preamble](crate::completion::CompletionRequestBuilder::preamble)).
     pub fn assistant(text: impl Into<String>) -> Self {
         Message::Assistant {
             content: OneOrMany::one(AssistantContent::text(text)),
         }
     }
 
+    /// Helper constructor to create a user message from a multiline string (e.g.: a raw string
+    /// literal indented along with the code). Leading and trailing blank lines are removed, as
+    /// well as the indentation common to all the non-blank lines.
+    pub fn from_str_multiline(text: &str) -> Self {
+        let lines = text
+            .lines()
+            .skip_while(|line| line.trim().is_empty())
+            .collect::<Vec<_>>();
+        let end = lines
+            .iter()
+            .rposition(|line| !line.trim().is_empty())
+            .map_or(0, |i| i + 1);
+        let lines = &lines[..end];
+
+        let indent = lines
+            .iter()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| line.len() - line.trim_start().len())
+            .min()
+            .unwrap_or(0);
+
+        Message::user(
+            lines
+                .iter()
+                .map(|line| line.get(indent..).unwrap_or("").trim_end())
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )
+    }
+
     /// Helper constructor to make creating tool result messages easier.
     pub fn tool_result(id: impl Into<String>, content: impl Into<String>) -> Self {
         Message::User {
-            content: OneOrMany::one(UserContent::ToolResult(ToolResult {
-                id: id.into(),
-                content: OneOrMany::one(ToolResultContent::text(content)),
-            })),
+            content: OneOrMany::one(UserContent::ToolResult(ToolResult::new(
+                id,
+                OneOrMany::one(ToolResultContent::text(content)),
+            ))),
         }
     }
 }
@@ -290,10 +341,26 @@ impl UserContent {
 
     /// Helper constructor to make creating user tool result content easier.
     pub fn tool_result(id: impl Into<String>, content: OneOrMany<ToolResultContent>) -> Self {
-        UserContent::ToolResult(ToolResult {
+        UserContent::ToolResult(ToolResult::new(id, content))
+    }
+}
+
+impl ToolResult {
+    /// Create the result of a successful tool call.
+    pub fn new(id: impl Into<String>, content: OneOrMany<ToolResultContent>) -> Self {
+        Self {
             id: id.into(),
             content,
-        })
+            is_error: false,
+        }
+    }
+
+    /// Create the result of a failed tool call, whose content is the error.
+    pub fn error(id: impl Into<String>, content: OneOrMany<ToolResultContent>) -> Self {
+        Self {
+            is_error: true,
+            ..Self::new(id, content)
+        }
     }
 }
 
@@ -618,14 +685,209 @@ impl From<ToolResult> for Message {
 impl From<ToolResultContent> for Message {
     fn from(tool_result_content: ToolResultContent) -> Self {
         Message::User {
-            content: OneOrMany::one(UserContent::ToolResult(ToolResult {
-                id: String::new(),
-                content: OneOrMany::one(tool_result_content),
-            })),
+            content: OneOrMany::one(UserContent::ToolResult(ToolResult::new(
+                String::new(),
+                OneOrMany::one(tool_result_content),
+            ))),
+        }
+    }
+}
+
+/// Converts a `(role, content)` pair (e.g.: a message of a dataset) into a text message.
+impl From<(Role, String)> for Message {
+    fn from((role, text): (Role, String)) -> Self {
+        match role {
+            Role::User => Message::user(text),
+            Role::Assistant => Message::assistant(text),
+        }
+    }
+}
+
+/// Converts a text message into a `(role, content)` pair. The texts of a message with several
+/// pieces of content are joined with new lines.
+///
+/// Fails if the message contains anything but text (e.g.: tool calls, tool results or images),
+/// which cannot be represented as a plain string.
+impl TryFrom<Message> for (Role, String) {
+    type Error = MessageError;
+
+    fn try_from(message: Message) -> Result<Self, Self::Error> {
+        let role = message.role();
+        let texts = match message {
+            Message::User { content } => content
+                .into_iter()
+                .map(|content| match content {
+                    UserContent::Text(Text { text }) => Ok(text),
+                    _ => Err(MessageError::ConversionError(
+                        "User message contains non-text content".into(),
+                    )),
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+            Message::Assistant { content } => content
+                .into_iter()
+                .map(|content| match content {
+                    AssistantContent::Text(Text { text }) => Ok(text),
+                    AssistantContent::ToolCall(_) => Err(MessageError::ConversionError(
+                        "Assistant message contains a tool call".into(),
+                    )),
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+        };
+
+        Ok((role, texts.join("\n")))
+    }
+}
+
+// ================================================================
+// Message builder
+// ================================================================
+
+/// The role of the author of a message.
+///
+/// Note: there is no system role, as system instructions are set with the preamble of the
+/// completion request, and tool results are user messages (see [UserContent::ToolResult]).
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    #[default]
+    User,
+    Assistant,
+}
+
+impl FromStr for Role {
+    type Err = MessageError;
+
+    /// Parse a role name (e.g.: the role of a message of a dataset), case insensitively.
+    fn from_str(role: &str) -> Result<Self, Self::Err> {
+        match role.to_lowercase().as_str() {
+            "user" | "human" => Ok(Role::User),
+            "assistant" | "ai" => Ok(Role::Assistant),
+            _ => Err(MessageError::ConversionError(format!(
+                "Unsupported message role: {role}"
+            ))),
         }
     }
 }
 
+/// A builder for messages made of several pieces of content (e.g.: text along with images).
+///
+/// # Example
+/// ```rust
+/// use rig::message::{MessageBuilder, Role};
+///
+/// let message = MessageBuilder::new()
+///     .role(Role::User)
+///     .text("What is in this image?")
+///     .image_url("https://example.com/image.png")
+///     .build();
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct MessageBuilder {
+    role: Role,
+    content: Vec<UserContent>,
+    html_escape: bool,
+}
+
+impl MessageBuilder {
+    /// Create a new builder for a user message.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the role of the message (defaults to [Role::User]).
+    pub fn role(mut self, role: Role) -> Self {
+        self.role = role;
+        self
+    }
+
+    /// Add text content to the message.
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.content.push(UserContent::text(text));
+        self
+    }
+
+    /// Add an image content referenced by its url to the message.
+    pub fn image_url(mut self, url: impl Into<String>) -> Self {
+        self.content.push(UserContent::image(
+            url,
+            Some(ContentFormat::String),
+            None,
+            None,
+        ));
+        self
+    }
+
+    /// Add a base64 encoded image content to the message.
+    pub fn image_base64(mut self, data: impl Into<String>, media_type: ImageMediaType) -> Self {
+        self.content.push(UserContent::image(
+            data,
+            Some(ContentFormat::Base64),
+            Some(media_type),
+            None,
+        ));
+        self
+    }
+
+    /// Add any content to the message.
+    pub fn content(mut self, content: UserContent) -> Self {
+        self.content.push(content);
+        self
+    }
+
+    /// Whether to HTML-encode the special characters (`&`, `<`, `>`, `"` and `'`) of the text
+    /// content, for providers that require it. Disabled by default.
+    pub fn html_escape(mut self, html_escape: bool) -> Self {
+        self.html_escape = html_escape;
+        self
+    }
+
+    /// Build the message. A message without content contains an empty text.
+    ///
+    /// Note: assistant messages can only contain text, so any other content is discarded
+    /// when the role is [Role::Assistant].
+    pub fn build(self) -> Message {
+        let html_escape = self.html_escape;
+        let content = self.content.into_iter().map(|content| match content {
+            UserContent::Text(Text { text }) if html_escape => {
+                UserContent::text(escape_html(&text))
+            }
+            content => content,
+        });
+
+        match self.role {
+            Role::User => Message::User {
+                content: OneOrMany::many(content)
+                    .unwrap_or_else(|_| OneOrMany::one(UserContent::text(""))),
+            },
+            Role::Assistant => Message::Assistant {
+                content: OneOrMany::many(content.filter_map(|content| match content {
+                    UserContent::Text(text) => Some(AssistantContent::Text(text)),
+                    _ => {
+                        tracing::warn!(target: "rig", "Discarding non-text content of an assistant message");
+                        None
+                    }
+                }))
+                .unwrap_or_else(|_| OneOrMany::one(AssistantContent::text(""))),
+            },
+        }
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
 // ================================================================
 // Error types
 // ================================================================
@@ -642,3 +904,87 @@ impl From<MessageError> for CompletionError {
         CompletionError::RequestError(error.into())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_str_multiline() {
+        let message = Message::from_str_multiline(
+            "
+            First line
+              indented line
+
+            Last line
+            ",
+        );
+        assert_eq!(
+            message,
+            Message::user("First line\n  indented line\n\nLast line")
+        );
+    }
+
+    #[test]
+    fn test_message_builder() {
+        let message = MessageBuilder::new()
+            .text("Is <b>this</b> a \"cat\"?")
+            .image_url("https://example.com/cat.png")
+            .html_escape(true)
+            .build();
+        assert_eq!(
+            message,
+            Message::User {
+                content: OneOrMany::many(vec![
+                    UserContent::text("Is &lt;b&gt;this&lt;/b&gt; a &quot;cat&quot;?"),
+                    UserContent::image(
+                        "https://example.com/cat.png",
+                        Some(ContentFormat::String),
+                        None,
+                        None
+                    ),
+                ])
+                .unwrap(),
+            }
+        );
+
+        let message = MessageBuilder::new()
+            .role(Role::Assistant)
+            .text("It is a cat.")
+            .build();
+        assert_eq!(message, Message::assistant("It is a cat."));
+    }
+
+    #[test]
+    fn test_role_content_pairs() {
+        let message = Message::from((Role::Assistant, "Hello!".to_string()));
+        assert_eq!(message, Message::assistant("Hello!"));
+        assert_eq!(message.role(), Role::Assistant);
+        assert_eq!(
+            <(Role, String)>::try_from(message).unwrap(),
+            (Role::Assistant, "Hello!".to_string())
+        );
+
+        let message = Message::User {
+            content: OneOrMany::many(vec![UserContent::text("Hi"), UserContent::text("there")])
+                .unwrap(),
+        };
+        assert_eq!(
+            <(Role, String)>::try_from(message).unwrap(),
+            (Role::User, "Hi\nthere".to_string())
+        );
+
+        let message = Message::from(ToolCall {
+            id: "call_1".to_string(),
+            function: ToolFunction {
+                name: "add".to_string(),
+                arguments: serde_json::json!({"x": 1, "y": 2}),
+            },
+        });
+        assert!(<(Role, String)>::try_from(message).is_err());
+
+        assert_eq!("Human".parse::<Role>().unwrap(), Role::User);
+        assert_eq!("assistant".parse::<Role>().unwrap(), Role::Assistant);
+        assert!("system".parse::<Role>().is_err());
+    }
+}
@@ -1,5 +1,11 @@
+pub mod cache;
+pub mod fallback;
 pub mod message;
+pub mod prompt_router;
 pub mod request;
 
-pub use message::{AssistantContent, Message, MessageError};
+pub use cache::CachingModel;
+pub use fallback::{FallbackModel, FallbackResponse};
+pub use message::{AssistantContent, Message, MessageBuilder, MessageError};
+pub use prompt_router::PromptRouter;
 pub use request::*;
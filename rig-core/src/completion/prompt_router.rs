@@ -0,0 +1,232 @@
+//! This module contains the [PromptRouter] struct, a [CompletionModel] that sends each request
+//! to the most suitable of its models (e.g.: the cheapest model able to handle the prompt),
+//! according to scoring functions of the prompt.
+//!
+//! The [scorers] module provides basic scoring functions based on the length of the prompt and
+//! the presence of keywords.
+//!
+//! # Example
+//! ```rust
+//! use rig::{
+//!     client::CompletionClient,
+//!     completion::{prompt_router::scorers, PromptRouter},
+//!     providers::openai,
+//! };
+//!
+//! let openai = openai::Client::from_env();
+//!
+//! let model = PromptRouter::new()
+//!     // Short prompts go to the cheap model...
+//!     .model(scorers::length_below(500), openai.completion_model(openai::GPT_4O_MINI))
+//!     // ... unless they are about code or require reasoning
+//!     .model(scorers::coding(), openai.completion_model(openai::GPT_4O))
+//!     .model(scorers::reasoning(), openai.completion_model(openai::O3_MINI));
+//!
+//! let agent = rig::agent::AgentBuilder::new(model).build();
+//! ```
+use std::sync::Arc;
+
+use crate::streaming::StreamingCompletionResponse;
+
+use super::{
+    CompletionError, CompletionModel, CompletionModelDyn, CompletionRequest, CompletionResponse,
+};
+
+/// A function rating the suitability of a model for a prompt (the higher, the more suitable).
+pub type Scorer = Arc<dyn Fn(&str) -> f32 + Send + Sync>;
+
+/// A completion model sending each request to the model whose scorer gives the highest score
+/// to the prompt (i.e.: the text of the last message of the request). In case of a tie, the model
+/// added first is selected.
+#[derive(Clone, Default)]
+pub struct PromptRouter {
+    models: Vec<(Scorer, Arc<dyn CompletionModelDyn>)>,
+}
+
+impl PromptRouter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a model, rated for each prompt by `scorer`.
+    pub fn model<M>(self, scorer: impl Fn(&str) -> f32 + Send + Sync + 'static, model: M) -> Self
+    where
+        M: CompletionModel + 'static,
+        M::StreamingResponse: 'static,
+    {
+        self.model_dyn(scorer, Box::new(model))
+    }
+
+    /// Add a (boxed) model, rated for each prompt by `scorer`.
+    pub fn model_dyn(
+        mut self,
+        scorer: impl Fn(&str) -> f32 + Send + Sync + 'static,
+        model: Box<dyn CompletionModelDyn>,
+    ) -> Self {
+        self.models.push((Arc::new(scorer), model.into()));
+        self
+    }
+
+    /// Returns the index (in the order they were added) of the model selected for `prompt`.
+    pub fn select(&self, prompt: &str) -> Option<usize> {
+        self.models
+            .iter()
+            .map(|(scorer, _)| scorer(prompt))
+            .enumerate()
+            .fold(None, |best: Option<(usize, f32)>, (i, score)| match best {
+                Some((_, best_score)) if best_score >= score => best,
+                _ => Some((i, score)),
+            })
+            .map(|(i, _)| i)
+    }
+
+    fn route(
+        &self,
+        request: &CompletionRequest,
+    ) -> Result<&dyn CompletionModelDyn, CompletionError> {
+        let prompt = request
+            .chat_history
+            .iter()
+            .last()
+            .and_then(|message| message.rag_text())
+            .unwrap_or_default();
+
+        let index = self.select(&prompt).ok_or_else(|| {
+            CompletionError::RequestError("The prompt router has no models".into())
+        })?;
+        tracing::debug!(target: "rig", "Routing request to model #{index}");
+
+        Ok(self.models[index].1.as_ref())
+    }
+}
+
+impl CompletionModel for PromptRouter {
+    type Response = ();
+    type StreamingResponse = ();
+
+    async fn completion(
+        &self,
+        request: CompletionRequest,
+    ) -> Result<CompletionResponse<Self::Response>, CompletionError> {
+        self.route(&request)?.completion(request).await
+    }
+
+    async fn stream(
+        &self,
+        request: CompletionRequest,
+    ) -> Result<StreamingCompletionResponse<Self::StreamingResponse>, CompletionError> {
+        self.route(&request)?.stream(request).await
+    }
+}
+
+/// Basic scoring functions to use with a [PromptRouter].
+pub mod scorers {
+    /// Keywords of prompts about code, used by [coding].
+    pub const CODING_KEYWORDS: &[&str] = &[
+        "write code",
+        "code",
+        "function",
+        "implement",
+        "debug",
+        "compile",
+        "refactor",
+        "stack trace",
+        "```",
+    ];
+
+    /// Keywords of prompts requiring reasoning, used by [reasoning].
+    pub const REASONING_KEYWORDS: &[&str] = &[
+        "step by step",
+        "prove",
+        "explain why",
+        "analyze",
+        "analyse",
+        "compare",
+    ];
+
+    /// Always returns `score`, e.g.: for a default model.
+    pub fn constant(score: f32) -> impl Fn(&str) -> f32 + Send + Sync + 'static {
+        move |_| score
+    }
+
+    /// Returns `1.0` for an empty prompt, decreasing linearly to `0.0` for prompts of
+    /// `max_chars` characters or more (e.g.: for a small model with a short context window).
+    pub fn length_below(max_chars: usize) -> impl Fn(&str) -> f32 + Send + Sync + 'static {
+        move |prompt| {
+            let length = prompt.chars().count();
+            if length >= max_chars {
+                0.0
+            } else {
+                1.0 - length as f32 / max_chars as f32
+            }
+        }
+    }
+
+    /// Returns `score` if the prompt contains any of `keywords` (case-insensitively),
+    /// `0.0` otherwise.
+    pub fn keywords(keywords: &[&str], score: f32) -> impl Fn(&str) -> f32 + Send + Sync + 'static {
+        let keywords = keywords
+            .iter()
+            .map(|keyword| keyword.to_lowercase())
+            .collect::<Vec<_>>();
+
+        move |prompt| {
+            let prompt = prompt.to_lowercase();
+            if keywords
+                .iter()
+                .any(|keyword| prompt.contains(keyword.as_str()))
+            {
+                score
+            } else {
+                0.0
+            }
+        }
+    }
+
+    /// Scores prompts about code (see [CODING_KEYWORDS]) above any [length_below] score.
+    pub fn coding() -> impl Fn(&str) -> f32 + Send + Sync + 'static {
+        keywords(CODING_KEYWORDS, 2.0)
+    }
+
+    /// Scores prompts requiring reasoning (see [REASONING_KEYWORDS]) above any
+    /// [length_below] score.
+    pub fn reasoning() -> impl Fn(&str) -> f32 + Send + Sync + 'static {
+        keywords(REASONING_KEYWORDS, 2.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{completion::AssistantContent, testing::MockCompletionModel, OneOrMany};
+
+    #[tokio::test]
+    async fn test_prompt_router() {
+        let router = PromptRouter::new()
+            .model(
+                scorers::length_below(100),
+                MockCompletionModel::new("small"),
+            )
+            .model(scorers::coding(), MockCompletionModel::new("coder"))
+            .model(scorers::constant(0.1), MockCompletionModel::new("large"));
+
+        assert_eq!(router.select("What's the capital of France?"), Some(0));
+        assert_eq!(router.select("Write code to reverse a list"), Some(1));
+        assert_eq!(router.select(&"Summarize this text. ".repeat(10)), Some(2));
+
+        // Both `CompletionModel` and `CompletionModelDyn` are in scope
+        let response = CompletionModel::completion_request(&router, "Debug this function")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(
+            response.choice,
+            OneOrMany::one(AssistantContent::text("coder"))
+        );
+
+        let result = CompletionModel::completion_request(&PromptRouter::new(), "Hi")
+            .send()
+            .await;
+        assert!(matches!(result, Err(CompletionError::RequestError(_))));
+    }
+}
@@ -0,0 +1,230 @@
+//! This module contains utilities for post-processing the context retrieved from vector stores
+//! before it is injected in a prompt.
+//!
+//! The [ChunkReranker] struct wraps a [VectorStoreIndex] and a [Reranker]. On each query,
+//! it over-fetches candidates from the wrapped index, scores them with the reranker and only
+//! returns the `n` best candidates according to the reranker. Since [ChunkReranker] implements
+//! [VectorStoreIndex] itself, it can be used anywhere an index is expected
+//! (e.g.: [AgentBuilder::dynamic_context](crate::agent::AgentBuilder::dynamic_context)).
+//!
+//! # Example
+//! ```rust
+//! use rig::context::{ChunkReranker, Reranker};
+//!
+//! let index = vector_store.index(embedding_model);
+//! let reranked_index = ChunkReranker::new(index, my_reranker).over_fetch_factor(4);
+//!
+//! let agent = openai.agent(openai::GPT_4O)
+//!     .dynamic_context(3, reranked_index)
+//!     .build();
+//! ```
+use std::future::Future;
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::vector_store::{VectorStoreError, VectorStoreIndex};
+
+/// Trait for models that score the relevance of documents to a query (e.g.: cross-encoders).
+pub trait Reranker: Send + Sync {
+    /// Score each document against the query. Higher scores are more relevant.
+    /// The returned vector must contain exactly one score per document, in the same order.
+    fn rerank(
+        &self,
+        query: &str,
+        documents: &[String],
+    ) -> impl Future<Output = Result<Vec<f64>, VectorStoreError>> + Send;
+}
+
+/// A [VectorStoreIndex] that reranks the candidates retrieved by an inner index.
+pub struct ChunkReranker<I: VectorStoreIndex, R: Reranker> {
+    index: I,
+    reranker: R,
+    over_fetch_factor: usize,
+}
+
+impl<I: VectorStoreIndex, R: Reranker> ChunkReranker<I, R> {
+    /// Create a new reranking index. By default, three times as many candidates as requested
+    /// are retrieved from the inner index.
+    pub fn new(index: I, reranker: R) -> Self {
+        Self {
+            index,
+            reranker,
+            over_fetch_factor: 3,
+        }
+    }
+
+    /// Set how many candidates are retrieved from the inner index for each requested result
+    /// (i.e.: `top_n(query, n)` retrieves `n * over_fetch_factor` candidates).
+    pub fn over_fetch_factor(mut self, over_fetch_factor: usize) -> Self {
+        self.over_fetch_factor = over_fetch_factor.max(1);
+        self
+    }
+
+    /// Retrieve the candidates from the inner index and return the `n` best ones, sorted by
+    /// descending reranker score.
+    async fn rerank(
+        &self,
+        query: &str,
+        n: usize,
+    ) -> Result<Vec<(f64, String, Value)>, VectorStoreError> {
+        let candidates = self
+            .index
+            .top_n::<Value>(query, n * self.over_fetch_factor)
+            .await?;
+
+        if candidates.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let texts = candidates
+            .iter()
+            .map(|(_, _, doc)| match doc {
+                Value::String(text) => text.clone(),
+                doc => doc.to_string(),
+            })
+            .collect::<Vec<_>>();
+
+        let scores = self.reranker.rerank(query, &texts).await?;
+
+        if scores.len() != candidates.len() {
+            return Err(VectorStoreError::DatastoreError(
+                format!(
+                    "Reranker returned {} scores for {} documents",
+                    scores.len(),
+                    candidates.len()
+                )
+                .into(),
+            ));
+        }
+
+        let mut reranked = scores
+            .into_iter()
+            .zip(candidates)
+            .map(|(score, (_, id, doc))| (score, id, doc))
+            .collect::<Vec<_>>();
+
+        reranked.sort_by(|a, b| b.0.total_cmp(&a.0));
+        reranked.truncate(n);
+
+        tracing::info!(target: "rig",
+            "Reranked documents: {}",
+            reranked
+                .iter()
+                .map(|(score, id, _)| format!("{id} ({score})"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+
+        Ok(reranked)
+    }
+}
+
+impl<I: VectorStoreIndex, R: Reranker> VectorStoreIndex for ChunkReranker<I, R> {
+    async fn top_n<T: for<'a> Deserialize<'a> + Send>(
+        &self,
+        query: &str,
+        n: usize,
+    ) -> Result<Vec<(f64, String, T)>, VectorStoreError> {
+        self.rerank(query, n)
+            .await?
+            .into_iter()
+            .map(|(score, id, doc)| {
+                Ok::<_, VectorStoreError>((score, id, serde_json::from_value(doc)?))
+            })
+            .collect()
+    }
+
+    async fn top_n_ids(
+        &self,
+        query: &str,
+        n: usize,
+    ) -> Result<Vec<(f64, String)>, VectorStoreError> {
+        Ok(self
+            .rerank(query, n)
+            .await?
+            .into_iter()
+            .map(|(score, id, _)| (score, id))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct MockIndex;
+
+    impl VectorStoreIndex for MockIndex {
+        async fn top_n<T: for<'a> Deserialize<'a> + Send>(
+            &self,
+            _query: &str,
+            n: usize,
+        ) -> Result<Vec<(f64, String, T)>, VectorStoreError> {
+            ["apple pie", "banana bread", "cherry tart", "date loaf"]
+                .into_iter()
+                .enumerate()
+                .take(n)
+                .map(|(i, doc)| {
+                    Ok::<_, VectorStoreError>((
+                        1.0 - i as f64 * 0.1,
+                        format!("doc{i}"),
+                        serde_json::from_value(Value::String(doc.to_string()))?,
+                    ))
+                })
+                .collect()
+        }
+
+        async fn top_n_ids(
+            &self,
+            query: &str,
+            n: usize,
+        ) -> Result<Vec<(f64, String)>, VectorStoreError> {
+            Ok(self
+                .top_n::<Value>(query, n)
+                .await?
+                .into_iter()
+                .map(|(score, id, _)| (score, id))
+                .collect())
+        }
+    }
+
+    /// Scores documents by whether they contain the query
+    struct ContainsReranker;
+
+    impl Reranker for ContainsReranker {
+        async fn rerank(
+            &self,
+            query: &str,
+            documents: &[String],
+        ) -> Result<Vec<f64>, VectorStoreError> {
+            Ok(documents
+                .iter()
+                .map(|doc| if doc.contains(query) { 1.0 } else { 0.0 })
+                .collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rerank_top_n() {
+        let index = ChunkReranker::new(MockIndex, ContainsReranker).over_fetch_factor(2);
+
+        let results = index.top_n::<String>("cherry", 2).await.unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(
+            results[0],
+            (1.0, "doc2".to_string(), "cherry tart".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rerank_top_n_ids() {
+        let index = ChunkReranker::new(MockIndex, ContainsReranker).over_fetch_factor(1);
+
+        // Only 1 candidate is fetched, so "cherry tart" is never seen by the reranker
+        let results = index.top_n_ids("cherry", 1).await.unwrap();
+
+        assert_eq!(results, vec![(0.0, "doc0".to_string())]);
+    }
+}
@@ -0,0 +1,180 @@
+//! This module provides primitives to evaluate the output of LLMs using another LLM as a judge.
+//!
+//! A [Judge] prompts an evaluator agent with a rubric, a question and the answer to evaluate,
+//! and parses the verdict of the agent into a [Score].
+//!
+//! # Example
+//! ```rust
+//! use rig::{client::CompletionClient, eval::Judge, providers::openai};
+//!
+//! let openai = openai::Client::from_env();
+//!
+//! let judge = Judge::new(
+//!     openai.agent(openai::GPT_4O).temperature(0.0).build(),
+//!     "The answer must be factually correct and mention the unit of measurement.",
+//! );
+//!
+//! let score = judge
+//!     .judge("How tall is the Eiffel Tower?", "About 330 meters.")
+//!     .await?;
+//!
+//! println!("{} ({}): {}", score.rating, score.pass, score.reasoning);
+//! ```
+use futures::future::join_all;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    agent::Agent,
+    completion::{CompletionModel, Prompt, PromptError},
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum EvalError {
+    /// Boxed, since [PromptError] holds the whole chat history
+    #[error("PromptError: {0}")]
+    PromptError(Box<PromptError>),
+
+    /// The evaluator's response could not be parsed into a [Score]
+    #[error("Failed to parse the evaluator's verdict: {0}")]
+    ParseError(#[from] serde_json::Error),
+}
+
+impl From<PromptError> for EvalError {
+    fn from(error: PromptError) -> Self {
+        EvalError::PromptError(Box::new(error))
+    }
+}
+
+/// The verdict of a [Judge] on an answer.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Score {
+    /// Rating of the answer, from 0 (worst) to 10 (best)
+    pub rating: f32,
+    /// Explanation of the rating
+    pub reasoning: String,
+    /// Whether the answer satisfies the rubric
+    pub pass: bool,
+}
+
+/// An LLM-as-judge evaluator, rating answers to questions according to a rubric.
+pub struct Judge<M: CompletionModel> {
+    agent: Agent<M>,
+    rubric: String,
+}
+
+impl<M: CompletionModel> Judge<M> {
+    /// Create a new judge from an evaluator agent and the rubric used to evaluate answers.
+    pub fn new(agent: Agent<M>, rubric: impl Into<String>) -> Self {
+        Self {
+            agent,
+            rubric: rubric.into(),
+        }
+    }
+
+    /// Evaluate `answer` as a response to `question`.
+    pub async fn judge(&self, question: &str, answer: &str) -> Result<Score, EvalError> {
+        let prompt = format!(
+            "You are evaluating the answer to a question according to the following rubric:\n\
+            <rubric>\n{}\n</rubric>\n\n\
+            <question>\n{question}\n</question>\n\n\
+            <answer>\n{answer}\n</answer>\n\n\
+            Respond ONLY with a JSON object of the form \
+            {{\"rating\": <number from 0 to 10>, \"reasoning\": <string>, \"pass\": <boolean>}}, \
+            where `pass` indicates whether the answer satisfies the rubric.",
+            self.rubric
+        );
+
+        let response = self.agent.prompt(prompt).await?;
+
+        parse_score(&response)
+    }
+
+    /// Evaluate multiple `(question, answer)` pairs concurrently.
+    /// The results are returned in the same order as the pairs.
+    pub async fn judge_all(&self, pairs: Vec<(String, String)>) -> Vec<Result<Score, EvalError>> {
+        join_all(
+            pairs
+                .iter()
+                .map(|(question, answer)| self.judge(question, answer)),
+        )
+        .await
+    }
+}
+
+/// Parse the verdict of the evaluator, ignoring any text around the JSON object
+/// (e.g.: markdown code fences).
+fn parse_score(response: &str) -> Result<Score, EvalError> {
+    let json = match (response.find('{'), response.rfind('}')) {
+        (Some(start), Some(end)) if start < end => &response[start..=end],
+        _ => response,
+    };
+
+    Ok(serde_json::from_str(json)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        agent::AgentBuilder,
+        completion::{AssistantContent, CompletionResponse},
+        message::{Message, UserContent},
+        testing::MockCompletionModel,
+        OneOrMany,
+    };
+
+    /// Mock evaluator passing the answers containing "Paris"
+    fn evaluator() -> MockCompletionModel {
+        MockCompletionModel::from_fn(|request| {
+            let prompt = match request.chat_history.iter().last() {
+                Some(Message::User { content }) => match content.first() {
+                    UserContent::Text(text) => text.text,
+                    _ => String::new(),
+                },
+                _ => String::new(),
+            };
+            let verdict = if prompt.contains("<answer>\nParis") {
+                "```json\n{\"rating\": 9.5, \"reasoning\": \"Correct.\", \"pass\": true}\n```"
+            } else {
+                "{\"rating\": 1, \"reasoning\": \"Incorrect.\", \"pass\": false}"
+            };
+            CompletionResponse {
+                choice: OneOrMany::one(AssistantContent::text(verdict)),
+                raw_response: (),
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn test_judge_all() {
+        let judge = Judge::new(
+            AgentBuilder::new(evaluator()).build(),
+            "The answer must be correct.",
+        );
+
+        let scores = judge
+            .judge_all(vec![
+                ("Capital of France?".to_string(), "Paris".to_string()),
+                ("Capital of France?".to_string(), "Lyon".to_string()),
+            ])
+            .await;
+
+        assert_eq!(
+            scores[0].as_ref().unwrap(),
+            &Score {
+                rating: 9.5,
+                reasoning: "Correct.".to_string(),
+                pass: true,
+            }
+        );
+        assert!(!scores[1].as_ref().unwrap().pass);
+    }
+
+    #[test]
+    fn test_parse_score_invalid() {
+        assert!(matches!(
+            parse_score("I think it's a good answer."),
+            Err(EvalError::ParseError(_))
+        ));
+    }
+}
@@ -0,0 +1,391 @@
+//! This module contains the [GuardrailsModel] struct, a [CompletionModel] wrapper that screens
+//! the user's prompt with an [InputClassifier] before sending it to the provider, and the
+//! model's response with an [OutputClassifier] before returning it.
+//!
+//! Flagged content is handled according to the [GuardrailsPolicy] of the model: the request
+//! can be refused, the flagged text replaced, or a warning logged.
+//!
+//! The [NsfwClassifier] and [ToxicityClassifier] provided here are simple keyword-based
+//! classifiers, meant as examples: implement [InputClassifier] and [OutputClassifier] for your
+//! own types to plug in a dedicated moderation model or API. The OpenAI
+//! [ModerationClient](crate::providers::openai::moderation::ModerationClient) implements both
+//! traits with OpenAI's moderation endpoint.
+//!
+//! # Example
+//! ```rust
+//! use rig::{
+//!     client::CompletionClient,
+//!     guardrails::{GuardrailsModel, GuardrailsPolicy, NsfwClassifier, ToxicityClassifier},
+//!     providers::openai,
+//! };
+//!
+//! let openai = openai::Client::from_env();
+//!
+//! let model = GuardrailsModel::new(
+//!     openai.completion_model(openai::GPT_4O),
+//!     ToxicityClassifier::new(),
+//!     NsfwClassifier::new(),
+//! )
+//! .policy(GuardrailsPolicy::Replace("I can't help with that.".to_string()));
+//!
+//! let agent = rig::agent::AgentBuilder::new(model)
+//!     .preamble("You are a helpful assistant.")
+//!     .build();
+//! ```
+use std::future::Future;
+
+use crate::{
+    completion::{
+        AssistantContent, CompletionError, CompletionModel, CompletionRequest, CompletionResponse,
+    },
+    message::{Message, UserContent},
+    streaming::StreamingCompletionResponse,
+    OneOrMany,
+};
+
+/// The verdict of an [InputClassifier] or [OutputClassifier] on a piece of text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Classification {
+    /// The text is safe
+    Allowed,
+    /// The text was flagged, e.g.: `Flagged { category: "toxicity", reason: "..." }`
+    Flagged { category: String, reason: String },
+}
+
+impl Classification {
+    /// Create a [Classification::Flagged] verdict.
+    pub fn flagged(category: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self::Flagged {
+            category: category.into(),
+            reason: reason.into(),
+        }
+    }
+
+    /// Returns `true` if the text was flagged.
+    pub fn is_flagged(&self) -> bool {
+        matches!(self, Self::Flagged { .. })
+    }
+}
+
+/// Trait for classifiers screening the user's prompt before it is sent to the model.
+/// The unit type `()` can be used to disable input screening.
+pub trait InputClassifier: Clone + Send + Sync {
+    fn classify(
+        &self,
+        input: &str,
+    ) -> impl Future<Output = Result<Classification, CompletionError>> + Send;
+}
+
+/// Trait for classifiers screening the text of the model's response before it is returned.
+/// The unit type `()` can be used to disable output screening.
+pub trait OutputClassifier: Clone + Send + Sync {
+    fn classify(
+        &self,
+        output: &str,
+    ) -> impl Future<Output = Result<Classification, CompletionError>> + Send;
+}
+
+impl InputClassifier for () {
+    async fn classify(&self, _input: &str) -> Result<Classification, CompletionError> {
+        Ok(Classification::Allowed)
+    }
+}
+
+impl OutputClassifier for () {
+    async fn classify(&self, _output: &str) -> Result<Classification, CompletionError> {
+        Ok(Classification::Allowed)
+    }
+}
+
+/// What a [GuardrailsModel] does when a classifier flags the prompt or the response.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum GuardrailsPolicy {
+    /// Refuse the request (or response) with a [CompletionError::SecurityViolation] error
+    #[default]
+    Block,
+    /// Replace the flagged text with the given text
+    Replace(String),
+    /// Log a warning and let the content through unchanged
+    Warn,
+}
+
+/// A completion model wrapper screening the user's prompt (i.e.: the text of the last message
+/// of the request) with `I` and the text of the model's response with `O`, and handling flagged
+/// content according to its [GuardrailsPolicy].
+///
+/// Note: only the prompt is screened for streaming requests.
+#[derive(Clone)]
+pub struct GuardrailsModel<M: CompletionModel, I: InputClassifier, O: OutputClassifier> {
+    model: M,
+    input_classifier: I,
+    output_classifier: O,
+    policy: GuardrailsPolicy,
+}
+
+impl<M, I, O> GuardrailsModel<M, I, O>
+where
+    M: CompletionModel,
+    I: InputClassifier,
+    O: OutputClassifier,
+{
+    /// Wrap `model` with the given classifiers, blocking flagged content.
+    pub fn new(model: M, input_classifier: I, output_classifier: O) -> Self {
+        Self {
+            model,
+            input_classifier,
+            output_classifier,
+            policy: GuardrailsPolicy::default(),
+        }
+    }
+
+    /// Set the policy applied when the prompt or the response is flagged.
+    pub fn policy(mut self, policy: GuardrailsPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Screen the prompt of the request and apply the policy if it is flagged.
+    async fn screen_input(
+        &self,
+        mut request: CompletionRequest,
+    ) -> Result<CompletionRequest, CompletionError> {
+        let Some(Message::User { content }) = request.chat_history.iter_mut().last() else {
+            return Ok(request);
+        };
+
+        for content in content.iter_mut() {
+            let UserContent::Text(text) = content else {
+                continue;
+            };
+            let classification = self.input_classifier.classify(&text.text).await?;
+            if let Some(replacement) = self.apply_policy("Prompt", classification)? {
+                text.text = replacement;
+            }
+        }
+
+        Ok(request)
+    }
+
+    /// Screen the text of the response and apply the policy if it is flagged.
+    async fn screen_output<R>(
+        &self,
+        mut response: CompletionResponse<R>,
+    ) -> Result<CompletionResponse<R>, CompletionError> {
+        let text = response
+            .choice
+            .iter()
+            .filter_map(|content| match content {
+                AssistantContent::Text(text) => Some(text.text.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        if text.is_empty() {
+            return Ok(response);
+        }
+
+        let classification = self.output_classifier.classify(&text).await?;
+        if let Some(replacement) = self.apply_policy("Response", classification)? {
+            // Tool calls are kept, all the text is replaced by the replacement text
+            let choice = std::iter::once(AssistantContent::text(replacement))
+                .chain(
+                    response
+                        .choice
+                        .into_iter()
+                        .filter(|content| !matches!(content, AssistantContent::Text(_))),
+                )
+                .collect::<Vec<_>>();
+            response.choice =
+                OneOrMany::many(choice).expect("There is at least the replacement text");
+        }
+
+        Ok(response)
+    }
+
+    /// Returns the replacement text of flagged content, if any.
+    fn apply_policy(
+        &self,
+        subject: &str,
+        classification: Classification,
+    ) -> Result<Option<String>, CompletionError> {
+        let Classification::Flagged { category, reason } = classification else {
+            return Ok(None);
+        };
+
+        match &self.policy {
+            GuardrailsPolicy::Block => Err(CompletionError::SecurityViolation(format!(
+                "{subject} flagged as {category}: {reason}"
+            ))),
+            GuardrailsPolicy::Replace(replacement) => {
+                tracing::warn!(target: "rig", "Replacing {subject} flagged as {category}: {reason}");
+                Ok(Some(replacement.clone()))
+            }
+            GuardrailsPolicy::Warn => {
+                tracing::warn!(target: "rig", "{subject} flagged as {category}: {reason}");
+                Ok(None)
+            }
+        }
+    }
+}
+
+impl<M, I, O> CompletionModel for GuardrailsModel<M, I, O>
+where
+    M: CompletionModel,
+    I: InputClassifier,
+    O: OutputClassifier,
+{
+    type Response = M::Response;
+    type StreamingResponse = M::StreamingResponse;
+
+    async fn completion(
+        &self,
+        request: CompletionRequest,
+    ) -> Result<CompletionResponse<Self::Response>, CompletionError> {
+        let request = self.screen_input(request).await?;
+        let response = self.model.completion(request).await?;
+        self.screen_output(response).await
+    }
+
+    async fn stream(
+        &self,
+        request: CompletionRequest,
+    ) -> Result<StreamingCompletionResponse<Self::StreamingResponse>, CompletionError> {
+        let request = self.screen_input(request).await?;
+        self.model.stream(request).await
+    }
+}
+
+/// Terms flagged by default by the [NsfwClassifier].
+pub const DEFAULT_NSFW_TERMS: &[&str] = &["nsfw", "explicit content", "nude", "porn"];
+
+/// Terms flagged by default by the [ToxicityClassifier].
+pub const DEFAULT_TOXIC_TERMS: &[&str] = &["idiot", "stupid", "moron", "shut up", "hate you"];
+
+/// Returns the first of `terms` (lowercase) found in `text`, if any.
+fn find_term<'a>(terms: &'a [String], text: &str) -> Option<&'a str> {
+    let text = text.to_lowercase();
+    terms
+        .iter()
+        .find(|term| text.contains(term.as_str()))
+        .map(String::as_str)
+}
+
+macro_rules! keyword_classifier {
+    ($name:ident, $category:literal, $default_terms:ident) => {
+        impl $name {
+            /// Create a classifier flagging the default terms.
+            pub fn new() -> Self {
+                Self {
+                    terms: $default_terms.iter().map(|term| term.to_string()).collect(),
+                }
+            }
+
+            /// Add a term to the flagged terms (matched case-insensitively).
+            pub fn term(mut self, term: impl Into<String>) -> Self {
+                self.terms.push(term.into().to_lowercase());
+                self
+            }
+
+            fn classify_text(&self, text: &str) -> Classification {
+                match find_term(&self.terms, text) {
+                    Some(term) => {
+                        Classification::flagged($category, format!("contains \"{term}\""))
+                    }
+                    None => Classification::Allowed,
+                }
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl InputClassifier for $name {
+            async fn classify(&self, input: &str) -> Result<Classification, CompletionError> {
+                Ok(self.classify_text(input))
+            }
+        }
+
+        impl OutputClassifier for $name {
+            async fn classify(&self, output: &str) -> Result<Classification, CompletionError> {
+                Ok(self.classify_text(output))
+            }
+        }
+    };
+}
+
+/// Example keyword-based classifier flagging NSFW content (category `"nsfw"`).
+#[derive(Clone, Debug)]
+pub struct NsfwClassifier {
+    terms: Vec<String>,
+}
+
+keyword_classifier!(NsfwClassifier, "nsfw", DEFAULT_NSFW_TERMS);
+
+/// Example keyword-based classifier flagging toxic content (category `"toxicity"`).
+#[derive(Clone, Debug)]
+pub struct ToxicityClassifier {
+    terms: Vec<String>,
+}
+
+keyword_classifier!(ToxicityClassifier, "toxicity", DEFAULT_TOXIC_TERMS);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::MockCompletionModel;
+
+    #[tokio::test]
+    async fn test_block_input() {
+        let mock = MockCompletionModel::new("Hello!");
+        let model = GuardrailsModel::new(mock.clone(), ToxicityClassifier::new(), ());
+
+        let result = model.completion_request("You are an idiot").send().await;
+        assert!(matches!(result, Err(CompletionError::SecurityViolation(_))));
+        assert!(mock.requests().is_empty());
+
+        let response = model.completion_request("Hi").send().await.unwrap();
+        assert_eq!(
+            response.choice,
+            OneOrMany::one(AssistantContent::text("Hello!"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_replace_output() {
+        let model = GuardrailsModel::new(
+            MockCompletionModel::new("I'm too busy for this."),
+            (),
+            ToxicityClassifier::new().term("busy"),
+        )
+        .policy(GuardrailsPolicy::Replace("[removed]".to_string()));
+
+        let response = model.completion_request("Hi").send().await.unwrap();
+        assert_eq!(
+            response.choice,
+            OneOrMany::one(AssistantContent::text("[removed]"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_warn() {
+        let model = GuardrailsModel::new(
+            MockCompletionModel::new("NSFW reply"),
+            NsfwClassifier::new(),
+            NsfwClassifier::new(),
+        )
+        .policy(GuardrailsPolicy::Warn);
+
+        let response = model
+            .completion_request("NSFW prompt")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(
+            response.choice,
+            OneOrMany::one(AssistantContent::text("NSFW reply"))
+        );
+    }
+}
@@ -88,8 +88,11 @@ pub mod audio_generation;
 pub mod cli_chatbot;
 pub mod client;
 pub mod completion;
+pub mod context;
 pub mod embeddings;
+pub mod eval;
 pub mod extractor;
+pub mod guardrails;
 #[cfg(feature = "image")]
 pub mod image_generation;
 pub(crate) mod json_utils;
@@ -97,9 +100,17 @@ pub mod loaders;
 pub mod one_or_many;
 pub mod pipeline;
 pub mod prelude;
+pub mod prompt;
 pub mod providers;
+pub mod security;
+#[cfg(feature = "server")]
+pub mod server;
 pub mod streaming;
+pub mod testing;
+pub mod tokens;
 pub mod tool;
+pub mod tools;
+pub mod tracing;
 pub mod transcription;
 pub mod vector_store;
 
@@ -107,6 +118,13 @@ pub mod vector_store;
 pub use completion::message;
 pub use embeddings::Embed;
 pub use one_or_many::{EmptyListError, OneOrMany};
+// Used by the JSON schemas generated by `#[rig::tool]`
+pub use schemars;
 
 #[cfg(feature = "derive")]
 pub use rig_derive::Embed;
+
+/// Attribute macro turning a function into a [Tool](crate::tool::Tool)
+/// (e.g.: `#[rig::tool]`, see [rig_tool](rig_derive::rig_tool)).
+#[cfg(feature = "derive")]
+pub use rig_derive::rig_tool as tool;
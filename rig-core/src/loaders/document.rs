@@ -0,0 +1,541 @@
+use std::{collections::HashMap, future::Future, path::Path};
+
+use thiserror::Error;
+
+use crate::{completion::Document, tokens::TokenCounter};
+
+use super::{file::FileLoaderError, FileLoader};
+
+#[cfg(feature = "pdf")]
+use super::pdf::{PdfFileLoader, PdfLoaderError};
+
+#[derive(Error, Debug)]
+pub enum LoadError {
+    #[error("{0}")]
+    FileLoaderError(#[from] FileLoaderError),
+
+    #[cfg(feature = "pdf")]
+    #[error("{0}")]
+    PdfLoaderError(#[from] PdfLoaderError),
+}
+
+/// Trait for loaders that turn a source (e.g.: a file path or glob pattern) into [Document]s
+/// that can be used as context by an agent
+/// (see [AgentBuilder::load_context](crate::agent::AgentBuilder::load_context)).
+pub trait DocumentLoader: Send + Sync {
+    fn load(&self, source: &str) -> impl Future<Output = Result<Vec<Document>, LoadError>> + Send;
+}
+
+/// Build a document from a file, using its path as id.
+fn document(path: &Path, text: String, format: &str, title: Option<String>) -> Document {
+    let mut additional_props = HashMap::from([("format".to_string(), format.to_string())]);
+    if let Some(title) = title {
+        additional_props.insert("title".to_string(), title);
+    }
+
+    Document {
+        id: path.to_string_lossy().to_string(),
+        text,
+        additional_props,
+    }
+}
+
+// ================================================================
+// Markdown
+// ================================================================
+
+/// Loads the markdown files matching a glob pattern (e.g.: `docs/**/*.md`) as is, one document per file.
+/// The first level 1 heading of each file (if any) is used as the document's `title`.
+#[derive(Clone, Debug, Default)]
+pub struct MarkdownLoader;
+
+impl DocumentLoader for MarkdownLoader {
+    async fn load(&self, source: &str) -> Result<Vec<Document>, LoadError> {
+        FileLoader::with_glob(source)?
+            .read_with_path()
+            .into_iter()
+            .map(|result| {
+                let (path, text) = result?;
+                let title = text
+                    .lines()
+                    .find_map(|line| line.strip_prefix("# "))
+                    .map(|title| title.trim().to_string());
+
+                Ok::<_, LoadError>(document(&path, text, "markdown", title))
+            })
+            .collect()
+    }
+}
+
+// ================================================================
+// HTML
+// ================================================================
+
+/// Loads the HTML files matching a glob pattern (e.g.: `site/*.html`), one document per file.
+/// Tags, scripts and stylesheets are stripped so only the text content of the page remains.
+/// The `<title>` of each page (if any) is used as the document's `title`.
+#[derive(Clone, Debug, Default)]
+pub struct HtmlLoader;
+
+impl DocumentLoader for HtmlLoader {
+    async fn load(&self, source: &str) -> Result<Vec<Document>, LoadError> {
+        FileLoader::with_glob(source)?
+            .read_with_path()
+            .into_iter()
+            .map(|result| {
+                let (path, html) = result?;
+                let title = html_title(&html);
+
+                Ok::<_, LoadError>(document(&path, html_to_text(&html), "html", title))
+            })
+            .collect()
+    }
+}
+
+/// Tags after which a line break is inserted so paragraphs, list items, etc. stay on separate lines.
+const BLOCK_TAGS: &[&str] = &[
+    "p",
+    "div",
+    "br",
+    "li",
+    "tr",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "section",
+    "article",
+    "header",
+    "footer",
+    "blockquote",
+    "pre",
+];
+
+fn html_title(html: &str) -> Option<String> {
+    // ASCII lowercasing preserves byte offsets, so indices found in `lower` are valid in `html`
+    let lower = html.to_ascii_lowercase();
+    let start = lower.find("<title")?;
+    let start = start + lower[start..].find('>')? + 1;
+    let end = start + lower[start..].find("</title>")?;
+
+    Some(decode_entities(html[start..end].trim()))
+}
+
+fn html_to_text(html: &str) -> String {
+    let lower = html.to_ascii_lowercase();
+    let mut text = String::with_capacity(html.len());
+    let mut pos = 0;
+
+    while let Some(offset) = lower[pos..].find('<') {
+        let start = pos + offset;
+        text.push_str(&html[pos..start]);
+
+        let Some(end) = lower[start..].find('>').map(|end| start + end + 1) else {
+            // Unclosed tag, ignore the rest of the document
+            pos = html.len();
+            break;
+        };
+
+        let name = lower[start + 1..end - 1]
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default();
+
+        pos = match name {
+            "script" | "style" | "head" if !lower[start + 1..].starts_with('/') => {
+                // Skip the content of the element altogether
+                let closing = format!("</{name}");
+                lower[end..]
+                    .find(&closing)
+                    .and_then(|close| {
+                        let close = end + close;
+                        lower[close..].find('>').map(|gt| close + gt + 1)
+                    })
+                    .unwrap_or(html.len())
+            }
+            _ => end,
+        };
+
+        if BLOCK_TAGS.contains(&name) {
+            text.push('\n');
+        }
+    }
+    text.push_str(&html[pos..]);
+
+    decode_entities(&text)
+        .lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+// ================================================================
+// PDF
+// ================================================================
+
+/// Loads the PDF files matching a glob pattern (e.g.: `papers/*.pdf`), one document per file.
+///
+/// Note: The [PdfLoader] requires the `pdf` feature to be enabled in the `Cargo.toml` file.
+#[cfg(feature = "pdf")]
+#[derive(Clone, Debug, Default)]
+pub struct PdfLoader;
+
+#[cfg(feature = "pdf")]
+impl DocumentLoader for PdfLoader {
+    async fn load(&self, source: &str) -> Result<Vec<Document>, LoadError> {
+        PdfFileLoader::with_glob(source)?
+            .read_with_path()
+            .into_iter()
+            .map(|result| {
+                let (path, text) = result?;
+                Ok::<_, LoadError>(document(&path, text, "pdf", None))
+            })
+            .collect()
+    }
+}
+
+// ================================================================
+// Chunking
+// ================================================================
+
+/// Unit of the chunk sizes and overlaps of a [DocumentChunker] or [SentenceChunker].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum ChunkUnit {
+    /// Characters (the default)
+    #[default]
+    Characters,
+    /// Tokens of the given model (e.g.: `gpt-4o`), as estimated by the [TokenCounter]
+    Tokens(String),
+}
+
+impl ChunkUnit {
+    /// Split `text` into the pieces a chunk is made of, along with the size of each piece.
+    fn pieces<'a>(&self, text: &'a str) -> (Vec<&'a str>, Vec<usize>) {
+        match self {
+            ChunkUnit::Characters => {
+                let pieces = text
+                    .char_indices()
+                    .map(|(i, c)| &text[i..i + c.len_utf8()])
+                    .collect::<Vec<_>>();
+                let sizes = vec![1; pieces.len()];
+                (pieces, sizes)
+            }
+            // Words, with their trailing whitespace
+            ChunkUnit::Tokens(_) => {
+                let pieces = text
+                    .split_inclusive(char::is_whitespace)
+                    .collect::<Vec<_>>();
+                let sizes = pieces.iter().map(|piece| self.size(piece)).collect();
+                (pieces, sizes)
+            }
+        }
+    }
+
+    fn size(&self, text: &str) -> usize {
+        match self {
+            ChunkUnit::Characters => text.chars().count(),
+            ChunkUnit::Tokens(model) => TokenCounter::count_text(model, text),
+        }
+    }
+}
+
+/// Trait for splitters of large texts into chunks (e.g.: to index them in a vector store).
+pub trait Chunker {
+    /// Split `text` into chunks.
+    fn split(&self, text: &str) -> Vec<String>;
+
+    /// Split the text of `document` into chunk documents. The id of each chunk is the id of the
+    /// document followed by the index of the chunk (e.g.: `report.pdf#0`, `report.pdf#1`, ...),
+    /// and its properties are those of the document along with the `source` (id of the document)
+    /// and `chunk` (index of the chunk) properties.
+    fn chunk(&self, document: &Document) -> Vec<Document> {
+        self.split(&document.text)
+            .into_iter()
+            .enumerate()
+            .map(|(i, text)| {
+                let mut additional_props = document.additional_props.clone();
+                additional_props.insert("source".to_string(), document.id.clone());
+                additional_props.insert("chunk".to_string(), i.to_string());
+
+                Document {
+                    id: format!("{}#{i}", document.id),
+                    text,
+                    additional_props,
+                }
+            })
+            .collect()
+    }
+
+    /// Split `text` into chunk documents, as [Chunker::chunk] would for a document of id `id`
+    /// without properties.
+    fn chunk_text(&self, id: &str, text: &str) -> Vec<Document> {
+        self.chunk(&Document {
+            id: id.to_string(),
+            text: text.to_string(),
+            additional_props: HashMap::new(),
+        })
+    }
+}
+
+/// Pack consecutive `pieces` into chunks of at most `chunk_size` (unless a single piece is
+/// larger), each chunk repeating the last pieces of the previous one, up to `overlap`.
+fn pack(pieces: &[&str], sizes: &[usize], chunk_size: usize, overlap: usize) -> Vec<String> {
+    let mut chunks = vec![];
+    let mut start = 0;
+
+    while start < pieces.len() {
+        let mut end = start;
+        let mut size = 0;
+        while end < pieces.len() && (end == start || size + sizes[end] <= chunk_size) {
+            size += sizes[end];
+            end += 1;
+        }
+        chunks.push(pieces[start..end].concat());
+
+        if end == pieces.len() {
+            break;
+        }
+
+        // Start the next chunk with the last pieces of this one, while always moving forward
+        let mut next = end;
+        let mut overlap_size = 0;
+        while next > start + 1 && overlap_size + sizes[next - 1] <= overlap {
+            overlap_size += sizes[next - 1];
+            next -= 1;
+        }
+        start = next;
+    }
+
+    chunks
+}
+
+/// Splits texts into chunks of `chunk_size` characters (or tokens, see [ChunkUnit]), consecutive
+/// chunks sharing `overlap` characters (or tokens) so that the context of a passage is not lost
+/// at the boundaries of the chunks.
+///
+/// # Example
+/// ```rust
+/// use rig::loaders::{Chunker, DocumentChunker, DocumentLoader, MarkdownLoader};
+///
+/// let chunker = DocumentChunker::new(1000, 200);
+///
+/// let chunks = MarkdownLoader.load("docs/*.md").await?
+///     .iter()
+///     .flat_map(|document| chunker.chunk(document))
+///     .collect::<Vec<_>>();
+/// ```
+#[derive(Clone, Debug)]
+pub struct DocumentChunker {
+    pub chunk_size: usize,
+    pub overlap: usize,
+    pub unit: ChunkUnit,
+}
+
+impl DocumentChunker {
+    /// Create a chunker of `chunk_size` characters with an `overlap` of characters
+    /// (which must be smaller than `chunk_size`).
+    pub fn new(chunk_size: usize, overlap: usize) -> Self {
+        Self {
+            chunk_size,
+            overlap: overlap.min(chunk_size.saturating_sub(1)),
+            unit: ChunkUnit::Characters,
+        }
+    }
+
+    /// Measure chunk sizes and overlaps in tokens of `model` (instead of characters).
+    /// Chunks are then split between words.
+    pub fn tokens(mut self, model: &str) -> Self {
+        self.unit = ChunkUnit::Tokens(model.to_string());
+        self
+    }
+}
+
+impl Chunker for DocumentChunker {
+    fn split(&self, text: &str) -> Vec<String> {
+        let (pieces, sizes) = self.unit.pieces(text);
+        pack(&pieces, &sizes, self.chunk_size, self.overlap)
+    }
+}
+
+/// Splits texts into chunks of whole sentences of at most `chunk_size` characters (or tokens,
+/// see [ChunkUnit]), consecutive chunks sharing their last sentences up to `overlap` characters
+/// (or tokens). A sentence larger than `chunk_size` makes up a chunk on its own.
+///
+/// Sentences end with `.`, `!` or `?` followed by whitespace, or with a blank line.
+#[derive(Clone, Debug)]
+pub struct SentenceChunker {
+    pub chunk_size: usize,
+    pub overlap: usize,
+    pub unit: ChunkUnit,
+}
+
+impl SentenceChunker {
+    /// Create a chunker of `chunk_size` characters with an `overlap` of characters.
+    pub fn new(chunk_size: usize, overlap: usize) -> Self {
+        Self {
+            chunk_size,
+            overlap,
+            unit: ChunkUnit::Characters,
+        }
+    }
+
+    /// Measure chunk sizes and overlaps in tokens of `model` (instead of characters).
+    pub fn tokens(mut self, model: &str) -> Self {
+        self.unit = ChunkUnit::Tokens(model.to_string());
+        self
+    }
+}
+
+/// Split `text` into sentences, with their trailing whitespace.
+fn sentences(text: &str) -> Vec<&str> {
+    let mut sentences = vec![];
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        let next = chars.peek().map(|(_, next)| *next);
+        let end_of_sentence = match c {
+            '.' | '!' | '?' => next.is_none_or(char::is_whitespace),
+            '\n' => next == Some('\n'),
+            _ => false,
+        };
+
+        if end_of_sentence {
+            // Include the trailing whitespace in the sentence
+            let mut end = i + c.len_utf8();
+            while let Some((j, next)) = chars.next_if(|(_, next)| next.is_whitespace()) {
+                end = j + next.len_utf8();
+            }
+            sentences.push(&text[start..end]);
+            start = end;
+        }
+    }
+
+    if start < text.len() {
+        sentences.push(&text[start..]);
+    }
+
+    sentences
+}
+
+impl Chunker for SentenceChunker {
+    fn split(&self, text: &str) -> Vec<String> {
+        let sentences = sentences(text);
+        let sizes = sentences
+            .iter()
+            .map(|sentence| self.unit.size(sentence.trim_end()))
+            .collect::<Vec<_>>();
+
+        pack(&sentences, &sizes, self.chunk_size, self.overlap)
+            .into_iter()
+            .map(|chunk| chunk.trim_end().to_string())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::prelude::{FileWriteStr, PathChild};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_markdown_loader() {
+        let temp = assert_fs::TempDir::new().expect("Failed to create temp dir");
+        let file = temp.child("intro.md");
+        file.write_str("# Introduction\n\nRig is a Rust library.")
+            .expect("Failed to write to intro.md");
+
+        let glob = temp.path().to_string_lossy().to_string() + "/*.md";
+        let documents = MarkdownLoader.load(&glob).await.unwrap();
+
+        assert_eq!(documents.len(), 1);
+        assert_eq!(
+            documents[0].text,
+            "# Introduction\n\nRig is a Rust library."
+        );
+        assert_eq!(documents[0].additional_props["title"], "Introduction");
+        assert!(documents[0].id.ends_with("intro.md"));
+    }
+
+    #[test]
+    fn test_html_to_text() {
+        let html = r#"
+            <html>
+                <head><title>Rig &amp; friends</title><style>p { color: red; }</style></head>
+                <body>
+                    <script>alert("hi");</script>
+                    <h1>Hello</h1>
+                    <p>Rig is a <b>Rust</b> library.</p>
+                </body>
+            </html>
+        "#;
+
+        assert_eq!(html_title(html).as_deref(), Some("Rig & friends"));
+        assert_eq!(html_to_text(html), "Hello\nRig is a Rust library.");
+    }
+
+    #[test]
+    fn test_document_chunker() {
+        let chunker = DocumentChunker::new(4, 1);
+        assert_eq!(chunker.split("abcdefghij"), vec!["abcd", "defg", "ghij"]);
+        assert_eq!(chunker.split(""), Vec::<String>::new());
+
+        let document = Document {
+            id: "letters.txt".to_string(),
+            text: "abcdef".to_string(),
+            additional_props: HashMap::from([("format".to_string(), "text".to_string())]),
+        };
+        let chunks = chunker.chunk(&document);
+        assert_eq!(
+            chunks
+                .iter()
+                .map(|chunk| chunk.id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["letters.txt#0", "letters.txt#1"]
+        );
+        assert_eq!(chunks[1].text, "def");
+        assert_eq!(chunks[1].additional_props["source"], "letters.txt");
+        assert_eq!(chunks[1].additional_props["chunk"], "1");
+        assert_eq!(chunks[1].additional_props["format"], "text");
+    }
+
+    #[test]
+    fn test_sentence_chunker() {
+        let text = "Rig is a Rust library. It builds LLM apps! Is it fast? Yes.\n\nA new paragraph";
+        assert_eq!(
+            sentences(text),
+            vec![
+                "Rig is a Rust library. ",
+                "It builds LLM apps! ",
+                "Is it fast? ",
+                "Yes.\n\n",
+                "A new paragraph"
+            ]
+        );
+
+        let chunker = SentenceChunker::new(45, 20);
+        assert_eq!(
+            chunker.split(text),
+            vec![
+                "Rig is a Rust library. It builds LLM apps!",
+                "It builds LLM apps! Is it fast? Yes.",
+                "Is it fast? Yes.\n\nA new paragraph"
+            ]
+        );
+    }
+}
@@ -15,11 +15,27 @@
 //! and keeping track of the chapter numbers along with their contents.
 //!
 //! Note: The [EpubFileLoader] requires the `epub` feature to be enabled in the `Cargo.toml` file.
+//!
+//! The [DocumentLoader] trait provides a higher level interface that directly produces
+//! [Document](crate::completion::Document)s which can be used as agent context (see
+//! [AgentBuilder::load_context](crate::agent::AgentBuilder::load_context)). Rig provides the
+//! [MarkdownLoader], [HtmlLoader] and [PdfLoader] (requires the `pdf` feature) implementations.
+//!
+//! Large documents can then be split into overlapping chunks (e.g.: to index them in a vector
+//! store) with the [DocumentChunker] or the [SentenceChunker].
 
+pub mod document;
 pub mod file;
 
+pub use document::{
+    ChunkUnit, Chunker, DocumentChunker, DocumentLoader, HtmlLoader, LoadError, MarkdownLoader,
+    SentenceChunker,
+};
 pub use file::FileLoader;
 
+#[cfg(feature = "pdf")]
+pub use document::PdfLoader;
+
 #[cfg(feature = "pdf")]
 pub mod pdf;
 
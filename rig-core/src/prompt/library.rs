@@ -0,0 +1,270 @@
+//! A library of named and versioned prompt templates, loaded from TOML files.
+//!
+//! Each file defines one version of a prompt:
+//! ```toml
+//! name = "support"
+//! version = "1.2.0"
+//! preamble = "You are a support agent of {company}. Answer in {language}."
+//!
+//! [variables]
+//! company = "Name of the company"
+//! language = "Language of the answers (e.g.: English)"
+//! ```
+//!
+//! All the variables referenced by the preamble must be documented in the `variables` table.
+//!
+//! # Example
+//! ```rust
+//! use rig::prompt::PromptLibrary;
+//!
+//! let library = PromptLibrary::load_from_dir("prompts")?;
+//!
+//! let preamble = library
+//!     .latest("support")
+//!     .expect("Missing prompt")
+//!     .var("company", "Acme")
+//!     .var("language", "French");
+//!
+//! let agent = openai.agent("gpt-4o").preamble_template(preamble).build();
+//! ```
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::Path,
+};
+
+use serde::Deserialize;
+
+use super::{PromptTemplate, PromptTemplateError};
+
+#[derive(Debug, thiserror::Error)]
+pub enum PromptLibraryError {
+    #[error("IoError: {0}")]
+    IoError(#[from] std::io::Error),
+
+    /// A prompt file is not a valid prompt definition
+    #[error("ParseError: {0}")]
+    ParseError(String),
+
+    /// A version is not made of dot-separated numbers (e.g.: `1.2.0`)
+    #[error("InvalidVersion: {0}")]
+    InvalidVersion(String),
+
+    /// The same version of a prompt is defined twice
+    #[error("DuplicatePrompt: {name} {version}")]
+    DuplicatePrompt { name: String, version: String },
+
+    /// A variable referenced by a prompt is not documented in its `variables` table
+    #[error("UndocumentedVariable: `{variable}` in {name} {version}")]
+    UndocumentedVariable {
+        name: String,
+        version: String,
+        variable: String,
+    },
+
+    #[error("TemplateError: {0}")]
+    TemplateError(#[from] PromptTemplateError),
+}
+
+/// A version of a prompt, as defined in its TOML file.
+#[derive(Debug, Deserialize)]
+struct PromptDefinition {
+    name: String,
+    version: String,
+    preamble: String,
+    /// Description of each variable of the preamble
+    #[serde(default)]
+    variables: HashMap<String, String>,
+}
+
+/// A version number, compared number by number (i.e.: `1.10` is greater than `1.9`).
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Version(Vec<u64>);
+
+impl Version {
+    fn parse(version: &str) -> Result<Self, PromptLibraryError> {
+        version
+            .split('.')
+            .map(|number| number.trim().parse::<u64>())
+            .collect::<Result<Vec<_>, _>>()
+            .map(|mut numbers| {
+                // `1.2` and `1.2.0` are the same version
+                while numbers.len() > 1 && numbers.last() == Some(&0) {
+                    numbers.pop();
+                }
+                Version(numbers)
+            })
+            .map_err(|_| PromptLibraryError::InvalidVersion(version.to_string()))
+    }
+}
+
+/// A collection of prompt templates, identified by their name and version.
+#[derive(Clone, Debug, Default)]
+pub struct PromptLibrary {
+    prompts: HashMap<String, BTreeMap<Version, PromptTemplate>>,
+}
+
+impl PromptLibrary {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load the prompts of all the `.toml` files of the directory `path` (not recursively).
+    pub fn load_from_dir(path: impl AsRef<Path>) -> Result<Self, PromptLibraryError> {
+        let mut library = Self::new();
+
+        let mut paths = std::fs::read_dir(path)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<Result<Vec<_>, _>>()?;
+        paths.sort();
+
+        for path in paths
+            .into_iter()
+            .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "toml"))
+        {
+            library
+                .add_toml(&std::fs::read_to_string(&path)?)
+                .map_err(|e| match e {
+                    PromptLibraryError::ParseError(e) => {
+                        PromptLibraryError::ParseError(format!("{}: {e}", path.display()))
+                    }
+                    e => e,
+                })?;
+        }
+
+        Ok(library)
+    }
+
+    /// Add the prompt defined by the TOML document `toml`, after validating it.
+    pub fn add_toml(&mut self, toml: &str) -> Result<(), PromptLibraryError> {
+        let definition: PromptDefinition =
+            toml::from_str(toml).map_err(|e| PromptLibraryError::ParseError(e.to_string()))?;
+        let version = Version::parse(&definition.version)?;
+
+        let template = PromptTemplate::new(definition.preamble);
+        if let Some(variable) = template
+            .variables()?
+            .into_iter()
+            .find(|variable| !definition.variables.contains_key(variable))
+        {
+            return Err(PromptLibraryError::UndocumentedVariable {
+                name: definition.name,
+                version: definition.version,
+                variable,
+            });
+        }
+
+        let versions = self.prompts.entry(definition.name.clone()).or_default();
+        if versions.contains_key(&version) {
+            return Err(PromptLibraryError::DuplicatePrompt {
+                name: definition.name,
+                version: definition.version,
+            });
+        }
+        versions.insert(version, template);
+
+        Ok(())
+    }
+
+    /// Returns the template of the version `version` of the prompt `name`.
+    pub fn get(&self, name: &str, version: &str) -> Option<PromptTemplate> {
+        let version = Version::parse(version).ok()?;
+        self.prompts.get(name)?.get(&version).cloned()
+    }
+
+    /// Returns the template of the highest version of the prompt `name`.
+    pub fn latest(&self, name: &str) -> Option<PromptTemplate> {
+        self.prompts
+            .get(name)?
+            .last_key_value()
+            .map(|(_, template)| template.clone())
+    }
+
+    /// Returns the names of the prompts of the library.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.prompts.keys().map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::prelude::{FileWriteStr, PathChild};
+
+    use super::*;
+
+    #[test]
+    fn test_load_from_dir() {
+        let temp = assert_fs::TempDir::new().expect("Failed to create temp dir");
+        temp.child("support_v1.toml")
+            .write_str(
+                r#"
+                name = "support"
+                version = "1.9"
+                preamble = "You are a support agent."
+                "#,
+            )
+            .unwrap();
+        temp.child("support_v2.toml")
+            .write_str(
+                r#"
+                name = "support"
+                version = "1.10.0"
+                preamble = "You are a support agent of {company}."
+
+                [variables]
+                company = "Name of the company"
+                "#,
+            )
+            .unwrap();
+        temp.child("notes.txt").write_str("Not a prompt").unwrap();
+
+        let library = PromptLibrary::load_from_dir(temp.path()).unwrap();
+
+        assert_eq!(
+            library.get("support", "1.9.0").unwrap().template(),
+            "You are a support agent."
+        );
+        assert_eq!(
+            library
+                .latest("support")
+                .unwrap()
+                .var("company", "Acme")
+                .render()
+                .unwrap(),
+            "You are a support agent of Acme."
+        );
+        assert!(library.get("support", "2").is_none());
+        assert!(library.latest("sales").is_none());
+    }
+
+    #[test]
+    fn test_validation() {
+        let mut library = PromptLibrary::new();
+
+        assert!(matches!(
+            library.add_toml(
+                r#"
+                name = "support"
+                version = "1"
+                preamble = "You are a support agent of {company}."
+                "#
+            ),
+            Err(PromptLibraryError::UndocumentedVariable { variable, .. }) if variable == "company"
+        ));
+        assert!(matches!(
+            library.add_toml("name = \"support\"\nversion = \"v1\"\npreamble = \"\""),
+            Err(PromptLibraryError::InvalidVersion(_))
+        ));
+        assert!(matches!(
+            library.add_toml("name = \"support\""),
+            Err(PromptLibraryError::ParseError(_))
+        ));
+
+        library
+            .add_toml("name = \"support\"\nversion = \"1\"\npreamble = \"\"")
+            .unwrap();
+        assert!(matches!(
+            library.add_toml("name = \"support\"\nversion = \"1.0\"\npreamble = \"\""),
+            Err(PromptLibraryError::DuplicatePrompt { .. })
+        ));
+    }
+}
@@ -0,0 +1,231 @@
+//! This module provides utilities for building prompts from templates.
+//!
+//! The [PromptTemplate] struct wraps a string containing `{variable}` placeholders together
+//! with the values used to fill them in. Templates are rendered lazily, which allows an
+//! [Agent](crate::agent::Agent) to inject per-request metadata (e.g.: user id, current date,
+//! persona) into its system prompt without being rebuilt.
+//!
+//! Literal braces can be written by doubling them (i.e.: `{{` and `}}`).
+//!
+//! # Example
+//! ```rust
+//! use rig::prompt::PromptTemplate;
+//!
+//! let template = PromptTemplate::new("You are {persona}. The user's name is {name}.")
+//!     .var("persona", "a helpful assistant")
+//!     .var("name", "Alice");
+//!
+//! assert_eq!(
+//!     template.render().unwrap(),
+//!     "You are a helpful assistant. The user's name is Alice."
+//! );
+//! ```
+//!
+//! Named and versioned templates can be managed with a [PromptLibrary].
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+mod library;
+
+pub use library::{PromptLibrary, PromptLibraryError};
+
+#[derive(Debug, thiserror::Error)]
+pub enum PromptTemplateError {
+    /// A placeholder in the template has no corresponding value
+    #[error("MissingVariable: {0}")]
+    MissingVariable(String),
+
+    /// A placeholder was opened but never closed (or closed without being opened)
+    #[error("MalformedTemplate: {0}")]
+    MalformedTemplate(String),
+}
+
+/// A string template with `{variable}` placeholders and the values used to render it.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct PromptTemplate {
+    template: String,
+    #[serde(default)]
+    vars: HashMap<String, String>,
+}
+
+impl PromptTemplate {
+    /// Create a new template from a string containing `{variable}` placeholders.
+    pub fn new(template: impl Into<String>) -> Self {
+        Self {
+            template: template.into(),
+            vars: HashMap::new(),
+        }
+    }
+
+    /// Set the value of a single variable.
+    pub fn var(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.vars.insert(key.into(), value.into());
+        self
+    }
+
+    /// Set the value of several variables at once. Existing values with the same key are overwritten.
+    pub fn vars(mut self, vars: HashMap<String, String>) -> Self {
+        self.vars.extend(vars);
+        self
+    }
+
+    /// The raw, unrendered template string.
+    pub fn template(&self) -> &str {
+        &self.template
+    }
+
+    /// Returns the names of the variables referenced by the template, in order of appearance.
+    pub fn variables(&self) -> Result<Vec<String>, PromptTemplateError> {
+        let mut variables = vec![];
+        parse(&self.template, |segment| {
+            if let Segment::Variable(name) = segment {
+                if !variables.iter().any(|v| v == name) {
+                    variables.push(name.to_string());
+                }
+            }
+            Ok(())
+        })?;
+        Ok(variables)
+    }
+
+    /// Render the template using the variables set on it.
+    pub fn render(&self) -> Result<String, PromptTemplateError> {
+        self.render_with(&HashMap::new())
+    }
+
+    /// Render the template, using `overrides` in priority over the variables set on it.
+    pub fn render_with(
+        &self,
+        overrides: &HashMap<String, String>,
+    ) -> Result<String, PromptTemplateError> {
+        let mut rendered = String::with_capacity(self.template.len());
+        parse(&self.template, |segment| {
+            match segment {
+                Segment::Literal(text) => rendered.push_str(text),
+                Segment::Variable(name) => {
+                    let value = overrides
+                        .get(name)
+                        .or_else(|| self.vars.get(name))
+                        .ok_or_else(|| PromptTemplateError::MissingVariable(name.to_string()))?;
+                    rendered.push_str(value);
+                }
+            }
+            Ok(())
+        })?;
+        Ok(rendered)
+    }
+}
+
+impl From<&str> for PromptTemplate {
+    fn from(template: &str) -> Self {
+        Self::new(template)
+    }
+}
+
+impl From<String> for PromptTemplate {
+    fn from(template: String) -> Self {
+        Self::new(template)
+    }
+}
+
+enum Segment<'a> {
+    Literal(&'a str),
+    Variable(&'a str),
+}
+
+/// Walk the template, calling `f` for every literal run and every placeholder.
+fn parse<'a>(
+    template: &'a str,
+    mut f: impl FnMut(Segment<'a>) -> Result<(), PromptTemplateError>,
+) -> Result<(), PromptTemplateError> {
+    let mut rest = template;
+
+    while let Some(idx) = rest.find(['{', '}']) {
+        f(Segment::Literal(&rest[..idx]))?;
+        let tail = &rest[idx..];
+
+        if let Some(after) = tail.strip_prefix("{{") {
+            f(Segment::Literal("{"))?;
+            rest = after;
+        } else if let Some(after) = tail.strip_prefix("}}") {
+            f(Segment::Literal("}"))?;
+            rest = after;
+        } else if tail.starts_with('}') {
+            return Err(PromptTemplateError::MalformedTemplate(format!(
+                "unmatched `}}` at byte {}",
+                template.len() - tail.len()
+            )));
+        } else {
+            let end = tail.find('}').ok_or_else(|| {
+                PromptTemplateError::MalformedTemplate(format!(
+                    "unclosed `{{` at byte {}",
+                    template.len() - tail.len()
+                ))
+            })?;
+            f(Segment::Variable(tail[1..end].trim()))?;
+            rest = &tail[end + 1..];
+        }
+    }
+
+    f(Segment::Literal(rest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_template() {
+        let template = PromptTemplate::new("Hello {name}, today is {date}.")
+            .var("name", "Bob")
+            .var("date", "Monday");
+
+        assert_eq!(template.render().unwrap(), "Hello Bob, today is Monday.");
+    }
+
+    #[test]
+    fn test_render_with_overrides() {
+        let template = PromptTemplate::new("Hello {name}!").var("name", "Bob");
+        let overrides = HashMap::from([("name".to_string(), "Alice".to_string())]);
+
+        assert_eq!(template.render_with(&overrides).unwrap(), "Hello Alice!");
+    }
+
+    #[test]
+    fn test_missing_variable() {
+        let template = PromptTemplate::new("Hello {name}!");
+
+        assert!(matches!(
+            template.render(),
+            Err(PromptTemplateError::MissingVariable(name)) if name == "name"
+        ));
+    }
+
+    #[test]
+    fn test_escaped_braces() {
+        let template =
+            PromptTemplate::new("Reply with {{\"answer\": {answer}}}").var("answer", "42");
+
+        assert_eq!(template.render().unwrap(), "Reply with {\"answer\": 42}");
+    }
+
+    #[test]
+    fn test_malformed_template() {
+        assert!(matches!(
+            PromptTemplate::new("Hello {name").render(),
+            Err(PromptTemplateError::MalformedTemplate(_))
+        ));
+        assert!(matches!(
+            PromptTemplate::new("Hello name}").render(),
+            Err(PromptTemplateError::MalformedTemplate(_))
+        ));
+    }
+
+    #[test]
+    fn test_variables() {
+        let template = PromptTemplate::new("{a} {b} {a} {{c}}");
+
+        assert_eq!(template.variables().unwrap(), vec!["a", "b"]);
+    }
+}
@@ -333,32 +333,33 @@ impl TryFrom<message::Message> for Message {
                     message::UserContent::Text(message::Text { text }) => {
                         Ok(Content::Text { text })
                     }
-                    message::UserContent::ToolResult(message::ToolResult { id, content }) => {
-                        Ok(Content::ToolResult {
-                            tool_use_id: id,
-                            content: content.try_map(|content| match content {
-                                message::ToolResultContent::Text(message::Text { text }) => {
-                                    Ok(ToolResultContent::Text { text })
-                                }
-                                message::ToolResultContent::Image(image) => {
-                                    let media_type =
-                                        image.media_type.ok_or(MessageError::ConversionError(
-                                            "Image media type is required".to_owned(),
-                                        ))?;
-                                    let format =
-                                        image.format.ok_or(MessageError::ConversionError(
-                                            "Image format is required".to_owned(),
-                                        ))?;
-                                    Ok(ToolResultContent::Image(ImageSource {
-                                        data: image.data,
-                                        media_type: media_type.try_into()?,
-                                        r#type: format.try_into()?,
-                                    }))
-                                }
-                            })?,
-                            is_error: None,
-                        })
-                    }
+                    message::UserContent::ToolResult(message::ToolResult {
+                        id,
+                        content,
+                        is_error,
+                    }) => Ok(Content::ToolResult {
+                        tool_use_id: id,
+                        content: content.try_map(|content| match content {
+                            message::ToolResultContent::Text(message::Text { text }) => {
+                                Ok(ToolResultContent::Text { text })
+                            }
+                            message::ToolResultContent::Image(image) => {
+                                let media_type =
+                                    image.media_type.ok_or(MessageError::ConversionError(
+                                        "Image media type is required".to_owned(),
+                                    ))?;
+                                let format = image.format.ok_or(MessageError::ConversionError(
+                                    "Image format is required".to_owned(),
+                                ))?;
+                                Ok(ToolResultContent::Image(ImageSource {
+                                    data: image.data,
+                                    media_type: media_type.try_into()?,
+                                    r#type: format.try_into()?,
+                                }))
+                            }
+                        })?,
+                        is_error: is_error.then_some(true),
+                    }),
                     message::UserContent::Image(message::Image {
                         data,
                         format,
@@ -465,11 +466,12 @@ impl TryFrom<Message> for message::Message {
                         Content::ToolResult {
                             tool_use_id,
                             content,
-                            ..
-                        } => message::UserContent::tool_result(
-                            tool_use_id,
-                            content.map(|content| content.into()),
-                        ),
+                            is_error,
+                        } => message::UserContent::ToolResult(message::ToolResult {
+                            id: tool_use_id,
+                            content: content.map(|content| content.into()),
+                            is_error: is_error.unwrap_or(false),
+                        }),
                         Content::Image { source } => message::UserContent::Image(message::Image {
                             data: source.data,
                             format: Some(message::ContentFormat::Base64),
@@ -553,6 +555,18 @@ pub enum ToolChoice {
     Tool {
         name: String,
     },
+    None,
+}
+
+impl From<completion::ToolChoice> for ToolChoice {
+    fn from(tool_choice: completion::ToolChoice) -> Self {
+        match tool_choice {
+            completion::ToolChoice::None => ToolChoice::None,
+            completion::ToolChoice::Auto => ToolChoice::Auto,
+            completion::ToolChoice::Required => ToolChoice::Any,
+            completion::ToolChoice::Function(name) => ToolChoice::Tool { name },
+        }
+    }
 }
 
 impl completion::CompletionModel for CompletionModel {
@@ -614,7 +628,7 @@ impl completion::CompletionModel for CompletionModel {
                             input_schema: tool.parameters,
                         })
                         .collect::<Vec<_>>(),
-                    "tool_choice": ToolChoice::Auto,
+                    "tool_choice": ToolChoice::from(completion_request.tool_choice.unwrap_or_default()),
                 }),
             );
         }
@@ -644,7 +658,7 @@ impl completion::CompletionModel for CompletionModel {
                 ApiResponse::Error(error) => Err(CompletionError::ProviderError(error.message)),
             }
         } else {
-            Err(CompletionError::ProviderError(response.text().await?))
+            Err(CompletionError::from_response(response).await)
         }
     }
 
@@ -984,4 +998,23 @@ mod tests {
         assert_eq!(assistant_message, original_assistant_message);
         assert_eq!(tool_message, original_tool_message);
     }
+
+    #[test]
+    fn test_tool_choice() {
+        assert_eq!(
+            serde_json::to_value(ToolChoice::from(completion::ToolChoice::Required)).unwrap(),
+            json!({"type": "any"})
+        );
+        assert_eq!(
+            serde_json::to_value(ToolChoice::from(completion::ToolChoice::Function(
+                "submit".to_string()
+            )))
+            .unwrap(),
+            json!({"type": "tool", "name": "submit"})
+        );
+        assert_eq!(
+            serde_json::to_value(ToolChoice::from(completion::ToolChoice::None)).unwrap(),
+            json!({"type": "none"})
+        );
+    }
 }
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use async_stream::stream;
 use futures::StreamExt;
 use serde::Deserialize;
@@ -18,7 +20,7 @@ pub enum StreamingEvent {
     },
     ContentBlockStart {
         index: usize,
-        content_block: Content,
+        content_block: ContentBlock,
     },
     ContentBlockDelta {
         index: usize,
@@ -48,11 +50,40 @@ pub struct MessageStart {
     pub usage: Usage,
 }
 
+/// A content block, as started by a `content_block_start` event. Its content is then streamed by
+/// the `content_block_delta` events of the same index, until the `content_block_stop` event.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ContentBlock {
+    Text {
+        text: String,
+    },
+    ToolUse {
+        id: String,
+        name: String,
+    },
+    Thinking {
+        thinking: String,
+    },
+    #[serde(other)]
+    Unknown,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ContentDelta {
-    TextDelta { text: String },
-    InputJsonDelta { partial_json: String },
+    TextDelta {
+        text: String,
+    },
+    InputJsonDelta {
+        partial_json: String,
+    },
+    ThinkingDelta {
+        thinking: String,
+    },
+    /// E.g.: the `signature_delta` of thinking blocks
+    #[serde(other)]
+    Unknown,
 }
 
 #[derive(Debug, Deserialize)]
@@ -68,6 +99,7 @@ pub struct PartialUsage {
     pub input_tokens: Option<usize>,
 }
 
+/// A `tool_use` content block being streamed, its input being accumulated until the block stops.
 #[derive(Default)]
 struct ToolCallState {
     name: String,
@@ -75,6 +107,64 @@ struct ToolCallState {
     input_json: String,
 }
 
+/// Accumulates the `tool_use` content blocks of a stream: each block is started by a
+/// `content_block_start` event, its input is streamed by the `input_json_delta` deltas of the
+/// same index, and the tool call is complete once its `content_block_stop` event is received.
+#[derive(Default)]
+struct AnthropicStreamingToolCallAccumulator {
+    /// Tool calls being streamed, by index of their content block
+    tool_calls: HashMap<usize, ToolCallState>,
+}
+
+impl AnthropicStreamingToolCallAccumulator {
+    /// Start the tool call of the content block `index`.
+    fn start(&mut self, index: usize, id: &str, name: &str) {
+        self.tool_calls.insert(
+            index,
+            ToolCallState {
+                name: name.to_string(),
+                id: id.to_string(),
+                input_json: String::new(),
+            },
+        );
+    }
+
+    /// Whether the content block `index` is a tool call being streamed.
+    fn contains(&self, index: usize) -> bool {
+        self.tool_calls.contains_key(&index)
+    }
+
+    /// Append a chunk of the JSON input of the tool call of the content block `index`.
+    fn push(&mut self, index: usize, partial_json: &str) {
+        if let Some(tool_call) = self.tool_calls.get_mut(&index) {
+            tool_call.input_json.push_str(partial_json);
+        }
+    }
+
+    /// Complete the tool call of the content block `index`, if it is one, parsing its input.
+    fn finish(
+        &mut self,
+        index: usize,
+    ) -> Option<Result<RawStreamingChoice<StreamingCompletionResponse>, CompletionError>> {
+        let tool_call = self.tool_calls.remove(&index)?;
+        let json_str = if tool_call.input_json.is_empty() {
+            "{}"
+        } else {
+            &tool_call.input_json
+        };
+
+        Some(
+            serde_json::from_str(json_str)
+                .map(|arguments| RawStreamingChoice::ToolCall {
+                    name: tool_call.name,
+                    id: tool_call.id,
+                    arguments,
+                })
+                .map_err(CompletionError::from),
+        )
+    }
+}
+
 #[derive(Clone)]
 pub struct StreamingCompletionResponse {
     pub usage: PartialUsage,
@@ -132,7 +222,7 @@ impl CompletionModel {
                             input_schema: tool.parameters,
                         })
                         .collect::<Vec<_>>(),
-                    "tool_choice": ToolChoice::Auto,
+                    "tool_choice": ToolChoice::from(completion_request.tool_choice.unwrap_or_default()),
                 }),
             );
         }
@@ -149,14 +239,14 @@ impl CompletionModel {
             .await?;
 
         if !response.status().is_success() {
-            return Err(CompletionError::ProviderError(response.text().await?));
+            return Err(CompletionError::from_response(response).await);
         }
 
         // Use our SSE decoder to directly handle Server-Sent Events format
         let sse_stream = sse_from_response(response);
 
         let stream: StreamingResult<StreamingCompletionResponse> = Box::pin(stream! {
-            let mut current_tool_call: Option<ToolCallState> = None;
+            let mut tool_calls = AnthropicStreamingToolCallAccumulator::default();
             let mut sse_stream = Box::pin(sse_stream);
             let mut input_tokens = 0;
 
@@ -184,7 +274,7 @@ impl CompletionModel {
                                     _ => {}
                                 }
 
-                                if let Some(result) = handle_event(&event, &mut current_tool_call) {
+                                if let Some(result) = handle_event(&event, &mut tool_calls) {
                                     yield result;
                                 }
                             },
@@ -209,56 +299,44 @@ impl CompletionModel {
     }
 }
 
+/// Handle a content block event: text (and thinking) deltas are returned as they arrive, while the
+/// input of `tool_use` blocks is accumulated, by block index, and the tool call returned once its
+/// block stops.
 fn handle_event(
     event: &StreamingEvent,
-    current_tool_call: &mut Option<ToolCallState>,
+    tool_calls: &mut AnthropicStreamingToolCallAccumulator,
 ) -> Option<Result<RawStreamingChoice<StreamingCompletionResponse>, CompletionError>> {
     match event {
-        StreamingEvent::ContentBlockDelta { delta, .. } => match delta {
-            ContentDelta::TextDelta { text } => {
-                if current_tool_call.is_none() {
-                    return Some(Ok(RawStreamingChoice::Message(text.clone())));
-                }
-                None
+        StreamingEvent::ContentBlockStart {
+            index,
+            content_block,
+        } => match content_block {
+            ContentBlock::Text { text } if !text.is_empty() => {
+                Some(Ok(RawStreamingChoice::Message(text.clone())))
             }
-            ContentDelta::InputJsonDelta { partial_json } => {
-                if let Some(ref mut tool_call) = current_tool_call {
-                    tool_call.input_json.push_str(partial_json);
-                }
-                None
+            ContentBlock::Thinking { thinking } if !thinking.is_empty() => {
+                Some(Ok(RawStreamingChoice::Reasoning(thinking.clone())))
             }
-        },
-        StreamingEvent::ContentBlockStart { content_block, .. } => match content_block {
-            Content::ToolUse { id, name, .. } => {
-                *current_tool_call = Some(ToolCallState {
-                    name: name.clone(),
-                    id: id.clone(),
-                    input_json: String::new(),
-                });
+            ContentBlock::ToolUse { id, name } => {
+                tool_calls.start(*index, id, name);
                 None
             }
-            // Handle other content types - they don't need special handling
             _ => None,
         },
-        StreamingEvent::ContentBlockStop { .. } => {
-            if let Some(tool_call) = current_tool_call.take() {
-                let json_str = if tool_call.input_json.is_empty() {
-                    "{}"
-                } else {
-                    &tool_call.input_json
-                };
-                match serde_json::from_str(json_str) {
-                    Ok(json_value) => Some(Ok(RawStreamingChoice::ToolCall {
-                        name: tool_call.name,
-                        id: tool_call.id,
-                        arguments: json_value,
-                    })),
-                    Err(e) => Some(Err(CompletionError::from(e))),
-                }
-            } else {
+        StreamingEvent::ContentBlockDelta { index, delta } => match delta {
+            ContentDelta::TextDelta { text } if !tool_calls.contains(*index) => {
+                Some(Ok(RawStreamingChoice::Message(text.clone())))
+            }
+            ContentDelta::ThinkingDelta { thinking } => {
+                Some(Ok(RawStreamingChoice::Reasoning(thinking.clone())))
+            }
+            ContentDelta::InputJsonDelta { partial_json } => {
+                tool_calls.push(*index, partial_json);
                 None
             }
-        }
+            _ => None,
+        },
+        StreamingEvent::ContentBlockStop { index } => tool_calls.finish(*index),
         // Ignore other event types or handle as needed
         StreamingEvent::MessageStart { .. }
         | StreamingEvent::MessageDelta { .. }
@@ -267,3 +345,58 @@ fn handle_event(
         | StreamingEvent::Unknown => None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_handle_events() {
+        let events = [
+            r#"{"type": "message_start", "message": {"id": "msg_1", "type": "message", "role": "assistant", "content": [], "model": "claude-3-7-sonnet-latest", "stop_reason": null, "stop_sequence": null, "usage": {"input_tokens": 25, "output_tokens": 1}}}"#,
+            r#"{"type": "content_block_start", "index": 0, "content_block": {"type": "thinking", "thinking": ""}}"#,
+            r#"{"type": "content_block_delta", "index": 0, "delta": {"type": "thinking_delta", "thinking": "The user wants the weather."}}"#,
+            r#"{"type": "content_block_delta", "index": 0, "delta": {"type": "signature_delta", "signature": "EqQBCgIYAhIM"}}"#,
+            r#"{"type": "content_block_stop", "index": 0}"#,
+            r#"{"type": "content_block_start", "index": 1, "content_block": {"type": "text", "text": ""}}"#,
+            r#"{"type": "content_block_delta", "index": 1, "delta": {"type": "text_delta", "text": "Let me "}}"#,
+            r#"{"type": "content_block_delta", "index": 1, "delta": {"type": "text_delta", "text": "check."}}"#,
+            r#"{"type": "content_block_stop", "index": 1}"#,
+            r#"{"type": "content_block_start", "index": 2, "content_block": {"type": "tool_use", "id": "toolu_1", "name": "get_weather", "input": {}}}"#,
+            r#"{"type": "content_block_delta", "index": 2, "delta": {"type": "input_json_delta", "partial_json": ""}}"#,
+            r#"{"type": "content_block_delta", "index": 2, "delta": {"type": "input_json_delta", "partial_json": "{\"location\": \"Par"}}"#,
+            r#"{"type": "content_block_delta", "index": 2, "delta": {"type": "input_json_delta", "partial_json": "is\"}"}}"#,
+            r#"{"type": "content_block_stop", "index": 2}"#,
+            r#"{"type": "content_block_start", "index": 3, "content_block": {"type": "tool_use", "id": "toolu_2", "name": "get_time", "input": {}}}"#,
+            r#"{"type": "content_block_stop", "index": 3}"#,
+            r#"{"type": "message_delta", "delta": {"stop_reason": "tool_use", "stop_sequence": null}, "usage": {"output_tokens": 89}}"#,
+            r#"{"type": "message_stop"}"#,
+        ];
+
+        let mut tool_calls = AnthropicStreamingToolCallAccumulator::default();
+        let choices = events
+            .iter()
+            .map(|event| serde_json::from_str::<StreamingEvent>(event).unwrap())
+            .filter_map(|event| handle_event(&event, &mut tool_calls))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert_eq!(choices.len(), 5);
+        assert!(
+            matches!(&choices[0], RawStreamingChoice::Reasoning(thinking) if thinking == "The user wants the weather.")
+        );
+        assert!(matches!(&choices[1], RawStreamingChoice::Message(text) if text == "Let me "));
+        assert!(matches!(&choices[2], RawStreamingChoice::Message(text) if text == "check."));
+        assert!(matches!(
+            &choices[3],
+            RawStreamingChoice::ToolCall { id, name, arguments }
+                if id == "toolu_1" && name == "get_weather" && arguments == &serde_json::json!({"location": "Paris"})
+        ));
+        assert!(matches!(
+            &choices[4],
+            RawStreamingChoice::ToolCall { id, arguments, .. }
+                if id == "toolu_2" && arguments == &serde_json::json!({})
+        ));
+        assert!(tool_calls.tool_calls.is_empty());
+    }
+}
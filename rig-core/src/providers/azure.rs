@@ -8,6 +8,25 @@
 //!
 //! let gpt4o = client.completion_model(azure::GPT_4O);
 //! ```
+//!
+//! On Azure, models are served by deployments (`https://{resource}.openai.azure.com/openai/deployments/{deployment}`).
+//! The name passed to `completion_model` is the name of the deployment. A default deployment can
+//! also be attached to the client (this module is also available as `providers::azure_openai`):
+//! ```
+//! use rig::providers::azure_openai;
+//!
+//! let client = azure_openai::Client::from_resource("my-resource", "my-gpt-4o", "YOUR_API_KEY", "2024-10-21");
+//!
+//! let gpt4o = client.deployment_model().expect("The client has a default deployment");
+//! ```
+//!
+//! With the `azure-identity` feature, requests can also be authenticated with Microsoft Entra ID
+//! (formerly Azure Active Directory) tokens, obtained and refreshed by the Azure Identity SDK:
+//! ```
+//! use rig::providers::azure_openai;
+//!
+//! let client = azure_openai::Client::from_managed_identity("2024-10-21", "https://my-resource.openai.azure.com");
+//! ```
 
 use super::openai::{send_compatible_streaming_request, TranscriptionResponse};
 
@@ -33,6 +52,7 @@ pub struct Client {
     api_version: String,
     azure_endpoint: String,
     auth: AzureOpenAIAuth,
+    deployment: Option<String>,
     http_client: reqwest::Client,
 }
 
@@ -43,14 +63,26 @@ impl std::fmt::Debug for Client {
             .field("http_client", &self.http_client)
             .field("auth", &"<REDACTED>")
             .field("api_version", &self.api_version)
+            .field("deployment", &self.deployment)
             .finish()
     }
 }
 
+/// Scope of the Entra ID tokens granting access to Azure OpenAI
+#[cfg(feature = "azure-identity")]
+const COGNITIVE_SERVICES_SCOPE: &str = "https://cognitiveservices.azure.com/.default";
+
 #[derive(Clone)]
 pub enum AzureOpenAIAuth {
     ApiKey(String),
     Token(String),
+    /// Entra ID (Azure Active Directory) credential, from which a token is requested for
+    /// each request. The credentials of the Azure Identity SDK cache their token, and only
+    /// request a new one when it is about to expire.
+    ///
+    /// Note: requires the `azure-identity` feature.
+    #[cfg(feature = "azure-identity")]
+    Credential(std::sync::Arc<dyn azure_core::auth::TokenCredential>),
 }
 
 impl std::fmt::Debug for AzureOpenAIAuth {
@@ -58,6 +90,8 @@ impl std::fmt::Debug for AzureOpenAIAuth {
         match self {
             Self::ApiKey(_) => write!(f, "API key <REDACTED>"),
             Self::Token(_) => write!(f, "Token <REDACTED>"),
+            #[cfg(feature = "azure-identity")]
+            Self::Credential(_) => write!(f, "Entra ID credential"),
         }
     }
 }
@@ -69,18 +103,35 @@ impl From<String> for AzureOpenAIAuth {
 }
 
 impl AzureOpenAIAuth {
-    fn as_header(&self) -> (reqwest::header::HeaderName, reqwest::header::HeaderValue) {
+    /// Returns the authentication header of a request, requesting a token from the credential
+    /// if needed.
+    async fn as_header(
+        &self,
+    ) -> Result<(reqwest::header::HeaderName, reqwest::header::HeaderValue), String> {
         match self {
-            AzureOpenAIAuth::ApiKey(api_key) => (
+            AzureOpenAIAuth::ApiKey(api_key) => Ok((
                 "api-key".parse().expect("Header value should parse"),
                 api_key.parse().expect("API key should parse"),
-            ),
-            AzureOpenAIAuth::Token(token) => (
+            )),
+            AzureOpenAIAuth::Token(token) => Ok((
                 AUTHORIZATION,
                 format!("Bearer {token}")
                     .parse()
                     .expect("Token should parse"),
-            ),
+            )),
+            #[cfg(feature = "azure-identity")]
+            AzureOpenAIAuth::Credential(credential) => {
+                let token = credential
+                    .get_token(&[COGNITIVE_SERVICES_SCOPE])
+                    .await
+                    .map_err(|e| format!("Failed to get an Entra ID token: {e}"))?;
+                Ok((
+                    AUTHORIZATION,
+                    format!("Bearer {}", token.token.secret())
+                        .parse()
+                        .expect("Token should parse"),
+                ))
+            }
         }
     }
 }
@@ -98,12 +149,48 @@ impl Client {
             api_version: api_version.to_string(),
             auth: auth.into(),
             azure_endpoint: azure_endpoint.to_string(),
+            deployment: None,
             http_client: reqwest::Client::builder()
                 .build()
                 .expect("Azure OpenAI reqwest client should build"),
         }
     }
 
+    /// Creates a new Azure OpenAI client for the given resource, with a default deployment.
+    ///
+    /// # Arguments
+    ///
+    /// * `resource` - Name of the Azure OpenAI resource (i.e.: `https://{resource}.openai.azure.com`)
+    /// * `deployment` - Name of the deployment used by [Client::deployment_model]
+    /// * `api_key` - Azure OpenAI API key required for authentication
+    /// * `api_version` - API version to use (e.g., "2024-10-21" for GA, "2024-10-01-preview" for preview)
+    pub fn from_resource(
+        resource: &str,
+        deployment: &str,
+        api_key: &str,
+        api_version: &str,
+    ) -> Self {
+        Self::from_api_key(
+            api_key,
+            api_version,
+            &format!("https://{resource}.openai.azure.com"),
+        )
+        .with_deployment(deployment)
+    }
+
+    /// Set the default deployment of the client (see [Client::deployment_model]).
+    pub fn with_deployment(mut self, deployment: &str) -> Self {
+        self.deployment = Some(deployment.to_string());
+        self
+    }
+
+    /// Create a completion model for the default deployment of the client, if any.
+    pub fn deployment_model(&self) -> Option<CompletionModel> {
+        self.deployment
+            .as_deref()
+            .map(|deployment| CompletionModel::new(self.clone(), deployment))
+    }
+
     /// Use your own `reqwest::Client`.
     /// The required headers will be automatically attached upon trying to make a request.
     pub fn with_custom_client(mut self, client: reqwest::Client) -> Self {
@@ -142,64 +229,153 @@ impl Client {
         )
     }
 
-    fn post_embedding(&self, deployment_id: &str) -> reqwest::RequestBuilder {
+    /// Creates a new Azure OpenAI client authenticated with Entra ID tokens of the managed
+    /// identity of the Azure resource (e.g.: VM or App Service) the client runs on.
+    ///
+    /// Note: requires the `azure-identity` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `api_version` - API version to use (e.g., "2024-10-21" for GA, "2024-10-01-preview" for preview)
+    /// * `azure_endpoint` - Azure OpenAI endpoint URL
+    #[cfg(feature = "azure-identity")]
+    pub fn from_managed_identity(api_version: &str, azure_endpoint: &str) -> Self {
+        let credential = azure_identity::VirtualMachineManagedIdentityCredential::new(
+            azure_identity::TokenCredentialOptions::default(),
+        );
+        Self::from_credential(std::sync::Arc::new(credential), api_version, azure_endpoint)
+    }
+
+    /// Creates a new Azure OpenAI client authenticated with Entra ID tokens of a service
+    /// principal (i.e.: an app registration and one of its client secrets).
+    /// The authority host can be overridden with the `AZURE_AUTHORITY_HOST` environment variable.
+    ///
+    /// Note: requires the `azure-identity` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `tenant_id` - Id of the Entra ID tenant of the service principal
+    /// * `client_id` - Application (client) id of the service principal
+    /// * `client_secret` - Client secret of the service principal
+    /// * `api_version` - API version to use (e.g., "2024-10-21" for GA, "2024-10-01-preview" for preview)
+    /// * `azure_endpoint` - Azure OpenAI endpoint URL
+    #[cfg(feature = "azure-identity")]
+    pub fn from_service_principal(
+        tenant_id: &str,
+        client_id: &str,
+        client_secret: &str,
+        api_version: &str,
+        azure_endpoint: &str,
+    ) -> Result<Self, azure_core::Error> {
+        let options = azure_identity::TokenCredentialOptions::default();
+        let credential = azure_identity::ClientSecretCredential::new(
+            options.http_client(),
+            options.authority_host()?,
+            tenant_id.to_string(),
+            client_id.to_string(),
+            client_secret.to_string(),
+        );
+        Ok(Self::from_credential(
+            std::sync::Arc::new(credential),
+            api_version,
+            azure_endpoint,
+        ))
+    }
+
+    /// Creates a new Azure OpenAI client authenticated with Entra ID tokens of any credential
+    /// of the Azure Identity SDK (e.g.: `DefaultAzureCredential`).
+    ///
+    /// Note: requires the `azure-identity` feature.
+    #[cfg(feature = "azure-identity")]
+    pub fn from_credential(
+        credential: std::sync::Arc<dyn azure_core::auth::TokenCredential>,
+        api_version: &str,
+        azure_endpoint: &str,
+    ) -> Self {
+        Self::new(
+            AzureOpenAIAuth::Credential(credential),
+            api_version,
+            azure_endpoint,
+        )
+    }
+
+    async fn post_embedding(&self, deployment_id: &str) -> Result<reqwest::RequestBuilder, String> {
         let url = format!(
             "{}/openai/deployments/{}/embeddings?api-version={}",
             self.azure_endpoint, deployment_id, self.api_version
         )
         .replace("//", "/");
 
-        let (key, value) = self.auth.as_header();
-        self.http_client.post(url).header(key, value)
+        let (key, value) = self.auth.as_header().await?;
+        Ok(self.http_client.post(url).header(key, value))
     }
 
-    fn post_chat_completion(&self, deployment_id: &str) -> reqwest::RequestBuilder {
+    async fn post_chat_completion(
+        &self,
+        deployment_id: &str,
+    ) -> Result<reqwest::RequestBuilder, String> {
         let url = format!(
             "{}/openai/deployments/{}/chat/completions?api-version={}",
             self.azure_endpoint, deployment_id, self.api_version
         )
         .replace("//", "/");
-        let (key, value) = self.auth.as_header();
-        self.http_client.post(url).header(key, value)
+        let (key, value) = self.auth.as_header().await?;
+        Ok(self.http_client.post(url).header(key, value))
     }
 
-    fn post_transcription(&self, deployment_id: &str) -> reqwest::RequestBuilder {
+    async fn post_transcription(
+        &self,
+        deployment_id: &str,
+    ) -> Result<reqwest::RequestBuilder, String> {
         let url = format!(
             "{}/openai/deployments/{}/audio/translations?api-version={}",
             self.azure_endpoint, deployment_id, self.api_version
         )
         .replace("//", "/");
-        let (key, value) = self.auth.as_header();
-        self.http_client.post(url).header(key, value)
+        let (key, value) = self.auth.as_header().await?;
+        Ok(self.http_client.post(url).header(key, value))
     }
 
     #[cfg(feature = "image")]
-    fn post_image_generation(&self, deployment_id: &str) -> reqwest::RequestBuilder {
+    async fn post_image_generation(
+        &self,
+        deployment_id: &str,
+    ) -> Result<reqwest::RequestBuilder, String> {
         let url = format!(
             "{}/openai/deployments/{}/images/generations?api-version={}",
             self.azure_endpoint, deployment_id, self.api_version
         )
         .replace("//", "/");
-        let (key, value) = self.auth.as_header();
-        self.http_client.post(url).header(key, value)
+        let (key, value) = self.auth.as_header().await?;
+        Ok(self.http_client.post(url).header(key, value))
     }
 
     #[cfg(feature = "audio")]
-    fn post_audio_generation(&self, deployment_id: &str) -> reqwest::RequestBuilder {
+    async fn post_audio_generation(
+        &self,
+        deployment_id: &str,
+    ) -> Result<reqwest::RequestBuilder, String> {
         let url = format!(
             "{}/openai/deployments/{}/audio/speech?api-version={}",
             self.azure_endpoint, deployment_id, self.api_version
         )
         .replace("//", "/");
-        let (key, value) = self.auth.as_header();
-        self.http_client.post(url).header(key, value)
+        let (key, value) = self.auth.as_header().await?;
+        Ok(self.http_client.post(url).header(key, value))
     }
 }
 
 impl ProviderClient for Client {
     /// Create a new Azure OpenAI client from the `AZURE_API_KEY` or `AZURE_TOKEN`, `AZURE_API_VERSION`, and `AZURE_ENDPOINT` environment variables.
+    /// The `AZURE_OPENAI_API_KEY`, `AZURE_OPENAI_API_VERSION` and `AZURE_OPENAI_ENDPOINT` variables are also
+    /// accepted, and the default deployment is read from `AZURE_OPENAI_DEPLOYMENT` (if set).
     fn from_env() -> Self {
-        let auth = if let Ok(api_key) = std::env::var("AZURE_API_KEY") {
+        let var = |name: &str| {
+            std::env::var(name)
+                .or_else(|_| std::env::var(name.replacen("AZURE_", "AZURE_OPENAI_", 1)))
+        };
+
+        let auth = if let Ok(api_key) = var("AZURE_API_KEY") {
             AzureOpenAIAuth::ApiKey(api_key)
         } else if let Ok(token) = std::env::var("AZURE_TOKEN") {
             AzureOpenAIAuth::Token(token)
@@ -207,10 +383,14 @@ impl ProviderClient for Client {
             panic!("Neither AZURE_API_KEY nor AZURE_TOKEN is set");
         };
 
-        let api_version = std::env::var("AZURE_API_VERSION").expect("AZURE_API_VERSION not set");
-        let azure_endpoint = std::env::var("AZURE_ENDPOINT").expect("AZURE_ENDPOINT not set");
+        let api_version = var("AZURE_API_VERSION").expect("AZURE_API_VERSION not set");
+        let azure_endpoint = var("AZURE_ENDPOINT").expect("AZURE_ENDPOINT not set");
 
-        Self::new(auth, &api_version, &azure_endpoint)
+        let client = Self::new(auth, &api_version, &azure_endpoint);
+        match std::env::var("AZURE_OPENAI_DEPLOYMENT") {
+            Ok(deployment) => client.with_deployment(&deployment),
+            Err(_) => client,
+        }
     }
 }
 
@@ -385,6 +565,8 @@ impl embeddings::EmbeddingModel for EmbeddingModel {
         let response = self
             .client
             .post_embedding(&self.model)
+            .await
+            .map_err(EmbeddingError::ProviderError)?
             .json(&json!({
                 "input": documents,
             }))
@@ -482,10 +664,12 @@ impl CompletionModel {
         &self,
         completion_request: CompletionRequest,
     ) -> Result<serde_json::Value, CompletionError> {
-        let mut full_history: Vec<openai::Message> = match &completion_request.preamble {
-            Some(preamble) => vec![openai::Message::system(preamble)],
-            None => vec![],
-        };
+        let mut full_history: Vec<openai::Message> = completion_request
+            .preamble
+            .iter()
+            .chain(&completion_request.system_messages)
+            .map(|content| openai::Message::system(content))
+            .collect();
         if let Some(docs) = completion_request.normalized_documents() {
             let docs: Vec<openai::Message> = docs.try_into()?;
             full_history.extend(docs);
@@ -513,7 +697,7 @@ impl CompletionModel {
                 "messages": full_history,
                 "temperature": completion_request.temperature,
                 "tools": completion_request.tools.into_iter().map(openai::ToolDefinition::from).collect::<Vec<_>>(),
-                "tool_choice": "auto",
+                "tool_choice": serde_json::Value::from(completion_request.tool_choice.unwrap_or_default()),
             })
         };
 
@@ -541,6 +725,8 @@ impl completion::CompletionModel for CompletionModel {
         let response = self
             .client
             .post_chat_completion(&self.model)
+            .await
+            .map_err(CompletionError::ProviderError)?
             .json(&request)
             .send()
             .await?;
@@ -560,7 +746,7 @@ impl completion::CompletionModel for CompletionModel {
                 ApiResponse::Err(err) => Err(CompletionError::ProviderError(err.message)),
             }
         } else {
-            Err(CompletionError::ProviderError(response.text().await?))
+            Err(CompletionError::from_response(response).await)
         }
     }
 
@@ -579,6 +765,8 @@ impl completion::CompletionModel for CompletionModel {
         let builder = self
             .client
             .post_chat_completion(self.model.as_str())
+            .await
+            .map_err(CompletionError::ProviderError)?
             .json(&request);
 
         send_compatible_streaming_request(builder).await
@@ -643,6 +831,8 @@ impl transcription::TranscriptionModel for TranscriptionModel {
         let response = self
             .client
             .post_transcription(&self.model)
+            .await
+            .map_err(TranscriptionError::ProviderError)?
             .multipart(body)
             .send()
             .await?;
@@ -700,6 +890,8 @@ mod image_generation {
             let response = self
                 .client
                 .post_image_generation(&self.model)
+                .await
+                .map_err(ImageGenerationError::ProviderError)?
                 .json(&request)
                 .send()
                 .await?;
@@ -774,6 +966,8 @@ mod audio_generation {
             let response = self
                 .client
                 .post_audio_generation("/audio/speech")
+                .await
+                .map_err(AudioGenerationError::ProviderError)?
                 .json(&request)
                 .send()
                 .await?;
@@ -815,6 +1009,19 @@ mod azure_tests {
     use crate::embeddings::EmbeddingModel;
     use crate::OneOrMany;
 
+    #[test]
+    fn test_client_from_resource() {
+        let client =
+            Client::from_resource("my-resource", "my-gpt-4o", "YOUR_API_KEY", "2024-10-21");
+
+        assert_eq!(
+            client.azure_endpoint,
+            "https://my-resource.openai.azure.com"
+        );
+        assert_eq!(client.api_version, "2024-10-21");
+        assert_eq!(client.deployment_model().unwrap().model, "my-gpt-4o");
+    }
+
     #[tokio::test]
     #[ignore]
     async fn test_azure_embedding() {
@@ -846,6 +1053,8 @@ mod azure_tests {
                 temperature: Some(0.0),
                 tools: vec![],
                 additional_params: None,
+                tool_choice: None,
+                system_messages: vec![],
             })
             .await
             .unwrap();
@@ -0,0 +1,338 @@
+//! Cerebras Inference API client and Rig integration
+//!
+//! # Example
+//! ```
+//! use rig::providers::cerebras;
+//!
+//! let client = cerebras::Client::new("YOUR_API_KEY");
+//!
+//! let llama = client.completion_model(cerebras::LLAMA3_1_8B);
+//! ```
+
+use crate::client::{CompletionClient, ProviderClient};
+use crate::json_utils::merge;
+use crate::providers::openai::send_compatible_streaming_request;
+use crate::streaming::StreamingCompletionResponse;
+use crate::{
+    completion::{self, CompletionError, CompletionRequest},
+    json_utils,
+    providers::openai,
+};
+use crate::{impl_conversion_traits, message};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+// ================================================================
+// Main Cerebras Client
+// ================================================================
+const CEREBRAS_API_BASE_URL: &str = "https://api.cerebras.ai/v1";
+
+#[derive(Clone)]
+pub struct Client {
+    base_url: String,
+    api_key: String,
+    http_client: reqwest::Client,
+}
+
+impl std::fmt::Debug for Client {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Client")
+            .field("base_url", &self.base_url)
+            .field("http_client", &self.http_client)
+            .field("api_key", &"<REDACTED>")
+            .finish()
+    }
+}
+
+impl Client {
+    /// Create a new Cerebras client with the given API key.
+    pub fn new(api_key: &str) -> Self {
+        Self::from_url(api_key, CEREBRAS_API_BASE_URL)
+    }
+
+    /// Create a new Cerebras client with the given API key and base API URL.
+    pub fn from_url(api_key: &str, base_url: &str) -> Self {
+        Self {
+            base_url: base_url.to_string(),
+            api_key: api_key.to_string(),
+            http_client: reqwest::Client::builder()
+                .build()
+                .expect("Cerebras reqwest client should build"),
+        }
+    }
+
+    /// Use your own `reqwest::Client`.
+    /// The required headers will be automatically attached upon trying to make a request.
+    pub fn with_custom_client(mut self, client: reqwest::Client) -> Self {
+        self.http_client = client;
+
+        self
+    }
+
+    fn post(&self, path: &str) -> reqwest::RequestBuilder {
+        let url = format!("{}/{}", self.base_url, path).replace("//", "/");
+        self.http_client.post(url).bearer_auth(&self.api_key)
+    }
+}
+
+impl ProviderClient for Client {
+    /// Create a new Cerebras client from the `CEREBRAS_API_KEY` environment variable.
+    /// Panics if the environment variable is not set.
+    fn from_env() -> Self {
+        let api_key = std::env::var("CEREBRAS_API_KEY").expect("CEREBRAS_API_KEY not set");
+        Self::new(&api_key)
+    }
+}
+
+impl CompletionClient for Client {
+    type CompletionModel = CompletionModel;
+
+    /// Create a completion model with the given name.
+    ///
+    /// # Example
+    /// ```
+    /// use rig::providers::cerebras::{Client, self};
+    ///
+    /// // Initialize the Cerebras client
+    /// let cerebras = Client::new("your-cerebras-api-key");
+    ///
+    /// let llama = cerebras.completion_model(cerebras::LLAMA3_1_70B);
+    /// ```
+    fn completion_model(&self, model: &str) -> CompletionModel {
+        CompletionModel::new(self.clone(), model)
+    }
+}
+
+impl_conversion_traits!(
+    AsEmbeddings,
+    AsTranscription,
+    AsImageGeneration,
+    AsAudioGeneration for Client
+);
+
+#[derive(Debug, Deserialize)]
+struct ApiErrorResponse {
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum ApiResponse<T> {
+    Ok(T),
+    Err(ApiErrorResponse),
+}
+
+// ================================================================
+// Cerebras Completion API
+// ================================================================
+/// The `llama3.1-8b` model. Used for chat completion.
+pub const LLAMA3_1_8B: &str = "llama3.1-8b";
+/// The `llama3.1-70b` model. Used for chat completion.
+pub const LLAMA3_1_70B: &str = "llama3.1-70b";
+
+/// Timing information returned by Cerebras along with each completion (in seconds).
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct CerebrasMetadata {
+    /// Time spent waiting in the queue
+    pub queue_time: Option<f64>,
+    /// Time spent processing the prompt
+    pub prompt_time: Option<f64>,
+    /// Time spent generating the completion
+    pub completion_time: Option<f64>,
+    /// Total time of the request
+    pub total_time: Option<f64>,
+}
+
+/// An OpenAI compatible completion response, with Cerebras' timing information.
+#[derive(Debug, Deserialize)]
+pub struct CompletionResponse {
+    #[serde(flatten)]
+    pub response: openai::CompletionResponse,
+    #[serde(default)]
+    pub time_info: Option<CerebrasMetadata>,
+}
+
+impl TryFrom<CompletionResponse> for completion::CompletionResponse<CompletionResponse> {
+    type Error = CompletionError;
+
+    fn try_from(response: CompletionResponse) -> Result<Self, Self::Error> {
+        let choice = response.response.choices.first().ok_or_else(|| {
+            CompletionError::ResponseError("Response contained no choices".to_owned())
+        })?;
+
+        Ok(completion::CompletionResponse {
+            choice: choice.content()?,
+            raw_response: response,
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CompletionModel {
+    client: Client,
+    /// Name of the model (e.g.: llama3.1-8b)
+    pub model: String,
+}
+
+impl CompletionModel {
+    pub fn new(client: Client, model: &str) -> Self {
+        Self {
+            client,
+            model: model.to_string(),
+        }
+    }
+
+    fn create_completion_request(
+        &self,
+        completion_request: CompletionRequest,
+    ) -> Result<Value, CompletionError> {
+        // Build up the order of messages (context, chat_history)
+        let mut partial_history = vec![];
+        if let Some(docs) = completion_request.normalized_documents() {
+            partial_history.push(docs);
+        }
+        partial_history.extend(completion_request.chat_history);
+
+        // Initialize full history with preamble (or empty if non-existent)
+        let mut full_history: Vec<openai::Message> = completion_request
+            .preamble
+            .map_or_else(Vec::new, |preamble| {
+                vec![openai::Message::system(&preamble)]
+            });
+
+        // Convert and extend the rest of the history
+        full_history.extend(
+            partial_history
+                .into_iter()
+                .map(message::Message::try_into)
+                .collect::<Result<Vec<Vec<openai::Message>>, _>>()?
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>(),
+        );
+
+        let request = if completion_request.tools.is_empty() {
+            json!({
+                "model": self.model,
+                "messages": full_history,
+                "temperature": completion_request.temperature,
+                "max_completion_tokens": completion_request.max_tokens,
+            })
+        } else {
+            json!({
+                "model": self.model,
+                "messages": full_history,
+                "temperature": completion_request.temperature,
+                "max_completion_tokens": completion_request.max_tokens,
+                "tools": completion_request.tools.into_iter().map(openai::ToolDefinition::from).collect::<Vec<_>>(),
+                "tool_choice": "auto",
+            })
+        };
+
+        let request = if let Some(params) = completion_request.additional_params {
+            json_utils::merge(request, params)
+        } else {
+            request
+        };
+
+        Ok(request)
+    }
+}
+
+impl completion::CompletionModel for CompletionModel {
+    type Response = CompletionResponse;
+    type StreamingResponse = openai::StreamingCompletionResponse;
+
+    #[cfg_attr(feature = "worker", worker::send)]
+    async fn completion(
+        &self,
+        completion_request: CompletionRequest,
+    ) -> Result<completion::CompletionResponse<CompletionResponse>, CompletionError> {
+        let request = self.create_completion_request(completion_request)?;
+
+        let response = self
+            .client
+            .post("/chat/completions")
+            .json(&request)
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            match response.json::<ApiResponse<CompletionResponse>>().await? {
+                ApiResponse::Ok(response) => {
+                    tracing::info!(target: "rig",
+                        "Cerebras completion token usage: {:?}, time info: {:?}",
+                        response.response.usage.clone().map(|usage| format!("{usage}")).unwrap_or("N/A".to_string()),
+                        response.time_info
+                    );
+                    response.try_into()
+                }
+                ApiResponse::Err(err) => Err(CompletionError::ProviderError(err.message)),
+            }
+        } else {
+            Err(CompletionError::from_response(response).await)
+        }
+    }
+
+    #[cfg_attr(feature = "worker", worker::send)]
+    async fn stream(
+        &self,
+        request: CompletionRequest,
+    ) -> Result<StreamingCompletionResponse<Self::StreamingResponse>, CompletionError> {
+        let mut request = self.create_completion_request(request)?;
+
+        request = merge(
+            request,
+            json!({"stream": true, "stream_options": {"include_usage": true}}),
+        );
+
+        let builder = self.client.post("/chat/completions").json(&request);
+
+        send_compatible_streaming_request(builder).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_completion_response() {
+        let response: CompletionResponse = serde_json::from_str(
+            r#"{
+                "id": "chatcmpl-123",
+                "object": "chat.completion",
+                "created": 1733000000,
+                "model": "llama3.1-8b",
+                "system_fingerprint": "fp_123",
+                "choices": [{
+                    "index": 0,
+                    "message": {"role": "assistant", "content": "Paris."},
+                    "logprobs": null,
+                    "finish_reason": "stop"
+                }],
+                "usage": {"prompt_tokens": 10, "completion_tokens": 2, "total_tokens": 12},
+                "time_info": {
+                    "queue_time": 0.0001,
+                    "prompt_time": 0.002,
+                    "completion_time": 0.001,
+                    "total_time": 0.004,
+                    "created": 1733000000
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let response: completion::CompletionResponse<CompletionResponse> =
+            response.try_into().unwrap();
+        assert_eq!(
+            response.choice,
+            crate::OneOrMany::one(completion::AssistantContent::text("Paris."))
+        );
+
+        let time_info = response.raw_response.time_info.unwrap();
+        assert_eq!(time_info.queue_time, Some(0.0001));
+        assert_eq!(time_info.prompt_time, Some(0.002));
+        assert_eq!(time_info.completion_time, Some(0.001));
+    }
+}
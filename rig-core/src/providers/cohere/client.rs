@@ -1,6 +1,6 @@
 use crate::{embeddings::EmbeddingsBuilder, Embed};
 
-use super::{CompletionModel, EmbeddingModel};
+use super::{CompletionModel, EmbedInputType, EmbeddingModel};
 use crate::client::{impl_conversion_traits, CompletionClient, EmbeddingsClient, ProviderClient};
 use serde::Deserialize;
 
@@ -69,14 +69,21 @@ impl Client {
     pub fn embeddings<D: Embed>(
         &self,
         model: &str,
-        input_type: &str,
+        input_type: impl Into<Option<EmbedInputType>>,
     ) -> EmbeddingsBuilder<EmbeddingModel, D> {
         EmbeddingsBuilder::new(self.embedding_model(model, input_type))
     }
 
+    /// Create an embedding model embedding texts of the given input type. When `None`, the
+    /// input type is routed automatically (see [EmbeddingModel::input_type]).
+    ///
     /// Note: default embedding dimension of 0 will be used if model is not known.
     /// If this is the case, it's better to use function `embedding_model_with_ndims`
-    pub fn embedding_model(&self, model: &str, input_type: &str) -> EmbeddingModel {
+    pub fn embedding_model(
+        &self,
+        model: &str,
+        input_type: impl Into<Option<EmbedInputType>>,
+    ) -> EmbeddingModel {
         let ndims = match model {
             super::EMBED_ENGLISH_V3
             | super::EMBED_MULTILINGUAL_V3
@@ -93,7 +100,7 @@ impl Client {
     pub fn embedding_model_with_ndims(
         &self,
         model: &str,
-        input_type: &str,
+        input_type: impl Into<Option<EmbedInputType>>,
         ndims: usize,
     ) -> EmbeddingModel {
         EmbeddingModel::new(self.clone(), model, input_type, ndims)
@@ -121,15 +128,15 @@ impl EmbeddingsClient for Client {
     type EmbeddingModel = EmbeddingModel;
 
     fn embedding_model(&self, model: &str) -> Self::EmbeddingModel {
-        self.embedding_model(model, "search_document")
+        self.embedding_model(model, None)
     }
 
     fn embedding_model_with_ndims(&self, model: &str, ndims: usize) -> Self::EmbeddingModel {
-        self.embedding_model_with_ndims(model, "search_document", ndims)
+        self.embedding_model_with_ndims(model, None, ndims)
     }
 
     fn embeddings<D: Embed>(&self, model: &str) -> EmbeddingsBuilder<Self::EmbeddingModel, D> {
-        self.embeddings(model, "search_document")
+        self.embeddings(model, None)
     }
 }
 
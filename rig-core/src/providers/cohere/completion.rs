@@ -287,7 +287,7 @@ impl TryFrom<message::Message> for Vec<Message> {
                     message::UserContent::Text(message::Text { text }) => Ok(Message::User {
                         content: OneOrMany::one(UserContent::Text { text }),
                     }),
-                    message::UserContent::ToolResult(message::ToolResult { id, content }) => {
+                    message::UserContent::ToolResult(message::ToolResult { id, content, .. }) => {
                         Ok(Message::Tool {
                             tool_call_id: id,
                             content: content.try_map(|content| match content {
@@ -503,7 +503,7 @@ impl completion::CompletionModel for CompletionModel {
                 json_response.try_into()?;
             Ok(completion)
         } else {
-            Err(CompletionError::ProviderError(response.text().await?))
+            Err(CompletionError::from_response(response).await)
         }
     }
 
@@ -2,20 +2,41 @@ use super::{client::ApiResponse, Client};
 
 use crate::embeddings::{self, EmbeddingError};
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 
+/// The type of the input of Embed v3 models, to produce embeddings optimized for its use
+/// (e.g.: asymmetric retrieval with `SearchDocument` and `SearchQuery` embeddings).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmbedInputType {
+    /// Documents stored in a vector store
+    SearchDocument,
+    /// Queries searching a vector store
+    SearchQuery,
+    /// Texts passed to a text classifier
+    Classification,
+    /// Texts to be clustered
+    Clustering,
+}
+
 #[derive(Deserialize)]
 pub struct EmbeddingResponse {
     #[serde(default)]
     pub response_type: Option<String>,
     pub id: String,
-    pub embeddings: Vec<Vec<f64>>,
+    pub embeddings: EmbeddingsByType,
     pub texts: Vec<String>,
     #[serde(default)]
     pub meta: Option<Meta>,
 }
 
+/// Embeddings of the `/v2/embed` endpoint, by embedding type (only `float` is requested).
+#[derive(Deserialize)]
+pub struct EmbeddingsByType {
+    pub float: Vec<Vec<f64>>,
+}
+
 #[derive(Deserialize)]
 pub struct Meta {
     pub api_version: ApiVersion,
@@ -59,7 +80,12 @@ impl std::fmt::Display for BilledUnits {
 pub struct EmbeddingModel {
     client: Client,
     pub model: String,
-    pub input_type: String,
+    /// Input type of the embedded texts. When not set, it is routed automatically:
+    /// [EmbedInputType::SearchDocument] when embedding documents (e.g.: with an
+    /// [EmbeddingsBuilder](crate::embeddings::EmbeddingsBuilder)) and
+    /// [EmbedInputType::SearchQuery] when embedding a single text (e.g.: the query of a
+    /// vector store index).
+    pub input_type: Option<EmbedInputType>,
     ndims: usize,
 }
 
@@ -75,15 +101,53 @@ impl embeddings::EmbeddingModel for EmbeddingModel {
         &self,
         documents: impl IntoIterator<Item = String>,
     ) -> Result<Vec<embeddings::Embedding>, EmbeddingError> {
-        let documents = documents.into_iter().collect::<Vec<_>>();
+        self.embed(
+            documents.into_iter().collect(),
+            self.input_type.unwrap_or(EmbedInputType::SearchDocument),
+        )
+        .await
+    }
 
+    #[cfg_attr(feature = "worker", worker::send)]
+    async fn embed_text(&self, text: &str) -> Result<embeddings::Embedding, EmbeddingError> {
+        self.embed(
+            vec![text.to_string()],
+            self.input_type.unwrap_or(EmbedInputType::SearchQuery),
+        )
+        .await?
+        .pop()
+        .ok_or_else(|| EmbeddingError::ResponseError("Response contained no embedding".into()))
+    }
+}
+
+impl EmbeddingModel {
+    pub fn new(
+        client: Client,
+        model: &str,
+        input_type: impl Into<Option<EmbedInputType>>,
+        ndims: usize,
+    ) -> Self {
+        Self {
+            client,
+            model: model.to_string(),
+            input_type: input_type.into(),
+            ndims,
+        }
+    }
+
+    async fn embed(
+        &self,
+        documents: Vec<String>,
+        input_type: EmbedInputType,
+    ) -> Result<Vec<embeddings::Embedding>, EmbeddingError> {
         let response = self
             .client
-            .post("/v1/embed")
+            .post("/v2/embed")
             .json(&json!({
                 "model": self.model,
                 "texts": documents,
-                "input_type": self.input_type,
+                "input_type": input_type,
+                "embedding_types": ["float"],
             }))
             .send()
             .await?;
@@ -101,19 +165,19 @@ impl embeddings::EmbeddingModel for EmbeddingModel {
                         ),
                     };
 
-                    if response.embeddings.len() != documents.len() {
+                    let vectors = response.embeddings.float;
+                    if vectors.len() != documents.len() {
                         return Err(EmbeddingError::DocumentError(
                             format!(
                                 "Expected {} embeddings, got {}",
                                 documents.len(),
-                                response.embeddings.len()
+                                vectors.len()
                             )
                             .into(),
                         ));
                     }
 
-                    Ok(response
-                        .embeddings
+                    Ok(vectors
                         .into_iter()
                         .zip(documents.into_iter())
                         .map(|(embedding, document)| embeddings::Embedding {
@@ -130,13 +194,31 @@ impl embeddings::EmbeddingModel for EmbeddingModel {
     }
 }
 
-impl EmbeddingModel {
-    pub fn new(client: Client, model: &str, input_type: &str, ndims: usize) -> Self {
-        Self {
-            client,
-            model: model.to_string(),
-            input_type: input_type.to_string(),
-            ndims,
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_v2_response() {
+        let response: EmbeddingResponse = serde_json::from_str(
+            r#"{
+                "id": "da6e531f-54c6-4a73-bf92-f60566d8d753",
+                "embeddings": {"float": [[0.016296387, -0.008354187, -0.04699707]]},
+                "texts": ["hello world"],
+                "meta": {
+                    "api_version": {"version": "2"},
+                    "billed_units": {"input_tokens": 2}
+                },
+                "response_type": "embeddings_by_type"
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(response.embeddings.float.len(), 1);
+        assert_eq!(response.meta.unwrap().billed_units.input_tokens, 2);
+        assert_eq!(
+            serde_json::to_value(EmbedInputType::SearchQuery).unwrap(),
+            "search_query"
+        );
     }
 }
@@ -17,7 +17,7 @@ pub mod streaming;
 pub use client::Client;
 pub use client::{ApiErrorResponse, ApiResponse};
 pub use completion::CompletionModel;
-pub use embeddings::EmbeddingModel;
+pub use embeddings::{EmbedInputType, EmbeddingModel};
 
 // ================================================================
 // Cohere Completion Models
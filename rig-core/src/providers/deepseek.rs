@@ -72,6 +72,11 @@ impl Client {
         self
     }
 
+    /// Create a model for the FIM completion endpoint with the given name (e.g.: [DEEPSEEK_CODER]).
+    pub fn fim_model(&self, model_name: &str) -> FimModel {
+        FimModel::new(self.clone(), model_name)
+    }
+
     fn post(&self, path: &str) -> reqwest::RequestBuilder {
         let url = format!("{}/{}", self.base_url, path).replace("//", "/");
         self.http_client.post(url).bearer_auth(&self.api_key)
@@ -548,7 +553,7 @@ impl CompletionModel for DeepSeekCompletionModel {
                 ApiResponse::Err(err) => Err(CompletionError::ProviderError(err.message)),
             }
         } else {
-            Err(CompletionError::ProviderError(response.text().await?))
+            Err(CompletionError::from_response(response).await)
         }
     }
 
@@ -596,6 +601,105 @@ impl CompletionModel for DeepSeekCompletionModel {
 pub const DEEPSEEK_CHAT: &str = "deepseek-chat";
 /// `deepseek-reasoner` completion model
 pub const DEEPSEEK_REASONER: &str = "deepseek-reasoner";
+/// `deepseek-coder` model, for FIM completions
+pub const DEEPSEEK_CODER: &str = "deepseek-coder";
+
+// ================================================================
+// DeepSeek FIM (Fill-In-Middle) API
+// ================================================================
+
+/// Trait for models generating the code between a prefix and a suffix (e.g.: to complete code
+/// at the cursor of an editor).
+pub trait FimCompletion: Clone + Send + Sync {
+    /// Generate the text between `prefix` and `suffix`, of at most `max_tokens` tokens.
+    fn fill(
+        &self,
+        prefix: &str,
+        suffix: &str,
+        max_tokens: usize,
+    ) -> impl std::future::Future<Output = Result<String, CompletionError>> + Send;
+}
+
+#[derive(Debug, Deserialize)]
+struct FimResponse {
+    choices: Vec<FimChoice>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FimChoice {
+    text: String,
+}
+
+/// A DeepSeek model used with the FIM completion endpoint (in beta).
+#[derive(Clone)]
+pub struct FimModel {
+    pub client: Client,
+    pub model: String,
+}
+
+impl FimModel {
+    pub fn new(client: Client, model: &str) -> Self {
+        Self {
+            client,
+            model: model.to_string(),
+        }
+    }
+
+    fn create_fim_request(
+        &self,
+        prefix: &str,
+        suffix: &str,
+        max_tokens: usize,
+    ) -> serde_json::Value {
+        json!({
+            "model": self.model,
+            "prompt": prefix,
+            "suffix": suffix,
+            "max_tokens": max_tokens,
+            "echo": false,
+        })
+    }
+}
+
+impl FimCompletion for FimModel {
+    #[cfg_attr(feature = "worker", worker::send)]
+    async fn fill(
+        &self,
+        prefix: &str,
+        suffix: &str,
+        max_tokens: usize,
+    ) -> Result<String, CompletionError> {
+        let request = self.create_fim_request(prefix, suffix, max_tokens);
+
+        tracing::debug!("DeepSeek FIM request: {request:?}");
+
+        let response = self
+            .client
+            .post("/v1/beta/completions")
+            .json(&request)
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            let t = response.text().await?;
+            tracing::debug!(target: "rig", "DeepSeek FIM completion: {}", t);
+
+            match serde_json::from_str::<ApiResponse<FimResponse>>(&t)? {
+                ApiResponse::Ok(response) => response
+                    .choices
+                    .into_iter()
+                    .next()
+                    .map(|choice| choice.text)
+                    .ok_or_else(|| {
+                        CompletionError::ResponseError("FIM response contains no choice".to_owned())
+                    }),
+                ApiResponse::Err(err) => Err(CompletionError::ProviderError(err.message)),
+            }
+        } else {
+            Err(CompletionError::from_response(response).await)
+        }
+    }
+}
 
 // Tests
 #[cfg(test)]
@@ -997,6 +1101,8 @@ mod tests {
                 "include_reason_in_content": false,
                 "include_reason_in_content_tag": "analysis"
             })),
+            tool_choice: None,
+            system_messages: vec![],
         };
 
         let json_request = model.create_completion_request(request).unwrap();
@@ -1005,4 +1111,34 @@ mod tests {
         assert_eq!(json_request["include_reason_in_content"], false);
         assert_eq!(json_request["include_reason_in_content_tag"], "analysis");
     }
+
+    #[test]
+    fn test_fim_request() {
+        let model = Client::new("key").fim_model(DEEPSEEK_CODER);
+
+        assert_eq!(
+            model.create_fim_request("def fib(a):", "    return fib(a-1) + fib(a-2)", 128),
+            json!({
+                "model": "deepseek-coder",
+                "prompt": "def fib(a):",
+                "suffix": "    return fib(a-1) + fib(a-2)",
+                "max_tokens": 128,
+                "echo": false,
+            })
+        );
+
+        let response: FimResponse = serde_json::from_str(
+            r#"{
+                "id": "5f1f6d3d-1b0e-4f5e-9c8e-0d0b6a0f9a3e",
+                "object": "text_completion",
+                "model": "deepseek-coder",
+                "choices": [{"text": "\n    if a <= 1:\n        return a\n", "index": 0, "finish_reason": "stop", "logprobs": null}]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            response.choices[0].text,
+            "\n    if a <= 1:\n        return a\n"
+        );
+    }
 }
@@ -100,7 +100,7 @@ impl completion::CompletionModel for CompletionModel {
 
             Ok(completion::CompletionResponse::try_from(response))
         } else {
-            Err(CompletionError::ProviderError(response.text().await?))
+            Err(CompletionError::from_response(response).await)
         }?
     }
 
@@ -468,7 +468,11 @@ pub mod gemini_api_types {
         fn try_from(content: message::UserContent) -> Result<Self, Self::Error> {
             match content {
                 message::UserContent::Text(message::Text { text }) => Ok(Self::Text(text)),
-                message::UserContent::ToolResult(message::ToolResult { id, content }) => {
+                message::UserContent::ToolResult(message::ToolResult {
+                    id,
+                    content,
+                    is_error,
+                }) => {
                     let content = match content.first() {
                         message::ToolResultContent::Text(text) => text.text,
                         message::ToolResultContent::Image(_) => {
@@ -477,6 +481,13 @@ pub mod gemini_api_types {
                             ))
                         }
                     };
+                    // Errors are sent under the `error` key, as documented by Gemini
+                    if is_error {
+                        return Ok(Part::FunctionResponse(FunctionResponse {
+                            name: id,
+                            response: Some(json!({ "error": content })),
+                        }));
+                    }
                     // Convert to JSON since this value may be a valid JSON value
                     let result: serde_json::Value = serde_json::from_str(&content)
                         .map_err(|x| MessageError::ConversionError(x.to_string()))?;
@@ -1201,4 +1212,20 @@ mod tests {
             panic!("Expected function call part");
         }
     }
+
+    #[test]
+    fn test_message_conversion_tool_result_error() {
+        let result = message::ToolResult::error(
+            "get_weather",
+            OneOrMany::one(message::ToolResultContent::text("Unknown city")),
+        );
+
+        let part: Part = message::UserContent::ToolResult(result).try_into().unwrap();
+        if let Part::FunctionResponse(response) = part {
+            assert_eq!(response.name, "get_weather");
+            assert_eq!(response.response, Some(json!({ "error": "Unknown city" })));
+        } else {
+            panic!("Expected function response part");
+        }
+    }
 }
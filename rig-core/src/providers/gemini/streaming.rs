@@ -2,7 +2,11 @@ use async_stream::stream;
 use futures::StreamExt;
 use serde::Deserialize;
 
-use super::completion::{create_request_body, gemini_api_types::ContentCandidate, CompletionModel};
+use super::completion::{
+    create_request_body,
+    gemini_api_types::{ContentCandidate, Part},
+    CompletionModel,
+};
 use crate::{
     completion::{CompletionError, CompletionRequest},
     streaming::{self},
@@ -47,66 +51,135 @@ impl CompletionModel {
             .await?;
 
         if !response.status().is_success() {
-            return Err(CompletionError::ProviderError(format!(
-                "{}: {}",
-                response.status(),
-                response.text().await?
-            )));
+            return Err(CompletionError::from_response(response).await);
         }
 
-        let stream = Box::pin(stream! {
-            let mut stream = response.bytes_stream();
+        Ok(stream_response(response))
+    }
+}
 
-            while let Some(chunk_result) = stream.next().await {
-                let chunk = match chunk_result {
-                    Ok(c) => c,
-                    Err(e) => {
-                        yield Err(CompletionError::from(e));
-                        break;
-                    }
-                };
+/// Parse the server-sent events of a `streamGenerateContent` response (shared with the
+/// Vertex AI provider, which uses the same format).
+pub(crate) fn stream_response(
+    response: reqwest::Response,
+) -> streaming::StreamingCompletionResponse<StreamingCompletionResponse> {
+    let stream = Box::pin(stream! {
+        let mut stream = response.bytes_stream();
+
+        while let Some(chunk_result) = stream.next().await {
+            let chunk = match chunk_result {
+                Ok(c) => c,
+                Err(e) => {
+                    yield Err(CompletionError::from(e));
+                    break;
+                }
+            };
 
-                let text = match String::from_utf8(chunk.to_vec()) {
-                    Ok(t) => t,
-                    Err(e) => {
-                        yield Err(CompletionError::ResponseError(e.to_string()));
-                        break;
-                    }
-                };
+            let text = match String::from_utf8(chunk.to_vec()) {
+                Ok(t) => t,
+                Err(e) => {
+                    yield Err(CompletionError::ResponseError(e.to_string()));
+                    break;
+                }
+            };
+
+
+            for line in text.lines() {
+                let Some(line) = line.strip_prefix("data: ") else { continue; };
 
+                let Ok(data) = serde_json::from_str::<StreamGenerateContentResponse>(line) else {
+                    continue;
+                };
 
-                for line in text.lines() {
-                    let Some(line) = line.strip_prefix("data: ") else { continue; };
-
-                    let Ok(data) = serde_json::from_str::<StreamGenerateContentResponse>(line) else {
-                        continue;
-                    };
-
-                    let choice = data.candidates.first().expect("Should have at least one choice");
-
-                    match choice.content.parts.first() {
-                        super::completion::gemini_api_types::Part::Text(text)
-                            => yield Ok(streaming::RawStreamingChoice::Message(text)),
-                        super::completion::gemini_api_types::Part::FunctionCall(function_call)
-                            => yield Ok(streaming::RawStreamingChoice::ToolCall {
-                                    name: function_call.name,
-                                    id: "".to_string(),
-                                    arguments: function_call.args
-                                }),
-                        _ => panic!("Unsupported response type with streaming.")
-                    };
-
-                    if choice.finish_reason.is_some() {
-                        yield Ok(streaming::RawStreamingChoice::FinalResponse(StreamingCompletionResponse {
-                            usage_metadata: PartialUsage {
-                                total_token_count: data.usage_metadata.unwrap().total_token_count,
-                            }
-                        }))
-                    }
+                for choice in parse_event(data) {
+                    yield choice;
                 }
             }
+        }
+    });
+
+    streaming::StreamingCompletionResponse::stream(stream)
+}
+
+/// Convert an event of a `streamGenerateContent` response to the streamed choices.
+fn parse_event(
+    data: StreamGenerateContentResponse,
+) -> Vec<Result<streaming::RawStreamingChoice<StreamingCompletionResponse>, CompletionError>> {
+    let Some(choice) = data.candidates.first() else {
+        return vec![Err(CompletionError::ResponseError(
+            "Streaming response contains no candidates".to_string(),
+        ))];
+    };
+
+    let mut choices = vec![match choice.content.parts.first() {
+        Part::Text(text) => Ok(streaming::RawStreamingChoice::Message(text)),
+        Part::FunctionCall(function_call) => Ok(streaming::RawStreamingChoice::ToolCall {
+            name: function_call.name,
+            id: "".to_string(),
+            arguments: function_call.args,
+        }),
+        _ => Err(CompletionError::ResponseError(
+            "Unsupported response type with streaming".to_string(),
+        )),
+    }];
+
+    if choice.finish_reason.is_some() {
+        choices.push(Ok(streaming::RawStreamingChoice::FinalResponse(
+            StreamingCompletionResponse {
+                usage_metadata: data.usage_metadata.unwrap_or_default(),
+            },
+        )));
+    }
+
+    choices
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(event: serde_json::Value) -> Vec<Result<String, String>> {
+        parse_event(serde_json::from_value(event).unwrap())
+            .into_iter()
+            .map(|choice| match choice {
+                Ok(streaming::RawStreamingChoice::Message(text)) => Ok(text),
+                Ok(streaming::RawStreamingChoice::FinalResponse(response)) => Ok(format!(
+                    "usage: {}",
+                    response.usage_metadata.total_token_count
+                )),
+                Ok(_) => Ok("other".to_string()),
+                Err(err) => Err(err.to_string()),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_event() {
+        let event = serde_json::json!({
+            "candidates": [{
+                "content": {"parts": [{"text": "Hello"}], "role": "model"},
+                "finishReason": "STOP"
+            }]
         });
+        // The usage is missing from the last event
+        assert_eq!(
+            parse(event),
+            vec![Ok("Hello".to_string()), Ok("usage: 0".to_string())]
+        );
+    }
 
-        Ok(streaming::StreamingCompletionResponse::stream(stream))
+    #[test]
+    fn test_parse_event_errors() {
+        assert!(parse(serde_json::json!({"candidates": []}))[0].is_err());
+
+        let event = serde_json::json!({
+            "candidates": [{
+                "content": {
+                    "parts": [{"inlineData": {"mimeType": "image/png", "data": "iVBORw0KGgo="}}],
+                    "role": "model"
+                }
+            }]
+        });
+        assert!(parse(event)[0].is_err());
     }
 }
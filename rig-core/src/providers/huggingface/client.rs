@@ -206,10 +206,13 @@ impl Client {
 }
 
 impl ProviderClient for Client {
-    /// Create a new Huggingface client from the `HUGGINGFACE_API_KEY` environment variable.
-    /// Panics if the environment variable is not set.
+    /// Create a new Huggingface client from the `HUGGINGFACE_API_KEY` (or `HF_API_KEY`)
+    /// environment variable.
+    /// Panics if neither environment variable is set.
     fn from_env() -> Self {
-        let api_key = std::env::var("HUGGINGFACE_API_KEY").expect("HUGGINGFACE_API_KEY is not set");
+        let api_key = std::env::var("HUGGINGFACE_API_KEY")
+            .or_else(|_| std::env::var("HF_API_KEY"))
+            .expect("HUGGINGFACE_API_KEY or HF_API_KEY is not set");
         Self::new(&api_key)
     }
 }
@@ -272,6 +272,7 @@ impl TryFrom<message::Message> for Vec<Message> {
                             message::UserContent::ToolResult(message::ToolResult {
                                 id,
                                 content,
+                                ..
                             }) => Ok::<_, message::MessageError>(Message::ToolResult {
                                 name: id,
                                 arguments: None,
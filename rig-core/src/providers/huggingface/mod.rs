@@ -9,6 +9,17 @@
 //!
 //! let completion_model = client.completion_model(completion::GEMMA_2);
 //! ```
+//!
+//! Self-hosted Text Generation Inference (TGI) servers are supported by pointing the client
+//! to the server, since they expose the same chat completion endpoint:
+//! ```
+//! use rig::providers::huggingface::{ClientBuilder, SubProvider};
+//!
+//! let client = ClientBuilder::new("unused")
+//!     .base_url("http://localhost:8080")
+//!     .sub_provider(SubProvider::Custom("".to_string()))
+//!     .build();
+//! ```
 
 pub mod client;
 pub mod completion;
@@ -1,56 +1,75 @@
 use super::completion::CompletionModel;
 use crate::completion::{CompletionError, CompletionRequest};
 use crate::json_utils::merge_inplace;
-use crate::providers::openai::{send_compatible_streaming_request, StreamingCompletionResponse};
-use crate::{json_utils, streaming};
-use serde::{Deserialize, Serialize};
-use serde_json::{json, Value};
-use std::convert::Infallible;
-use std::str::FromStr;
-
-#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
-#[serde(rename_all = "lowercase", tag = "type")]
-/// Represents the content sent back in the StreamDelta for an Assistant
-enum AssistantContent {
-    Text { text: String },
-}
+use crate::providers::openai::{StreamingCompletionResponse, Usage};
+use crate::streaming::{self, RawStreamingChoice};
+use async_stream::stream;
+use futures::StreamExt;
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::BTreeMap;
+use tracing::debug;
 
-// Ensure that string contents can be serialized correctly
-impl FromStr for AssistantContent {
-    type Err = Infallible;
+// ================================================================
+// Huggingface Streaming API
+// ================================================================
+// The chat completion endpoints of the Inference API stream OpenAI compatible chunks,
+// while Text Generation Inference (TGI) servers stream one generated token per event.
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(AssistantContent::Text {
-            text: s.to_string(),
-        })
-    }
+#[derive(Debug, Deserialize)]
+struct StreamingFunction {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    arguments: String,
 }
 
-#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
-#[serde(rename_all = "lowercase", tag = "role")]
-enum StreamDelta {
-    Assistant {
-        #[serde(deserialize_with = "json_utils::string_or_vec")]
-        content: Vec<AssistantContent>,
-    },
+#[derive(Debug, Deserialize)]
+struct StreamingToolCall {
+    index: usize,
+    id: Option<String>,
+    function: StreamingFunction,
 }
 
-#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
+#[derive(Debug, Deserialize)]
+struct StreamingDelta {
+    #[serde(default)]
+    content: Option<String>,
+    #[serde(default, deserialize_with = "crate::json_utils::null_or_vec")]
+    tool_calls: Vec<StreamingToolCall>,
+}
+
+#[derive(Debug, Deserialize)]
 struct StreamingChoice {
-    index: usize,
-    delta: StreamDelta,
-    logprobs: Value,
-    finish_reason: Option<String>,
+    delta: StreamingDelta,
 }
 
-#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
-struct CompletionChunk {
-    id: String,
-    created: i32,
-    model: String,
+#[derive(Debug, Deserialize)]
+struct TgiToken {
+    text: String,
     #[serde(default)]
-    system_fingerprint: String,
-    choices: Vec<StreamingChoice>,
+    special: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct TgiDetails {
+    generated_tokens: usize,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum StreamingChunk {
+    /// OpenAI compatible chunk, e.g.: `{"choices": [{"delta": {"content": "..."}}]}`
+    Chat {
+        choices: Vec<StreamingChoice>,
+        usage: Option<Usage>,
+    },
+    /// TGI token chunk, e.g.: `{"token": {"text": "..."}}`
+    Tgi {
+        token: TgiToken,
+        #[serde(default)]
+        details: Option<TgiDetails>,
+    },
 }
 
 impl CompletionModel {
@@ -74,8 +93,150 @@ impl CompletionModel {
         // HF Inference API uses the model in the path even though its specified in the request body
         let path = self.client.sub_provider.completion_endpoint(&self.model);
 
-        let builder = self.client.post(&path).json(&request);
+        let response = self.client.post(&path).json(&request).send().await?;
+
+        if !response.status().is_success() {
+            return Err(CompletionError::from_response(response).await);
+        }
+
+        let inner = Box::pin(stream! {
+            let mut stream = response.bytes_stream();
+            let mut buffer = Vec::new();
+            let mut usage = Usage {
+                prompt_tokens: 0,
+                total_tokens: 0,
+            };
+            // Tool calls are streamed in parts, indexed by their position in the message
+            let mut calls: BTreeMap<usize, (String, String, String)> = BTreeMap::new();
+
+            while let Some(chunk) = stream.next().await {
+                let chunk = match chunk {
+                    Ok(chunk) => chunk,
+                    Err(e) => {
+                        yield Err(CompletionError::from(e));
+                        break;
+                    }
+                };
+                buffer.extend_from_slice(&chunk);
+
+                // Only process complete lines, keeping the rest for the next chunk
+                while let Some(line) = next_line(&mut buffer) {
+                    let Some(data) = line.strip_prefix("data:") else {
+                        continue;
+                    };
+
+                    match serde_json::from_str::<StreamingChunk>(data.trim()) {
+                        Ok(StreamingChunk::Chat { choices, usage: chunk_usage }) => {
+                            for choice in choices {
+                                if let Some(content) = choice.delta.content.filter(|c| !c.is_empty()) {
+                                    yield Ok(RawStreamingChoice::Message(content));
+                                }
+
+                                for tool_call in choice.delta.tool_calls {
+                                    let call = calls.entry(tool_call.index).or_default();
+                                    if let Some(id) = tool_call.id {
+                                        call.0 = id;
+                                    }
+                                    if let Some(name) = tool_call.function.name {
+                                        call.1.push_str(&name);
+                                    }
+                                    call.2.push_str(&tool_call.function.arguments);
+                                }
+                            }
+
+                            if let Some(chunk_usage) = chunk_usage {
+                                usage = chunk_usage;
+                            }
+                        }
+                        Ok(StreamingChunk::Tgi { token, details }) => {
+                            if !token.special {
+                                yield Ok(RawStreamingChoice::Message(token.text));
+                            }
+
+                            if let Some(details) = details {
+                                usage.total_tokens = details.generated_tokens;
+                            }
+                        }
+                        Err(e) => {
+                            debug!("Couldn't deserialize streaming chunk: {:?}", e);
+                        }
+                    }
+                }
+            }
+
+            for (_, (id, name, arguments)) in calls {
+                let arguments = if arguments.is_empty() { "{}".to_string() } else { arguments };
+                let Ok(arguments) = serde_json::from_str(&arguments) else {
+                    debug!("Couldn't deserialize tool call arguments: {}", arguments);
+                    continue;
+                };
+
+                yield Ok(RawStreamingChoice::ToolCall { id, name, arguments });
+            }
+
+            yield Ok(RawStreamingChoice::FinalResponse(StreamingCompletionResponse { usage }));
+        });
+
+        Ok(streaming::StreamingCompletionResponse::stream(inner))
+    }
+}
+
+/// Remove the first complete line from `buffer` and return it, trimmed.
+/// Lines are only decoded once complete, since a chunk may end in the middle of a multi-byte
+/// character.
+fn next_line(buffer: &mut Vec<u8>) -> Option<String> {
+    let end = buffer.iter().position(|&byte| byte == b'\n')?;
+    let line = buffer.drain(..=end).collect::<Vec<_>>();
+    Some(String::from_utf8_lossy(&line).trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_line() {
+        // "é" is encoded on two bytes, split between the two chunks
+        let line = "data: {\"text\": \"café\"}\n".as_bytes();
+        let (first, second) = line.split_at(line.len() - 4);
+
+        let mut buffer = first.to_vec();
+        assert_eq!(next_line(&mut buffer), None);
+
+        buffer.extend_from_slice(second);
+        buffer.extend_from_slice(b"data: [DONE]");
+        assert_eq!(
+            next_line(&mut buffer).as_deref(),
+            Some("data: {\"text\": \"café\"}")
+        );
+        assert_eq!(next_line(&mut buffer), None);
+        assert_eq!(buffer, b"data: [DONE]");
+    }
+
+    #[test]
+    fn test_deserialize_tgi_chunk() {
+        let chunk: StreamingChunk = serde_json::from_str(
+            r#"{"index": 3, "token": {"id": 1234, "text": " world", "logprob": -0.2, "special": false}, "generated_text": null, "details": null}"#,
+        )
+        .unwrap();
+
+        let StreamingChunk::Tgi { token, details } = chunk else {
+            panic!("Expected a TGI chunk");
+        };
+        assert_eq!(token.text, " world");
+        assert!(details.is_none());
+    }
+
+    #[test]
+    fn test_deserialize_chat_chunk() {
+        let chunk: StreamingChunk = serde_json::from_str(
+            r#"{"id": "", "object": "chat.completion.chunk", "created": 1, "model": "google/gemma-2-2b-it", "choices": [{"index": 0, "delta": {"role": "assistant", "content": "Hello"}, "logprobs": null, "finish_reason": null}], "usage": null}"#,
+        )
+        .unwrap();
 
-        send_compatible_streaming_request(builder).await
+        let StreamingChunk::Chat { choices, .. } = chunk else {
+            panic!("Expected a chat chunk");
+        };
+        assert_eq!(choices[0].delta.content.as_deref(), Some("Hello"));
     }
 }
@@ -0,0 +1,309 @@
+//! MiniMax API client and Rig integration
+//!
+//! # Example
+//! ```
+//! use rig::providers::minimax;
+//!
+//! let client = minimax::Client::new("YOUR_API_KEY", "YOUR_GROUP_ID");
+//!
+//! let abab = client.completion_model(minimax::ABAB_6_5S_CHAT);
+//! ```
+
+use crate::client::{CompletionClient, ProviderClient};
+use crate::json_utils::merge;
+use crate::providers::openai::send_compatible_streaming_request;
+use crate::streaming::StreamingCompletionResponse;
+use crate::{
+    completion::{self, CompletionError, CompletionRequest},
+    json_utils,
+    providers::openai,
+};
+use crate::{impl_conversion_traits, message};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+// ================================================================
+// Main MiniMax Client
+// ================================================================
+const MINIMAX_API_BASE_URL: &str = "https://api.minimax.chat/v1";
+
+#[derive(Clone)]
+pub struct Client {
+    base_url: String,
+    api_key: String,
+    /// Id of the group (i.e.: account) of the API key, sent along with each request
+    group_id: String,
+    http_client: reqwest::Client,
+}
+
+impl std::fmt::Debug for Client {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Client")
+            .field("base_url", &self.base_url)
+            .field("group_id", &self.group_id)
+            .field("http_client", &self.http_client)
+            .field("api_key", &"<REDACTED>")
+            .finish()
+    }
+}
+
+impl Client {
+    /// Create a new MiniMax client with the given API key and group id.
+    pub fn new(api_key: &str, group_id: &str) -> Self {
+        Self::from_url(api_key, group_id, MINIMAX_API_BASE_URL)
+    }
+
+    /// Create a new MiniMax client with the given API key, group id and base API URL.
+    pub fn from_url(api_key: &str, group_id: &str, base_url: &str) -> Self {
+        Self {
+            base_url: base_url.to_string(),
+            api_key: api_key.to_string(),
+            group_id: group_id.to_string(),
+            http_client: reqwest::Client::builder()
+                .build()
+                .expect("MiniMax reqwest client should build"),
+        }
+    }
+
+    /// Use your own `reqwest::Client`.
+    /// The required headers will be automatically attached upon trying to make a request.
+    pub fn with_custom_client(mut self, client: reqwest::Client) -> Self {
+        self.http_client = client;
+
+        self
+    }
+
+    fn post(&self, path: &str) -> reqwest::RequestBuilder {
+        let url = format!("{}/{}", self.base_url, path).replace("//", "/");
+        self.http_client
+            .post(url)
+            .query(&[("GroupId", &self.group_id)])
+            .bearer_auth(&self.api_key)
+    }
+}
+
+impl ProviderClient for Client {
+    /// Create a new MiniMax client from the `MINIMAX_API_KEY` and `MINIMAX_GROUP_ID`
+    /// environment variables.
+    /// Panics if the environment variables are not set.
+    fn from_env() -> Self {
+        let api_key = std::env::var("MINIMAX_API_KEY").expect("MINIMAX_API_KEY not set");
+        let group_id = std::env::var("MINIMAX_GROUP_ID").expect("MINIMAX_GROUP_ID not set");
+        Self::new(&api_key, &group_id)
+    }
+}
+
+impl CompletionClient for Client {
+    type CompletionModel = CompletionModel;
+
+    /// Create a completion model with the given name.
+    ///
+    /// # Example
+    /// ```
+    /// use rig::providers::minimax::{Client, self};
+    ///
+    /// // Initialize the MiniMax client
+    /// let minimax = Client::new("your-minimax-api-key", "your-minimax-group-id");
+    ///
+    /// let completion_model = minimax.completion_model(minimax::ABAB_6_5S_CHAT);
+    /// ```
+    fn completion_model(&self, model: &str) -> CompletionModel {
+        CompletionModel::new(self.clone(), model)
+    }
+}
+
+impl_conversion_traits!(
+    AsEmbeddings,
+    AsTranscription,
+    AsImageGeneration,
+    AsAudioGeneration for Client
+);
+
+/// Status of a MiniMax response. MiniMax reports most errors with a 200 HTTP status and a
+/// non-zero `status_code`.
+#[derive(Debug, Deserialize)]
+struct BaseResp {
+    status_code: i64,
+    #[serde(default)]
+    status_msg: String,
+}
+
+/// Returns the error reported by the `base_resp` of a MiniMax response, if any.
+fn check_base_resp(response: &Value) -> Result<(), CompletionError> {
+    let Some(base_resp) = response.get("base_resp") else {
+        return Ok(());
+    };
+
+    match BaseResp::deserialize(base_resp)? {
+        BaseResp { status_code: 0, .. } => Ok(()),
+        BaseResp {
+            status_code,
+            status_msg,
+        } => Err(CompletionError::ProviderError(format!(
+            "MiniMax error {status_code}: {status_msg}"
+        ))),
+    }
+}
+
+// ================================================================
+// MiniMax Completion API
+// ================================================================
+/// `abab6.5s-chat` completion model
+pub const ABAB_6_5S_CHAT: &str = "abab6.5s-chat";
+/// `abab7-chat-preview` completion model
+pub const ABAB_7_PREVIEW: &str = "abab7-chat-preview";
+
+#[derive(Clone)]
+pub struct CompletionModel {
+    client: Client,
+    pub model: String,
+}
+
+impl CompletionModel {
+    pub fn new(client: Client, model: &str) -> Self {
+        Self {
+            client,
+            model: model.to_string(),
+        }
+    }
+
+    fn create_completion_request(
+        &self,
+        completion_request: CompletionRequest,
+    ) -> Result<Value, CompletionError> {
+        // Build up the order of messages (context, chat_history)
+        let mut partial_history = vec![];
+        if let Some(docs) = completion_request.normalized_documents() {
+            partial_history.push(docs);
+        }
+        partial_history.extend(completion_request.chat_history);
+
+        // Initialize full history with preamble (or empty if non-existent)
+        let mut full_history: Vec<openai::Message> = completion_request
+            .preamble
+            .map_or_else(Vec::new, |preamble| {
+                vec![openai::Message::system(&preamble)]
+            });
+
+        // Convert and extend the rest of the history
+        full_history.extend(
+            partial_history
+                .into_iter()
+                .map(message::Message::try_into)
+                .collect::<Result<Vec<Vec<openai::Message>>, _>>()?
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>(),
+        );
+
+        let mut request = json!({
+            "model": self.model,
+            "messages": full_history,
+            "temperature": completion_request.temperature,
+        });
+
+        if let Some(max_tokens) = completion_request.max_tokens {
+            request["max_tokens"] = json!(max_tokens);
+        }
+
+        if !completion_request.tools.is_empty() {
+            request = merge(
+                request,
+                json!({
+                    "tools": completion_request
+                        .tools
+                        .into_iter()
+                        .map(openai::ToolDefinition::from)
+                        .collect::<Vec<_>>(),
+                    "tool_choice": "auto",
+                }),
+            );
+        }
+
+        let request = if let Some(params) = completion_request.additional_params {
+            json_utils::merge(request, params)
+        } else {
+            request
+        };
+
+        Ok(request)
+    }
+}
+
+impl completion::CompletionModel for CompletionModel {
+    type Response = openai::CompletionResponse;
+    type StreamingResponse = openai::StreamingCompletionResponse;
+
+    #[cfg_attr(feature = "worker", worker::send)]
+    async fn completion(
+        &self,
+        completion_request: CompletionRequest,
+    ) -> Result<completion::CompletionResponse<openai::CompletionResponse>, CompletionError> {
+        let request = self.create_completion_request(completion_request)?;
+
+        let response = self
+            .client
+            .post("/text/chatcompletion_v2")
+            .json(&request)
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            let t = response.text().await?;
+            tracing::debug!(target: "rig", "MiniMax completion response: {}", t);
+
+            let response: Value = serde_json::from_str(&t)?;
+            check_base_resp(&response)?;
+
+            let response: openai::CompletionResponse = serde_json::from_value(response)?;
+            tracing::info!(target: "rig",
+                "MiniMax completion token usage: {:?}",
+                response.usage.clone().map(|usage| format!("{usage}")).unwrap_or("N/A".to_string())
+            );
+            response.try_into()
+        } else {
+            Err(CompletionError::from_response(response).await)
+        }
+    }
+
+    #[cfg_attr(feature = "worker", worker::send)]
+    async fn stream(
+        &self,
+        request: CompletionRequest,
+    ) -> Result<StreamingCompletionResponse<Self::StreamingResponse>, CompletionError> {
+        let mut request = self.create_completion_request(request)?;
+
+        request = merge(
+            request,
+            json!({"stream": true, "stream_options": {"include_usage": true}}),
+        );
+
+        let builder = self.client.post("/text/chatcompletion_v2").json(&request);
+
+        send_compatible_streaming_request(builder).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_base_resp() {
+        let response = json!({
+            "id": "02ff1cbd1b6e4a1d9a6cbd7b2c2c0a0e",
+            "choices": [],
+            "base_resp": {"status_code": 0, "status_msg": ""}
+        });
+        assert!(check_base_resp(&response).is_ok());
+
+        let response = json!({
+            "id": "",
+            "base_resp": {"status_code": 1004, "status_msg": "login fail: Please carry the API secret key in the 'Authorization' field of the request header"}
+        });
+        assert!(matches!(
+            check_base_resp(&response),
+            Err(CompletionError::ProviderError(message)) if message.starts_with("MiniMax error 1004")
+        ));
+    }
+}
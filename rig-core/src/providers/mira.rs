@@ -348,11 +348,7 @@ impl completion::CompletionModel for CompletionModel {
             .map_err(|e| CompletionError::ProviderError(e.to_string()))?;
 
         if !response.status().is_success() {
-            let status = response.status().as_u16();
-            let error_text = response.text().await.unwrap_or_default();
-            return Err(CompletionError::ProviderError(format!(
-                "API error: {status} - {error_text}"
-            )));
+            return Err(CompletionError::from_response(response).await);
         }
 
         let response: CompletionResponse = response
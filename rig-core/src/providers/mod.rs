@@ -6,11 +6,16 @@
 //! - Perplexity
 //! - Anthropic
 //! - Google Gemini
+//! - Google Vertex AI (requires the `vertex-ai` feature)
 //! - xAI
 //! - EternalAI
 //! - DeepSeek
 //! - Azure OpenAI
 //! - Mira
+//! - Cerebras
+//! - Fireworks AI
+//! - NVIDIA NIM
+//! - MiniMax
 //!
 //! Each provider has its own module, which contains a `Client` implementation that can
 //! be used to initialize completion and embedding models and execute requests to those models.
@@ -47,20 +52,29 @@
 //! be used with the Cohere provider client.
 pub mod anthropic;
 pub mod azure;
+pub mod cerebras;
 pub mod cohere;
 pub mod deepseek;
+pub mod fireworks;
 pub mod galadriel;
 pub mod gemini;
 pub mod groq;
 pub mod huggingface;
 pub mod hyperbolic;
+pub mod minimax;
 pub mod mira;
 pub mod mistral;
 pub mod moonshot;
+pub mod nvidia;
 pub mod ollama;
 pub mod openai;
 pub mod openrouter;
 pub mod perplexity;
 pub mod together;
+#[cfg(feature = "vertex-ai")]
+pub mod vertex_ai;
 pub mod voyageai;
 pub mod xai;
+
+pub use azure as azure_openai;
+pub use together as together_ai;
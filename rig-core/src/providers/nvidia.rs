@@ -0,0 +1,303 @@
+//! NVIDIA NIM API client and Rig integration
+//!
+//! # Example
+//! ```
+//! use rig::providers::nvidia;
+//!
+//! let client = nvidia::Client::new("YOUR_API_KEY");
+//!
+//! let llama = client.completion_model(nvidia::LLAMA_3_1_405B_INSTRUCT);
+//! ```
+//!
+//! # Sampling parameters
+//! NIM-specific sampling parameters (e.g.: `top_k`) can be set via
+//! [additional_params](crate::agent::AgentBuilder::additional_params):
+//! ```
+//! use rig::providers::nvidia;
+//! use serde_json::json;
+//!
+//! let client = nvidia::Client::from_env();
+//!
+//! let agent = client
+//!     .agent(nvidia::MISTRAL_7B_INSTRUCT)
+//!     .temperature(0.5)
+//!     .additional_params(json!({ "top_k": 40, "top_p": 0.9 }))
+//!     .build();
+//! ```
+
+use crate::client::{CompletionClient, ProviderClient};
+use crate::json_utils::merge;
+use crate::providers::openai::send_compatible_streaming_request;
+use crate::streaming::StreamingCompletionResponse;
+use crate::{
+    completion::{self, CompletionError, CompletionRequest},
+    json_utils,
+    providers::openai,
+};
+use crate::{impl_conversion_traits, message};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+// ================================================================
+// Main NVIDIA NIM Client
+// ================================================================
+const NVIDIA_API_BASE_URL: &str = "https://integrate.api.nvidia.com/v1";
+
+#[derive(Clone)]
+pub struct Client {
+    base_url: String,
+    api_key: String,
+    http_client: reqwest::Client,
+}
+
+impl std::fmt::Debug for Client {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Client")
+            .field("base_url", &self.base_url)
+            .field("http_client", &self.http_client)
+            .field("api_key", &"<REDACTED>")
+            .finish()
+    }
+}
+
+impl Client {
+    /// Create a new NVIDIA NIM client with the given API key.
+    pub fn new(api_key: &str) -> Self {
+        Self::from_url(api_key, NVIDIA_API_BASE_URL)
+    }
+
+    /// Create a new NVIDIA NIM client with the given API key and base API URL.
+    pub fn from_url(api_key: &str, base_url: &str) -> Self {
+        Self {
+            base_url: base_url.to_string(),
+            api_key: api_key.to_string(),
+            http_client: reqwest::Client::builder()
+                .build()
+                .expect("NVIDIA reqwest client should build"),
+        }
+    }
+
+    /// Use your own `reqwest::Client`.
+    /// The required headers will be automatically attached upon trying to make a request.
+    pub fn with_custom_client(mut self, client: reqwest::Client) -> Self {
+        self.http_client = client;
+
+        self
+    }
+
+    fn post(&self, path: &str) -> reqwest::RequestBuilder {
+        let url = format!("{}/{}", self.base_url, path).replace("//", "/");
+        self.http_client.post(url).bearer_auth(&self.api_key)
+    }
+}
+
+impl ProviderClient for Client {
+    /// Create a new NVIDIA NIM client from the `NVIDIA_API_KEY` environment variable.
+    /// Panics if the environment variable is not set.
+    fn from_env() -> Self {
+        let api_key = std::env::var("NVIDIA_API_KEY").expect("NVIDIA_API_KEY not set");
+        Self::new(&api_key)
+    }
+}
+
+impl CompletionClient for Client {
+    type CompletionModel = CompletionModel;
+
+    /// Create a completion model with the given name.
+    ///
+    /// # Example
+    /// ```
+    /// use rig::providers::nvidia::{Client, self};
+    ///
+    /// // Initialize the NVIDIA NIM client
+    /// let nvidia = Client::new("your-nvidia-api-key");
+    ///
+    /// let llama = nvidia.completion_model(nvidia::LLAMA_3_1_405B_INSTRUCT);
+    /// ```
+    fn completion_model(&self, model: &str) -> CompletionModel {
+        CompletionModel::new(self.clone(), model)
+    }
+}
+
+impl_conversion_traits!(
+    AsEmbeddings,
+    AsTranscription,
+    AsImageGeneration,
+    AsAudioGeneration for Client
+);
+
+#[derive(Debug, Deserialize)]
+struct ApiErrorResponse {
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum ApiResponse<T> {
+    Ok(T),
+    Err(ApiErrorResponse),
+}
+
+// ================================================================
+// NVIDIA NIM Completion API
+// ================================================================
+/// The `meta/llama-3.1-405b-instruct` model. Used for chat completion.
+pub const LLAMA_3_1_405B_INSTRUCT: &str = "meta/llama-3.1-405b-instruct";
+/// The `mistralai/mistral-7b-instruct-v0.3` model. Used for chat completion.
+pub const MISTRAL_7B_INSTRUCT: &str = "mistralai/mistral-7b-instruct-v0.3";
+
+#[derive(Clone, Debug)]
+pub struct CompletionModel {
+    client: Client,
+    /// Name of the model (e.g.: meta/llama-3.1-405b-instruct)
+    pub model: String,
+}
+
+impl CompletionModel {
+    pub fn new(client: Client, model: &str) -> Self {
+        Self {
+            client,
+            model: model.to_string(),
+        }
+    }
+
+    fn create_completion_request(
+        &self,
+        completion_request: CompletionRequest,
+    ) -> Result<Value, CompletionError> {
+        // Build up the order of messages (context, chat_history)
+        let mut partial_history = vec![];
+        if let Some(docs) = completion_request.normalized_documents() {
+            partial_history.push(docs);
+        }
+        partial_history.extend(completion_request.chat_history);
+
+        // Initialize full history with preamble (or empty if non-existent)
+        let mut full_history: Vec<openai::Message> = completion_request
+            .preamble
+            .map_or_else(Vec::new, |preamble| {
+                vec![openai::Message::system(&preamble)]
+            });
+
+        // Convert and extend the rest of the history
+        full_history.extend(
+            partial_history
+                .into_iter()
+                .map(message::Message::try_into)
+                .collect::<Result<Vec<Vec<openai::Message>>, _>>()?
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>(),
+        );
+
+        let request = if completion_request.tools.is_empty() {
+            json!({
+                "model": self.model,
+                "messages": full_history,
+                "temperature": completion_request.temperature,
+                "max_tokens": completion_request.max_tokens,
+            })
+        } else {
+            json!({
+                "model": self.model,
+                "messages": full_history,
+                "temperature": completion_request.temperature,
+                "max_tokens": completion_request.max_tokens,
+                "tools": completion_request.tools.into_iter().map(openai::ToolDefinition::from).collect::<Vec<_>>(),
+                "tool_choice": "auto",
+            })
+        };
+
+        let request = if let Some(params) = completion_request.additional_params {
+            json_utils::merge(request, params)
+        } else {
+            request
+        };
+
+        Ok(request)
+    }
+}
+
+impl completion::CompletionModel for CompletionModel {
+    type Response = openai::CompletionResponse;
+    type StreamingResponse = openai::StreamingCompletionResponse;
+
+    #[cfg_attr(feature = "worker", worker::send)]
+    async fn completion(
+        &self,
+        completion_request: CompletionRequest,
+    ) -> Result<completion::CompletionResponse<openai::CompletionResponse>, CompletionError> {
+        let request = self.create_completion_request(completion_request)?;
+
+        let response = self
+            .client
+            .post("/chat/completions")
+            .json(&request)
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            match response
+                .json::<ApiResponse<openai::CompletionResponse>>()
+                .await?
+            {
+                ApiResponse::Ok(response) => {
+                    tracing::info!(target: "rig",
+                        "NVIDIA NIM completion token usage: {:?}",
+                        response.usage.clone().map(|usage| format!("{usage}")).unwrap_or("N/A".to_string())
+                    );
+                    response.try_into()
+                }
+                ApiResponse::Err(err) => Err(CompletionError::ProviderError(err.message)),
+            }
+        } else {
+            Err(CompletionError::from_response(response).await)
+        }
+    }
+
+    #[cfg_attr(feature = "worker", worker::send)]
+    async fn stream(
+        &self,
+        request: CompletionRequest,
+    ) -> Result<StreamingCompletionResponse<Self::StreamingResponse>, CompletionError> {
+        let mut request = self.create_completion_request(request)?;
+
+        request = merge(
+            request,
+            json!({"stream": true, "stream_options": {"include_usage": true}}),
+        );
+
+        let builder = self.client.post("/chat/completions").json(&request);
+
+        send_compatible_streaming_request(builder).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sampling_parameters() {
+        let model = Client::new("key").completion_model(MISTRAL_7B_INSTRUCT);
+
+        let request = model
+            .create_completion_request(CompletionRequest {
+                preamble: Some("Be concise.".to_string()),
+                chat_history: crate::OneOrMany::one("Hello!".into()),
+                documents: vec![],
+                tools: vec![],
+                temperature: Some(0.5),
+                max_tokens: Some(64),
+                additional_params: Some(json!({ "top_k": 40 })),
+                tool_choice: None,
+                system_messages: vec![],
+            })
+            .unwrap();
+
+        assert_eq!(request["model"], MISTRAL_7B_INSTRUCT);
+        assert_eq!(request["top_k"], 40);
+        assert_eq!(request["max_tokens"], 64);
+        assert_eq!(request["messages"].as_array().unwrap().len(), 2);
+    }
+}
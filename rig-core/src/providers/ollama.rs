@@ -97,6 +97,38 @@ impl Client {
         let url = format!("{}/{}", self.base_url, path);
         self.http_client.post(url)
     }
+
+    fn get(&self, path: &str) -> reqwest::RequestBuilder {
+        let url = format!("{}/{}", self.base_url, path);
+        self.http_client.get(url)
+    }
+
+    /// List the names of the models installed on the Ollama server (e.g.: `llama3.2:latest`).
+    pub async fn list_models(&self) -> Result<Vec<String>, reqwest::Error> {
+        let response = self
+            .get("api/tags")
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<ListModelsResponse>()
+            .await?;
+
+        Ok(response
+            .models
+            .into_iter()
+            .map(|model| model.name)
+            .collect())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ListModelsResponse {
+    models: Vec<ModelInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ModelInfo {
+    name: String,
 }
 
 impl ProviderClient for Client {
@@ -907,6 +939,41 @@ mod tests {
         );
     }
 
+    // Test deserialization of the /api/tags endpoint used by `Client::list_models`.
+    #[test]
+    fn test_list_models_response() {
+        let sample_tags_response = json!({
+            "models": [
+                {
+                    "name": "llama3.2:latest",
+                    "model": "llama3.2:latest",
+                    "modified_at": "2024-10-10T12:00:00.000000Z",
+                    "size": 2019393189u64,
+                    "digest": "a80c4f17acd55265feec403c7aef86be0c25983ab279d83f3bcd3abbcb5b8b72",
+                    "details": {
+                        "format": "gguf",
+                        "family": "llama",
+                        "parameter_size": "3.2B",
+                        "quantization_level": "Q4_K_M"
+                    }
+                },
+                {
+                    "name": "nomic-embed-text:latest",
+                    "model": "nomic-embed-text:latest"
+                }
+            ]
+        });
+
+        let resp: ListModelsResponse =
+            serde_json::from_value(sample_tags_response).expect("Invalid JSON structure");
+        let names = resp
+            .models
+            .into_iter()
+            .map(|model| model.name)
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["llama3.2:latest", "nomic-embed-text:latest"]);
+    }
+
     // Test conversion from provider Message to completion Message.
     #[test]
     fn test_message_conversion() {
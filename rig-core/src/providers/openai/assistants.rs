@@ -0,0 +1,638 @@
+//! OpenAI Assistants API (v2) integration.
+//!
+//! Unlike the Chat Completions API, the conversation state of an assistant is managed by OpenAI:
+//! messages are added to a [Thread], and a [Run] of an [Assistant] on the thread generates the
+//! response, calling the (locally executed) tools of the assistant if needed.
+//!
+//! # Example
+//! ```
+//! use rig::providers::openai::{
+//!     self,
+//!     assistants::{AssistantBuilder, Run, Thread},
+//! };
+//!
+//! let client = openai::Client::from_env();
+//!
+//! let assistant = AssistantBuilder::new(&client, openai::GPT_4O)
+//!     .name("Math tutor")
+//!     .preamble("You are a personal math tutor.")
+//!     .tool(Adder)
+//!     .build()
+//!     .await?;
+//!
+//! let thread = Thread::create(&client).await?;
+//! thread.add_message("What is 2 + 5?").await?;
+//!
+//! let run = Run::create_and_poll(&assistant, &thread).await?;
+//! let messages = thread.messages().await?;
+//! println!("{}", messages.last().unwrap().text());
+//! ```
+use std::{pin::Pin, time::Duration};
+
+use async_stream::stream;
+use futures::{Stream, StreamExt};
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::{json, Value};
+
+use crate::{
+    completion::CompletionError,
+    json_utils,
+    tool::{Tool, ToolSet},
+};
+
+use super::{ApiResponse, Client, ToolCall, ToolDefinition};
+
+/// Header required by the endpoints of the Assistants API (v2)
+const ASSISTANTS_BETA_HEADER: (&str, &str) = ("OpenAI-Beta", "assistants=v2");
+
+/// Interval between two polls of the status of a run
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Send a request to an endpoint of the Assistants API and parse its response.
+async fn send<T: DeserializeOwned>(request: reqwest::RequestBuilder) -> Result<T, CompletionError> {
+    let response = request
+        .header(ASSISTANTS_BETA_HEADER.0, ASSISTANTS_BETA_HEADER.1)
+        .send()
+        .await?;
+
+    if response.status().is_success() {
+        match response.json::<ApiResponse<T>>().await? {
+            ApiResponse::Ok(response) => Ok(response),
+            ApiResponse::Err(err) => Err(CompletionError::ProviderError(err.message)),
+        }
+    } else {
+        Err(CompletionError::from_response(response).await)
+    }
+}
+
+// ================================================================
+// Assistants
+// ================================================================
+
+#[derive(Debug, Deserialize)]
+struct AssistantObject {
+    id: String,
+}
+
+/// A builder for OpenAI-managed assistants, with the same preamble and tools interface as
+/// [AgentBuilder](crate::agent::AgentBuilder).
+pub struct AssistantBuilder {
+    client: Client,
+    model: String,
+    name: Option<String>,
+    preamble: Option<String>,
+    temperature: Option<f64>,
+    tools: ToolSet,
+    additional_params: Option<Value>,
+}
+
+impl AssistantBuilder {
+    pub fn new(client: &Client, model: &str) -> Self {
+        Self {
+            client: client.clone(),
+            model: model.to_string(),
+            name: None,
+            preamble: None,
+            temperature: None,
+            tools: ToolSet::default(),
+            additional_params: None,
+        }
+    }
+
+    /// Set the name of the assistant
+    pub fn name(mut self, name: &str) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Set the instructions (i.e.: system prompt) of the assistant
+    pub fn preamble(mut self, preamble: &str) -> Self {
+        self.preamble = Some(preamble.into());
+        self
+    }
+
+    /// Append to the instructions of the assistant
+    pub fn append_preamble(mut self, doc: &str) -> Self {
+        self.preamble = Some(format!(
+            "{}\n{}",
+            self.preamble.unwrap_or_else(|| "".into()),
+            doc
+        ));
+        self
+    }
+
+    /// Set the temperature of the assistant
+    pub fn temperature(mut self, temperature: f64) -> Self {
+        self.temperature = Some(temperature);
+        self
+    }
+
+    /// Add a tool to the assistant. The tool is declared to OpenAI and executed locally when
+    /// a run of the assistant calls it.
+    pub fn tool(mut self, tool: impl Tool + 'static) -> Self {
+        self.tools.add_tool(tool);
+        self
+    }
+
+    /// Set additional parameters of the assistant (e.g.: `{"tools": [{"type": "file_search"}]}`)
+    pub fn additional_params(mut self, params: Value) -> Self {
+        self.additional_params = Some(params);
+        self
+    }
+
+    /// Create the assistant on OpenAI.
+    pub async fn build(self) -> Result<Assistant, CompletionError> {
+        let mut tools = vec![];
+        // Assistants are not created for a prompt, so conditional tools are offered when they
+        // are available for an empty one (and called as such)
+        for tool in self
+            .tools
+            .tools
+            .values()
+            .filter(|tool| tool.is_available(""))
+        {
+            tools.push(ToolDefinition::from(tool.definition(String::new()).await));
+        }
+
+        let mut request = json!({
+            "model": self.model,
+            "name": self.name,
+            "instructions": self.preamble,
+            "temperature": self.temperature,
+            "tools": tools,
+        });
+        if let Some(params) = self.additional_params {
+            request = json_utils::merge(request, params);
+        }
+
+        let assistant: AssistantObject =
+            send(self.client.post("/assistants").json(&request)).await?;
+        tracing::info!(target: "rig", "Created OpenAI assistant {}", assistant.id);
+
+        Ok(Assistant {
+            id: assistant.id,
+            client: self.client,
+            tools: self.tools,
+        })
+    }
+}
+
+/// An OpenAI-managed assistant, along with the tools executed when its runs call them.
+pub struct Assistant {
+    /// Id of the assistant (e.g.: asst_abc123)
+    pub id: String,
+    client: Client,
+    tools: ToolSet,
+}
+
+impl Assistant {
+    /// Use an existing assistant, created with the given `tools`.
+    pub fn from_id(client: &Client, id: &str, tools: ToolSet) -> Self {
+        Self {
+            id: id.to_string(),
+            client: client.clone(),
+            tools,
+        }
+    }
+
+    /// Execute the tool calls of a run and return their outputs.
+    async fn tool_outputs(&self, tool_calls: &[ToolCall]) -> Result<Value, CompletionError> {
+        let mut outputs = vec![];
+        for tool_call in tool_calls {
+            let output = self
+                .tools
+                .call_with_prompt(
+                    &tool_call.function.name,
+                    tool_call.function.arguments.to_string(),
+                    "",
+                )
+                .await
+                .map_err(|e| CompletionError::RequestError(Box::new(e)))?;
+            outputs.push(json!({ "tool_call_id": tool_call.id, "output": output }));
+        }
+
+        Ok(json!({ "tool_outputs": outputs }))
+    }
+}
+
+// ================================================================
+// Threads
+// ================================================================
+
+/// A conversation with an assistant, stored by OpenAI.
+#[derive(Clone, Debug)]
+pub struct Thread {
+    /// Id of the thread (e.g.: thread_abc123)
+    pub id: String,
+    client: Client,
+}
+
+#[derive(Debug, Deserialize)]
+struct ThreadObject {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct MessageList {
+    data: Vec<ThreadMessage>,
+}
+
+/// A message of a [Thread].
+#[derive(Clone, Debug, Deserialize)]
+pub struct ThreadMessage {
+    pub id: String,
+    /// Either `user` or `assistant`
+    pub role: String,
+    pub content: Vec<ThreadMessageContent>,
+    /// Id of the run which generated the message, if any
+    pub run_id: Option<String>,
+}
+
+impl ThreadMessage {
+    /// Returns the text content of the message.
+    pub fn text(&self) -> String {
+        self.content
+            .iter()
+            .filter_map(|content| match content {
+                ThreadMessageContent::Text { text } => Some(text.value.as_str()),
+                ThreadMessageContent::Other => None,
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ThreadMessageContent {
+    Text {
+        text: TextValue,
+    },
+    /// Other content types (e.g.: images), not supported yet
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct TextValue {
+    pub value: String,
+}
+
+impl Thread {
+    /// Create a new (empty) thread.
+    pub async fn create(client: &Client) -> Result<Self, CompletionError> {
+        let thread: ThreadObject = send(client.post("/threads").json(&json!({}))).await?;
+
+        Ok(Self {
+            id: thread.id,
+            client: client.clone(),
+        })
+    }
+
+    /// Use an existing thread.
+    pub fn from_id(client: &Client, id: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            client: client.clone(),
+        }
+    }
+
+    /// Add a user message to the thread.
+    pub async fn add_message(
+        &self,
+        content: impl Into<String>,
+    ) -> Result<ThreadMessage, CompletionError> {
+        send(
+            self.client
+                .post(&format!("/threads/{}/messages", self.id))
+                .json(&json!({ "role": "user", "content": content.into() })),
+        )
+        .await
+    }
+
+    /// Returns the messages of the thread, from the oldest to the most recent.
+    pub async fn messages(&self) -> Result<Vec<ThreadMessage>, CompletionError> {
+        let messages: MessageList = send(
+            self.client
+                .get(&format!("/threads/{}/messages?order=asc", self.id)),
+        )
+        .await?;
+
+        Ok(messages.data)
+    }
+}
+
+// ================================================================
+// Runs
+// ================================================================
+
+/// The status of a [Run].
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RunStatus {
+    Queued,
+    InProgress,
+    RequiresAction,
+    Cancelling,
+    Cancelled,
+    Failed,
+    Completed,
+    Incomplete,
+    Expired,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct RequiredAction {
+    pub submit_tool_outputs: SubmitToolOutputs,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct SubmitToolOutputs {
+    pub tool_calls: Vec<ToolCall>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct RunError {
+    pub code: String,
+    pub message: String,
+}
+
+/// An execution of an [Assistant] on a [Thread].
+#[derive(Clone, Debug, Deserialize)]
+pub struct Run {
+    /// Id of the run (e.g.: run_abc123)
+    pub id: String,
+    pub thread_id: String,
+    pub status: RunStatus,
+    /// The tool calls to execute to continue the run (when its status is `requires_action`)
+    pub required_action: Option<RequiredAction>,
+    pub last_error: Option<RunError>,
+}
+
+impl Run {
+    /// Run `assistant` on `thread` and wait for the run to complete, executing the tool calls
+    /// of the assistant along the way. The response of the assistant is added to the thread.
+    pub async fn create_and_poll(
+        assistant: &Assistant,
+        thread: &Thread,
+    ) -> Result<Run, CompletionError> {
+        let client = &assistant.client;
+        let mut run: Run = send(
+            client
+                .post(&format!("/threads/{}/runs", thread.id))
+                .json(&json!({ "assistant_id": assistant.id })),
+        )
+        .await?;
+
+        loop {
+            match run.status {
+                RunStatus::Completed => return Ok(run),
+                RunStatus::RequiresAction => {
+                    let tool_calls = run
+                        .required_action
+                        .as_ref()
+                        .map(|action| action.submit_tool_outputs.tool_calls.as_slice())
+                        .unwrap_or_default();
+                    let tool_outputs = assistant.tool_outputs(tool_calls).await?;
+
+                    run = send(
+                        client
+                            .post(&format!(
+                                "/threads/{}/runs/{}/submit_tool_outputs",
+                                thread.id, run.id
+                            ))
+                            .json(&tool_outputs),
+                    )
+                    .await?;
+                }
+                RunStatus::Queued | RunStatus::InProgress | RunStatus::Cancelling => {
+                    tokio::time::sleep(POLL_INTERVAL).await;
+                    run = send(client.get(&format!("/threads/{}/runs/{}", thread.id, run.id)))
+                        .await?;
+                }
+                status => return Err(run_failed(status, run.last_error)),
+            }
+        }
+    }
+
+    /// Run `assistant` on `thread`, streaming the text of its response, and executing the tool
+    /// calls of the assistant along the way. The response of the assistant is added to the thread.
+    pub async fn stream<'a>(
+        assistant: &'a Assistant,
+        thread: &'a Thread,
+    ) -> Result<
+        Pin<Box<dyn Stream<Item = Result<String, CompletionError>> + Send + 'a>>,
+        CompletionError,
+    > {
+        let mut response = send_streaming(
+            assistant
+                .client
+                .post(&format!("/threads/{}/runs", thread.id))
+                .json(&json!({ "assistant_id": assistant.id, "stream": true })),
+        )
+        .await?;
+
+        Ok(Box::pin(stream! {
+            loop {
+                let mut events = Box::pin(parse_events(response));
+                let mut required_action = None;
+
+                while let Some(event) = events.next().await {
+                    match event {
+                        Ok(StreamEvent::MessageDelta { delta }) => {
+                            for content in delta.content {
+                                if let ThreadMessageContent::Text { text } = content {
+                                    yield Ok(text.value);
+                                }
+                            }
+                        }
+                        Ok(StreamEvent::Run(run)) => match run.status {
+                            RunStatus::RequiresAction => required_action = Some(run),
+                            RunStatus::Failed
+                            | RunStatus::Cancelled
+                            | RunStatus::Incomplete
+                            | RunStatus::Expired => {
+                                yield Err(run_failed(run.status, run.last_error));
+                                return;
+                            }
+                            _ => {}
+                        },
+                        Ok(StreamEvent::Other) => {}
+                        Err(e) => {
+                            yield Err(e);
+                            return;
+                        }
+                    }
+                }
+
+                // The run is over, unless it is waiting for the outputs of tool calls
+                let Some(run) = required_action else {
+                    return;
+                };
+
+                let tool_calls = run
+                    .required_action
+                    .as_ref()
+                    .map(|action| action.submit_tool_outputs.tool_calls.as_slice())
+                    .unwrap_or_default();
+                let tool_outputs = match assistant.tool_outputs(tool_calls).await {
+                    Ok(outputs) => json_utils::merge(outputs, json!({ "stream": true })),
+                    Err(e) => {
+                        yield Err(e);
+                        return;
+                    }
+                };
+
+                response = match send_streaming(
+                    assistant
+                        .client
+                        .post(&format!(
+                            "/threads/{}/runs/{}/submit_tool_outputs",
+                            thread.id, run.id
+                        ))
+                        .json(&tool_outputs),
+                )
+                .await
+                {
+                    Ok(response) => response,
+                    Err(e) => {
+                        yield Err(e);
+                        return;
+                    }
+                };
+            }
+        }))
+    }
+}
+
+fn run_failed(status: RunStatus, error: Option<RunError>) -> CompletionError {
+    CompletionError::ProviderError(match error {
+        Some(error) => format!("Run {status:?}: {} ({})", error.message, error.code),
+        None => format!("Run {status:?}"),
+    })
+}
+
+async fn send_streaming(
+    request: reqwest::RequestBuilder,
+) -> Result<reqwest::Response, CompletionError> {
+    let response = request
+        .header(ASSISTANTS_BETA_HEADER.0, ASSISTANTS_BETA_HEADER.1)
+        .send()
+        .await?;
+
+    if response.status().is_success() {
+        Ok(response)
+    } else {
+        Err(CompletionError::from_response(response).await)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct MessageDelta {
+    #[serde(default)]
+    content: Vec<ThreadMessageContent>,
+}
+
+/// The server-sent events of a run, identified by their `object` field.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "object")]
+enum StreamEvent {
+    #[serde(rename = "thread.message.delta")]
+    MessageDelta { delta: MessageDelta },
+    #[serde(rename = "thread.run")]
+    Run(Run),
+    #[serde(other)]
+    Other,
+}
+
+/// Parse the `data` lines of the server-sent events of a streaming response.
+fn parse_events(
+    response: reqwest::Response,
+) -> impl Stream<Item = Result<StreamEvent, CompletionError>> {
+    stream! {
+        let mut bytes = response.bytes_stream();
+        let mut buffer = String::new();
+
+        while let Some(chunk) = bytes.next().await {
+            match chunk {
+                Ok(chunk) => buffer.push_str(&String::from_utf8_lossy(&chunk)),
+                Err(e) => {
+                    yield Err(CompletionError::from(e));
+                    return;
+                }
+            }
+
+            // Only complete lines are parsed, the rest is kept for the next chunk
+            while let Some(end) = buffer.find('\n') {
+                let line = buffer[..end].trim().to_string();
+                buffer.drain(..=end);
+
+                let Some(data) = line.strip_prefix("data:").map(str::trim) else {
+                    continue;
+                };
+                if data == "[DONE]" {
+                    return;
+                }
+                yield serde_json::from_str(data).map_err(CompletionError::from);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_stream_events() {
+        let event: StreamEvent = serde_json::from_str(
+            r#"{
+                "id": "msg_123",
+                "object": "thread.message.delta",
+                "delta": {
+                    "content": [{"index": 0, "type": "text", "text": {"value": "Hello", "annotations": []}}]
+                }
+            }"#,
+        )
+        .unwrap();
+        let StreamEvent::MessageDelta { delta } = event else {
+            panic!("Expected a message delta");
+        };
+        assert!(matches!(
+            &delta.content[..],
+            [ThreadMessageContent::Text { text }] if text.value == "Hello"
+        ));
+
+        let event: StreamEvent = serde_json::from_str(
+            r#"{
+                "id": "run_123",
+                "object": "thread.run",
+                "thread_id": "thread_123",
+                "status": "requires_action",
+                "required_action": {
+                    "type": "submit_tool_outputs",
+                    "submit_tool_outputs": {
+                        "tool_calls": [{
+                            "id": "call_123",
+                            "type": "function",
+                            "function": {"name": "add", "arguments": "{\"x\": 2, \"y\": 5}"}
+                        }]
+                    }
+                },
+                "last_error": null
+            }"#,
+        )
+        .unwrap();
+        let StreamEvent::Run(run) = event else {
+            panic!("Expected a run");
+        };
+        assert_eq!(run.status, RunStatus::RequiresAction);
+        let tool_call = &run.required_action.unwrap().submit_tool_outputs.tool_calls[0];
+        assert_eq!(tool_call.function.name, "add");
+        assert_eq!(tool_call.function.arguments, json!({"x": 2, "y": 5}));
+
+        let event: StreamEvent =
+            serde_json::from_str(r#"{"id": "step_123", "object": "thread.run.step"}"#).unwrap();
+        assert!(matches!(event, StreamEvent::Other));
+    }
+}
@@ -0,0 +1,257 @@
+//! OpenAI audio API integration: text-to-speech and speech-to-text (Whisper).
+//!
+//! Unlike the [AudioGenerationModel](super::audio_generation::AudioGenerationModel) and
+//! [TranscriptionModel](super::TranscriptionModel), these clients expose the OpenAI specific
+//! options (voices, models, formats) directly, and support streaming the generated speech.
+//!
+//! Note: requires the `audio` feature.
+//!
+//! # Example
+//! ```
+//! use futures::StreamExt;
+//! use rig::providers::openai::{self, audio::{SpeechToText, TextToSpeech, TtsModel, Voice}};
+//!
+//! let client = openai::Client::from_env();
+//!
+//! let tts = TextToSpeech::new(&client);
+//! let audio = tts.synthesize("Hello world!", Voice::Alloy, TtsModel::Tts1).await?;
+//!
+//! // Play the audio as soon as its first chunks are received
+//! let mut stream = tts.synthesize_stream("Hello world!", Voice::Nova, TtsModel::Tts1).await?;
+//! while let Some(chunk) = stream.next().await {
+//!     player.write(&chunk?);
+//! }
+//!
+//! let stt = SpeechToText::new(&client);
+//! let text = stt.transcribe(audio, Some("en".to_string())).await?;
+//! ```
+use futures::{Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::{audio_generation::AudioGenerationError, transcription::TranscriptionError};
+
+use super::{ApiResponse, Client, TranscriptionResponse, WHISPER_1};
+
+/// The voice of the generated speech.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Voice {
+    Alloy,
+    Ash,
+    Ballad,
+    Coral,
+    Echo,
+    Fable,
+    Onyx,
+    Nova,
+    Sage,
+    Shimmer,
+}
+
+/// The text-to-speech model.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TtsModel {
+    /// Optimized for real-time use
+    #[serde(rename = "tts-1")]
+    Tts1,
+    /// Optimized for quality
+    #[serde(rename = "tts-1-hd")]
+    Tts1Hd,
+    #[serde(rename = "gpt-4o-mini-tts")]
+    Gpt4oMiniTts,
+}
+
+/// The format of the generated audio.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AudioFormat {
+    #[default]
+    Mp3,
+    Opus,
+    Aac,
+    Flac,
+    Wav,
+    /// Raw 24kHz 16-bit signed little-endian samples (no header)
+    Pcm,
+}
+
+/// A client of the text-to-speech API (`/v1/audio/speech`).
+#[derive(Clone, Debug)]
+pub struct TextToSpeech {
+    client: Client,
+    format: AudioFormat,
+    speed: Option<f32>,
+}
+
+impl TextToSpeech {
+    /// Create a text-to-speech client generating MP3 audio.
+    pub fn new(client: &Client) -> Self {
+        Self {
+            client: client.clone(),
+            format: AudioFormat::default(),
+            speed: None,
+        }
+    }
+
+    /// Set the format of the generated audio.
+    pub fn format(mut self, format: AudioFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Set the speed of the generated speech, from 0.25 to 4.0 (1.0 by default).
+    pub fn speed(mut self, speed: f32) -> Self {
+        self.speed = Some(speed);
+        self
+    }
+
+    async fn send(
+        &self,
+        text: &str,
+        voice: Voice,
+        model: TtsModel,
+    ) -> Result<reqwest::Response, AudioGenerationError> {
+        let mut request = json!({
+            "model": model,
+            "input": text,
+            "voice": voice,
+            "response_format": self.format,
+        });
+        if let Some(speed) = self.speed {
+            request["speed"] = json!(speed);
+        }
+
+        let response = self
+            .client
+            .post("/audio/speech")
+            .json(&request)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(AudioGenerationError::ProviderError(format!(
+                "{}: {}",
+                response.status(),
+                response.text().await?
+            )));
+        }
+
+        Ok(response)
+    }
+
+    /// Generate the speech of `text` and return the audio (in the format of the client).
+    pub async fn synthesize(
+        &self,
+        text: &str,
+        voice: Voice,
+        model: TtsModel,
+    ) -> Result<Vec<u8>, AudioGenerationError> {
+        let response = self.send(text, voice, model).await?;
+
+        Ok(response.bytes().await?.to_vec())
+    }
+
+    /// Same as `synthesize`, but the audio is streamed as it is generated, in chunks.
+    pub async fn synthesize_stream(
+        &self,
+        text: &str,
+        voice: Voice,
+        model: TtsModel,
+    ) -> Result<impl Stream<Item = Result<Vec<u8>, AudioGenerationError>>, AudioGenerationError>
+    {
+        let response = self.send(text, voice, model).await?;
+
+        Ok(response.bytes_stream().map(|chunk| {
+            chunk
+                .map(|bytes| bytes.to_vec())
+                .map_err(AudioGenerationError::from)
+        }))
+    }
+}
+
+/// A client of the speech-to-text API (`/v1/audio/transcriptions`).
+#[derive(Clone, Debug)]
+pub struct SpeechToText {
+    client: Client,
+    model: String,
+    filename: String,
+}
+
+impl SpeechToText {
+    /// Create a speech-to-text client using the [WHISPER_1] model.
+    pub fn new(client: &Client) -> Self {
+        Self {
+            client: client.clone(),
+            model: WHISPER_1.to_string(),
+            filename: "audio.mp3".to_string(),
+        }
+    }
+
+    /// Set the transcription model (e.g.: `gpt-4o-transcribe`).
+    pub fn model(mut self, model: &str) -> Self {
+        self.model = model.to_string();
+        self
+    }
+
+    /// Set the filename of the uploaded audio (`audio.mp3` by default). OpenAI infers the
+    /// format of the audio from its extension (e.g.: `audio.wav`).
+    pub fn filename(mut self, filename: &str) -> Self {
+        self.filename = filename.to_string();
+        self
+    }
+
+    /// Transcribe the audio. The `language` (in ISO-639-1 format, e.g.: `en`) of the audio
+    /// is detected when not set.
+    pub async fn transcribe(
+        &self,
+        audio_bytes: Vec<u8>,
+        language: Option<String>,
+    ) -> Result<String, TranscriptionError> {
+        let mut body = reqwest::multipart::Form::new()
+            .text("model", self.model.clone())
+            .part(
+                "file",
+                reqwest::multipart::Part::bytes(audio_bytes).file_name(self.filename.clone()),
+            );
+        if let Some(language) = language {
+            body = body.text("language", language);
+        }
+
+        let response = self
+            .client
+            .post("/audio/transcriptions")
+            .multipart(body)
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            match response
+                .json::<ApiResponse<TranscriptionResponse>>()
+                .await?
+            {
+                ApiResponse::Ok(response) => Ok(response.text),
+                ApiResponse::Err(err) => Err(TranscriptionError::ProviderError(err.message)),
+            }
+        } else {
+            Err(TranscriptionError::ProviderError(response.text().await?))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialize_speech_options() {
+        assert_eq!(
+            json!({
+                "model": TtsModel::Tts1Hd,
+                "voice": Voice::Shimmer,
+                "response_format": AudioFormat::Wav,
+            }),
+            json!({"model": "tts-1-hd", "voice": "shimmer", "response_format": "wav"})
+        );
+    }
+}
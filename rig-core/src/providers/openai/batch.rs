@@ -0,0 +1,278 @@
+//! OpenAI Batch API integration.
+//!
+//! The Batch API processes large amounts of completion requests asynchronously (within 24
+//! hours) at a lower cost than the regular Chat Completions API.
+//!
+//! # Example
+//! ```
+//! use rig::providers::openai::{self, batch::BatchStatus};
+//!
+//! let client = openai::Client::from_env();
+//! let model = client.completion_model(openai::GPT_4O_MINI);
+//!
+//! let requests = ["Translate 'hello' to French", "Translate 'hello' to Spanish"]
+//!     .into_iter()
+//!     .map(|prompt| model.completion_request(prompt).build())
+//!     .collect();
+//!
+//! let batch = model.batch(requests).await?;
+//!
+//! // ... later on ...
+//! if batch.poll().await? == BatchStatus::Completed {
+//!     for response in batch.results().await? {
+//!         println!("{:?}", response?.choice);
+//!     }
+//! }
+//! ```
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::completion::{self, CompletionError, CompletionRequest};
+
+use super::{ApiResponse, Client, CompletionModel, CompletionResponse};
+
+/// Endpoint of the requests of the batches
+const BATCH_ENDPOINT: &str = "/v1/chat/completions";
+
+/// The response to a request of a batch (or its error).
+pub type BatchResponse =
+    Result<completion::CompletionResponse<CompletionResponse>, CompletionError>;
+
+/// The status of a batch.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchStatus {
+    Validating,
+    Failed,
+    InProgress,
+    Finalizing,
+    Completed,
+    Expired,
+    Cancelling,
+    Cancelled,
+}
+
+#[derive(Debug, Deserialize)]
+struct FileObject {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct BatchObject {
+    id: String,
+    status: BatchStatus,
+    output_file_id: Option<String>,
+    error_file_id: Option<String>,
+}
+
+/// A line of the output (or error) file of a batch.
+#[derive(Debug, Deserialize)]
+struct BatchOutput {
+    custom_id: String,
+    response: Option<BatchOutputResponse>,
+    error: Option<BatchOutputError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BatchOutputResponse {
+    status_code: u16,
+    body: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct BatchOutputError {
+    message: String,
+}
+
+/// Send `request` and deserialize its response, failing on error responses.
+pub(super) async fn send<T: serde::de::DeserializeOwned>(
+    request: reqwest::RequestBuilder,
+) -> Result<T, CompletionError> {
+    let response = request.send().await?;
+
+    if response.status().is_success() {
+        match response.json::<ApiResponse<T>>().await? {
+            ApiResponse::Ok(response) => Ok(response),
+            ApiResponse::Err(err) => Err(CompletionError::ProviderError(err.message)),
+        }
+    } else {
+        Err(CompletionError::from_response(response).await)
+    }
+}
+
+impl CompletionModel {
+    /// Submit `requests` to the Batch API. The returned [BatchHandle] is used to check the status
+    /// of the batch and retrieve its results once completed.
+    pub async fn batch(
+        &self,
+        requests: Vec<CompletionRequest>,
+    ) -> Result<BatchHandle, CompletionError> {
+        let num_requests = requests.len();
+        let input = self.batch_input(requests)?;
+
+        let file = reqwest::multipart::Form::new()
+            .text("purpose", "batch")
+            .part(
+                "file",
+                reqwest::multipart::Part::text(input).file_name("batch.jsonl"),
+            );
+        let file: FileObject = send(self.client.post("/files").multipart(file)).await?;
+
+        let batch: BatchObject = send(self.client.post("/batches").json(&json!({
+            "input_file_id": file.id,
+            "endpoint": BATCH_ENDPOINT,
+            "completion_window": "24h",
+        })))
+        .await?;
+        tracing::info!(target: "rig", "Created OpenAI batch {} of {num_requests} requests", batch.id);
+
+        Ok(BatchHandle {
+            id: batch.id,
+            num_requests,
+            client: self.client.clone(),
+        })
+    }
+
+    /// Returns the JSONL input file of a batch, the id of each request being its index.
+    fn batch_input(&self, requests: Vec<CompletionRequest>) -> Result<String, CompletionError> {
+        let lines = requests
+            .into_iter()
+            .enumerate()
+            .map(|(i, request)| {
+                Ok(json!({
+                    "custom_id": i.to_string(),
+                    "method": "POST",
+                    "url": BATCH_ENDPOINT,
+                    "body": self.create_completion_request(request)?,
+                })
+                .to_string())
+            })
+            .collect::<Result<Vec<_>, CompletionError>>()?;
+
+        Ok(lines.join("\n"))
+    }
+}
+
+/// A handle to a batch submitted with [CompletionModel::batch].
+#[derive(Clone, Debug)]
+pub struct BatchHandle {
+    /// Id of the batch (e.g.: batch_abc123)
+    pub id: String,
+    num_requests: usize,
+    client: Client,
+}
+
+impl BatchHandle {
+    /// Use an existing batch of `num_requests` requests.
+    pub fn from_id(client: &Client, id: &str, num_requests: usize) -> Self {
+        Self {
+            id: id.to_string(),
+            num_requests,
+            client: client.clone(),
+        }
+    }
+
+    async fn retrieve(&self) -> Result<BatchObject, CompletionError> {
+        send(self.client.get(&format!("/batches/{}", self.id))).await
+    }
+
+    /// Returns the current status of the batch.
+    pub async fn poll(&self) -> Result<BatchStatus, CompletionError> {
+        Ok(self.retrieve().await?.status)
+    }
+
+    /// Returns the responses of the requests of the batch, in the same order as the requests.
+    /// Fails if the batch is not completed yet.
+    pub async fn results(&self) -> Result<Vec<BatchResponse>, CompletionError> {
+        let batch = self.retrieve().await?;
+        if batch.status != BatchStatus::Completed {
+            return Err(CompletionError::ProviderError(format!(
+                "Batch {} is not completed (status: {:?})",
+                self.id, batch.status
+            )));
+        }
+
+        let mut output = String::new();
+        for file_id in [batch.output_file_id, batch.error_file_id]
+            .into_iter()
+            .flatten()
+        {
+            output.push_str(&self.file_content(&file_id).await?);
+            output.push('\n');
+        }
+
+        parse_results(&output, self.num_requests)
+    }
+
+    async fn file_content(&self, file_id: &str) -> Result<String, CompletionError> {
+        let response = self
+            .client
+            .get(&format!("/files/{file_id}/content"))
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            Ok(response.text().await?)
+        } else {
+            Err(CompletionError::from_response(response).await)
+        }
+    }
+}
+
+/// Parse the output files of a batch (one JSON object per line) into the responses of its
+/// `num_requests` requests.
+fn parse_results(output: &str, num_requests: usize) -> Result<Vec<BatchResponse>, CompletionError> {
+    let mut outputs = output
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let output: BatchOutput = serde_json::from_str(line)?;
+            Ok((output.custom_id.clone(), output))
+        })
+        .collect::<Result<HashMap<_, _>, CompletionError>>()?;
+
+    Ok((0..num_requests)
+        .map(|i| match outputs.remove(&i.to_string()) {
+            Some(BatchOutput {
+                response: Some(response),
+                ..
+            }) if response.status_code == 200 => {
+                serde_json::from_value::<CompletionResponse>(response.body)?.try_into()
+            }
+            Some(BatchOutput {
+                response: Some(response),
+                ..
+            }) => Err(CompletionError::ProviderError(response.body.to_string())),
+            Some(BatchOutput {
+                error: Some(error), ..
+            }) => Err(CompletionError::ProviderError(error.message)),
+            _ => Err(CompletionError::ResponseError(format!(
+                "No output for the request {i} of the batch"
+            ))),
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_results() {
+        let output = r#"
+{"id": "batch_req_2", "custom_id": "1", "response": {"status_code": 400, "request_id": "req_2", "body": {"error": {"message": "Invalid request"}}}, "error": null}
+{"id": "batch_req_1", "custom_id": "0", "response": {"status_code": 200, "request_id": "req_1", "body": {"id": "chatcmpl-1", "object": "chat.completion", "created": 1711652795, "model": "gpt-4o-mini", "system_fingerprint": null, "choices": [{"index": 0, "message": {"role": "assistant", "content": "Bonjour"}, "logprobs": null, "finish_reason": "stop"}], "usage": {"prompt_tokens": 12, "total_tokens": 14}}}, "error": null}
+"#;
+
+        let results = parse_results(output, 3).unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(
+            results[0].as_ref().unwrap().choice,
+            crate::OneOrMany::one(completion::AssistantContent::text("Bonjour"))
+        );
+        assert!(matches!(results[1], Err(CompletionError::ProviderError(_))));
+        assert!(matches!(results[2], Err(CompletionError::ResponseError(_))));
+    }
+}
@@ -25,8 +25,9 @@ const OPENAI_API_BASE_URL: &str = "https://api.openai.com/v1";
 
 #[derive(Clone)]
 pub struct Client {
-    base_url: String,
-    api_key: String,
+    pub(crate) base_url: String,
+    pub(crate) api_key: String,
+    default_headers: reqwest::header::HeaderMap,
     http_client: reqwest::Client,
 }
 
@@ -35,6 +36,7 @@ impl std::fmt::Debug for Client {
         f.debug_struct("Client")
             .field("base_url", &self.base_url)
             .field("http_client", &self.http_client)
+            .field("default_headers", &self.default_headers)
             .field("api_key", &"<REDACTED>")
             .finish()
     }
@@ -51,12 +53,50 @@ impl Client {
         Self {
             base_url: base_url.to_string(),
             api_key: api_key.to_string(),
+            default_headers: reqwest::header::HeaderMap::new(),
             http_client: reqwest::Client::builder()
                 .build()
                 .expect("OpenAI reqwest client should build"),
         }
     }
 
+    /// Create a new client for an OpenAI compatible server (e.g.: LM Studio, llama.cpp or vLLM),
+    /// whose API is served at `url` (e.g.: `http://localhost:1234/v1`).
+    /// Servers which do not check the API key accept any value.
+    ///
+    /// # Example
+    /// ```
+    /// use rig::providers::openai::Client;
+    ///
+    /// let lm_studio = Client::with_base_url("http://localhost:1234/v1", "lm-studio");
+    ///
+    /// let qwen = lm_studio.completion_model("qwen2.5-7b-instruct");
+    /// ```
+    pub fn with_base_url(url: &str, api_key: &str) -> Self {
+        Self::from_url(api_key, url.trim_end_matches('/'))
+    }
+
+    /// Create a new client for a [LiteLLM](https://docs.litellm.ai/docs/simple_proxy) proxy,
+    /// which exposes an OpenAI compatible API in front of 100+ LLM providers.
+    /// The `X-Litellm-Client-Type: rig` header is attached to every request.
+    ///
+    /// # Example
+    /// ```
+    /// use rig::providers::openai::Client;
+    ///
+    /// let litellm = Client::from_litellm_proxy("http://localhost:4000", "sk-1234");
+    ///
+    /// let claude = litellm.completion_model("claude-3-5-sonnet");
+    /// ```
+    pub fn from_litellm_proxy(base_url: &str, api_key: &str) -> Self {
+        let mut client = Self::from_url(api_key, base_url);
+        client.default_headers.insert(
+            "X-Litellm-Client-Type",
+            reqwest::header::HeaderValue::from_static("rig"),
+        );
+        client
+    }
+
     /// Use your own `reqwest::Client`.
     /// The required headers will be automatically attached upon trying to make a request.
     pub fn with_custom_client(mut self, client: reqwest::Client) -> Self {
@@ -67,7 +107,18 @@ impl Client {
 
     pub(crate) fn post(&self, path: &str) -> reqwest::RequestBuilder {
         let url = format!("{}/{}", self.base_url, path).replace("//", "/");
-        self.http_client.post(url).bearer_auth(&self.api_key)
+        self.http_client
+            .post(url)
+            .bearer_auth(&self.api_key)
+            .headers(self.default_headers.clone())
+    }
+
+    pub(crate) fn get(&self, path: &str) -> reqwest::RequestBuilder {
+        let url = format!("{}/{}", self.base_url, path).replace("//", "/");
+        self.http_client
+            .get(url)
+            .bearer_auth(&self.api_key)
+            .headers(self.default_headers.clone())
     }
 }
 
@@ -491,4 +542,237 @@ mod tests {
         assert_eq!(original_user_message[0], user_message);
         assert_eq!(original_assistant_message[0], assistant_message);
     }
+
+    #[test]
+    fn test_litellm_proxy_headers() {
+        let client = super::Client::from_litellm_proxy("http://localhost:4000", "sk-1234");
+
+        let request = client.post("/chat/completions").build().unwrap();
+
+        assert_eq!(
+            request.url().as_str(),
+            "http://localhost:4000/chat/completions"
+        );
+        assert_eq!(request.headers()["X-Litellm-Client-Type"], "rig");
+        assert_eq!(request.headers()["Authorization"], "Bearer sk-1234");
+    }
+
+    #[test]
+    fn test_deserialize_choice_logprobs() {
+        let choice_json = r#"
+        {
+            "index": 0,
+            "message": { "role": "assistant", "content": "Yes" },
+            "logprobs": {
+                "content": [
+                    {
+                        "token": "Yes",
+                        "logprob": -0.01,
+                        "bytes": [89, 101, 115],
+                        "top_logprobs": [
+                            { "token": "Yes", "logprob": -0.01, "bytes": [89, 101, 115] },
+                            { "token": "No", "logprob": -4.6, "bytes": [78, 111] }
+                        ]
+                    }
+                ],
+                "refusal": null
+            },
+            "finish_reason": "stop"
+        }
+        "#;
+
+        let choice: crate::providers::openai::Choice =
+            deserialize(&mut serde_json::Deserializer::from_str(choice_json)).unwrap();
+        let logprobs = choice.logprobs.unwrap();
+
+        assert_eq!(logprobs.tokens, vec!["Yes"]);
+        assert_eq!(logprobs.logprobs, vec![-0.01]);
+        assert_eq!(
+            logprobs.top_logprobs,
+            vec![vec![("Yes".to_string(), -0.01), ("No".to_string(), -4.6)]]
+        );
+    }
+
+    #[test]
+    fn test_enable_logprobs() {
+        use crate::client::CompletionClient;
+        use crate::completion::CompletionModel;
+
+        let model =
+            super::Client::new("sk-1234").completion_model(crate::providers::openai::GPT_4O);
+        let request = model
+            .completion_request("Heads or tails?")
+            .enable_logprobs(2)
+            .build();
+        let body = model.create_completion_request(request).unwrap();
+        assert_eq!(body["logprobs"], true);
+        assert_eq!(body["top_logprobs"], 2);
+
+        let response: crate::providers::openai::CompletionResponse =
+            serde_json::from_value(serde_json::json!({
+                "id": "chatcmpl-123",
+                "object": "chat.completion",
+                "created": 1700000000,
+                "model": "gpt-4o",
+                "system_fingerprint": null,
+                "choices": [{
+                    "index": 0,
+                    "message": { "role": "assistant", "content": "Heads" },
+                    "logprobs": {
+                        "content": [{ "token": "Heads", "logprob": -0.5, "top_logprobs": [] }]
+                    },
+                    "finish_reason": "stop"
+                }],
+                "usage": null
+            }))
+            .unwrap();
+        let logprobs = response.logprobs().unwrap();
+        assert_eq!(logprobs.tokens, vec!["Heads"]);
+        assert_eq!(logprobs.logprobs, vec![-0.5]);
+        assert_eq!(logprobs.top_logprobs, vec![Vec::<(String, f64)>::new()]);
+    }
+
+    #[test]
+    fn test_seed() {
+        use crate::client::CompletionClient;
+        use crate::completion::CompletionModel;
+
+        let model =
+            super::Client::new("sk-1234").completion_model(crate::providers::openai::GPT_4O);
+        let request = model
+            .completion_request("Pick a number")
+            .temperature(1.0)
+            .seed(42)
+            .build();
+        let body = model.create_completion_request(request).unwrap();
+        assert_eq!(body["seed"], 42);
+        assert_eq!(body["temperature"], 1.0);
+
+        let response: crate::providers::openai::CompletionResponse =
+            serde_json::from_value(serde_json::json!({
+                "id": "chatcmpl-123",
+                "object": "chat.completion",
+                "created": 1700000000,
+                "model": "gpt-4o",
+                "system_fingerprint": "fp_44709d6fcb",
+                "choices": [{
+                    "index": 0,
+                    "message": { "role": "assistant", "content": "7" },
+                    "logprobs": null,
+                    "finish_reason": "stop"
+                }],
+                "usage": null
+            }))
+            .unwrap();
+        let response: crate::completion::CompletionResponse<_> = response.try_into().unwrap();
+        assert_eq!(
+            response.raw_response.system_fingerprint.as_deref(),
+            Some("fp_44709d6fcb")
+        );
+    }
+
+    #[test]
+    fn test_completion_response_candidates() {
+        let response_json = r#"
+        {
+            "id": "chatcmpl-123",
+            "object": "chat.completion",
+            "created": 1700000000,
+            "model": "gpt-4o",
+            "system_fingerprint": null,
+            "choices": [
+                {
+                    "index": 0,
+                    "message": { "role": "assistant", "content": "Heads" },
+                    "logprobs": null,
+                    "finish_reason": "stop"
+                },
+                {
+                    "index": 1,
+                    "message": { "role": "assistant", "content": "Tails" },
+                    "logprobs": null,
+                    "finish_reason": "stop"
+                }
+            ],
+            "usage": null
+        }
+        "#;
+
+        let response: crate::providers::openai::CompletionResponse =
+            deserialize(&mut serde_json::Deserializer::from_str(response_json)).unwrap();
+        let candidates = response.candidates().unwrap();
+        let response: crate::completion::CompletionResponse<_> = response.try_into().unwrap();
+
+        assert_eq!(
+            candidates,
+            vec![
+                crate::OneOrMany::one(crate::completion::AssistantContent::text("Heads")),
+                crate::OneOrMany::one(crate::completion::AssistantContent::text("Tails")),
+            ]
+        );
+        assert_eq!(response.choice, candidates[0]);
+    }
+
+    #[test]
+    fn test_completion_request_round_trip() {
+        use crate::client::CompletionClient;
+        use crate::completion::{CompletionModel, CompletionRequest, ToolDefinition};
+
+        let client = super::Client::new("sk-1234");
+        let model = client.completion_model(crate::providers::openai::GPT_4O);
+
+        let request = model
+            .completion_request("What is the weather in Paris?")
+            .preamble("You are a helpful assistant.".to_string())
+            .message(message::Message::assistant("Let me check."))
+            .document(crate::completion::Document {
+                id: "doc0".to_string(),
+                text: "Paris is the capital of France.".to_string(),
+                additional_props: [("source".to_string(), "wiki".to_string())].into(),
+            })
+            .tool(ToolDefinition {
+                name: "weather".to_string(),
+                description: "Get the weather of a city".to_string(),
+                parameters: serde_json::json!({
+                    "type": "object",
+                    "properties": { "city": { "type": "string" } }
+                }),
+            })
+            .temperature(0.5)
+            .max_tokens(100)
+            .seed(42)
+            .build();
+
+        let json = serde_json::to_string(&request).unwrap();
+        let deserialized: CompletionRequest = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(serde_json::to_string(&deserialized).unwrap(), json);
+        assert_eq!(
+            model.create_completion_request(deserialized).unwrap(),
+            model.create_completion_request(request).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_with_base_url() {
+        use crate::client::CompletionClient;
+
+        let client = super::Client::with_base_url("http://localhost:1234/v1/", "lm-studio");
+        let request = client.post("chat/completions").build().unwrap();
+        assert_eq!(
+            request.url().as_str(),
+            "http://localhost:1234/v1/chat/completions"
+        );
+
+        let model = client
+            .completion_model(crate::providers::openai::GPT_4O)
+            .with_base_url("http://localhost:8080/v1", "vllm");
+        let request = model.client.post("chat/completions").build().unwrap();
+        assert_eq!(
+            request.url().as_str(),
+            "http://localhost:8080/v1/chat/completions"
+        );
+        assert_eq!(request.headers()["Authorization"], "Bearer vllm");
+        assert_eq!(model.model, crate::providers::openai::GPT_4O);
+    }
 }
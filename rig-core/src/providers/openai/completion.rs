@@ -99,6 +99,25 @@ pub struct CompletionResponse {
     pub usage: Option<Usage>,
 }
 
+impl CompletionResponse {
+    /// Content of all the candidate completions of the response, in order. There are multiple
+    /// candidates when the request set `n` (see
+    /// [CompletionRequestBuilder::n_completions](crate::completion::CompletionRequestBuilder::n_completions)).
+    pub fn candidates(
+        &self,
+    ) -> Result<Vec<OneOrMany<completion::AssistantContent>>, CompletionError> {
+        self.choices.iter().map(Choice::content).collect()
+    }
+
+    /// Token-level log probabilities of the first choice, if they were requested
+    /// (see [CompletionRequestBuilder::enable_logprobs](crate::completion::CompletionRequestBuilder::enable_logprobs)).
+    pub fn logprobs(&self) -> Option<&LogProbs> {
+        self.choices
+            .first()
+            .and_then(|choice| choice.logprobs.as_ref())
+    }
+}
+
 impl From<ApiErrorResponse> for CompletionError {
     fn from(err: ApiErrorResponse) -> Self {
         CompletionError::ProviderError(err.message)
@@ -113,7 +132,25 @@ impl TryFrom<CompletionResponse> for completion::CompletionResponse<CompletionRe
             CompletionError::ResponseError("Response contained no choices".to_owned())
         })?;
 
-        let content = match &choice.message {
+        Ok(completion::CompletionResponse {
+            choice: choice.content()?,
+            raw_response: response,
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Choice {
+    pub index: usize,
+    pub message: Message,
+    pub logprobs: Option<LogProbs>,
+    pub finish_reason: String,
+}
+
+impl Choice {
+    /// Converts the message of the choice into assistant content.
+    pub fn content(&self) -> Result<OneOrMany<completion::AssistantContent>, CompletionError> {
+        let content = match &self.message {
             Message::Assistant {
                 content,
                 tool_calls,
@@ -153,25 +190,87 @@ impl TryFrom<CompletionResponse> for completion::CompletionResponse<CompletionRe
             )),
         }?;
 
-        let choice = OneOrMany::many(content).map_err(|_| {
+        OneOrMany::many(content).map_err(|_| {
             CompletionError::ResponseError(
                 "Response contained no message or tool call (empty)".to_owned(),
             )
-        })?;
-
-        Ok(completion::CompletionResponse {
-            choice,
-            raw_response: response,
         })
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct Choice {
-    pub index: usize,
-    pub message: Message,
-    pub logprobs: Option<serde_json::Value>,
-    pub finish_reason: String,
+/// Token-level log probabilities of a choice. `tokens`, `logprobs` and `top_logprobs` all have
+/// one entry per generated token, in order.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(from = "ApiLogProbs", into = "ApiLogProbs")]
+pub struct LogProbs {
+    pub tokens: Vec<String>,
+    pub logprobs: Vec<f64>,
+    /// The most likely alternatives (token, log probability) at each position.
+    /// Empty unless `top_logprobs` was set on the request.
+    pub top_logprobs: Vec<Vec<(String, f64)>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ApiLogProbs {
+    #[serde(default)]
+    content: Option<Vec<ApiTokenLogProb>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ApiTokenLogProb {
+    token: String,
+    logprob: f64,
+    #[serde(default)]
+    top_logprobs: Vec<ApiTopLogProb>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ApiTopLogProb {
+    token: String,
+    logprob: f64,
+}
+
+impl From<ApiLogProbs> for LogProbs {
+    fn from(logprobs: ApiLogProbs) -> Self {
+        let mut result = LogProbs::default();
+        for token in logprobs.content.unwrap_or_default() {
+            result.tokens.push(token.token);
+            result.logprobs.push(token.logprob);
+            result.top_logprobs.push(
+                token
+                    .top_logprobs
+                    .into_iter()
+                    .map(|top| (top.token, top.logprob))
+                    .collect(),
+            );
+        }
+        result
+    }
+}
+
+impl From<LogProbs> for ApiLogProbs {
+    fn from(logprobs: LogProbs) -> Self {
+        let mut top_logprobs = logprobs.top_logprobs.into_iter();
+        let content = logprobs
+            .tokens
+            .into_iter()
+            .zip(logprobs.logprobs)
+            .map(|(token, logprob)| ApiTokenLogProb {
+                token,
+                logprob,
+                top_logprobs: top_logprobs
+                    .next()
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|(token, logprob)| ApiTopLogProb { token, logprob })
+                    .collect(),
+            })
+            .collect();
+
+        ApiLogProbs {
+            content: Some(content),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
@@ -363,6 +462,7 @@ impl TryFrom<message::Message> for Vec<Message> {
                             message::UserContent::ToolResult(message::ToolResult {
                                 id,
                                 content,
+                                ..
                             }) => Ok::<_, message::MessageError>(Message::ToolResult {
                                 tool_call_id: id,
                                 content: content.try_map(|content| match content {
@@ -586,6 +686,13 @@ impl FromStr for AssistantContent {
         })
     }
 }
+impl SystemContent {
+    /// The text of the system message.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
 impl From<String> for SystemContent {
     fn from(s: String) -> Self {
         SystemContent {
@@ -621,6 +728,15 @@ impl CompletionModel {
         }
     }
 
+    /// Send the requests of the model to the OpenAI compatible API served at `url`
+    /// (e.g.: `http://localhost:8000/v1` for vLLM), authenticated with `api_key` (the API key of
+    /// its client is not sent to the new server).
+    pub fn with_base_url(mut self, url: &str, api_key: &str) -> Self {
+        self.client.base_url = url.trim_end_matches('/').to_string();
+        self.client.api_key = api_key.to_string();
+        self
+    }
+
     pub(crate) fn create_completion_request(
         &self,
         completion_request: CompletionRequest,
@@ -632,10 +748,13 @@ impl CompletionModel {
         }
         partial_history.extend(completion_request.chat_history);
 
-        // Initialize full history with preamble (or empty if non-existent)
+        // Initialize full history with preamble and system messages (or empty if non-existent)
         let mut full_history: Vec<Message> = completion_request
             .preamble
-            .map_or_else(Vec::new, |preamble| vec![Message::system(&preamble)]);
+            .iter()
+            .chain(&completion_request.system_messages)
+            .map(|content| Message::system(content))
+            .collect();
 
         // Convert and extend the rest of the history
         full_history.extend(
@@ -659,7 +778,7 @@ impl CompletionModel {
                 "model": self.model,
                 "messages": full_history,
                 "tools": completion_request.tools.into_iter().map(ToolDefinition::from).collect::<Vec<_>>(),
-                "tool_choice": "auto",
+                "tool_choice": serde_json::Value::from(completion_request.tool_choice.unwrap_or_default()),
             })
         };
 
@@ -719,7 +838,7 @@ impl completion::CompletionModel for CompletionModel {
                 ApiResponse::Err(err) => Err(CompletionError::ProviderError(err.message)),
             }
         } else {
-            Err(CompletionError::ProviderError(response.text().await?))
+            Err(CompletionError::from_response(response).await)
         }
     }
 
@@ -116,3 +116,201 @@ impl EmbeddingModel {
         }
     }
 }
+
+/// Default number of concurrent requests of a [BatchEmbedder]
+#[cfg(not(target_arch = "wasm32"))]
+const DEFAULT_CONCURRENCY: usize = 4;
+/// Default number of retries of a failed batch
+#[cfg(not(target_arch = "wasm32"))]
+const DEFAULT_MAX_RETRIES: usize = 3;
+/// Default delay before the first retry of a failed batch (doubled on each retry)
+#[cfg(not(target_arch = "wasm32"))]
+const DEFAULT_RETRY_DELAY: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// Embeds many documents by splitting them into batches, sent concurrently.
+///
+/// The number of concurrent requests is bounded (see [BatchEmbedder::concurrency]) to stay
+/// within the rate limits of the provider, and failed batches (e.g.: rate limited requests) are
+/// retried with an exponential backoff.
+///
+/// # Example
+/// ```
+/// use rig::providers::openai::{self, embedding::BatchEmbedder};
+///
+/// let openai = openai::Client::from_env();
+/// let embedder = BatchEmbedder::new(openai.embedding_model(openai::TEXT_EMBEDDING_3_SMALL))
+///     .concurrency(8);
+///
+/// let embeddings = embedder.embed_batch(documents, 256).await?;
+/// ```
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Clone)]
+pub struct BatchEmbedder<M: embeddings::EmbeddingModel = EmbeddingModel> {
+    model: M,
+    concurrency: usize,
+    max_retries: usize,
+    retry_delay: std::time::Duration,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<M: embeddings::EmbeddingModel> BatchEmbedder<M> {
+    pub fn new(model: M) -> Self {
+        Self {
+            model,
+            concurrency: DEFAULT_CONCURRENCY,
+            max_retries: DEFAULT_MAX_RETRIES,
+            retry_delay: DEFAULT_RETRY_DELAY,
+        }
+    }
+
+    /// Set the maximum number of batches embedded concurrently (defaults to 4).
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Set the number of times a failed batch is retried before giving up (defaults to 3).
+    pub fn max_retries(mut self, max_retries: usize) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Set the delay before the first retry of a failed batch, doubled on each retry
+    /// (defaults to 500ms).
+    pub fn retry_delay(mut self, retry_delay: std::time::Duration) -> Self {
+        self.retry_delay = retry_delay;
+        self
+    }
+
+    /// Embed `texts` in batches of at most `batch_size` documents (capped to the maximum
+    /// number of documents per request of the model).
+    /// The embeddings are returned in the order of `texts`.
+    pub async fn embed_batch(
+        &self,
+        texts: Vec<String>,
+        batch_size: usize,
+    ) -> Result<Vec<Vec<f64>>, EmbeddingError> {
+        use futures::{StreamExt, TryStreamExt};
+
+        let batch_size = batch_size.clamp(1, M::MAX_DOCUMENTS);
+
+        // `buffered` yields the results in the order of the batches, regardless of the order
+        // in which the requests complete
+        let batches = futures::stream::iter(texts.chunks(batch_size).map(<[String]>::to_vec))
+            .map(|batch| self.embed_with_retries(batch))
+            .buffered(self.concurrency)
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        Ok(batches.into_iter().flatten().collect())
+    }
+
+    async fn embed_with_retries(
+        &self,
+        batch: Vec<String>,
+    ) -> Result<Vec<Vec<f64>>, EmbeddingError> {
+        let mut retries = 0;
+        loop {
+            match self.model.embed_texts(batch.clone()).await {
+                Ok(embeddings) => {
+                    return Ok(embeddings
+                        .into_iter()
+                        .map(|embedding| embedding.vec)
+                        .collect())
+                }
+                // Only request and provider errors (e.g.: timeouts, rate limits) are transient
+                Err(e @ (EmbeddingError::HttpError(_) | EmbeddingError::ProviderError(_)))
+                    if retries < self.max_retries =>
+                {
+                    let delay = self.retry_delay * 2u32.pow(retries as u32);
+                    retries += 1;
+                    tracing::warn!(target: "rig",
+                        "Embedding batch failed ({}), retry {}/{} in {:?}",
+                        e, retries, self.max_retries, delay
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use super::*;
+
+    /// Embeds each document as its length, failing every other request
+    #[derive(Clone, Default)]
+    struct FlakyEmbeddingModel {
+        requests: Arc<AtomicUsize>,
+    }
+
+    impl embeddings::EmbeddingModel for FlakyEmbeddingModel {
+        const MAX_DOCUMENTS: usize = 3;
+
+        fn ndims(&self) -> usize {
+            1
+        }
+
+        async fn embed_texts(
+            &self,
+            documents: impl IntoIterator<Item = String>,
+        ) -> Result<Vec<embeddings::Embedding>, EmbeddingError> {
+            if self
+                .requests
+                .fetch_add(1, Ordering::SeqCst)
+                .is_multiple_of(2)
+            {
+                return Err(EmbeddingError::ProviderError("Rate limit reached".into()));
+            }
+
+            Ok(documents
+                .into_iter()
+                .map(|document| embeddings::Embedding {
+                    vec: vec![document.len() as f64],
+                    document,
+                })
+                .collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_embed_batch() {
+        let model = FlakyEmbeddingModel::default();
+        let embedder = BatchEmbedder::new(model.clone())
+            .concurrency(1)
+            .retry_delay(std::time::Duration::ZERO);
+
+        let texts = ["a", "bb", "ccc", "dddd", "eeeee", "ffffff", "g"]
+            .map(String::from)
+            .to_vec();
+        let embeddings = embedder.embed_batch(texts, 10).await.unwrap();
+
+        assert_eq!(
+            embeddings,
+            vec![
+                vec![1.0],
+                vec![2.0],
+                vec![3.0],
+                vec![4.0],
+                vec![5.0],
+                vec![6.0],
+                vec![1.0]
+            ]
+        );
+        // 3 batches (of at most `MAX_DOCUMENTS` documents), each failing once
+        assert_eq!(model.requests.load(Ordering::SeqCst), 6);
+
+        let embedder = embedder.max_retries(0);
+        assert!(embedder
+            .embed_batch(vec!["a".to_string()], 10)
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,370 @@
+//! OpenAI fine-tuning API integration.
+//!
+//! Training examples are uploaded as a JSONL file, from which a fine-tuning job creates a new
+//! model. Once the job has succeeded, the fine-tuned model can be used like any other model.
+//!
+//! # Example
+//! ```
+//! use rig::providers::openai::{self, fine_tuning::{ChatExample, FineTuningClient, FineTuningStatus}};
+//!
+//! let client = openai::Client::from_env();
+//! let fine_tuning = FineTuningClient::new(&client);
+//!
+//! let examples = vec![
+//!     ChatExample::new()
+//!         .system("Marv is a factual chatbot that is also sarcastic.")
+//!         .user("What's the capital of France?")
+//!         .assistant("Paris, as if everyone doesn't know that already."),
+//!     // ... at least 10 examples
+//! ];
+//!
+//! let file_id = fine_tuning.upload_training_file(examples).await?;
+//! let job = fine_tuning.create_job("gpt-4o-mini-2024-07-18", &file_id, None).await?;
+//!
+//! // ... later on ...
+//! let job = fine_tuning.get_job(&job.id).await?;
+//! if let Some(model_name) = job.model_name() {
+//!     let model = openai::FineTunedModel::new(&client, model_name)?;
+//! }
+//! ```
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::{
+    completion::{self, CompletionError, CompletionRequest},
+    streaming::StreamingCompletionResponse,
+};
+
+use super::{batch::send, Client};
+
+/// The role of the author of a message of a [ChatExample].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExampleRole {
+    System,
+    User,
+    Assistant,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ExampleMessage {
+    pub role: ExampleRole,
+    pub content: String,
+}
+
+/// A training example (i.e.: a conversation), serialized as a line of the JSONL training file:
+/// `{"messages": [{"role": "system", "content": "..."}, {"role": "user", ...}, ...]}`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ChatExample {
+    pub messages: Vec<ExampleMessage>,
+}
+
+impl ChatExample {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn message(mut self, role: ExampleRole, content: &str) -> Self {
+        self.messages.push(ExampleMessage {
+            role,
+            content: content.to_string(),
+        });
+        self
+    }
+
+    /// Add a system message (usually the first message of the example).
+    pub fn system(self, content: &str) -> Self {
+        self.message(ExampleRole::System, content)
+    }
+
+    /// Add a user message.
+    pub fn user(self, content: &str) -> Self {
+        self.message(ExampleRole::User, content)
+    }
+
+    /// Add an assistant message (i.e.: the expected response of the model).
+    pub fn assistant(self, content: &str) -> Self {
+        self.message(ExampleRole::Assistant, content)
+    }
+}
+
+/// The hyperparameters of a fine-tuning job (chosen by OpenAI when not set).
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Hyperparameters {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub n_epochs: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub batch_size: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub learning_rate_multiplier: Option<f64>,
+}
+
+/// The status of a fine-tuning job.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FineTuningStatus {
+    ValidatingFiles,
+    Queued,
+    Running,
+    Succeeded,
+    Failed,
+    Cancelled,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct FineTuningError {
+    pub code: Option<String>,
+    pub message: Option<String>,
+}
+
+/// A fine-tuning job.
+#[derive(Clone, Debug, Deserialize)]
+pub struct FineTuningJob {
+    /// Id of the job (e.g.: ftjob-abc123)
+    pub id: String,
+    /// The base model being fine-tuned
+    pub model: String,
+    pub status: FineTuningStatus,
+    /// Id of the fine-tuned model, once the job has succeeded
+    pub fine_tuned_model: Option<String>,
+    pub training_file: String,
+    pub created_at: u64,
+    pub finished_at: Option<u64>,
+    /// Error of the job, if it failed
+    pub error: Option<FineTuningError>,
+}
+
+impl FineTuningJob {
+    /// Returns the id of the fine-tuned model (e.g.: `ft:gpt-4o-mini-2024-07-18:org::abc123`),
+    /// to use with [FineTunedModel::new], once the job has succeeded.
+    pub fn model_name(&self) -> Option<&str> {
+        self.fine_tuned_model.as_deref()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct FileObject {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ListResponse<T> {
+    data: Vec<T>,
+}
+
+/// A client of the fine-tuning API.
+#[derive(Clone, Debug)]
+pub struct FineTuningClient {
+    client: Client,
+}
+
+impl FineTuningClient {
+    pub fn new(client: &Client) -> Self {
+        Self {
+            client: client.clone(),
+        }
+    }
+
+    /// Upload the training examples as a JSONL file and return the id of the file.
+    pub async fn upload_training_file(
+        &self,
+        data: Vec<ChatExample>,
+    ) -> Result<String, CompletionError> {
+        let file = reqwest::multipart::Form::new()
+            .text("purpose", "fine-tune")
+            .part(
+                "file",
+                reqwest::multipart::Part::text(training_file(&data)?).file_name("training.jsonl"),
+            );
+        let file: FileObject = send(self.client.post("/files").multipart(file)).await?;
+
+        Ok(file.id)
+    }
+
+    /// Create a job fine-tuning `base_model` (e.g.: `gpt-4o-mini-2024-07-18`) on the training
+    /// file `file_id`.
+    pub async fn create_job(
+        &self,
+        base_model: &str,
+        file_id: &str,
+        hyperparams: Option<Hyperparameters>,
+    ) -> Result<FineTuningJob, CompletionError> {
+        let mut request = json!({
+            "model": base_model,
+            "training_file": file_id,
+        });
+        if let Some(hyperparams) = hyperparams {
+            request["hyperparameters"] = serde_json::to_value(hyperparams)?;
+        }
+
+        let job: FineTuningJob = send(self.client.post("/fine_tuning/jobs").json(&request)).await?;
+        tracing::info!(target: "rig", "Created OpenAI fine-tuning job {} of {base_model}", job.id);
+
+        Ok(job)
+    }
+
+    /// Returns the job `job_id`, e.g.: to check its status.
+    pub async fn get_job(&self, job_id: &str) -> Result<FineTuningJob, CompletionError> {
+        send(self.client.get(&format!("/fine_tuning/jobs/{job_id}"))).await
+    }
+
+    /// Returns the fine-tuning jobs of the organization, most recent first.
+    pub async fn list_jobs(&self) -> Result<Vec<FineTuningJob>, CompletionError> {
+        let jobs: ListResponse<FineTuningJob> = send(self.client.get("/fine_tuning/jobs")).await?;
+
+        Ok(jobs.data)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Invalid fine-tuned model id `{id}`: {reason}")]
+pub struct InvalidModelError {
+    pub id: String,
+    pub reason: String,
+}
+
+/// A fine-tuned OpenAI completion model, whose id is validated at construction.
+///
+/// The id of a fine-tuned model has the form `ft:{base_model}:{organization}:{suffix}:{id}`
+/// (e.g.: `ft:gpt-4o-mini-2024-07-18:my-org:support:9wXyZ1aB`), where the suffix can be empty.
+#[derive(Clone)]
+pub struct FineTunedModel {
+    model: super::CompletionModel,
+}
+
+impl FineTunedModel {
+    pub fn new(client: &Client, id: &str) -> Result<Self, InvalidModelError> {
+        let invalid = |reason: &str| InvalidModelError {
+            id: id.to_string(),
+            reason: reason.to_string(),
+        };
+
+        let Some(parts) = id.strip_prefix("ft:") else {
+            return Err(invalid("it should start with `ft:`"));
+        };
+        match parts.split(':').collect::<Vec<_>>()[..] {
+            [base_model, organization, _suffix, model_id]
+                if !base_model.is_empty() && !organization.is_empty() && !model_id.is_empty() =>
+            {
+                Ok(Self {
+                    model: super::CompletionModel::new(client.clone(), id),
+                })
+            }
+            _ => Err(invalid(
+                "it should have the form `ft:{base_model}:{organization}:{suffix}:{id}`",
+            )),
+        }
+    }
+
+    /// Returns the id of the fine-tuned model.
+    pub fn id(&self) -> &str {
+        &self.model.model
+    }
+
+    /// Returns the model the fine-tuned model was trained from (e.g.: `gpt-4o-mini-2024-07-18`).
+    pub fn base_model(&self) -> &str {
+        self.id()
+            .split(':')
+            .nth(1)
+            .expect("The id should have been validated")
+    }
+}
+
+impl completion::CompletionModel for FineTunedModel {
+    type Response = super::CompletionResponse;
+    type StreamingResponse = super::StreamingCompletionResponse;
+
+    #[cfg_attr(feature = "worker", worker::send)]
+    async fn completion(
+        &self,
+        request: CompletionRequest,
+    ) -> Result<completion::CompletionResponse<Self::Response>, CompletionError> {
+        completion::CompletionModel::completion(&self.model, request).await
+    }
+
+    #[cfg_attr(feature = "worker", worker::send)]
+    async fn stream(
+        &self,
+        request: CompletionRequest,
+    ) -> Result<StreamingCompletionResponse<Self::StreamingResponse>, CompletionError> {
+        completion::CompletionModel::stream(&self.model, request).await
+    }
+}
+
+/// Returns the JSONL training file of the examples.
+fn training_file(data: &[ChatExample]) -> Result<String, CompletionError> {
+    Ok(data
+        .iter()
+        .map(serde_json::to_string)
+        .collect::<Result<Vec<_>, _>>()?
+        .join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_training_file() {
+        let data = vec![
+            ChatExample::new()
+                .system("Be brief.")
+                .user("Capital of France?")
+                .assistant("Paris."),
+            ChatExample::new().user("2 + 2?").assistant("4."),
+        ];
+
+        assert_eq!(
+            training_file(&data).unwrap(),
+            r#"{"messages":[{"role":"system","content":"Be brief."},{"role":"user","content":"Capital of France?"},{"role":"assistant","content":"Paris."}]}
+{"messages":[{"role":"user","content":"2 + 2?"},{"role":"assistant","content":"4."}]}"#
+        );
+    }
+
+    #[test]
+    fn test_deserialize_job() {
+        let job: FineTuningJob = serde_json::from_str(
+            r#"{
+                "object": "fine_tuning.job",
+                "id": "ftjob-abc123",
+                "model": "gpt-4o-mini-2024-07-18",
+                "created_at": 1721764800,
+                "finished_at": 1721765800,
+                "fine_tuned_model": "ft:gpt-4o-mini-2024-07-18:my-org::abc123",
+                "organization_id": "org-123",
+                "result_files": ["file-abc123"],
+                "status": "succeeded",
+                "validation_file": null,
+                "training_file": "file-abc123",
+                "error": null
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(job.status, FineTuningStatus::Succeeded);
+        assert_eq!(
+            job.model_name(),
+            Some("ft:gpt-4o-mini-2024-07-18:my-org::abc123")
+        );
+    }
+
+    #[test]
+    fn test_fine_tuned_model() {
+        let client = Client::new("YOUR_API_KEY");
+
+        let model =
+            FineTunedModel::new(&client, "ft:gpt-4o-mini-2024-07-18:my-org::9wXyZ1aB").unwrap();
+        assert_eq!(model.id(), "ft:gpt-4o-mini-2024-07-18:my-org::9wXyZ1aB");
+        assert_eq!(model.base_model(), "gpt-4o-mini-2024-07-18");
+
+        for id in [
+            "gpt-4o-mini",
+            "ft-gpt-4o-mini:my-org::9wXyZ1aB",
+            "ft:gpt-4o-mini:my-org:9wXyZ1aB",
+            "ft::my-org::9wXyZ1aB",
+            "ft:gpt-4o-mini:my-org:support:",
+        ] {
+            assert!(FineTunedModel::new(&client, id).is_err(), "{id}");
+        }
+    }
+}
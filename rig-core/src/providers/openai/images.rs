@@ -0,0 +1,217 @@
+//! OpenAI image generation API integration, exposing the DALL-E 3 options.
+//!
+//! Unlike the [ImageGenerationModel](super::ImageGenerationModel), which returns the decoded
+//! image, the [ImageClient] returns the URL (or the base64 encoded data) of the image, along
+//! with the prompt revised by DALL-E 3.
+//!
+//! Note: requires the `image` feature.
+//!
+//! # Example
+//! ```
+//! use rig::providers::openai::{self, images::{ImageClient, ImageGenerationConfig, ImageQuality, ImageSize}};
+//!
+//! let client = openai::Client::from_env();
+//! let images = ImageClient::new(&client);
+//!
+//! let response = images
+//!     .generate(
+//!         "A castle sitting upon a large mountain, overlooking the water.",
+//!         ImageGenerationConfig::default()
+//!             .size(ImageSize::Landscape)
+//!             .quality(ImageQuality::Hd),
+//!     )
+//!     .await?;
+//! println!("Image: {}", response.url.unwrap());
+//! ```
+use serde::{Deserialize, Serialize};
+
+use crate::image_generation::ImageGenerationError;
+
+use super::{ApiResponse, Client, DALL_E_3};
+
+/// The size of the generated image.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ImageSize {
+    #[default]
+    #[serde(rename = "1024x1024")]
+    Square,
+    #[serde(rename = "1792x1024")]
+    Landscape,
+    #[serde(rename = "1024x1792")]
+    Portrait,
+}
+
+/// The quality of the generated image (`hd` images have finer details, but take longer to
+/// generate).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageQuality {
+    Standard,
+    Hd,
+}
+
+/// The style of the generated image.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageStyle {
+    /// Hyper-real and dramatic images
+    Vivid,
+    /// More natural, less hyper-real images
+    Natural,
+}
+
+/// The format in which the generated image is returned.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImageResponseFormat {
+    /// URL of the image, valid for 60 minutes
+    Url,
+    /// Base64 encoded image
+    B64Json,
+}
+
+/// The options of an image generation. Unset options are chosen by OpenAI.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ImageGenerationConfig {
+    /// The image generation model (defaults to [DALL_E_3])
+    pub model: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<ImageSize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quality: Option<ImageQuality>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<ImageStyle>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_format: Option<ImageResponseFormat>,
+}
+
+impl Default for ImageGenerationConfig {
+    fn default() -> Self {
+        Self {
+            model: DALL_E_3.to_string(),
+            size: None,
+            quality: None,
+            style: None,
+            response_format: None,
+        }
+    }
+}
+
+impl ImageGenerationConfig {
+    pub fn model(mut self, model: &str) -> Self {
+        self.model = model.to_string();
+        self
+    }
+
+    pub fn size(mut self, size: ImageSize) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    pub fn quality(mut self, quality: ImageQuality) -> Self {
+        self.quality = Some(quality);
+        self
+    }
+
+    pub fn style(mut self, style: ImageStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    pub fn response_format(mut self, response_format: ImageResponseFormat) -> Self {
+        self.response_format = Some(response_format);
+        self
+    }
+}
+
+/// A generated image. Either `url` or `b64_json` is set, depending on the response format.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct ImageGenerationResponse {
+    pub url: Option<String>,
+    pub b64_json: Option<String>,
+    /// The prompt actually used to generate the image (DALL-E 3 rewrites the prompts)
+    #[serde(default)]
+    pub revised_prompt: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ImagesResponse {
+    data: Vec<ImageGenerationResponse>,
+}
+
+/// A client of the image generation API.
+#[derive(Clone, Debug)]
+pub struct ImageClient {
+    client: Client,
+}
+
+impl ImageClient {
+    pub fn new(client: &Client) -> Self {
+        Self {
+            client: client.clone(),
+        }
+    }
+
+    /// Generate an image from `prompt`.
+    pub async fn generate(
+        &self,
+        prompt: &str,
+        config: ImageGenerationConfig,
+    ) -> Result<ImageGenerationResponse, ImageGenerationError> {
+        let mut request = serde_json::to_value(config)?;
+        request["prompt"] = prompt.into();
+
+        let response = self
+            .client
+            .post("/images/generations")
+            .json(&request)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(ImageGenerationError::ProviderError(format!(
+                "{}: {}",
+                response.status(),
+                response.text().await?
+            )));
+        }
+
+        match response.json::<ApiResponse<ImagesResponse>>().await? {
+            ApiResponse::Ok(response) => response.data.into_iter().next().ok_or_else(|| {
+                ImageGenerationError::ResponseError("Response contained no image".into())
+            }),
+            ApiResponse::Err(err) => Err(ImageGenerationError::ProviderError(err.message)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_serialize_config() {
+        let config = ImageGenerationConfig::default()
+            .size(ImageSize::Portrait)
+            .quality(ImageQuality::Hd)
+            .style(ImageStyle::Natural)
+            .response_format(ImageResponseFormat::B64Json);
+
+        assert_eq!(
+            serde_json::to_value(config).unwrap(),
+            json!({
+                "model": "dall-e-3",
+                "size": "1024x1792",
+                "quality": "hd",
+                "style": "natural",
+                "response_format": "b64_json",
+            })
+        );
+        assert_eq!(
+            serde_json::to_value(ImageGenerationConfig::default()).unwrap(),
+            json!({"model": "dall-e-3"})
+        );
+    }
+}
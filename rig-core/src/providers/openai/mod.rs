@@ -8,20 +8,32 @@
 //!
 //! let gpt4o = client.completion_model(openai::GPT_4O);
 //! ```
+#[cfg(not(target_arch = "wasm32"))]
+pub mod assistants;
+pub mod batch;
 pub mod client;
 pub mod completion;
 pub mod embedding;
+pub mod fine_tuning;
+pub mod moderation;
 
+#[cfg(feature = "audio")]
+pub mod audio;
 #[cfg(feature = "audio")]
 pub mod audio_generation;
 #[cfg(feature = "image")]
 pub mod image_generation;
+#[cfg(feature = "image")]
+pub mod images;
 pub mod streaming;
 pub mod transcription;
+pub mod vision;
+pub mod whisper;
 
 pub use client::*;
 pub use completion::*;
 pub use embedding::*;
+pub use fine_tuning::{FineTunedModel, InvalidModelError};
 
 #[cfg(feature = "audio")]
 pub use audio_generation::{TTS_1, TTS_1_HD};
@@ -30,3 +42,4 @@ pub use audio_generation::{TTS_1, TTS_1_HD};
 pub use image_generation::*;
 pub use streaming::*;
 pub use transcription::*;
+pub use whisper::{SpeechToText, TranscriptWithTimestamps, WordTimestamp};
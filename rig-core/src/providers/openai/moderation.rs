@@ -0,0 +1,221 @@
+//! OpenAI moderation API integration.
+//!
+//! The [ModerationClient] checks whether a text is potentially harmful. It implements the
+//! [InputClassifier] and [OutputClassifier] traits, so it can screen the prompts and responses
+//! of any model with a [GuardrailsModel](crate::guardrails::GuardrailsModel).
+//!
+//! # Example
+//! ```
+//! use rig::{
+//!     guardrails::GuardrailsModel,
+//!     providers::openai::{self, moderation::ModerationClient},
+//! };
+//!
+//! let client = openai::Client::from_env();
+//! let moderation = ModerationClient::new(&client);
+//!
+//! let result = moderation.check("I want to hurt someone").await?;
+//! println!("Flagged: {}, violence: {}", result.flagged, result.category_scores.violence);
+//!
+//! let model = GuardrailsModel::new(
+//!     client.completion_model(openai::GPT_4O),
+//!     moderation.clone(),
+//!     moderation,
+//! );
+//! ```
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::{
+    completion::CompletionError,
+    guardrails::{Classification, InputClassifier, OutputClassifier},
+};
+
+use super::{batch::send, Client};
+
+/// The latest omni-moderation model (text and images)
+pub const OMNI_MODERATION_LATEST: &str = "omni-moderation-latest";
+/// The latest (legacy) text-only moderation model
+pub const TEXT_MODERATION_LATEST: &str = "text-moderation-latest";
+
+/// A value (flag or score) per moderation category.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ModerationCategories<T> {
+    pub sexual: T,
+    #[serde(rename = "sexual/minors")]
+    pub sexual_minors: T,
+    pub harassment: T,
+    #[serde(rename = "harassment/threatening")]
+    pub harassment_threatening: T,
+    pub hate: T,
+    #[serde(rename = "hate/threatening")]
+    pub hate_threatening: T,
+    pub illicit: T,
+    #[serde(rename = "illicit/violent")]
+    pub illicit_violent: T,
+    #[serde(rename = "self-harm")]
+    pub self_harm: T,
+    #[serde(rename = "self-harm/intent")]
+    pub self_harm_intent: T,
+    #[serde(rename = "self-harm/instructions")]
+    pub self_harm_instructions: T,
+    pub violence: T,
+    #[serde(rename = "violence/graphic")]
+    pub violence_graphic: T,
+}
+
+impl<T> ModerationCategories<T> {
+    /// Returns the value of each category, along with its name (as named by OpenAI).
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, &T)> {
+        [
+            ("sexual", &self.sexual),
+            ("sexual/minors", &self.sexual_minors),
+            ("harassment", &self.harassment),
+            ("harassment/threatening", &self.harassment_threatening),
+            ("hate", &self.hate),
+            ("hate/threatening", &self.hate_threatening),
+            ("illicit", &self.illicit),
+            ("illicit/violent", &self.illicit_violent),
+            ("self-harm", &self.self_harm),
+            ("self-harm/intent", &self.self_harm_intent),
+            ("self-harm/instructions", &self.self_harm_instructions),
+            ("violence", &self.violence),
+            ("violence/graphic", &self.violence_graphic),
+        ]
+        .into_iter()
+    }
+}
+
+/// The result of the moderation of a text.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ModerationResult {
+    /// Whether the text is potentially harmful in any category
+    pub flagged: bool,
+    /// Whether the text is flagged in each category
+    pub categories: ModerationCategories<bool>,
+    /// The confidence (between 0 and 1) of the text belonging to each category
+    pub category_scores: ModerationCategories<f64>,
+}
+
+impl ModerationResult {
+    /// Returns the names of the categories in which the text is flagged.
+    pub fn flagged_categories(&self) -> Vec<&'static str> {
+        self.categories
+            .iter()
+            .filter(|(_, flagged)| **flagged)
+            .map(|(category, _)| category)
+            .collect()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ModerationResponse {
+    results: Vec<ModerationResult>,
+}
+
+/// A client of the moderation API.
+#[derive(Clone, Debug)]
+pub struct ModerationClient {
+    client: Client,
+    model: String,
+}
+
+impl ModerationClient {
+    /// Create a moderation client using the [OMNI_MODERATION_LATEST] model.
+    pub fn new(client: &Client) -> Self {
+        Self {
+            client: client.clone(),
+            model: OMNI_MODERATION_LATEST.to_string(),
+        }
+    }
+
+    /// Set the moderation model.
+    pub fn model(mut self, model: &str) -> Self {
+        self.model = model.to_string();
+        self
+    }
+
+    /// Check whether `text` is potentially harmful.
+    pub async fn check(&self, text: &str) -> Result<ModerationResult, CompletionError> {
+        let response: ModerationResponse = send(self.client.post("/moderations").json(&json!({
+            "model": self.model,
+            "input": text,
+        })))
+        .await?;
+
+        response.results.into_iter().next().ok_or_else(|| {
+            CompletionError::ResponseError("Moderation response contained no result".into())
+        })
+    }
+
+    async fn classification(&self, text: &str) -> Result<Classification, CompletionError> {
+        let result = self.check(text).await?;
+        if !result.flagged {
+            return Ok(Classification::Allowed);
+        }
+
+        let categories = result.flagged_categories();
+        Ok(Classification::flagged(
+            categories.first().copied().unwrap_or("moderation"),
+            format!(
+                "Flagged by the OpenAI moderation endpoint ({})",
+                categories.join(", ")
+            ),
+        ))
+    }
+}
+
+impl InputClassifier for ModerationClient {
+    async fn classify(&self, input: &str) -> Result<Classification, CompletionError> {
+        self.classification(input).await
+    }
+}
+
+impl OutputClassifier for ModerationClient {
+    async fn classify(&self, output: &str) -> Result<Classification, CompletionError> {
+        self.classification(output).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_moderation_response() {
+        let response: ModerationResponse = serde_json::from_str(
+            r#"{
+                "id": "modr-0d9740456c391e43c445bf0f010940c7",
+                "model": "omni-moderation-latest",
+                "results": [{
+                    "flagged": true,
+                    "categories": {
+                        "harassment": true, "harassment/threatening": true, "sexual": false,
+                        "hate": false, "hate/threatening": false, "illicit": false,
+                        "illicit/violent": false, "self-harm/intent": false,
+                        "self-harm/instructions": false, "self-harm": false,
+                        "sexual/minors": false, "violence": true, "violence/graphic": false
+                    },
+                    "category_scores": {
+                        "harassment": 0.81, "harassment/threatening": 0.80, "sexual": 0.00002,
+                        "hate": 0.02, "hate/threatening": 0.01, "illicit": 0.004,
+                        "illicit/violent": 0.002, "self-harm/intent": 0.0002,
+                        "self-harm/instructions": 0.0001, "self-harm": 0.0004,
+                        "sexual/minors": 0.00001, "violence": 0.91, "violence/graphic": 0.006
+                    },
+                    "category_applied_input_types": {"violence": ["text"]}
+                }]
+            }"#,
+        )
+        .unwrap();
+
+        let result = &response.results[0];
+        assert!(result.flagged);
+        assert_eq!(result.category_scores.violence, 0.91);
+        assert_eq!(
+            result.flagged_categories(),
+            vec!["harassment", "harassment/threatening", "violence"]
+        );
+    }
+}
@@ -29,7 +29,7 @@ impl TryFrom<TranscriptionResponse>
 
 #[derive(Clone)]
 pub struct TranscriptionModel {
-    client: Client,
+    pub(crate) client: Client,
     /// Name of the model (e.g.: gpt-3.5-turbo-1106)
     pub model: String,
 }
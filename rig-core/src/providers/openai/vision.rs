@@ -0,0 +1,228 @@
+//! This module contains the [VisionAgent] struct, an [Agent] wrapper for prompts with images.
+//!
+//! The agent checks on creation that its model accepts image inputs, and converts the images of
+//! each prompt to the format of the chat completions API (`image_url` content parts, whose URL
+//! is either a web URL or a `data:` URL embedding the base64 encoded image).
+//!
+//! # Example
+//! ```rust
+//! use rig::{
+//!     message::{Image, ImageDetail},
+//!     providers::openai::{self, vision::VisionAgent},
+//! };
+//!
+//! let openai = openai::Client::from_env();
+//!
+//! let agent = VisionAgent::new(openai.agent(openai::GPT_4O).build())?;
+//!
+//! let response = agent
+//!     .prompt_with_images(
+//!         "What is in this image?",
+//!         vec![Image {
+//!             data: "https://upload.wikimedia.org/wikipedia/commons/a/a7/Camponotus_flavomarginatus_ant.jpg".into(),
+//!             detail: Some(ImageDetail::Low),
+//!             ..Default::default()
+//!         }],
+//!     )
+//!     .await?;
+//! ```
+use crate::{
+    agent::Agent,
+    completion::{CompletionError, Message, Prompt, PromptError},
+    message::{ContentFormat, Image, MimeType, Text, UserContent},
+    OneOrMany,
+};
+
+use super::CompletionModel;
+
+/// Prefixes of the names of the models accepting image inputs.
+const VISION_MODEL_PREFIXES: &[&str] = &[
+    "gpt-4o",
+    "chatgpt-4o",
+    "gpt-4-turbo",
+    "gpt-4.1",
+    "gpt-4.5",
+    "gpt-4-vision",
+    "gpt-4-1106-vision",
+    "o1",
+    "o3",
+    "o4",
+];
+
+/// Prefixes of the names of text-only models matching [VISION_MODEL_PREFIXES].
+const TEXT_ONLY_MODEL_PREFIXES: &[&str] =
+    &["gpt-4-turbo-preview", "o1-mini", "o1-preview", "o3-mini"];
+
+/// Whether the model `model` accepts image inputs (fine-tuned models are checked by their
+/// base model, e.g.: `ft:gpt-4o-2024-08-06:my-org::abc123`).
+pub fn supports_vision(model: &str) -> bool {
+    let model = model.strip_prefix("ft:").unwrap_or(model);
+
+    VISION_MODEL_PREFIXES
+        .iter()
+        .any(|prefix| model.starts_with(prefix))
+        && !TEXT_ONLY_MODEL_PREFIXES
+            .iter()
+            .any(|prefix| model.starts_with(prefix))
+}
+
+/// Convert `image` to an image accepted by the chat completions API, whose data is a web URL or
+/// a `data:` URL.
+fn to_image_url(image: Image) -> Result<Image, CompletionError> {
+    let is_url = ["http://", "https://", "data:"]
+        .iter()
+        .any(|scheme| image.data.starts_with(scheme));
+    if is_url || image.format == Some(ContentFormat::String) {
+        return Ok(image);
+    }
+
+    let media_type = image.media_type.as_ref().ok_or_else(|| {
+        CompletionError::RequestError("The media type of base64 encoded images is required".into())
+    })?;
+
+    Ok(Image {
+        data: format!("data:{};base64,{}", media_type.to_mime_type(), image.data),
+        format: Some(ContentFormat::String),
+        ..image
+    })
+}
+
+/// An agent whose model accepts image inputs, converting the images of its prompts to the
+/// format expected by OpenAI.
+pub struct VisionAgent {
+    agent: Agent<CompletionModel>,
+}
+
+impl VisionAgent {
+    /// Wrap `agent`, failing with [CompletionError::UnsupportedFeature] if its model does not
+    /// accept image inputs.
+    pub fn new(agent: Agent<CompletionModel>) -> Result<Self, CompletionError> {
+        if !supports_vision(&agent.model.model) {
+            return Err(CompletionError::UnsupportedFeature(format!(
+                "Model {} does not accept image inputs",
+                agent.model.model
+            )));
+        }
+
+        Ok(Self { agent })
+    }
+
+    /// Prompt the agent with `prompt` and the images `images`.
+    pub async fn prompt_with_images(
+        &self,
+        prompt: &str,
+        images: Vec<Image>,
+    ) -> Result<String, PromptError> {
+        let content = std::iter::once(UserContent::Text(Text {
+            text: prompt.to_string(),
+        }))
+        .chain(images.into_iter().map(UserContent::Image));
+
+        self.prompt(Message::User {
+            content: OneOrMany::many(content).expect("There is at least the text of the prompt"),
+        })
+        .await
+    }
+}
+
+#[allow(refining_impl_trait)]
+impl Prompt for VisionAgent {
+    async fn prompt(&self, prompt: impl Into<Message> + Send) -> Result<String, PromptError> {
+        let prompt = match prompt.into() {
+            Message::User { content } => Message::User {
+                content: OneOrMany::many(
+                    content
+                        .into_iter()
+                        .map(|content| match content {
+                            UserContent::Image(image) => {
+                                to_image_url(image).map(UserContent::Image)
+                            }
+                            content => Ok(content),
+                        })
+                        .collect::<Result<Vec<_>, _>>()?,
+                )
+                .expect("The content of a message is never empty"),
+            },
+            prompt => prompt,
+        };
+
+        self.agent.prompt(prompt).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        client::CompletionClient,
+        message::{ImageDetail, ImageMediaType},
+        providers::openai,
+    };
+
+    #[test]
+    fn test_supports_vision() {
+        for model in [
+            openai::GPT_4O,
+            openai::GPT_4O_MINI,
+            openai::GPT_4_TURBO,
+            openai::GPT_4_TURBO_2024_04_09,
+            openai::GPT_4_1,
+            "ft:gpt-4o-2024-08-06:my-org::abc123",
+        ] {
+            assert!(supports_vision(model), "{model}");
+        }
+        for model in [
+            openai::GPT_35_TURBO,
+            openai::GPT_4,
+            openai::GPT_4_TURBO_PREVIEW,
+            "o1-mini",
+            "o3-mini-2025-01-31",
+        ] {
+            assert!(!supports_vision(model), "{model}");
+        }
+
+        let client = openai::Client::new("key");
+        assert!(VisionAgent::new(client.agent(openai::GPT_4O).build()).is_ok());
+        assert!(matches!(
+            VisionAgent::new(client.agent(openai::GPT_35_TURBO).build()),
+            Err(CompletionError::UnsupportedFeature(_))
+        ));
+    }
+
+    #[test]
+    fn test_to_image_url() {
+        let image = to_image_url(Image {
+            data: "iVBORw0KGgo=".to_string(),
+            media_type: Some(ImageMediaType::PNG),
+            detail: Some(ImageDetail::High),
+            ..Default::default()
+        })
+        .unwrap();
+
+        let message: Vec<openai::Message> = Message::User {
+            content: OneOrMany::one(UserContent::Image(image)),
+        }
+        .try_into()
+        .unwrap();
+        assert_eq!(
+            serde_json::to_value(&message[0]).unwrap()["content"],
+            serde_json::json!([{
+                "type": "image_url",
+                "image_url": {"url": "data:image/png;base64,iVBORw0KGgo=", "detail": "high"}
+            }])
+        );
+
+        let url = "https://example.com/ant.jpg";
+        let image = Image {
+            data: url.to_string(),
+            ..Default::default()
+        };
+        assert_eq!(to_image_url(image).unwrap().data, url);
+
+        assert!(to_image_url(Image {
+            data: "iVBORw0KGgo=".to_string(),
+            ..Default::default()
+        })
+        .is_err());
+    }
+}
@@ -0,0 +1,137 @@
+//! Speech-to-text with the timings of the words (e.g.: for subtitles, or to search in audio).
+//!
+//! # Example
+//! ```rust
+//! use rig::providers::openai::{self, SpeechToText};
+//!
+//! let openai = openai::Client::from_env();
+//! let whisper = openai.transcription_model(openai::WHISPER_1);
+//!
+//! let audio = std::fs::read("interview.mp3")?;
+//! let transcript = whisper.transcribe_with_timestamps(audio).await?;
+//!
+//! for word in transcript.words {
+//!     println!("[{:.2} - {:.2}] {}", word.start, word.end, word.word);
+//! }
+//! ```
+use reqwest::multipart::Part;
+use serde::{Deserialize, Serialize};
+
+use super::{ApiResponse, TranscriptionModel};
+use crate::transcription::TranscriptionError;
+
+/// A word of a transcript, with its start and end time in the audio (in seconds).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WordTimestamp {
+    pub word: String,
+    pub start: f64,
+    pub end: f64,
+}
+
+/// The transcript of an audio file, word by word.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TranscriptWithTimestamps {
+    /// The full text of the transcript
+    pub text: String,
+    #[serde(default)]
+    pub words: Vec<WordTimestamp>,
+}
+
+/// Trait for speech-to-text models returning the timings of the transcribed words.
+pub trait SpeechToText {
+    /// Transcribe `audio` (the content of an audio file, e.g.: mp3 or wav), with the start and
+    /// end time of each word.
+    fn transcribe_with_timestamps(
+        &self,
+        audio: Vec<u8>,
+    ) -> impl std::future::Future<Output = Result<TranscriptWithTimestamps, TranscriptionError>> + Send;
+}
+
+/// Returns a file name whose extension matches the format of `audio`, since the API relies on
+/// it to decode the file (defaults to mp3).
+fn audio_file_name(audio: &[u8]) -> &'static str {
+    match audio {
+        [b'R', b'I', b'F', b'F', ..] => "audio.wav",
+        [b'f', b'L', b'a', b'C', ..] => "audio.flac",
+        [b'O', b'g', b'g', b'S', ..] => "audio.ogg",
+        [0x1A, 0x45, 0xDF, 0xA3, ..] => "audio.webm",
+        [_, _, _, _, b'f', b't', b'y', b'p', ..] => "audio.m4a",
+        _ => "audio.mp3",
+    }
+}
+
+impl SpeechToText for TranscriptionModel {
+    #[cfg_attr(feature = "worker", worker::send)]
+    async fn transcribe_with_timestamps(
+        &self,
+        audio: Vec<u8>,
+    ) -> Result<TranscriptWithTimestamps, TranscriptionError> {
+        let file_name = audio_file_name(&audio);
+        let body = reqwest::multipart::Form::new()
+            .text("model", self.model.clone())
+            .text("response_format", "verbose_json")
+            .text("timestamp_granularities[]", "word")
+            .part("file", Part::bytes(audio).file_name(file_name));
+
+        let response = self
+            .client
+            .post("audio/transcriptions")
+            .multipart(body)
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            match response
+                .json::<ApiResponse<TranscriptWithTimestamps>>()
+                .await?
+            {
+                ApiResponse::Ok(transcript) => Ok(transcript),
+                ApiResponse::Err(api_error_response) => Err(TranscriptionError::ProviderError(
+                    api_error_response.message,
+                )),
+            }
+        } else {
+            Err(TranscriptionError::ProviderError(response.text().await?))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_transcript() {
+        let transcript: TranscriptWithTimestamps = serde_json::from_str(
+            r#"{
+                "task": "transcribe",
+                "language": "english",
+                "duration": 1.2,
+                "text": "Hello world.",
+                "words": [
+                    {"word": "Hello", "start": 0.0, "end": 0.48},
+                    {"word": "world", "start": 0.48, "end": 1.04}
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(transcript.text, "Hello world.");
+        assert_eq!(
+            transcript.words[1],
+            WordTimestamp {
+                word: "world".to_string(),
+                start: 0.48,
+                end: 1.04,
+            }
+        );
+    }
+
+    #[test]
+    fn test_audio_file_name() {
+        assert_eq!(audio_file_name(b"RIFF\x24\x08\x00\x00WAVE"), "audio.wav");
+        assert_eq!(audio_file_name(b"\x00\x00\x00\x20ftypM4A "), "audio.m4a");
+        assert_eq!(audio_file_name(b"ID3\x04\x00"), "audio.mp3");
+        assert_eq!(audio_file_name(b""), "audio.mp3");
+    }
+}
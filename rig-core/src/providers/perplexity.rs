@@ -6,7 +6,17 @@
 //!
 //! let client = perplexity::Client::new("YOUR_API_KEY");
 //!
-//! let llama_3_1_sonar_small_online = client.completion_model(perplexity::LLAMA_3_1_SONAR_SMALL_ONLINE);
+//! let sonar_pro = client.completion_model(perplexity::SONAR_PRO);
+//! ```
+//!
+//! Perplexity's models search the web to answer prompts. The sources they used are available
+//! in the [metadata](CompletionResponse::metadata) of the raw response:
+//! ```
+//! let response = sonar_pro.completion_request("What's new in Rust 1.85?").send().await?;
+//!
+//! for citation in &response.raw_response.metadata.citations {
+//!     println!("Source: {citation}");
+//! }
 //! ```
 
 use crate::{
@@ -131,6 +141,12 @@ enum ApiResponse<T> {
 pub const SONAR_PRO: &str = "sonar-pro";
 /// `sonar` completion model
 pub const SONAR: &str = "sonar";
+/// `sonar-reasoning-pro` completion model
+pub const SONAR_REASONING_PRO: &str = "sonar-reasoning-pro";
+/// `sonar-reasoning` completion model
+pub const SONAR_REASONING: &str = "sonar-reasoning";
+/// `sonar-deep-research` completion model
+pub const SONAR_DEEP_RESEARCH: &str = "sonar-deep-research";
 
 #[derive(Debug, Deserialize)]
 pub struct CompletionResponse {
@@ -141,6 +157,16 @@ pub struct CompletionResponse {
     #[serde(default)]
     pub choices: Vec<Choice>,
     pub usage: Usage,
+    #[serde(flatten)]
+    pub metadata: PerplexityMetadata,
+}
+
+/// Perplexity specific information about a response.
+#[derive(Debug, Default, Clone, Deserialize, Serialize, PartialEq)]
+pub struct PerplexityMetadata {
+    /// URLs of the sources used to generate the response
+    #[serde(default)]
+    pub citations: Vec<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -362,7 +388,7 @@ impl completion::CompletionModel for CompletionModel {
                 ApiResponse::Err(error) => Err(CompletionError::ProviderError(error.message)),
             }
         } else {
-            Err(CompletionError::ProviderError(response.text().await?))
+            Err(CompletionError::from_response(response).await)
         }
     }
 
@@ -411,6 +437,38 @@ mod tests {
         assert_eq!(json_data, expected_json);
     }
 
+    #[test]
+    fn test_deserialize_response_citations() {
+        let json_data = r#"
+        {
+            "id": "3c90c3cc",
+            "model": "sonar-pro",
+            "object": "chat.completion",
+            "created": 1724369245,
+            "citations": ["https://www.rust-lang.org", "https://blog.rust-lang.org"],
+            "choices": [
+                {
+                    "index": 0,
+                    "finish_reason": "stop",
+                    "message": { "role": "assistant", "content": "Rust is great [1]." },
+                    "delta": { "role": "assistant", "content": "" }
+                }
+            ],
+            "usage": { "prompt_tokens": 10, "completion_tokens": 5, "total_tokens": 15 }
+        }
+        "#;
+
+        let response: CompletionResponse = serde_json::from_str(json_data).unwrap();
+        assert_eq!(
+            response.metadata.citations,
+            vec!["https://www.rust-lang.org", "https://blog.rust-lang.org"]
+        );
+
+        let response: completion::CompletionResponse<CompletionResponse> =
+            response.try_into().unwrap();
+        assert_eq!(response.raw_response.metadata.citations.len(), 2);
+    }
+
     #[test]
     fn test_message_to_message_conversion() {
         let user_message = message::Message::user("User message");
@@ -63,6 +63,8 @@ pub const LLAMA_3_1_405B_INSTRUCT_LITE_PRO: &str =
     "meta-llama/Meta-Llama-3.1-405B-Instruct-Lite-Pro";
 pub const LLAMA_2_7B_CHAT: &str = "meta-llama/Llama-2-7b-chat-hf";
 pub const LLAMA_3_1_405B_INSTRUCT_TURBO: &str = "meta-llama/Meta-Llama-3.1-405B-Instruct-Turbo";
+/// Shorthand for [LLAMA_3_1_405B_INSTRUCT_TURBO], the variant of Llama 3.1 405B served by Together AI
+pub const LLAMA_3_1_405B_INSTRUCT: &str = LLAMA_3_1_405B_INSTRUCT_TURBO;
 pub const LLAMA_VISION_FREE: &str = "meta-llama/Llama-Vision-Free";
 pub const LLAMA_3_70B_INSTRUCT_TURBO: &str = "meta-llama/Meta-Llama-3-70B-Instruct-Turbo";
 pub const LLAMA_3_1_8B_INSTRUCT_TURBO: &str = "meta-llama/Meta-Llama-3.1-8B-Instruct-Turbo";
@@ -85,6 +87,8 @@ pub const MISTRAL_7B_INSTRUCT_V0_2: &str = "mistralai/Mistral-7B-Instruct-v0.2";
 pub const MISTRAL_7B_INSTRUCT_V0_3: &str = "mistralai/Mistral-7B-Instruct-v0.3";
 pub const MIXTRAL_8X7B_INSTRUCT_V0_1: &str = "mistralai/Mixtral-8x7B-Instruct-v0.1";
 pub const MIXTRAL_8X22B_INSTRUCT_V0_1: &str = "mistralai/Mixtral-8x22B-Instruct-v0.1";
+/// Shorthand for [MIXTRAL_8X22B_INSTRUCT_V0_1]
+pub const MIXTRAL_8X22B: &str = MIXTRAL_8X22B_INSTRUCT_V0_1;
 pub const NOUS_HERMES_2_MIXTRAL_8X7B_DPO: &str = "NousResearch/Nous-Hermes-2-Mixtral-8x7B-DPO";
 pub const NOUS_HERMES_LLAMA2_70B: &str = "NousResearch/Nous-Hermes-Llama2-70b";
 pub const NOUS_HERMES_2_MIXTRAL_8X7B_SFT: &str = "NousResearch/Nous-Hermes-2-Mixtral-8x7B-SFT";
@@ -221,7 +225,7 @@ impl completion::CompletionModel for CompletionModel {
                 ApiResponse::Error(err) => Err(CompletionError::ProviderError(err.error)),
             }
         } else {
-            Err(CompletionError::ProviderError(response.text().await?))
+            Err(CompletionError::from_response(response).await)
         }
     }
 
@@ -1,5 +1,8 @@
 //! Together AI API client and Rig integration
 //!
+//! Together AI exposes an OpenAI compatible API (`https://api.together.xyz/v1`). This module is
+//! also available as `providers::together_ai`.
+//!
 //! # Example
 //! ```
 //! use rig::providers::together_ai;
@@ -8,6 +11,35 @@
 //!
 //! let together_embedding_model = client.embedding_model(together_ai::EMBEDDING_V1);
 //! ```
+//!
+//! # JSON mode
+//! Some models (e.g.: [LLAMA_3_1_405B_INSTRUCT], [MIXTRAL_8X22B]) can be constrained to answer with
+//! JSON following a given schema. The `response_format` parameter can be passed to Together AI
+//! using `additional_params`:
+//! ```
+//! use rig::providers::together_ai;
+//! use serde_json::json;
+//!
+//! let client = together_ai::Client::from_env();
+//!
+//! let agent = client
+//!     .agent(together_ai::LLAMA_3_1_405B_INSTRUCT)
+//!     .preamble("Extract the name and age of the person. Only answer in JSON.")
+//!     .additional_params(json!({
+//!         "response_format": {
+//!             "type": "json_object",
+//!             "schema": {
+//!                 "type": "object",
+//!                 "properties": {
+//!                     "name": { "type": "string" },
+//!                     "age": { "type": "integer" }
+//!                 },
+//!                 "required": ["name", "age"]
+//!             }
+//!         }
+//!     }))
+//!     .build();
+//! ```
 
 pub mod client;
 pub mod completion;
@@ -22,15 +54,16 @@ pub use completion::{
     DEEPSEEK_LLM_67B_CHAT, DOLPHIN_2_5_MIXTRAL_8X7B, GEMMA_2B_IT, GEMMA_2_27B_IT, GEMMA_2_9B_IT,
     GEMMA_7B_IT, GUANACO_13B, GUANACO_33B, GUANACO_65B, GUANACO_7B, HERMES_2_THETA_LLAMA_3_70B,
     KOALA_13B, KOALA_7B, LLAMA_2_13B_CHAT, LLAMA_2_13B_CHAT_TOGETHER, LLAMA_2_70B_CHAT_TOGETHER,
-    LLAMA_2_7B_CHAT, LLAMA_2_7B_CHAT_TOGETHER, LLAMA_3_1_405B_INSTRUCT_LITE_PRO,
-    LLAMA_3_1_405B_INSTRUCT_TURBO, LLAMA_3_1_70B_INSTRUCT_REFERENCE, LLAMA_3_1_70B_INSTRUCT_TURBO,
+    LLAMA_2_7B_CHAT, LLAMA_2_7B_CHAT_TOGETHER, LLAMA_3_1_405B_INSTRUCT,
+    LLAMA_3_1_405B_INSTRUCT_LITE_PRO, LLAMA_3_1_405B_INSTRUCT_TURBO,
+    LLAMA_3_1_70B_INSTRUCT_REFERENCE, LLAMA_3_1_70B_INSTRUCT_TURBO,
     LLAMA_3_1_8B_INSTRUCT_REFERENCE, LLAMA_3_1_8B_INSTRUCT_TURBO,
     LLAMA_3_2_11B_VISION_INSTRUCT_TURBO, LLAMA_3_2_3B_INSTRUCT_TURBO,
     LLAMA_3_2_90B_VISION_INSTRUCT_TURBO, LLAMA_3_70B_CHAT_HF, LLAMA_3_70B_INSTRUCT,
     LLAMA_3_70B_INSTRUCT_GRADIENT_1048K, LLAMA_3_70B_INSTRUCT_LITE, LLAMA_3_70B_INSTRUCT_TURBO,
     LLAMA_3_8B_CHAT_HF, LLAMA_3_8B_CHAT_HF_INT4, LLAMA_3_8B_CHAT_HF_INT8, LLAMA_3_8B_INSTRUCT,
     LLAMA_3_8B_INSTRUCT_LITE, LLAMA_3_8B_INSTRUCT_TURBO, LLAMA_VISION_FREE, LLAVA_NEXT_MISTRAL_7B,
-    MISTRAL_7B_INSTRUCT_V0_1, MISTRAL_7B_INSTRUCT_V0_2, MISTRAL_7B_INSTRUCT_V0_3,
+    MISTRAL_7B_INSTRUCT_V0_1, MISTRAL_7B_INSTRUCT_V0_2, MISTRAL_7B_INSTRUCT_V0_3, MIXTRAL_8X22B,
     MIXTRAL_8X22B_INSTRUCT_V0_1, MIXTRAL_8X7B_INSTRUCT_V0_1, ML318BR, MYTHOMAX_L2_13B,
     MYTHOMAX_L2_13B_LITE, NOUS_CAPYBARA_V1_9, NOUS_HERMES_2_MISTRAL_DPO,
     NOUS_HERMES_2_MIXTRAL_8X7B_DPO, NOUS_HERMES_2_MIXTRAL_8X7B_SFT, NOUS_HERMES_LLAMA2_13B,
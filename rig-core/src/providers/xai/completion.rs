@@ -126,7 +126,7 @@ impl completion::CompletionModel for CompletionModel {
                 ApiResponse::Error(error) => Err(CompletionError::ProviderError(error.message())),
             }
         } else {
-            Err(CompletionError::ProviderError(response.text().await?))
+            Err(CompletionError::from_response(response).await)
         }
     }
 
@@ -0,0 +1,469 @@
+//! This module provides an HTTP handler serving an [Agent] behind an OpenAI compatible
+//! `POST /v1/chat/completions` endpoint, so that existing OpenAI clients can be pointed at a
+//! Rig agent by simply changing their base URL.
+//!
+//! Requests are answered by the agent like any prompt: its tools are called and its hooks run
+//! on the server, and only the final response is returned to the client. The system messages of
+//! the request are appended to the agent's preamble; the sampling parameters are the agent's.
+//!
+//! Both regular and streaming (i.e.: `"stream": true`, served as Server-Sent Events) requests
+//! are supported. The response is streamed as it is generated when the agent has no tools,
+//! hooks nor response validator (which need the whole response), and sent as a single chunk
+//! otherwise.
+//!
+//! Requires the `server` feature.
+//!
+//! # Example
+//! ```rust
+//! use rig::{client::CompletionClient, providers::openai, server::OpenAICompatHandler};
+//!
+//! let openai = openai::Client::from_env();
+//! let agent = openai
+//!     .agent(openai::GPT_4O)
+//!     .preamble("You are a helpful assistant.")
+//!     .build();
+//!
+//! let app = OpenAICompatHandler::new(agent).router();
+//!
+//! let listener = tokio::net::TcpListener::bind("0.0.0.0:3000").await?;
+//! axum::serve(listener, app).await?;
+//! ```
+use std::{
+    convert::Infallible,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use async_stream::stream;
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{
+        sse::{Event, Sse},
+        IntoResponse, Response,
+    },
+    routing::post,
+    Json, Router,
+};
+use futures::{Stream, StreamExt};
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::{
+    agent::{append_preamble, Agent},
+    completion::{Completion, CompletionError, CompletionModel, Message, Prompt, PromptError},
+    providers::openai,
+};
+
+/// A chat completion request, in the OpenAI Chat Completions wire format.
+/// Unsupported parameters are ignored.
+#[derive(Debug, Deserialize)]
+pub struct ChatCompletionRequest {
+    #[serde(default)]
+    pub model: Option<String>,
+    pub messages: Vec<openai::Message>,
+    #[serde(default)]
+    pub stream: bool,
+}
+
+/// Handler serving an [Agent] behind an OpenAI compatible chat completions endpoint.
+pub struct OpenAICompatHandler<M: CompletionModel> {
+    agent: Arc<Agent<M>>,
+}
+
+impl<M: CompletionModel + 'static> OpenAICompatHandler<M> {
+    pub fn new(agent: Agent<M>) -> Self {
+        Self {
+            agent: Arc::new(agent),
+        }
+    }
+
+    /// Create a handler from an agent shared with the rest of the application.
+    pub fn from_shared(agent: Arc<Agent<M>>) -> Self {
+        Self { agent }
+    }
+
+    /// Returns a router serving the agent at `POST /v1/chat/completions`.
+    pub fn router(self) -> Router {
+        Router::new()
+            .route("/v1/chat/completions", post(chat_completions::<M>))
+            .with_state(self.agent)
+    }
+}
+
+/// The messages of a chat completion request, converted for the agent.
+struct AgentRequest {
+    /// The last non-system message of the request
+    prompt: Message,
+    chat_history: Vec<Message>,
+    /// The text of the system messages of the request, if any
+    system: Option<String>,
+}
+
+impl TryFrom<&ChatCompletionRequest> for AgentRequest {
+    type Error = Response;
+
+    fn try_from(request: &ChatCompletionRequest) -> Result<Self, Self::Error> {
+        let (system, messages): (Vec<_>, Vec<_>) = request
+            .messages
+            .iter()
+            .cloned()
+            .partition(|message| matches!(message, openai::Message::System { .. }));
+
+        let mut chat_history = messages
+            .into_iter()
+            .map(Message::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| error_response(StatusCode::BAD_REQUEST, err.to_string()))?;
+        let Some(prompt) = chat_history.pop() else {
+            return Err(error_response(
+                StatusCode::BAD_REQUEST,
+                "`messages` must contain at least one non-system message".to_string(),
+            ));
+        };
+
+        let system = system
+            .iter()
+            .filter_map(|message| match message {
+                openai::Message::System { content, .. } => Some(
+                    content
+                        .iter()
+                        .map(|content| content.text())
+                        .collect::<Vec<_>>()
+                        .join("\n"),
+                ),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        Ok(Self {
+            prompt,
+            chat_history,
+            system: (!system.is_empty()).then(|| system.join("\n\n")),
+        })
+    }
+}
+
+/// Whether the response of the agent can be streamed as it is generated, i.e.: the agent has
+/// no tools to call and nothing to do with the whole response.
+fn streams_directly<M: CompletionModel>(agent: &Agent<M>) -> bool {
+    agent.static_tools.is_empty()
+        && agent.dynamic_tools.is_empty()
+        && agent.pre_prompt_hooks.is_empty()
+        && agent.post_prompt_hooks.is_empty()
+        && agent.response_validator.is_none()
+}
+
+/// Stream the response of the model to the agent request, without tools.
+async fn stream_text<M: CompletionModel + 'static>(
+    agent: &Agent<M>,
+    request: AgentRequest,
+) -> Result<impl Stream<Item = Result<String, CompletionError>>, CompletionError> {
+    let mut completion_request = agent
+        .completion(request.prompt, request.chat_history)
+        .await?
+        .build();
+    if let Some(system) = &request.system {
+        completion_request.preamble = Some(append_preamble(completion_request.preamble, system));
+    }
+
+    Ok(agent
+        .model
+        .stream(completion_request)
+        .await?
+        .into_text_stream())
+}
+
+/// Answer the agent request with the agent's prompt loop (calling its tools and hooks).
+async fn prompt<M: CompletionModel>(
+    agent: &Agent<M>,
+    request: AgentRequest,
+) -> Result<String, PromptError> {
+    let mut chat_history = request.chat_history;
+    let prompt_request = agent
+        .prompt(request.prompt)
+        .with_history(&mut chat_history)
+        .multi_turn(agent.max_tool_iterations);
+
+    match request.system {
+        Some(system) => prompt_request.additional_preamble(system).await,
+        None => prompt_request.await,
+    }
+}
+
+async fn chat_completions<M: CompletionModel + 'static>(
+    State(agent): State<Arc<Agent<M>>>,
+    Json(request): Json<ChatCompletionRequest>,
+) -> Response {
+    let agent_request = match AgentRequest::try_from(&request) {
+        Ok(agent_request) => agent_request,
+        Err(response) => return response,
+    };
+
+    let id = format!("chatcmpl-{}", timestamp().as_nanos());
+    let created = timestamp().as_secs();
+    let model = request.model.unwrap_or_else(|| "rig".to_string());
+
+    if !request.stream {
+        return match prompt(&agent, agent_request).await {
+            Ok(text) => Json(json!({
+                "id": id,
+                "object": "chat.completion",
+                "created": created,
+                "model": model,
+                "choices": [{
+                    "index": 0,
+                    "message": {"role": "assistant", "content": text},
+                    "finish_reason": "stop",
+                }],
+            }))
+            .into_response(),
+            Err(err) => error_response(StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+        };
+    }
+
+    let texts = if streams_directly(&agent) {
+        match stream_text(&agent, agent_request).await {
+            Ok(texts) => texts.boxed(),
+            Err(err) => return error_response(StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+        }
+    } else {
+        match prompt(&agent, agent_request).await {
+            Ok(text) => futures::stream::once(async { Ok(text) }).boxed(),
+            Err(err) => return error_response(StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+        }
+    };
+
+    let chunk = move |delta: serde_json::Value, finish_reason: Option<&str>| {
+        json!({
+            "id": id,
+            "object": "chat.completion.chunk",
+            "created": created,
+            "model": model,
+            "choices": [{
+                "index": 0,
+                "delta": delta,
+                "finish_reason": finish_reason,
+            }],
+        })
+    };
+
+    let events = stream! {
+        yield chunk(json!({"role": "assistant", "content": ""}), None);
+
+        let mut texts = texts;
+        while let Some(text) = texts.next().await {
+            match text {
+                Ok(text) => yield chunk(json!({"content": text}), None),
+                Err(err) => {
+                    // The response status has already been sent, so the error is reported in the stream
+                    yield json!({"error": {"message": err.to_string(), "type": "server_error"}});
+                    return;
+                }
+            }
+        }
+
+        yield chunk(json!({}), Some("stop"));
+    };
+
+    let events = events
+        .map(|data| Ok::<_, Infallible>(Event::default().data(data.to_string())))
+        .chain(futures::stream::once(async {
+            Ok(Event::default().data("[DONE]"))
+        }));
+
+    Sse::new(events).into_response()
+}
+
+fn error_response(status: StatusCode, message: String) -> Response {
+    let r#type = if status.is_client_error() {
+        "invalid_request_error"
+    } else {
+        "server_error"
+    };
+
+    (
+        status,
+        Json(json!({"error": {"message": message, "type": r#type}})),
+    )
+        .into_response()
+}
+
+fn timestamp() -> std::time::Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{body::Body, http::Request};
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::{
+        agent::AgentBuilder,
+        completion::{AssistantContent, CompletionResponse, ToolDefinition},
+        message::UserContent,
+        testing::MockCompletionModel,
+        tool::{Tool, ToolError},
+        OneOrMany,
+    };
+
+    struct Ping;
+
+    impl Tool for Ping {
+        const NAME: &'static str = "ping";
+
+        type Error = ToolError;
+        type Args = serde_json::Value;
+        type Output = String;
+
+        async fn definition(&self, _prompt: String) -> ToolDefinition {
+            ToolDefinition {
+                name: Self::NAME.to_string(),
+                description: "Returns pong".to_string(),
+                parameters: json!({}),
+            }
+        }
+
+        async fn call(&self, _args: Self::Args) -> Result<Self::Output, Self::Error> {
+            Ok("pong".to_string())
+        }
+    }
+
+    fn request(body: serde_json::Value) -> Request<Body> {
+        Request::post("/v1/chat/completions")
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    async fn body_text(response: Response) -> String {
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_chat_completion() {
+        let model = MockCompletionModel::new("Hello!");
+        let agent = AgentBuilder::new(model.clone())
+            .preamble("You are a helpful assistant.")
+            .build();
+        let app = OpenAICompatHandler::new(agent).router();
+
+        let response = app
+            .oneshot(request(json!({
+                "model": "my-agent",
+                "messages": [
+                    {"role": "system", "content": "Be concise."},
+                    {"role": "user", "content": "Hi"}
+                ]
+            })))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body: serde_json::Value = serde_json::from_str(&body_text(response).await).unwrap();
+        assert_eq!(body["model"], "my-agent");
+        assert_eq!(body["choices"][0]["message"]["content"], "Hello!");
+        assert_eq!(body["choices"][0]["finish_reason"], "stop");
+
+        let requests = model.requests();
+        assert_eq!(
+            requests[0].preamble.as_deref(),
+            Some("You are a helpful assistant.\n\nBe concise.")
+        );
+        assert_eq!(requests[0].chat_history.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_chat_completion_stream() {
+        let agent = AgentBuilder::new(crate::testing::MockStreamingModel::new("Hey")).build();
+        let app = OpenAICompatHandler::new(agent).router();
+
+        let response = app
+            .oneshot(request(json!({
+                "messages": [{"role": "user", "content": "Hi"}],
+                "stream": true
+            })))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = body_text(response).await;
+        let events = body
+            .lines()
+            .filter_map(|line| line.strip_prefix("data: "))
+            .collect::<Vec<_>>();
+        assert_eq!(events.last(), Some(&"[DONE]"));
+
+        let chunks = events[..events.len() - 1]
+            .iter()
+            .map(|event| serde_json::from_str::<serde_json::Value>(event).unwrap())
+            .collect::<Vec<_>>();
+        let content = chunks
+            .iter()
+            .filter_map(|chunk| chunk["choices"][0]["delta"]["content"].as_str())
+            .collect::<String>();
+        assert_eq!(content, "Hey");
+        assert_eq!(
+            chunks.last().unwrap()["choices"][0]["finish_reason"],
+            "stop"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_chat_completion_without_prompt() {
+        let agent = AgentBuilder::new(MockCompletionModel::new("Hello!")).build();
+        let app = OpenAICompatHandler::new(agent).router();
+
+        let response = app
+            .oneshot(request(json!({
+                "messages": [{"role": "system", "content": "Be concise."}]
+            })))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_chat_completion_calls_tools() {
+        // The model calls the tool, then answers with its result
+        let model = MockCompletionModel::from_fn(|request| {
+            let choice = match request.chat_history.iter().last() {
+                Some(Message::User { content }) => match content.first() {
+                    UserContent::ToolResult(_) => AssistantContent::text("The server says pong."),
+                    _ => AssistantContent::tool_call("call_1", "ping", json!({})),
+                },
+                _ => unreachable!(),
+            };
+            CompletionResponse {
+                choice: OneOrMany::one(choice),
+                raw_response: (),
+            }
+        });
+        let agent = AgentBuilder::new(model.clone()).tool(Ping).build();
+        let app = OpenAICompatHandler::new(agent).router();
+
+        for stream in [false, true] {
+            let response = app
+                .clone()
+                .oneshot(request(json!({
+                    "messages": [{"role": "user", "content": "Ping the server"}],
+                    "stream": stream
+                })))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+
+            // The tool call is handled by the agent, and never sent to the client
+            let body = body_text(response).await;
+            assert!(body.contains("The server says pong."));
+            assert!(!body.contains("tool_calls"));
+        }
+        assert_eq!(model.requests().len(), 4);
+    }
+}
@@ -63,6 +63,8 @@ pub struct StreamingCompletionResponse<R: Clone + Unpin> {
     /// The final response from the stream, may be `None`
     /// if the provider didn't yield it during the stream
     pub response: Option<R>,
+    /// RAG text of the request (see [StreamingCompletionResponse::rag_text])
+    rag_text: Option<String>,
 }
 
 impl<R: Clone + Unpin> StreamingCompletionResponse<R> {
@@ -74,8 +76,81 @@ impl<R: Clone + Unpin> StreamingCompletionResponse<R> {
             tool_calls: vec![],
             choice: OneOrMany::one(AssistantContent::text("")),
             response: None,
+            rag_text: None,
         }
     }
+
+    pub(crate) fn with_rag_text(mut self, rag_text: Option<String>) -> Self {
+        self.rag_text = rag_text;
+        self
+    }
+
+    /// The RAG text of the request (i.e.: the text of its prompt) when it was streamed by an
+    /// [Agent], which determines the tools available to the model: it is the prompt to pass
+    /// to [ToolSet::call_with_prompt](crate::tool::ToolSet::call_with_prompt).
+    pub fn rag_text(&self) -> Option<&str> {
+        self.rag_text.as_deref()
+    }
+
+    /// Consume the rest of the stream and return the assembled text content (excluding
+    /// reasoning) along with all the tool calls requested by the model.
+    /// The aggregated `choice` and `response` fields are populated as usual once this returns.
+    ///
+    /// # Example
+    /// ```rust
+    /// let mut stream = agent.stream_prompt("What is 2 + 2?").await?;
+    /// let (text, tool_calls) = stream.collect_tool_calls().await?;
+    ///
+    /// for tool_call in tool_calls {
+    ///     let result = agent
+    ///         .tools
+    ///         .call_with_prompt(
+    ///             &tool_call.function.name,
+    ///             tool_call.function.arguments.to_string(),
+    ///             stream.rag_text().unwrap_or_default(),
+    ///         )
+    ///         .await?;
+    /// }
+    /// ```
+    pub async fn collect_tool_calls(&mut self) -> Result<(String, Vec<ToolCall>), CompletionError> {
+        while let Some(chunk) = self.next().await {
+            chunk?;
+        }
+
+        Ok((self.text.clone(), self.tool_calls.clone()))
+    }
+
+    /// Consume the stream and return the assembled text content, for callers which don't need
+    /// to process the chunks as they arrive. Tool calls and reasoning are ignored.
+    ///
+    /// # Example
+    /// ```rust
+    /// let text = agent.stream_prompt("Tell me a story").await?.take_text().await?;
+    /// ```
+    pub async fn take_text(mut self) -> Result<String, CompletionError> {
+        let (text, _) = self.collect_tool_calls().await?;
+        Ok(text)
+    }
+
+    /// Convert the response into a stream of its text chunks, ignoring tool calls.
+    ///
+    /// # Example
+    /// ```rust
+    /// let mut stream = agent.stream_prompt("Tell me a story").await?.into_text_stream();
+    ///
+    /// while let Some(text) = stream.next().await {
+    ///     print!("{}", text?);
+    /// }
+    /// ```
+    pub fn into_text_stream(self) -> impl Stream<Item = Result<String, CompletionError>> {
+        self.filter_map(|chunk| async move {
+            match chunk {
+                Ok(AssistantContent::Text(text)) => Some(Ok(text.text)),
+                Ok(AssistantContent::ToolCall(_)) => None,
+                Err(err) => Some(Err(err)),
+            }
+        })
+    }
 }
 
 impl<R: Clone + Unpin> From<StreamingCompletionResponse<R>> for CompletionResponse<Option<R>> {
@@ -247,9 +322,10 @@ pub async fn stream_to_stdout<M: CompletionModel>(
             Ok(AssistantContent::ToolCall(tool_call)) => {
                 let res = agent
                     .tools
-                    .call(
+                    .call_with_prompt(
                         &tool_call.function.name,
                         tool_call.function.arguments.to_string(),
+                        stream.rag_text().unwrap_or_default(),
                     )
                     .await
                     .map_err(|e| std::io::Error::other(e.to_string()))?;
@@ -415,6 +491,62 @@ mod tests {
         assert_eq!(stream.response.unwrap().id, "test_123");
     }
 
+    #[tokio::test]
+    async fn test_collect_tool_calls() {
+        let chunks = vec![
+            RawStreamingChoice::Message("Let me ".to_string()),
+            RawStreamingChoice::ToolCall {
+                id: "call_1".to_string(),
+                name: "add".to_string(),
+                arguments: json!({"x": 2, "y": 2}),
+            },
+            RawStreamingChoice::Message("check.".to_string()),
+            RawStreamingChoice::ToolCall {
+                id: "call_2".to_string(),
+                name: "subtract".to_string(),
+                arguments: json!({"x": 4, "y": 1}),
+            },
+        ];
+
+        let mut stream = StreamingCompletionResponse::stream(create_mock_stream(chunks));
+        let (text, tool_calls) = stream.collect_tool_calls().await.unwrap();
+
+        assert_eq!(text, "Let me check.");
+        assert_eq!(
+            tool_calls
+                .iter()
+                .map(|tool_call| tool_call.function.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["add", "subtract"]
+        );
+        assert_eq!(tool_calls[1].function.arguments, json!({"x": 4, "y": 1}));
+        assert_eq!(stream.choice.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_take_text() {
+        let chunks = vec![
+            RawStreamingChoice::Reasoning("Let me think...".to_string()),
+            RawStreamingChoice::Message("The answer".to_string()),
+            RawStreamingChoice::ToolCall {
+                id: "call_1".to_string(),
+                name: "add".to_string(),
+                arguments: json!({"x": 2, "y": 2}),
+            },
+            RawStreamingChoice::Message(" is 4".to_string()),
+        ];
+
+        let stream = StreamingCompletionResponse::stream(create_mock_stream(chunks));
+        assert_eq!(stream.take_text().await.unwrap(), "The answer is 4");
+
+        let inner: StreamingResult<MockResponse> = Box::pin(stream::iter(vec![
+            Ok(RawStreamingChoice::Message("The answer".to_string())),
+            Err(CompletionError::ProviderError("Connection reset".to_string())),
+        ]));
+        let result = StreamingCompletionResponse::stream(inner).take_text().await;
+        assert!(matches!(result, Err(CompletionError::ProviderError(_))));
+    }
+
     #[test]
     fn test_raw_streaming_choice_debug() {
         let reasoning = RawStreamingChoice::<()>::Reasoning("thinking...".to_string());
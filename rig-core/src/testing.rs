@@ -0,0 +1,278 @@
+//! This module provides mock completion models, to unit test code using Rig without calling
+//! a provider.
+//!
+//! # Example
+//! ```rust
+//! use rig::{agent::AgentBuilder, completion::Prompt, testing::MockCompletionModel};
+//!
+//! let model = MockCompletionModel::sequence(["Hello!", "Goodbye!"]);
+//! let agent = AgentBuilder::new(model.clone()).build();
+//!
+//! assert_eq!(agent.prompt("Hi").await?, "Hello!");
+//! assert_eq!(agent.prompt("Bye").await?, "Goodbye!");
+//! assert_eq!(model.requests().len(), 2);
+//! ```
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    completion::{
+        AssistantContent, CompletionError, CompletionModel, CompletionRequest, CompletionResponse,
+    },
+    streaming::{RawStreamingChoice, StreamingCompletionResponse},
+    OneOrMany,
+};
+
+type ResponseFn =
+    dyn Fn(&CompletionRequest) -> Result<CompletionResponse<()>, CompletionError> + Send + Sync;
+
+#[derive(Clone)]
+enum Responder {
+    Fixed(String),
+    Fn(Arc<ResponseFn>),
+    Sequence(Arc<Mutex<VecDeque<String>>>),
+}
+
+/// A mock completion model, returning configurable responses and recording the requests
+/// it receives. Clones of a [MockCompletionModel] share the same responses and requests.
+///
+/// Streaming requests return the whole response as a single chunk
+/// (see [MockStreamingModel] to stream a response character by character).
+#[derive(Clone)]
+pub struct MockCompletionModel {
+    responder: Responder,
+    requests: Arc<Mutex<Vec<CompletionRequest>>>,
+}
+
+impl MockCompletionModel {
+    fn with_responder(responder: Responder) -> Self {
+        Self {
+            responder,
+            requests: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Create a mock model always responding with `response`.
+    pub fn new(response: impl Into<String>) -> Self {
+        Self::with_responder(Responder::Fixed(response.into()))
+    }
+
+    /// Create a mock model responding with the result of `f` applied to the request
+    /// (e.g.: to return tool calls, or responses depending on the prompt).
+    pub fn from_fn(
+        f: impl Fn(&CompletionRequest) -> CompletionResponse<()> + Send + Sync + 'static,
+    ) -> Self {
+        Self::try_from_fn(move |request| Ok(f(request)))
+    }
+
+    /// Create a mock model responding with the result of `f` applied to the request, which
+    /// may be an error (e.g.: to test how failures of the provider are handled).
+    pub fn try_from_fn(
+        f: impl Fn(&CompletionRequest) -> Result<CompletionResponse<()>, CompletionError>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        Self::with_responder(Responder::Fn(Arc::new(f)))
+    }
+
+    /// Create a mock model responding with `responses` on successive calls, and returning a
+    /// [CompletionError::ProviderError] once they are exhausted.
+    pub fn sequence(responses: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self::with_responder(Responder::Sequence(Arc::new(Mutex::new(
+            responses.into_iter().map(Into::into).collect(),
+        ))))
+    }
+
+    /// Returns the requests received by the model so far, in order.
+    pub fn requests(&self) -> Vec<CompletionRequest> {
+        self.requests
+            .lock()
+            .expect("Requests lock should not be poisoned")
+            .clone()
+    }
+
+    fn respond(
+        &self,
+        request: CompletionRequest,
+    ) -> Result<CompletionResponse<()>, CompletionError> {
+        let response = match &self.responder {
+            Responder::Fixed(text) => text_response(text.clone()),
+            Responder::Fn(f) => f(&request)?,
+            Responder::Sequence(responses) => responses
+                .lock()
+                .expect("Responses lock should not be poisoned")
+                .pop_front()
+                .map(text_response)
+                .ok_or_else(|| {
+                    CompletionError::ProviderError("No more mock responses".to_string())
+                })?,
+        };
+
+        self.requests
+            .lock()
+            .expect("Requests lock should not be poisoned")
+            .push(request);
+
+        Ok(response)
+    }
+}
+
+fn text_response(text: String) -> CompletionResponse<()> {
+    CompletionResponse {
+        choice: OneOrMany::one(AssistantContent::text(text)),
+        raw_response: (),
+    }
+}
+
+impl CompletionModel for MockCompletionModel {
+    type Response = ();
+    type StreamingResponse = ();
+
+    async fn completion(
+        &self,
+        request: CompletionRequest,
+    ) -> Result<CompletionResponse<()>, CompletionError> {
+        self.respond(request)
+    }
+
+    async fn stream(
+        &self,
+        request: CompletionRequest,
+    ) -> Result<StreamingCompletionResponse<()>, CompletionError> {
+        let response = self.respond(request)?;
+
+        let chunks = response
+            .choice
+            .into_iter()
+            .map(|content| match content {
+                AssistantContent::Text(text) => RawStreamingChoice::Message(text.text),
+                AssistantContent::ToolCall(tool_call) => RawStreamingChoice::ToolCall {
+                    id: tool_call.id,
+                    name: tool_call.function.name,
+                    arguments: tool_call.function.arguments,
+                },
+            })
+            .chain([RawStreamingChoice::FinalResponse(())])
+            .map(Ok)
+            .collect::<Vec<_>>();
+
+        Ok(StreamingCompletionResponse::stream(Box::pin(
+            futures::stream::iter(chunks),
+        )))
+    }
+}
+
+/// A mock completion model always responding with the same text, which is streamed character
+/// by character.
+#[derive(Clone)]
+pub struct MockStreamingModel {
+    response: String,
+}
+
+impl MockStreamingModel {
+    pub fn new(response: impl Into<String>) -> Self {
+        Self {
+            response: response.into(),
+        }
+    }
+}
+
+impl CompletionModel for MockStreamingModel {
+    type Response = ();
+    type StreamingResponse = ();
+
+    async fn completion(
+        &self,
+        _request: CompletionRequest,
+    ) -> Result<CompletionResponse<()>, CompletionError> {
+        Ok(text_response(self.response.clone()))
+    }
+
+    async fn stream(
+        &self,
+        _request: CompletionRequest,
+    ) -> Result<StreamingCompletionResponse<()>, CompletionError> {
+        let chunks = self
+            .response
+            .chars()
+            .map(|c| RawStreamingChoice::Message(c.to_string()))
+            .chain([RawStreamingChoice::FinalResponse(())])
+            .map(Ok)
+            .collect::<Vec<_>>();
+
+        Ok(StreamingCompletionResponse::stream(Box::pin(
+            futures::stream::iter(chunks),
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_mock_sequence() {
+        let model = MockCompletionModel::sequence(["first", "second"]);
+
+        let first = model.completion_request("1").send().await.unwrap();
+        let second = model.completion_request("2").send().await.unwrap();
+        let third = model.completion_request("3").send().await;
+
+        assert_eq!(
+            first.choice,
+            OneOrMany::one(AssistantContent::text("first"))
+        );
+        assert_eq!(
+            second.choice,
+            OneOrMany::one(AssistantContent::text("second"))
+        );
+        assert!(matches!(third, Err(CompletionError::ProviderError(_))));
+        assert_eq!(model.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_mock_from_fn() {
+        let model = MockCompletionModel::from_fn(|request| {
+            text_response(format!("{} messages", request.chat_history.len()))
+        });
+
+        let response = model.completion_request("Hello").send().await.unwrap();
+
+        assert_eq!(
+            response.choice,
+            OneOrMany::one(AssistantContent::text("1 messages"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mock_try_from_fn() {
+        let model = MockCompletionModel::try_from_fn(|_| {
+            Err(CompletionError::ProviderError("Overloaded".to_string()))
+        });
+
+        let response = model.completion_request("Hello").send().await;
+
+        assert!(matches!(response, Err(CompletionError::ProviderError(_))));
+        assert!(model.requests().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_mock_streaming() {
+        let model = MockStreamingModel::new("Hey");
+
+        let mut stream = model.completion_request("Hello").stream().await.unwrap();
+        let mut chunks = 0;
+        while let Some(chunk) = stream.next().await {
+            chunk.unwrap();
+            chunks += 1;
+        }
+
+        assert_eq!(chunks, 3);
+        assert_eq!(stream.choice, OneOrMany::one(AssistantContent::text("Hey")));
+    }
+}
@@ -0,0 +1,160 @@
+//! This module provides utilities for estimating the number of tokens in a prompt before
+//! sending it to a completion model provider.
+//!
+//! When the `tiktoken` feature is enabled, the [TokenCounter] uses the exact tokenizer of
+//! OpenAI models (e.g.: `gpt-4o`, `gpt-3.5-turbo`). For every other model (or when the feature
+//! is disabled), a character based heuristic (~4 characters per token) is used instead.
+//!
+//! # Example
+//! ```rust
+//! use rig::{message::Message, tokens::TokenCounter};
+//!
+//! let messages = vec![Message::user("What is the capital of France?")];
+//!
+//! let estimate = TokenCounter::count("gpt-4o", &messages, "You are a helpful assistant.");
+//! ```
+use crate::completion::message::{AssistantContent, Message, ToolResultContent, UserContent};
+
+/// Average number of characters per token used by the heuristic estimate
+const CHARS_PER_TOKEN: usize = 4;
+/// Tokens added by chat formatting for each message (role, separators, etc.)
+const TOKENS_PER_MESSAGE: usize = 4;
+/// Tokens used to prime the model's reply
+const TOKENS_PER_REPLY: usize = 3;
+
+/// Estimates the number of tokens of a prompt for a given model.
+#[derive(Clone, Debug, Default)]
+pub struct TokenCounter {
+    model: String,
+}
+
+impl TokenCounter {
+    /// Create a token counter for the given model name (e.g.: `gpt-4o`).
+    pub fn new(model: &str) -> Self {
+        Self {
+            model: model.to_string(),
+        }
+    }
+
+    /// Estimate the number of tokens of the preamble and messages with this counter's model.
+    pub fn estimate(&self, messages: &[Message], preamble: &str) -> usize {
+        Self::count(&self.model, messages, preamble)
+    }
+
+    /// Estimate the number of tokens of the preamble and messages for the given model.
+    pub fn count(model: &str, messages: &[Message], preamble: &str) -> usize {
+        let preamble_tokens = if preamble.is_empty() {
+            0
+        } else {
+            Self::count_text(model, preamble) + TOKENS_PER_MESSAGE
+        };
+
+        let message_tokens = messages
+            .iter()
+            .map(|message| Self::count_text(model, &message_text(message)) + TOKENS_PER_MESSAGE)
+            .sum::<usize>();
+
+        preamble_tokens + message_tokens + TOKENS_PER_REPLY
+    }
+
+    /// Estimate the number of tokens of a raw string for the given model.
+    pub fn count_text(model: &str, text: &str) -> usize {
+        #[cfg(feature = "tiktoken")]
+        {
+            if let Some(bpe) = bpe(model) {
+                return bpe.encode_with_special_tokens(text).len();
+            }
+        }
+
+        #[cfg(not(feature = "tiktoken"))]
+        let _ = model;
+
+        text.chars().count().div_ceil(CHARS_PER_TOKEN)
+    }
+}
+
+/// Returns the tokenizer of `model`, which is loaded once per tokenizer (loading one takes
+/// longer than encoding most prompts).
+#[cfg(feature = "tiktoken")]
+fn bpe(model: &str) -> Option<std::sync::Arc<tiktoken_rs::CoreBPE>> {
+    use std::{
+        collections::HashMap,
+        sync::{Arc, Mutex, OnceLock},
+    };
+    use tiktoken_rs::{tokenizer::Tokenizer, CoreBPE};
+
+    static BPES: OnceLock<Mutex<HashMap<Tokenizer, Arc<CoreBPE>>>> = OnceLock::new();
+
+    let tokenizer = tiktoken_rs::tokenizer::get_tokenizer(model)?;
+    let mut bpes = BPES
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+
+    if let Some(bpe) = bpes.get(&tokenizer) {
+        return Some(bpe.clone());
+    }
+    let bpe = Arc::new(tiktoken_rs::get_bpe_from_tokenizer(tokenizer).ok()?);
+    bpes.insert(tokenizer, bpe.clone());
+
+    Some(bpe)
+}
+
+/// Concatenates the textual content of a message. Non-textual content (images, audio) is ignored.
+pub(crate) fn message_text(message: &Message) -> String {
+    match message {
+        Message::User { content } => content
+            .iter()
+            .filter_map(|content| match content {
+                UserContent::Text(text) => Some(text.text.clone()),
+                UserContent::Document(document) => Some(document.data.clone()),
+                UserContent::ToolResult(tool_result) => Some(
+                    tool_result
+                        .content
+                        .iter()
+                        .filter_map(|content| match content {
+                            ToolResultContent::Text(text) => Some(text.text.clone()),
+                            ToolResultContent::Image(_) => None,
+                        })
+                        .collect::<Vec<_>>()
+                        .join("\n"),
+                ),
+                UserContent::Image(_) | UserContent::Audio(_) => None,
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Message::Assistant { content } => content
+            .iter()
+            .map(|content| match content {
+                AssistantContent::Text(text) => text.text.clone(),
+                AssistantContent::ToolCall(tool_call) => format!(
+                    "{}({})",
+                    tool_call.function.name, tool_call.function.arguments
+                ),
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heuristic_count_text() {
+        assert_eq!(TokenCounter::count_text("my-local-model", ""), 0);
+        assert_eq!(TokenCounter::count_text("my-local-model", "abcd"), 1);
+        assert_eq!(TokenCounter::count_text("my-local-model", "abcde"), 2);
+    }
+
+    #[test]
+    fn test_count_includes_message_overhead() {
+        let messages = vec![Message::user("abcd"), Message::assistant("abcdefgh")];
+
+        // (1 + 4) + (2 + 4) + 3
+        assert_eq!(TokenCounter::count("my-local-model", &messages, ""), 14);
+        // (1 + 4) + (1 + 4) + (2 + 4) + 3
+        assert_eq!(TokenCounter::count("my-local-model", &messages, "abcd"), 19);
+    }
+}
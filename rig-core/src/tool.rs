@@ -9,7 +9,7 @@
 //! The [ToolSet] struct is a collection of tools that can be used by an [Agent](crate::agent::Agent)
 //! and optionally RAGged.
 
-use std::{collections::HashMap, pin::Pin};
+use std::{collections::HashMap, pin::Pin, sync::Arc};
 
 use futures::Future;
 use serde::{Deserialize, Serialize};
@@ -17,6 +17,8 @@ use serde::{Deserialize, Serialize};
 use crate::{
     completion::{self, ToolDefinition},
     embeddings::{embed::EmbedError, tool::ToolSchema},
+    message::{ToolCall, ToolResult, ToolResultContent},
+    OneOrMany,
 };
 
 #[derive(Debug, thiserror::Error)]
@@ -27,6 +29,31 @@ pub enum ToolError {
 
     #[error("JsonError: {0}")]
     JsonError(#[from] serde_json::Error),
+
+    /// Error the model can recover from (e.g.: invalid arguments). Instead of failing the
+    /// prompt, the message is sent back to the model as an error tool result.
+    #[error("{0}")]
+    Recoverable(String),
+
+    /// Error returned by the tool that cannot be recovered from, which fails the prompt
+    #[error("Fatal: {0}")]
+    Fatal(Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl ToolError {
+    /// Returns the message of the error if it is [ToolError::Recoverable], including when it is
+    /// wrapped in a [ToolError::ToolCallError] (i.e.: when returned by [Tool::call]), or if it
+    /// is a [ToolError::JsonError] (e.g.: the model generated invalid arguments).
+    pub fn recoverable_message(&self) -> Option<String> {
+        match self {
+            ToolError::Recoverable(message) => Some(message.clone()),
+            ToolError::JsonError(_) => Some(self.to_string()),
+            ToolError::ToolCallError(error) => error
+                .downcast_ref::<ToolError>()
+                .and_then(ToolError::recoverable_message),
+            _ => None,
+        }
+    }
 }
 
 /// Trait that represents a simple LLM tool
@@ -104,6 +131,13 @@ pub trait Tool: Sized + Send + Sync {
     /// tailor the definition to the specific use case.
     fn definition(&self, _prompt: String) -> impl Future<Output = ToolDefinition> + Send + Sync;
 
+    /// A method returning usage examples of the tool, as pairs of arguments and their expected
+    /// output. The examples are appended to the description of the tool definition sent to the
+    /// model, which helps it call the tool reliably.
+    fn examples(&self) -> Vec<(serde_json::Value, String)> {
+        Vec::new()
+    }
+
     /// The tool execution method.
     /// Both the arguments and return value are a String since these values are meant to
     /// be the output and input of LLM models (respectively)
@@ -153,6 +187,12 @@ pub trait ToolDyn: Send + Sync {
         &self,
         args: String,
     ) -> Pin<Box<dyn Future<Output = Result<String, ToolError>> + Send + Sync + '_>>;
+
+    /// Whether the tool should be offered to the model for the given prompt (i.e.: the RAG text
+    /// of the request). Tools are always available by default (see [ConditionalTool]).
+    fn is_available(&self, _prompt: &str) -> bool {
+        true
+    }
 }
 
 impl<T: Tool> ToolDyn for T {
@@ -164,7 +204,12 @@ impl<T: Tool> ToolDyn for T {
         &self,
         prompt: String,
     ) -> Pin<Box<dyn Future<Output = ToolDefinition> + Send + Sync + '_>> {
-        Box::pin(<Self as Tool>::definition(self, prompt))
+        let examples = self.examples();
+        Box::pin(async move {
+            let mut definition = <Self as Tool>::definition(self, prompt).await;
+            definition.description = describe_examples(definition.description, &examples);
+            definition
+        })
     }
 
     fn call(
@@ -185,6 +230,72 @@ impl<T: Tool> ToolDyn for T {
     }
 }
 
+/// A tool which is only added to a completion request when its predicate, evaluated against
+/// the RAG text of the request (i.e.: usually the user's prompt), returns `true`.
+///
+/// # Example
+/// ```
+/// use rig::tool::ConditionalTool;
+///
+/// let agent = openai.agent("gpt-4o")
+///     .conditional_tool(Calculator, |prompt| {
+///         prompt.chars().any(|c| c.is_ascii_digit())
+///     })
+///     .build();
+/// ```
+pub struct ConditionalTool<T: Tool> {
+    tool: T,
+    predicate: Box<dyn Fn(&str) -> bool + Send + Sync>,
+}
+
+impl<T: Tool> ConditionalTool<T> {
+    pub fn new(tool: T, predicate: impl Fn(&str) -> bool + Send + Sync + 'static) -> Self {
+        Self {
+            tool,
+            predicate: Box::new(predicate),
+        }
+    }
+}
+
+impl<T: Tool> ToolDyn for ConditionalTool<T> {
+    fn name(&self) -> String {
+        ToolDyn::name(&self.tool)
+    }
+
+    fn definition(
+        &self,
+        prompt: String,
+    ) -> Pin<Box<dyn Future<Output = ToolDefinition> + Send + Sync + '_>> {
+        ToolDyn::definition(&self.tool, prompt)
+    }
+
+    fn call(
+        &self,
+        args: String,
+    ) -> Pin<Box<dyn Future<Output = Result<String, ToolError>> + Send + Sync + '_>> {
+        ToolDyn::call(&self.tool, args)
+    }
+
+    fn is_available(&self, prompt: &str) -> bool {
+        (self.predicate)(prompt)
+    }
+}
+
+/// Append the usage `examples` of a tool to its `description`.
+fn describe_examples(description: String, examples: &[(serde_json::Value, String)]) -> String {
+    if examples.is_empty() {
+        return description;
+    }
+
+    let examples = examples
+        .iter()
+        .map(|(args, output)| format!("- Input: {args}\n  Output: {output}"))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    format!("{description}\n\nExamples:\n{examples}")
+}
+
 #[cfg(feature = "mcp")]
 pub struct McpTool<T: mcp_core::transport::Transport> {
     definition: mcp_core::types::Tool,
@@ -369,6 +480,13 @@ impl ToolType {
             ToolType::Embedding(tool) => tool.call(args).await,
         }
     }
+
+    pub fn is_available(&self, prompt: &str) -> bool {
+        match self {
+            ToolType::Simple(tool) => tool.is_available(prompt),
+            ToolType::Embedding(tool) => tool.is_available(prompt),
+        }
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -385,10 +503,20 @@ pub enum ToolSetError {
     JsonError(#[from] serde_json::Error),
 }
 
+/// A function called with the name and arguments of a tool before the tool is executed.
+/// It can return modified arguments (e.g.: sanitized input), or an error to prevent the
+/// tool from being called.
+pub type ToolInterceptor =
+    Arc<dyn Fn(&str, &serde_json::Value) -> Result<serde_json::Value, ToolError> + Send + Sync>;
+
 /// A struct that holds a set of tools
 #[derive(Default)]
 pub struct ToolSet {
     pub(crate) tools: HashMap<String, ToolType>,
+    /// Interceptors applied to every tool
+    interceptors: Vec<ToolInterceptor>,
+    /// Interceptors applied to a specific tool (identified by its name)
+    tool_interceptors: HashMap<String, Vec<ToolInterceptor>>,
 }
 
 impl ToolSet {
@@ -420,15 +548,104 @@ impl ToolSet {
     /// Merge another toolset into this one
     pub fn add_tools(&mut self, toolset: ToolSet) {
         self.tools.extend(toolset.tools);
+        self.interceptors.extend(toolset.interceptors);
+        for (toolname, interceptors) in toolset.tool_interceptors {
+            self.tool_interceptors
+                .entry(toolname)
+                .or_default()
+                .extend(interceptors);
+        }
+    }
+
+    /// Add an interceptor called before the tool with the given name is executed.
+    /// Interceptors are called in the order they were added, each one receiving the arguments
+    /// returned by the previous one. If an interceptor returns an error, the tool is not called.
+    ///
+    /// # Example
+    /// ```
+    /// use rig::tool::{ToolError, ToolSet};
+    ///
+    /// let mut toolset = ToolSet::default();
+    /// toolset.intercept("query_database", |_, args| {
+    ///     if args["query"].as_str().is_some_and(|query| query.contains("DROP")) {
+    ///         return Err(ToolError::ToolCallError("Forbidden query".into()));
+    ///     }
+    ///     Ok(args.clone())
+    /// });
+    /// ```
+    pub fn intercept(
+        &mut self,
+        fn_name: &str,
+        interceptor: impl Fn(&str, &serde_json::Value) -> Result<serde_json::Value, ToolError>
+            + Send
+            + Sync
+            + 'static,
+    ) {
+        self.tool_interceptors
+            .entry(fn_name.to_string())
+            .or_default()
+            .push(Arc::new(interceptor));
+    }
+
+    /// Add an interceptor called before any tool of the toolset is executed (e.g.: for audit logging).
+    /// Interceptors added with this method are called before the tool specific ones.
+    pub fn intercept_all(
+        &mut self,
+        interceptor: impl Fn(&str, &serde_json::Value) -> Result<serde_json::Value, ToolError>
+            + Send
+            + Sync
+            + 'static,
+    ) {
+        self.interceptors.push(Arc::new(interceptor));
     }
 
-    pub(crate) fn get(&self, toolname: &str) -> Option<&ToolType> {
-        self.tools.get(toolname)
+    /// Run the interceptors of the tool with the given name on its arguments.
+    fn apply_interceptors(&self, toolname: &str, args: String) -> Result<String, ToolSetError> {
+        let interceptors = self
+            .interceptors
+            .iter()
+            .chain(self.tool_interceptors.get(toolname).into_iter().flatten())
+            .collect::<Vec<_>>();
+
+        if interceptors.is_empty() {
+            return Ok(args);
+        }
+
+        let args = interceptors.into_iter().try_fold(
+            serde_json::from_str::<serde_json::Value>(&args)?,
+            |args, interceptor| interceptor(toolname, &args),
+        )?;
+
+        Ok(args.to_string())
+    }
+
+    /// Returns the definitions of the tools `toolnames` available for the given prompt, i.e.:
+    /// skipping the [ConditionalTool]s whose predicate is not satisfied.
+    pub(crate) async fn available_definitions(
+        &self,
+        toolnames: impl IntoIterator<Item = &String>,
+        prompt: &str,
+    ) -> Vec<ToolDefinition> {
+        let mut definitions = vec![];
+        for toolname in toolnames {
+            match self.tools.get(toolname) {
+                Some(tool) if tool.is_available(prompt) => {
+                    // TODO: tool definitions should likely take an `Option<String>`
+                    definitions.push(tool.definition(prompt.into()).await)
+                }
+                Some(_) => {
+                    tracing::debug!(target: "rig", "Tool {toolname} is not available for the prompt")
+                }
+                None => tracing::warn!("Tool implementation not found in toolset: {}", toolname),
+            }
+        }
+        definitions
     }
 
     /// Call a tool with the given name and arguments
     pub async fn call(&self, toolname: &str, args: String) -> Result<String, ToolSetError> {
         if let Some(tool) = self.tools.get(toolname) {
+            let args = self.apply_interceptors(toolname, args)?;
             tracing::info!(target: "rig",
                 "Calling tool {toolname} with args:\n{}",
                 serde_json::to_string_pretty(&args).unwrap_or_else(|_| args.clone())
@@ -439,6 +656,58 @@ impl ToolSet {
         }
     }
 
+    /// Call a tool with the given name and arguments, like [ToolSet::call], if it is available
+    /// for `prompt`, i.e.: the prompt the tool call answers (the RAG text of the request, as
+    /// given to [ToolSet::available_definitions]). A [ConditionalTool] which is not available
+    /// for it is not found, so that the model cannot call a tool that was not offered to it.
+    pub async fn call_with_prompt(
+        &self,
+        toolname: &str,
+        args: String,
+        prompt: &str,
+    ) -> Result<String, ToolSetError> {
+        match self.tools.get(toolname) {
+            Some(tool) if tool.is_available(prompt) => self.call(toolname, args).await,
+            _ => Err(ToolSetError::ToolNotFoundError(toolname.to_string())),
+        }
+    }
+
+    /// Execute all the given tool calls concurrently (e.g.: when a model requests several tool
+    /// calls in a single response). The results are returned in the same order as `calls`.
+    ///
+    /// [Recoverable](ToolError::Recoverable) tool errors are returned as tool results flagged
+    /// with `is_error`, so that they can be sent back to the model.
+    /// `prompt` is the prompt the tool calls answer (see [ToolSet::call_with_prompt]).
+    pub async fn execute_all(
+        &self,
+        calls: Vec<ToolCall>,
+        prompt: &str,
+    ) -> Vec<Result<ToolResult, ToolSetError>> {
+        futures::future::join_all(calls.into_iter().map(|call| async move {
+            let result = match self
+                .call_with_prompt(
+                    &call.function.name,
+                    call.function.arguments.to_string(),
+                    prompt,
+                )
+                .await
+            {
+                Ok(output) => {
+                    ToolResult::new(call.id, OneOrMany::one(ToolResultContent::text(output)))
+                }
+                Err(ToolSetError::ToolCallError(error)) => match error.recoverable_message() {
+                    Some(message) => {
+                        ToolResult::error(call.id, OneOrMany::one(ToolResultContent::text(message)))
+                    }
+                    None => return Err(ToolSetError::ToolCallError(error)),
+                },
+                Err(error) => return Err(error),
+            };
+            Ok(result)
+        }))
+        .await
+    }
+
     /// Get the documents of all the tools in the toolset
     pub async fn documents(&self) -> Result<Vec<completion::Document>, ToolSetError> {
         let mut docs = Vec::new();
@@ -519,6 +788,222 @@ impl ToolSetBuilder {
                 .into_iter()
                 .map(|tool| (tool.name(), tool))
                 .collect(),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    struct Echo;
+
+    impl Tool for Echo {
+        const NAME: &'static str = "echo";
+
+        type Error = ToolError;
+        type Args = serde_json::Value;
+        type Output = serde_json::Value;
+
+        async fn definition(&self, _prompt: String) -> ToolDefinition {
+            ToolDefinition {
+                name: Self::NAME.to_string(),
+                description: "Returns its arguments".to_string(),
+                parameters: json!({}),
+            }
+        }
+
+        async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+            Ok(args)
+        }
+
+        fn examples(&self) -> Vec<(serde_json::Value, String)> {
+            vec![
+                (json!({ "value": "hi" }), r#"{"value":"hi"}"#.to_string()),
+                (json!({}), "{}".to_string()),
+            ]
+        }
+    }
+
+    #[derive(Deserialize, Serialize)]
+    struct StrictArgs {
+        id: Option<u64>,
+    }
+
+    struct Strict;
+
+    impl Tool for Strict {
+        const NAME: &'static str = "strict";
+
+        type Error = ToolError;
+        type Args = StrictArgs;
+        type Output = StrictArgs;
+
+        async fn definition(&self, _prompt: String) -> ToolDefinition {
+            ToolDefinition {
+                name: Self::NAME.to_string(),
+                description: "Returns its arguments, which must contain an id".to_string(),
+                parameters: json!({}),
+            }
+        }
+
+        async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+            match args.id {
+                Some(_) => Ok(args),
+                None => Err(ToolError::Recoverable("Missing `id` argument".to_string())),
+            }
         }
     }
+
+    #[tokio::test]
+    async fn test_definition_examples() {
+        let definition = ToolDyn::definition(&Echo, String::new()).await;
+        assert_eq!(
+            definition.description,
+            "Returns its arguments\n\nExamples:\n\
+             - Input: {\"value\":\"hi\"}\n  Output: {\"value\":\"hi\"}\n\
+             - Input: {}\n  Output: {}"
+        );
+
+        let definition = ToolDyn::definition(&Strict, String::new()).await;
+        assert_eq!(
+            definition.description,
+            "Returns its arguments, which must contain an id"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_conditional_tool() {
+        let toolset = ToolSet::builder()
+            .static_tool(ConditionalTool::new(Echo, |prompt| prompt.contains("echo")))
+            .static_tool(Strict)
+            .build();
+        let toolnames = ["echo".to_string(), "strict".to_string()];
+
+        let definitions = toolset
+            .available_definitions(&toolnames, "Please echo this")
+            .await;
+        assert_eq!(definitions.len(), 2);
+
+        let definitions = toolset.available_definitions(&toolnames, "Hello").await;
+        assert_eq!(definitions.len(), 1);
+        assert_eq!(definitions[0].name, "strict");
+
+        // The tool can only be called for the prompts it is available for
+        assert_eq!(
+            toolset
+                .call_with_prompt("echo", "{}".into(), "Please echo this")
+                .await
+                .unwrap(),
+            "{}"
+        );
+        assert!(matches!(
+            toolset.call_with_prompt("echo", "{}".into(), "Hello").await,
+            Err(ToolSetError::ToolNotFoundError(_))
+        ));
+        // Unless it is called directly
+        assert_eq!(toolset.call("echo", "{}".into()).await.unwrap(), "{}");
+    }
+
+    #[tokio::test]
+    async fn test_interceptors_compose() {
+        let mut toolset = ToolSet::from_tools(vec![Echo]);
+        toolset.intercept_all(|_, args| Ok(json!({ "value": args["value"], "audited": true })));
+        toolset.intercept("echo", |_, args| {
+            let mut args = args.clone();
+            args["value"] = json!(args["value"].as_str().unwrap_or_default().trim());
+            Ok(args)
+        });
+        toolset.intercept("other", |_, _| {
+            Err(ToolError::ToolCallError("Not applied to echo".into()))
+        });
+
+        let result = toolset
+            .call("echo", json!({ "value": "  hello  " }).to_string())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&result).unwrap(),
+            json!({ "value": "hello", "audited": true })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_interceptor_blocks_call() {
+        let mut toolset = ToolSet::from_tools(vec![Echo]);
+        toolset.intercept("echo", |_, _| {
+            Err(ToolError::ToolCallError("Forbidden".into()))
+        });
+
+        let result = toolset.call("echo", "{}".to_string()).await;
+
+        assert!(matches!(result, Err(ToolSetError::ToolCallError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_execute_all() {
+        let toolset = ToolSet::from_tools(vec![Echo]);
+        let call = |id: &str, name: &str| ToolCall {
+            id: id.to_string(),
+            function: completion::message::ToolFunction {
+                name: name.to_string(),
+                arguments: json!({ "id": id }),
+            },
+        };
+
+        let results = toolset
+            .execute_all(
+                vec![call("1", "echo"), call("2", "missing"), call("3", "echo")],
+                "",
+            )
+            .await;
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(
+            results[0].as_ref().unwrap().content,
+            OneOrMany::one(ToolResultContent::text(r#"{"id":"1"}"#))
+        );
+        assert!(matches!(
+            results[1],
+            Err(ToolSetError::ToolNotFoundError(_))
+        ));
+        assert_eq!(results[2].as_ref().unwrap().id, "3");
+    }
+
+    #[tokio::test]
+    async fn test_execute_all_recoverable_error() {
+        let toolset = ToolSet::from_tools(vec![Strict]);
+        let call = |id: &str, arguments: serde_json::Value| ToolCall {
+            id: id.to_string(),
+            function: completion::message::ToolFunction {
+                name: "strict".to_string(),
+                arguments,
+            },
+        };
+
+        let results = toolset
+            .execute_all(
+                vec![
+                    call("1", json!({ "id": 1 })),
+                    call("2", json!({})),
+                    call("3", json!({ "id": "one" })),
+                ],
+                "",
+            )
+            .await;
+
+        assert!(!results[0].as_ref().unwrap().is_error);
+        let result = results[1].as_ref().unwrap();
+        assert!(result.is_error);
+        assert_eq!(
+            result.content,
+            OneOrMany::one(ToolResultContent::text("Missing `id` argument"))
+        );
+        // Invalid arguments are sent back to the model as well
+        assert!(results[2].as_ref().unwrap().is_error);
+    }
 }
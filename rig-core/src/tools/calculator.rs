@@ -0,0 +1,111 @@
+use std::convert::Infallible;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{completion::ToolDefinition, tool::Tool};
+
+#[derive(Deserialize)]
+pub struct CalculatorArgs {
+    expression: String,
+}
+
+/// The result of the evaluation of an expression, as returned to the model.
+/// Exactly one of `result` and `error` is set.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct CalculatorOutput {
+    pub result: Option<f64>,
+    pub error: Option<String>,
+}
+
+/// A tool evaluating the arithmetic expressions of the model (e.g.: `(3 + 4.5) * 2 / sqrt(16)`),
+/// so that it does not have to compute them itself.
+///
+/// Invalid expressions, divisions by zero and overflows are reported to the model in the `error`
+/// field of the [CalculatorOutput] instead of failing the tool call.
+///
+/// Note: The [Calculator] requires the `calculator` feature to be enabled in the `Cargo.toml` file.
+///
+/// # Example
+/// ```rust
+/// use rig::tools::Calculator;
+///
+/// let agent = openai.agent("gpt-4o")
+///     .preamble("You are an accountant. Use the calculator for every computation.")
+///     .tool(Calculator)
+///     .build();
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Calculator;
+
+impl Calculator {
+    /// Evaluate `expression`, reporting any error in the output.
+    pub fn evaluate(&self, expression: &str) -> CalculatorOutput {
+        let (result, error) = match meval::eval_str(expression) {
+            Ok(result) if result.is_finite() => (Some(result), None),
+            // Divisions by zero and overflows evaluate to infinity (or NaN for `0 / 0`)
+            Ok(result) => (
+                None,
+                Some(format!(
+                    "The expression evaluates to {result} (division by zero or overflow)"
+                )),
+            ),
+            Err(e) => (None, Some(format!("Invalid expression: {e}"))),
+        };
+
+        CalculatorOutput { result, error }
+    }
+}
+
+impl Tool for Calculator {
+    const NAME: &'static str = "calculator";
+
+    type Error = Infallible;
+    type Args = CalculatorArgs;
+    type Output = CalculatorOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: "Evaluate an arithmetic expression and return its exact result. \
+                Supports +, -, *, /, % and ^, parentheses, constants (pi, e) and functions \
+                (sqrt, abs, exp, ln, sin, cos, tan, floor, ceil, round, min, max)."
+                .to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "expression": {
+                        "type": "string",
+                        "description": "The expression to evaluate, e.g.: (3 + 4.5) * 2"
+                    }
+                },
+                "required": ["expression"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        Ok(self.evaluate(&args.expression))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_evaluate() {
+        assert_eq!(
+            Calculator.evaluate("(3 + 4.5) * 2 / sqrt(16)"),
+            CalculatorOutput {
+                result: Some(3.75),
+                error: None
+            }
+        );
+
+        for expression in ["1 / 0", "0 / 0", "10 ^ 400", "2 +", "foo(2)"] {
+            let output = Calculator.evaluate(expression);
+            assert_eq!(output.result, None, "{expression}");
+            assert!(output.error.is_some(), "{expression}");
+        }
+    }
+}
@@ -0,0 +1,300 @@
+use std::{
+    path::Path,
+    process::Stdio,
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{completion::ToolDefinition, tool::Tool};
+
+/// Counter used to give a unique name to the containers of concurrent calls
+static CONTAINER_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// The sandbox in which a [CodeInterpreter] runs the code of the model.
+/// In both cases, the code has no network access and a read-only view of the filesystem.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Sandbox {
+    /// Run the code in a throwaway Docker container of the given image (which must provide `python`)
+    Docker { image: String },
+    /// Run the code with the host's `python3` inside a [bubblewrap](https://github.com/containers/bubblewrap)
+    /// sandbox (requires `bwrap` to be installed, Linux only). Only the system directories
+    /// (`/usr`, `/lib`, `/bin`...) of the host are visible.
+    Bubblewrap,
+}
+
+impl Default for Sandbox {
+    fn default() -> Self {
+        Sandbox::Docker {
+            image: "python:3.12-slim".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CodeInterpreterError {
+    #[error("IoError: {0}")]
+    IoError(#[from] std::io::Error),
+
+    #[error("Execution timed out after {0:?}")]
+    Timeout(Duration),
+}
+
+#[derive(Deserialize)]
+pub struct CodeInterpreterArgs {
+    code: String,
+}
+
+/// The result of the execution of the code, as returned to the model.
+#[derive(Debug, Serialize)]
+pub struct CodeInterpreterOutput {
+    pub stdout: String,
+    pub stderr: String,
+    /// Exit code of the interpreter (`None` if it was killed by a signal)
+    pub exit_code: Option<i32>,
+}
+
+/// A tool executing the Python code written by the model in a sandbox and returning its
+/// output (i.e.: stdout, stderr and exit code).
+///
+/// Note: The [CodeInterpreter] requires the `code-interpreter` feature to be enabled in the `Cargo.toml` file.
+///
+/// # Example
+/// ```rust
+/// use std::time::Duration;
+/// use rig::tools::{CodeInterpreter, Sandbox};
+///
+/// let interpreter = CodeInterpreter::new(Sandbox::Bubblewrap).timeout(Duration::from_secs(10));
+///
+/// let agent = openai.agent("gpt-4o")
+///     .preamble("You are a data analyst. Use Python to compute exact results.")
+///     .tool(interpreter)
+///     .build();
+/// ```
+#[derive(Clone, Debug)]
+pub struct CodeInterpreter {
+    sandbox: Sandbox,
+    timeout: Duration,
+}
+
+impl Default for CodeInterpreter {
+    fn default() -> Self {
+        Self::new(Sandbox::default())
+    }
+}
+
+impl CodeInterpreter {
+    /// Create a code interpreter running code in `sandbox`, with a timeout of 30 seconds.
+    pub fn new(sandbox: Sandbox) -> Self {
+        Self {
+            sandbox,
+            timeout: Duration::from_secs(30),
+        }
+    }
+
+    /// Set the maximum duration of an execution, after which the interpreter is killed.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Build the command running the script at `script` in the sandbox (in the container
+    /// `container` for [Sandbox::Docker]).
+    fn command(&self, script: &Path, container: &str) -> tokio::process::Command {
+        let script = script.to_string_lossy();
+
+        let mut command = match &self.sandbox {
+            Sandbox::Docker { image } => {
+                let mut command = tokio::process::Command::new("docker");
+                command.args(["run", "--rm", "--name", container]);
+                command.args(["--network", "none", "--read-only"]);
+                command.args(["--tmpfs", "/tmp", "--memory", "512m", "--pids-limit", "64"]);
+                command
+                    .arg("-v")
+                    .arg(format!("{script}:/sandbox/main.py:ro"));
+                command.args([image.as_str(), "python", "/sandbox/main.py"]);
+                command
+            }
+            Sandbox::Bubblewrap => {
+                let mut command = tokio::process::Command::new("bwrap");
+                // Only the directories needed by the interpreter are visible (read-only), not the
+                // files of the host (e.g.: credentials) which the output would leak to the model
+                command.args(["--ro-bind", "/usr", "/usr"]);
+                for dir in ["/lib", "/lib64", "/bin", "/etc/alternatives"] {
+                    command.args(["--ro-bind-try", dir, dir]);
+                }
+                command.args(["--dev", "/dev", "--proc", "/proc"]);
+                command.args(["--tmpfs", "/tmp", "--unshare-all", "--die-with-parent"]);
+                command.args([
+                    "--clearenv",
+                    "--setenv",
+                    "PATH",
+                    "/usr/local/bin:/usr/bin:/bin",
+                ]);
+                command.arg("--ro-bind").arg(&*script).arg("/tmp/main.py");
+                command.args(["python3", "/tmp/main.py"]);
+                command
+            }
+        };
+
+        command
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+        command
+    }
+
+    async fn run(&self, script: &Path) -> Result<CodeInterpreterOutput, CodeInterpreterError> {
+        let container = format!(
+            "rig-code-interpreter-{}-{}",
+            std::process::id(),
+            CONTAINER_COUNTER.fetch_add(1, Ordering::Relaxed)
+        );
+        let result = execute(self.command(script, &container), self.timeout).await;
+
+        // Killing the `docker run` client leaves the container running, so it is killed too
+        if let (Err(CodeInterpreterError::Timeout(_)), Sandbox::Docker { .. }) =
+            (&result, &self.sandbox)
+        {
+            let killed = tokio::process::Command::new("docker")
+                .args(["kill", &container])
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status()
+                .await;
+            if !killed.is_ok_and(|status| status.success()) {
+                tracing::warn!(target: "rig", "Failed to kill the container {container}");
+            }
+        }
+
+        result
+    }
+}
+
+/// Run `command`, killing it if it is still running after `timeout`.
+async fn execute(
+    mut command: tokio::process::Command,
+    timeout: Duration,
+) -> Result<CodeInterpreterOutput, CodeInterpreterError> {
+    let output = tokio::time::timeout(timeout, command.output())
+        .await
+        .map_err(|_| CodeInterpreterError::Timeout(timeout))??;
+
+    Ok(CodeInterpreterOutput {
+        stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+        stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+        exit_code: output.status.code(),
+    })
+}
+
+impl Tool for CodeInterpreter {
+    const NAME: &'static str = "code_interpreter";
+
+    type Error = CodeInterpreterError;
+    type Args = CodeInterpreterArgs;
+    type Output = CodeInterpreterOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: "Execute Python code and return its output (stdout, stderr and exit \
+                code). Print the values you need to see. The code has no network access and \
+                cannot modify files."
+                .to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "code": {
+                        "type": "string",
+                        "description": "The Python code to execute"
+                    }
+                },
+                "required": ["code"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        // The script is removed when dropped
+        let script = tempfile::Builder::new()
+            .prefix("rig-code-interpreter-")
+            .suffix(".py")
+            .tempfile()?;
+        tokio::fs::write(script.path(), args.code).await?;
+
+        self.run(script.path()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bubblewrap_command() {
+        let interpreter = CodeInterpreter::new(Sandbox::Bubblewrap);
+        let command = interpreter.command(Path::new("/tmp/script.py"), "container");
+        let command = command.as_std();
+
+        let args = command
+            .get_args()
+            .map(|arg| arg.to_string_lossy().to_string())
+            .collect::<Vec<_>>();
+
+        assert_eq!(command.get_program(), "bwrap");
+        assert!(args.contains(&"--unshare-all".to_string()));
+        assert!(args.contains(&"--clearenv".to_string()));
+        assert!(args.contains(&"--die-with-parent".to_string()));
+        // The host filesystem is not visible as a whole
+        assert!(!args
+            .windows(3)
+            .any(|window| window[0].starts_with("--") && window[1] == "/" && window[2] == "/"));
+        assert!(args
+            .windows(3)
+            .any(|window| window == ["--ro-bind", "/usr", "/usr"]));
+        assert!(args.ends_with(&["python3".to_string(), "/tmp/main.py".to_string()]));
+    }
+
+    #[test]
+    fn test_docker_command() {
+        let interpreter = CodeInterpreter::default();
+        let command = interpreter.command(Path::new("/tmp/script.py"), "container");
+        let command = command.as_std();
+
+        let args = command
+            .get_args()
+            .map(|arg| arg.to_string_lossy().to_string())
+            .collect::<Vec<_>>();
+
+        assert_eq!(command.get_program(), "docker");
+        assert_eq!(args[..4], ["run", "--rm", "--name", "container"]);
+        assert!(args.contains(&"/tmp/script.py:/sandbox/main.py:ro".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_execute() {
+        let mut command = tokio::process::Command::new("sh");
+        command
+            .args(["-c", "echo out; echo err >&2; exit 3"])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+
+        let output = execute(command, Duration::from_secs(10)).await.unwrap();
+        assert_eq!(output.stdout, "out\n");
+        assert_eq!(output.stderr, "err\n");
+        assert_eq!(output.exit_code, Some(3));
+    }
+
+    #[tokio::test]
+    async fn test_execute_timeout() {
+        let mut command = tokio::process::Command::new("sleep");
+        command.arg("10").kill_on_drop(true);
+
+        let start = std::time::Instant::now();
+        let result = execute(command, Duration::from_millis(100)).await;
+        assert!(matches!(result, Err(CodeInterpreterError::Timeout(_))));
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+}
@@ -0,0 +1,352 @@
+use std::ops::ControlFlow;
+
+use serde::Deserialize;
+use sqlparser::{
+    ast::{ObjectName, Query, SetExpr, Statement, Visit, Visitor},
+    dialect::GenericDialect,
+    parser::Parser,
+};
+use sqlx::{any::AnyRow, AnyPool, Column, Row};
+
+use crate::{completion::ToolDefinition, tool::Tool};
+
+#[derive(Debug, thiserror::Error)]
+pub enum DatabaseError {
+    #[error("ParseError: {0}")]
+    ParseError(#[from] sqlparser::parser::ParserError),
+
+    /// The query is not a single `SELECT` statement
+    #[error("Only read-only SELECT queries are allowed")]
+    NotReadOnly,
+
+    #[error("Table {0} is not allowed")]
+    TableNotAllowed(String),
+
+    #[error("SqlError: {0}")]
+    SqlError(#[from] sqlx::Error),
+
+    /// The task running the query panicked
+    #[error("JoinError: {0}")]
+    JoinError(#[from] tokio::task::JoinError),
+}
+
+#[derive(Deserialize)]
+pub struct DatabaseArgs {
+    query: String,
+}
+
+/// A tool running the read-only SQL queries of the model on a database.
+///
+/// Each query is parsed before being executed, and rejected unless it is a single `SELECT`
+/// statement that only reads the allowed tables (tables are matched by their name as written in
+/// the query, e.g.: `public.users` must be allowed to be read as such). Since this check cannot
+/// account for functions with side effects, the pool should still connect with a read-only user.
+///
+/// The rows are returned as a JSON array of objects (one property per column).
+///
+/// # Example
+/// ```rust
+/// use rig::tools::DatabaseTool;
+///
+/// sqlx::any::install_default_drivers();
+/// let pool = sqlx::AnyPool::connect("postgres://readonly@localhost/shop").await?;
+///
+/// let agent = openai.agent("gpt-4o")
+///     .preamble("You are a data analyst. Answer the questions with the `orders` table.")
+///     .tool(DatabaseTool::new(pool, vec!["orders".to_string()]))
+///     .build();
+/// ```
+#[derive(Clone, Debug)]
+pub struct DatabaseTool {
+    pool: AnyPool,
+    allowed_tables: Vec<String>,
+}
+
+impl DatabaseTool {
+    pub fn new(pool: AnyPool, allowed_tables: Vec<String>) -> Self {
+        Self {
+            pool,
+            allowed_tables,
+        }
+    }
+}
+
+/// Visitor collecting the tables read by a query, and whether all its (sub)queries are read-only.
+/// The common table expressions in scope where a table is read are not collected.
+#[derive(Default)]
+struct QueryVisitor {
+    tables: Vec<String>,
+    scopes: Vec<CteScope>,
+    read_only: bool,
+}
+
+/// The common table expressions defined by a query being visited.
+struct CteScope {
+    query: *const Query,
+    ctes: Vec<(String, *const Query)>,
+    recursive: bool,
+    /// Number of CTEs in scope, i.e.: the ones defined before the one being visited (including
+    /// it if `recursive`), or all of them once the body of the query is visited
+    visible: usize,
+}
+
+impl QueryVisitor {
+    fn is_cte(&self, name: &str) -> bool {
+        self.scopes.iter().any(|scope| {
+            scope.ctes[..scope.visible]
+                .iter()
+                .any(|(cte, _)| cte.eq_ignore_ascii_case(name))
+        })
+    }
+}
+
+impl Visitor for QueryVisitor {
+    type Break = ();
+
+    fn pre_visit_query(&mut self, query: &Query) -> ControlFlow<Self::Break> {
+        // e.g.: `SELECT ... FOR UPDATE`
+        if !query.locks.is_empty() || !is_read_only(&query.body) {
+            self.read_only = false;
+            return ControlFlow::Break(());
+        }
+        // A CTE only sees the ones defined before it (and itself if recursive)
+        if let Some(parent) = self.scopes.last_mut() {
+            if let Some(i) = parent
+                .ctes
+                .iter()
+                .position(|(_, cte)| std::ptr::eq(*cte, query))
+            {
+                parent.visible = if parent.recursive { i + 1 } else { i };
+            }
+        }
+        let (ctes, recursive) = match &query.with {
+            Some(with) => (
+                with.cte_tables
+                    .iter()
+                    .map(|cte| (cte.alias.name.value.clone(), &*cte.query as *const Query))
+                    .collect(),
+                with.recursive,
+            ),
+            None => (vec![], false),
+        };
+        self.scopes.push(CteScope {
+            query,
+            ctes,
+            recursive,
+            visible: 0,
+        });
+        ControlFlow::Continue(())
+    }
+
+    fn post_visit_query(&mut self, query: &Query) -> ControlFlow<Self::Break> {
+        if self
+            .scopes
+            .last()
+            .is_some_and(|scope| std::ptr::eq(scope.query, query))
+        {
+            self.scopes.pop();
+        }
+        // The CTEs visited so far are in scope of the next ones and of the body
+        if let Some(parent) = self.scopes.last_mut() {
+            if let Some(i) = parent
+                .ctes
+                .iter()
+                .position(|(_, cte)| std::ptr::eq(*cte, query))
+            {
+                parent.visible = i + 1;
+            }
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn pre_visit_relation(&mut self, relation: &ObjectName) -> ControlFlow<Self::Break> {
+        let table = relation
+            .0
+            .iter()
+            .map(|ident| ident.value.as_str())
+            .collect::<Vec<_>>()
+            .join(".");
+        if relation.0.len() > 1 || !self.is_cte(&table) {
+            self.tables.push(table);
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+fn is_read_only(body: &SetExpr) -> bool {
+    match body {
+        SetExpr::Select(select) => select.into.is_none(),
+        SetExpr::Query(query) => is_read_only(&query.body),
+        SetExpr::SetOperation { left, right, .. } => is_read_only(left) && is_read_only(right),
+        SetExpr::Values(_) => true,
+        _ => false,
+    }
+}
+
+/// Check that `sql` is a single read-only query reading only `allowed_tables`
+/// (or the common table expressions it defines).
+fn check_query(sql: &str, allowed_tables: &[String]) -> Result<(), DatabaseError> {
+    let statements = Parser::parse_sql(&GenericDialect {}, sql)?;
+    let [statement @ Statement::Query(_)] = statements.as_slice() else {
+        return Err(DatabaseError::NotReadOnly);
+    };
+
+    let mut visitor = QueryVisitor {
+        read_only: true,
+        ..Default::default()
+    };
+    let _ = statement.visit(&mut visitor);
+    if !visitor.read_only {
+        return Err(DatabaseError::NotReadOnly);
+    }
+
+    match visitor.tables.into_iter().find(|table| {
+        !allowed_tables
+            .iter()
+            .any(|allowed| allowed.eq_ignore_ascii_case(table))
+    }) {
+        Some(table) => Err(DatabaseError::TableNotAllowed(table)),
+        None => Ok(()),
+    }
+}
+
+/// Convert a row to a JSON object, decoding each column as the first matching JSON type.
+fn row_to_json(row: &AnyRow) -> serde_json::Value {
+    let object = row
+        .columns()
+        .iter()
+        .map(|column| {
+            let i = column.ordinal();
+            let value = if let Ok(value) = row.try_get::<Option<i64>, _>(i) {
+                serde_json::json!(value)
+            } else if let Ok(value) = row.try_get::<Option<f64>, _>(i) {
+                serde_json::json!(value)
+            } else if let Ok(value) = row.try_get::<Option<bool>, _>(i) {
+                serde_json::json!(value)
+            } else if let Ok(value) = row.try_get::<Option<String>, _>(i) {
+                serde_json::json!(value)
+            } else {
+                serde_json::Value::Null
+            };
+            (column.name().to_string(), value)
+        })
+        .collect();
+
+    serde_json::Value::Object(object)
+}
+
+impl Tool for DatabaseTool {
+    const NAME: &'static str = "database";
+
+    type Error = DatabaseError;
+    type Args = DatabaseArgs;
+    type Output = serde_json::Value;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: format!(
+                "Run a read-only SQL query (a single SELECT statement) on the database and \
+                return the rows as a JSON array of objects. The tables available are: {}.",
+                self.allowed_tables.join(", ")
+            ),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "query": {
+                        "type": "string",
+                        "description": "The SQL query to run (e.g.: SELECT count(*) AS total FROM orders)"
+                    }
+                },
+                "required": ["query"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        check_query(&args.query, &self.allowed_tables)?;
+
+        // The futures of sqlx are not `Sync`, so the query runs in its own task
+        let pool = self.pool.clone();
+        let rows = tokio::spawn(async move {
+            let rows = sqlx::query(&args.query).fetch_all(&pool).await?;
+            Ok::<_, DatabaseError>(rows.iter().map(row_to_json).collect())
+        })
+        .await??;
+
+        Ok(serde_json::Value::Array(rows))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_query() {
+        let allowed = vec!["orders".to_string(), "customers".to_string()];
+
+        for sql in [
+            "SELECT * FROM orders",
+            "select o.id, c.name from Orders o join customers c on o.customer_id = c.id",
+            "SELECT count(*) FROM (SELECT id FROM orders WHERE total > 10) AS big",
+            "WITH recent AS (SELECT * FROM orders) SELECT * FROM recent",
+            "SELECT id FROM orders UNION SELECT id FROM customers",
+            "WITH a AS (SELECT * FROM orders), b AS (SELECT * FROM a) SELECT * FROM b",
+            "SELECT * FROM orders WHERE id IN (WITH a AS (SELECT id FROM orders) SELECT id FROM a)",
+            "WITH RECURSIVE ids AS (SELECT id FROM orders UNION ALL SELECT id + 1 FROM ids) \
+             SELECT * FROM ids",
+        ] {
+            assert!(check_query(sql, &allowed).is_ok(), "{sql}");
+        }
+
+        for sql in [
+            "DELETE FROM orders",
+            "UPDATE orders SET total = 0",
+            "DROP TABLE orders",
+            "SELECT * FROM orders; DELETE FROM orders",
+            "SELECT * INTO backup FROM orders",
+            "SELECT * FROM orders FOR UPDATE",
+        ] {
+            assert!(
+                matches!(check_query(sql, &allowed), Err(DatabaseError::NotReadOnly)),
+                "{sql}"
+            );
+        }
+
+        assert!(matches!(
+            check_query("SELECT * FROM orders JOIN users ON true", &allowed),
+            Err(DatabaseError::TableNotAllowed(table)) if table == "users"
+        ));
+        assert!(matches!(
+            check_query("SELECT * FROM orders WHERE id IN (SELECT id FROM secrets)", &allowed),
+            Err(DatabaseError::TableNotAllowed(table)) if table == "secrets"
+        ));
+        // A CTE does not hide the table it is named after in its own definition,
+        // nor in the ones before it
+        for sql in [
+            "WITH secrets AS (SELECT * FROM secrets) SELECT * FROM secrets",
+            "WITH secrets AS (SELECT * FROM orders WHERE id IN (SELECT id FROM secrets)) \
+             SELECT * FROM secrets",
+            "SELECT * FROM orders WHERE id IN \
+             (WITH secrets AS (SELECT id FROM secrets) SELECT id FROM secrets)",
+            "WITH a AS (SELECT * FROM secrets), secrets AS (SELECT * FROM orders) SELECT * FROM a",
+        ] {
+            assert!(
+                matches!(
+                    check_query(sql, &allowed),
+                    Err(DatabaseError::TableNotAllowed(table)) if table == "secrets"
+                ),
+                "{sql}"
+            );
+        }
+        assert!(matches!(
+            check_query("SELECT * FROM other.orders", &allowed),
+            Err(DatabaseError::TableNotAllowed(table)) if table == "other.orders"
+        ));
+        assert!(matches!(
+            check_query("SELEC * FROM orders", &allowed),
+            Err(DatabaseError::ParseError(_))
+        ));
+    }
+}
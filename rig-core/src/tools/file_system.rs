@@ -0,0 +1,239 @@
+use std::{
+    path::{Component, Path, PathBuf},
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use serde::Deserialize;
+
+use crate::{completion::ToolDefinition, tool::Tool};
+
+/// Counter used to give a unique name to the temporary files of concurrent writes
+static TEMP_FILE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Debug, thiserror::Error)]
+pub enum FileSystemError {
+    #[error("IoError: {0}")]
+    IoError(#[from] std::io::Error),
+
+    #[error("Path {0} is outside of the allowed root")]
+    PathOutsideRoot(String),
+
+    #[error("Missing `content` argument for write_file")]
+    MissingContent,
+}
+
+/// An operation of a [FileSystemTool].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileSystemOperation {
+    ReadFile,
+    WriteFile,
+    ListDir,
+}
+
+#[derive(Deserialize)]
+pub struct FileSystemArgs {
+    operation: FileSystemOperation,
+    path: String,
+    content: Option<String>,
+}
+
+/// A tool reading, writing and listing the files of a directory (the allowed root).
+///
+/// The paths of the model are relative to the allowed root, and rejected if they are absolute,
+/// contain `..` components or resolve (through symlinks) outside of the root. Files are written
+/// atomically: the content is written to a temporary file which then replaces the target file.
+///
+/// # Example
+/// ```rust
+/// use rig::tools::FileSystemTool;
+///
+/// let agent = openai.agent("gpt-4o")
+///     .preamble("You are a technical writer. Keep the documentation in the `docs` directory up to date.")
+///     .tool(FileSystemTool::new("./docs".into()))
+///     .build();
+/// ```
+#[derive(Clone, Debug)]
+pub struct FileSystemTool {
+    allowed_root: PathBuf,
+}
+
+impl FileSystemTool {
+    pub fn new(allowed_root: PathBuf) -> Self {
+        Self { allowed_root }
+    }
+
+    /// Resolve the `path` of the model within the allowed root, failing if it escapes it.
+    async fn resolve(&self, path: &str) -> Result<PathBuf, FileSystemError> {
+        let outside_root = || FileSystemError::PathOutsideRoot(path.to_string());
+
+        let relative = Path::new(path);
+        if !relative
+            .components()
+            .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+        {
+            return Err(outside_root());
+        }
+
+        let root = tokio::fs::canonicalize(&self.allowed_root).await?;
+        let resolved = root.join(relative);
+
+        // Check the deepest existing ancestor, in case of symlinks pointing outside of the root
+        let mut existing = resolved.as_path();
+        while !tokio::fs::try_exists(existing).await? {
+            existing = existing.parent().ok_or_else(outside_root)?;
+        }
+        if !tokio::fs::canonicalize(existing).await?.starts_with(&root) {
+            return Err(outside_root());
+        }
+
+        Ok(resolved)
+    }
+
+    async fn read_file(&self, path: &str) -> Result<String, FileSystemError> {
+        Ok(tokio::fs::read_to_string(self.resolve(path).await?).await?)
+    }
+
+    async fn write_file(&self, path: &str, content: &str) -> Result<String, FileSystemError> {
+        let target = self.resolve(path).await?;
+        let (Some(parent), Some(file_name)) = (target.parent(), target.file_name()) else {
+            return Err(FileSystemError::PathOutsideRoot(path.to_string()));
+        };
+        tokio::fs::create_dir_all(parent).await?;
+
+        let temp = parent.join(format!(
+            ".{}.{}.tmp",
+            file_name.to_string_lossy(),
+            TEMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        if let Err(e) = tokio::fs::write(&temp, content).await {
+            let _ = tokio::fs::remove_file(&temp).await;
+            return Err(e.into());
+        }
+        if let Err(e) = tokio::fs::rename(&temp, &target).await {
+            let _ = tokio::fs::remove_file(&temp).await;
+            return Err(e.into());
+        }
+
+        Ok(format!("Wrote {} bytes to {path}", content.len()))
+    }
+
+    async fn list_dir(&self, path: &str) -> Result<String, FileSystemError> {
+        let mut entries = tokio::fs::read_dir(self.resolve(path).await?).await?;
+
+        let mut names = vec![];
+        while let Some(entry) = entries.next_entry().await? {
+            let mut name = entry.file_name().to_string_lossy().into_owned();
+            if entry.file_type().await?.is_dir() {
+                name.push('/');
+            }
+            names.push(name);
+        }
+        names.sort();
+
+        Ok(names.join("\n"))
+    }
+}
+
+impl Tool for FileSystemTool {
+    const NAME: &'static str = "file_system";
+
+    type Error = FileSystemError;
+    type Args = FileSystemArgs;
+    type Output = String;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: "Read a file, write a file or list the entries of a directory \
+                (directories end with `/`). Paths are relative to the working directory."
+                .to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "operation": {
+                        "type": "string",
+                        "enum": ["read_file", "write_file", "list_dir"],
+                        "description": "The operation to perform"
+                    },
+                    "path": {
+                        "type": "string",
+                        "description": "The relative path of the file or directory (e.g.: src/main.rs, or . for the working directory)"
+                    },
+                    "content": {
+                        "type": "string",
+                        "description": "The new content of the file (write_file only)"
+                    }
+                },
+                "required": ["operation", "path"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        match args.operation {
+            FileSystemOperation::ReadFile => self.read_file(&args.path).await,
+            FileSystemOperation::WriteFile => {
+                let content = args.content.ok_or(FileSystemError::MissingContent)?;
+                self.write_file(&args.path, &content).await
+            }
+            FileSystemOperation::ListDir => self.list_dir(&args.path).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(operation: FileSystemOperation, path: &str, content: Option<&str>) -> FileSystemArgs {
+        FileSystemArgs {
+            operation,
+            path: path.to_string(),
+            content: content.map(str::to_string),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_file_system() {
+        let root = assert_fs::TempDir::new().unwrap();
+        let tool = FileSystemTool::new(root.path().to_path_buf());
+
+        let output = tool
+            .call(args(
+                FileSystemOperation::WriteFile,
+                "notes/todo.md",
+                Some("- Write tests"),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(output, "Wrote 13 bytes to notes/todo.md");
+
+        let content = tool
+            .call(args(FileSystemOperation::ReadFile, "notes/todo.md", None))
+            .await
+            .unwrap();
+        assert_eq!(content, "- Write tests");
+
+        let entries = tool
+            .call(args(FileSystemOperation::ListDir, ".", None))
+            .await
+            .unwrap();
+        assert_eq!(entries, "notes/");
+
+        for path in ["../secret.txt", "notes/../../secret.txt", "/etc/passwd"] {
+            let result = tool
+                .call(args(FileSystemOperation::ReadFile, path, None))
+                .await;
+            assert!(
+                matches!(result, Err(FileSystemError::PathOutsideRoot(_))),
+                "{path}"
+            );
+        }
+
+        let result = tool
+            .call(args(FileSystemOperation::WriteFile, "notes/todo.md", None))
+            .await;
+        assert!(matches!(result, Err(FileSystemError::MissingContent)));
+    }
+}
@@ -0,0 +1,36 @@
+//! This module contains ready-to-use [Tools](crate::tool::Tool) that can be added to agents
+//! (see [AgentBuilder::tool](crate::agent::AgentBuilder::tool)).
+//!
+//! The following tools are available:
+//! - [WebhookTool]: Calls an HTTP endpoint (e.g.: a REST API) with the input of the model
+//! - `CodeInterpreter`: Executes the Python code of the model in a sandbox (requires the
+//!   `code-interpreter` feature)
+//! - `SearchTool`: Searches the web with SerpApi, Brave Search or Tavily (requires the
+//!   `serpapi`, `brave-search` or `tavily` feature)
+//! - `Calculator`: Evaluates arithmetic expressions (requires the `calculator` feature)
+//! - `FileSystemTool`: Reads, writes and lists the files of a directory (not available on WASM)
+//! - `DatabaseTool`: Runs read-only SQL queries on a database (requires the `database` feature)
+
+#[cfg(feature = "calculator")]
+mod calculator;
+#[cfg(feature = "code-interpreter")]
+mod code_interpreter;
+#[cfg(feature = "database")]
+mod database;
+#[cfg(not(target_arch = "wasm32"))]
+mod file_system;
+#[cfg(any(feature = "serpapi", feature = "brave-search", feature = "tavily"))]
+mod search;
+mod webhook;
+
+#[cfg(feature = "calculator")]
+pub use calculator::{Calculator, CalculatorOutput};
+#[cfg(feature = "code-interpreter")]
+pub use code_interpreter::{CodeInterpreter, CodeInterpreterError, CodeInterpreterOutput, Sandbox};
+#[cfg(feature = "database")]
+pub use database::{DatabaseError, DatabaseTool};
+#[cfg(not(target_arch = "wasm32"))]
+pub use file_system::{FileSystemError, FileSystemOperation, FileSystemTool};
+#[cfg(any(feature = "serpapi", feature = "brave-search", feature = "tavily"))]
+pub use search::{SearchError, SearchProvider, SearchResult, SearchTool};
+pub use webhook::{HttpMethod, WebhookError, WebhookTool};
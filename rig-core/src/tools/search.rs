@@ -0,0 +1,334 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{completion::ToolDefinition, tool::Tool};
+
+/// Number of results returned when the model does not specify it.
+const DEFAULT_NUM_RESULTS: usize = 5;
+
+/// Web search provider used by a [SearchTool]. Each provider requires its own feature.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SearchProvider {
+    /// [SerpApi](https://serpapi.com) Google search (requires the `serpapi` feature)
+    #[cfg(feature = "serpapi")]
+    SerpApi,
+    /// [Brave Search API](https://brave.com/search/api) (requires the `brave-search` feature)
+    #[cfg(feature = "brave-search")]
+    BraveSearch,
+    /// [Tavily](https://tavily.com) search API (requires the `tavily` feature)
+    #[cfg(feature = "tavily")]
+    Tavily,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SearchError {
+    #[error("HttpError: {0}")]
+    HttpError(#[from] reqwest::Error),
+
+    #[error("JsonError: {0}")]
+    JsonError(#[from] serde_json::Error),
+
+    #[error("Search provider returned {status}: {body}")]
+    StatusError {
+        status: reqwest::StatusCode,
+        body: String,
+    },
+}
+
+#[derive(Deserialize)]
+pub struct SearchArgs {
+    query: String,
+    num_results: Option<usize>,
+}
+
+/// A web search result returned to the model.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SearchResult {
+    pub title: String,
+    pub url: String,
+    pub snippet: String,
+}
+
+/// A tool searching the web with one of the supported [SearchProvider]s.
+/// The model provides the `query` (and optionally the number of results), and receives a
+/// JSON array of `{title, url, snippet}` results.
+///
+/// # Example
+/// ```rust
+/// use rig::tools::{SearchProvider, SearchTool};
+///
+/// let search = SearchTool::new(SearchProvider::Tavily, &std::env::var("TAVILY_API_KEY")?);
+///
+/// let agent = openai.agent("gpt-4o")
+///     .preamble("You are a research assistant. Search the web to answer questions.")
+///     .tool(search)
+///     .build();
+/// ```
+#[derive(Clone, Debug)]
+pub struct SearchTool {
+    pub provider: SearchProvider,
+    api_key: String,
+    http_client: reqwest::Client,
+}
+
+impl SearchTool {
+    pub fn new(provider: SearchProvider, api_key: &str) -> Self {
+        Self {
+            provider,
+            api_key: api_key.to_string(),
+            http_client: reqwest::Client::new(),
+        }
+    }
+
+    /// Use your own `reqwest::Client`.
+    pub fn with_custom_client(mut self, client: reqwest::Client) -> Self {
+        self.http_client = client;
+        self
+    }
+
+    fn request(&self, query: &str, num_results: usize) -> reqwest::RequestBuilder {
+        match self.provider {
+            #[cfg(feature = "serpapi")]
+            SearchProvider::SerpApi => self
+                .http_client
+                .get("https://serpapi.com/search.json")
+                .query(&[
+                    ("engine", "google"),
+                    ("q", query),
+                    ("num", &num_results.to_string()),
+                    ("api_key", &self.api_key),
+                ]),
+            #[cfg(feature = "brave-search")]
+            SearchProvider::BraveSearch => self
+                .http_client
+                .get("https://api.search.brave.com/res/v1/web/search")
+                .header("Accept", "application/json")
+                .header("X-Subscription-Token", &self.api_key)
+                .query(&[("q", query), ("count", &num_results.to_string())]),
+            #[cfg(feature = "tavily")]
+            SearchProvider::Tavily => self
+                .http_client
+                .post("https://api.tavily.com/search")
+                .bearer_auth(&self.api_key)
+                .json(&serde_json::json!({
+                    "query": query,
+                    "max_results": num_results,
+                })),
+        }
+    }
+
+    fn parse_results(&self, body: &str) -> Result<Vec<SearchResult>, serde_json::Error> {
+        match self.provider {
+            #[cfg(feature = "serpapi")]
+            SearchProvider::SerpApi => Ok(serde_json::from_str::<serpapi::Response>(body)?.into()),
+            #[cfg(feature = "brave-search")]
+            SearchProvider::BraveSearch => {
+                Ok(serde_json::from_str::<brave::Response>(body)?.into())
+            }
+            #[cfg(feature = "tavily")]
+            SearchProvider::Tavily => Ok(serde_json::from_str::<tavily::Response>(body)?.into()),
+        }
+    }
+}
+
+impl Tool for SearchTool {
+    const NAME: &'static str = "web_search";
+
+    type Error = SearchError;
+    type Args = SearchArgs;
+    type Output = Vec<SearchResult>;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: "Search the web. Returns the title, url and a snippet of each result."
+                .to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "query": {
+                        "type": "string",
+                        "description": "The search query"
+                    },
+                    "num_results": {
+                        "type": "number",
+                        "description": format!(
+                            "The number of results to return (default: {DEFAULT_NUM_RESULTS})"
+                        )
+                    }
+                },
+                "required": ["query"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let num_results = args.num_results.unwrap_or(DEFAULT_NUM_RESULTS);
+
+        let response = self.request(&args.query, num_results).send().await?;
+        let status = response.status();
+        let body = response.text().await?;
+
+        if !status.is_success() {
+            return Err(SearchError::StatusError { status, body });
+        }
+
+        let mut results = self.parse_results(&body)?;
+        results.truncate(num_results);
+
+        Ok(results)
+    }
+}
+
+#[cfg(feature = "serpapi")]
+mod serpapi {
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    pub struct Response {
+        #[serde(default)]
+        organic_results: Vec<OrganicResult>,
+    }
+
+    #[derive(Deserialize)]
+    struct OrganicResult {
+        title: String,
+        link: String,
+        #[serde(default)]
+        snippet: String,
+    }
+
+    impl From<Response> for Vec<super::SearchResult> {
+        fn from(response: Response) -> Self {
+            response
+                .organic_results
+                .into_iter()
+                .map(|result| super::SearchResult {
+                    title: result.title,
+                    url: result.link,
+                    snippet: result.snippet,
+                })
+                .collect()
+        }
+    }
+}
+
+#[cfg(feature = "brave-search")]
+mod brave {
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    pub struct Response {
+        web: Option<WebResults>,
+    }
+
+    #[derive(Deserialize)]
+    struct WebResults {
+        results: Vec<WebResult>,
+    }
+
+    #[derive(Deserialize)]
+    struct WebResult {
+        title: String,
+        url: String,
+        #[serde(default)]
+        description: String,
+    }
+
+    impl From<Response> for Vec<super::SearchResult> {
+        fn from(response: Response) -> Self {
+            response
+                .web
+                .map(|web| web.results)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|result| super::SearchResult {
+                    title: result.title,
+                    url: result.url,
+                    snippet: result.description,
+                })
+                .collect()
+        }
+    }
+}
+
+#[cfg(feature = "tavily")]
+mod tavily {
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    pub struct Response {
+        results: Vec<TavilyResult>,
+    }
+
+    #[derive(Deserialize)]
+    struct TavilyResult {
+        title: String,
+        url: String,
+        #[serde(default)]
+        content: String,
+    }
+
+    impl From<Response> for Vec<super::SearchResult> {
+        fn from(response: Response) -> Self {
+            response
+                .results
+                .into_iter()
+                .map(|result| super::SearchResult {
+                    title: result.title,
+                    url: result.url,
+                    snippet: result.content,
+                })
+                .collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "serpapi")]
+    #[test]
+    fn test_parse_serpapi_results() {
+        let tool = SearchTool::new(SearchProvider::SerpApi, "key");
+        let results = tool
+            .parse_results(
+                r#"{"organic_results": [{"position": 1, "title": "Rust", "link": "https://www.rust-lang.org", "snippet": "A language"}]}"#,
+            )
+            .unwrap();
+        assert_eq!(
+            results,
+            vec![SearchResult {
+                title: "Rust".into(),
+                url: "https://www.rust-lang.org".into(),
+                snippet: "A language".into(),
+            }]
+        );
+    }
+
+    #[cfg(feature = "brave-search")]
+    #[test]
+    fn test_parse_brave_results() {
+        let tool = SearchTool::new(SearchProvider::BraveSearch, "key");
+        let results = tool
+            .parse_results(
+                r#"{"type": "search", "web": {"results": [{"title": "Rust", "url": "https://www.rust-lang.org", "description": "A language"}]}}"#,
+            )
+            .unwrap();
+        assert_eq!(results[0].url, "https://www.rust-lang.org");
+        assert_eq!(results[0].snippet, "A language");
+    }
+
+    #[cfg(feature = "tavily")]
+    #[test]
+    fn test_parse_tavily_results() {
+        let tool = SearchTool::new(SearchProvider::Tavily, "key");
+        let results = tool
+            .parse_results(
+                r#"{"query": "rust", "results": [{"title": "Rust", "url": "https://www.rust-lang.org", "content": "A language", "score": 0.9}]}"#,
+            )
+            .unwrap();
+        assert_eq!(results[0].title, "Rust");
+        assert_eq!(results[0].snippet, "A language");
+    }
+}
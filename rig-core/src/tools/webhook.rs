@@ -0,0 +1,311 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{completion::ToolDefinition, tool::Tool};
+
+/// HTTP method used by a [WebhookTool].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum HttpMethod {
+    Get,
+    #[default]
+    Post,
+    Put,
+    Patch,
+    Delete,
+}
+
+impl From<HttpMethod> for reqwest::Method {
+    fn from(method: HttpMethod) -> Self {
+        match method {
+            HttpMethod::Get => reqwest::Method::GET,
+            HttpMethod::Post => reqwest::Method::POST,
+            HttpMethod::Put => reqwest::Method::PUT,
+            HttpMethod::Patch => reqwest::Method::PATCH,
+            HttpMethod::Delete => reqwest::Method::DELETE,
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum WebhookError {
+    #[error("HttpError: {0}")]
+    HttpError(#[from] reqwest::Error),
+
+    #[error("Webhook returned {status}: {body}")]
+    StatusError {
+        status: reqwest::StatusCode,
+        body: String,
+    },
+}
+
+#[derive(Deserialize)]
+pub struct WebhookArgs {
+    input: String,
+}
+
+/// A tool calling an HTTP endpoint (e.g.: a REST API or a webhook of an automation platform).
+///
+/// The model provides a single `input` string, which replaces every `{input}` placeholder
+/// of the `body_template`. If the template is a JSON document, the placeholders of its strings
+/// are replaced by the escaped input (so that the body stays valid JSON whatever the input),
+/// otherwise the input is inserted as is.
+/// The body of the endpoint's response is returned to the model.
+///
+/// # Example
+/// ```rust
+/// use rig::tools::{HttpMethod, WebhookTool};
+///
+/// let create_ticket = WebhookTool::new(
+///     "create_ticket",
+///     "Create a support ticket. The input is the description of the issue.",
+///     "https://support.example.com/api/tickets",
+/// )
+/// .method(HttpMethod::Post)
+/// .header("Authorization", "Bearer <token>")
+/// .header("Content-Type", "application/json")
+/// .body_template(r#"{"description": "{input}"}"#);
+///
+/// let agent = openai.agent("gpt-4o")
+///     .preamble("You are a support assistant.")
+///     .tool(create_ticket)
+///     .build();
+/// ```
+#[derive(Clone, Debug)]
+pub struct WebhookTool {
+    /// Name of the tool, as seen by the model
+    pub name: String,
+    /// Description of the tool, as seen by the model
+    pub description: String,
+    pub url: String,
+    pub method: HttpMethod,
+    pub headers: HashMap<String, String>,
+    /// Body of the request, in which `{input}` is replaced by the input of the model.
+    /// No body is sent if empty.
+    pub body_template: String,
+    http_client: reqwest::Client,
+}
+
+impl WebhookTool {
+    /// Create a webhook tool sending `POST` requests to `url`, with the input of the
+    /// model as body.
+    pub fn new(name: &str, description: &str, url: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            description: description.to_string(),
+            url: url.to_string(),
+            method: HttpMethod::default(),
+            headers: HashMap::new(),
+            body_template: "{input}".to_string(),
+            http_client: reqwest::Client::new(),
+        }
+    }
+
+    /// Set the HTTP method of the requests.
+    pub fn method(mut self, method: HttpMethod) -> Self {
+        self.method = method;
+        self
+    }
+
+    /// Add a header to the requests.
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.insert(name.to_string(), value.to_string());
+        self
+    }
+
+    /// Set the body template of the requests.
+    pub fn body_template(mut self, body_template: &str) -> Self {
+        self.body_template = body_template.to_string();
+        self
+    }
+
+    /// Use your own `reqwest::Client`.
+    pub fn with_custom_client(mut self, client: reqwest::Client) -> Self {
+        self.http_client = client;
+        self
+    }
+
+    fn render_body(&self, input: &str) -> String {
+        match serde_json::from_str::<Value>(&self.body_template) {
+            Ok(mut template) => {
+                replace_input(&mut template, input);
+                template.to_string()
+            }
+            Err(_) => self.body_template.replace("{input}", input),
+        }
+    }
+}
+
+/// Replace the `{input}` placeholders of the strings of the JSON template `value` by `input`.
+fn replace_input(value: &mut Value, input: &str) {
+    match value {
+        Value::String(string) => *string = string.replace("{input}", input),
+        Value::Array(values) => values
+            .iter_mut()
+            .for_each(|value| replace_input(value, input)),
+        Value::Object(map) => map
+            .values_mut()
+            .for_each(|value| replace_input(value, input)),
+        _ => {}
+    }
+}
+
+impl Tool for WebhookTool {
+    const NAME: &'static str = "webhook";
+
+    type Error = WebhookError;
+    type Args = WebhookArgs;
+    type Output = String;
+
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: self.name.clone(),
+            description: self.description.clone(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "input": {
+                        "type": "string",
+                        "description": "The input sent to the endpoint"
+                    }
+                },
+                "required": ["input"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let mut request = self.http_client.request(self.method.into(), &self.url);
+
+        for (name, value) in &self.headers {
+            request = request.header(name, value);
+        }
+
+        if !self.body_template.is_empty() {
+            request = request.body(self.render_body(&args.input));
+        }
+
+        let response = request.send().await?;
+        let status = response.status();
+        let body = response.text().await?;
+
+        if status.is_success() {
+            Ok(body)
+        } else {
+            Err(WebhookError::StatusError { status, body })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    /// Serve a single request with `status`, echoing its body, and return the URL of the server
+    /// and the handle of the task returning the received request.
+    async fn serve_once(status: &'static str) -> (String, tokio::task::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+
+        let handle = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buffer = [0; 1024];
+            // Read the headers, then the body up to its `Content-Length`
+            let (head, body) = loop {
+                let n = socket.read(&mut buffer).await.unwrap();
+                request.extend_from_slice(&buffer[..n]);
+                let text = String::from_utf8_lossy(&request).to_string();
+                if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                    let length = head
+                        .lines()
+                        .find_map(|line| line.strip_prefix("content-length: "))
+                        .map_or(0, |length| length.parse().unwrap());
+                    if body.len() >= length {
+                        break (head.to_string(), body.to_string());
+                    }
+                }
+            };
+
+            let response = format!(
+                "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+            format!("{head}\r\n\r\n{body}")
+        });
+
+        (url, handle)
+    }
+
+    #[tokio::test]
+    async fn test_webhook_definition() {
+        let tool = WebhookTool::new("notify", "Send a notification", "http://localhost/hook")
+            .body_template(r#"{"text": "{input}", "echo": "{input}"}"#);
+
+        let definition = tool.definition(String::new()).await;
+
+        assert_eq!(tool.name(), "notify");
+        assert_eq!(definition.name, "notify");
+        assert_eq!(definition.parameters["required"][0], "input");
+        assert_eq!(
+            serde_json::from_str::<Value>(&tool.render_body(r#"Say "hi""#)).unwrap(),
+            serde_json::json!({"text": r#"Say "hi""#, "echo": r#"Say "hi""#})
+        );
+
+        let tool = tool.body_template("text={input}");
+        assert_eq!(tool.render_body(r#"Say "hi""#), r#"text=Say "hi""#);
+    }
+
+    #[tokio::test]
+    async fn test_webhook_call() {
+        let (url, handle) = serve_once("200 OK").await;
+        let tool = WebhookTool::new("notify", "Send a notification", &url)
+            .header("X-Token", "secret")
+            .body_template(r#"{"text": "{input}"}"#);
+
+        let input = "Line 1\nSay \"hi\"";
+        let response = tool
+            .call(WebhookArgs {
+                input: input.to_string(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            serde_json::from_str::<Value>(&response).unwrap(),
+            serde_json::json!({ "text": input })
+        );
+
+        let request = handle.await.unwrap();
+        assert!(request.starts_with("POST /hook HTTP/1.1"));
+        assert!(request.contains("x-token: secret"));
+    }
+
+    #[tokio::test]
+    async fn test_webhook_call_status_error() {
+        let (url, handle) = serve_once("500 Internal Server Error").await;
+        let tool = WebhookTool::new("notify", "Send a notification", &url);
+
+        let result = tool
+            .call(WebhookArgs {
+                input: "Hello".to_string(),
+            })
+            .await;
+        assert!(matches!(
+            result,
+            Err(WebhookError::StatusError { status, body })
+                if status == reqwest::StatusCode::INTERNAL_SERVER_ERROR && body == "Hello"
+        ));
+        handle.await.unwrap();
+    }
+}
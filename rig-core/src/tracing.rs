@@ -0,0 +1,185 @@
+//! This module contains the [OtelInstrumented] struct, a [CompletionModel] wrapper that creates a
+//! span for each `completion` and `stream` call, with the attributes of the OpenTelemetry spans
+//! expected by observability backends.
+//!
+//! The spans are regular `tracing` spans, which are exported as OpenTelemetry spans by the
+//! `tracing-opentelemetry` bridge (i.e.: by adding its layer to the `tracing` subscriber).
+//! Each span is a child of the current span (see [tracing::Span::current]), so the completion
+//! spans of an agent are nested in the span of the prompt (and of any span of the application
+//! it was called from), which propagates the trace context across the whole call.
+//!
+//! The span attributes are:
+//! - `provider` and `model_name`: as given to [OtelInstrumented::new]
+//! - `temperature`: the temperature of the request, if set
+//! - `prompt_tokens` and `completion_tokens`: estimated with the [TokenCounter], since the token
+//!   usage reported by providers is not exposed uniformly
+//!
+//! # Example
+//! ```rust
+//! use opentelemetry::trace::TracerProvider;
+//! use rig::{client::CompletionClient, providers::openai, tracing::OtelInstrumented};
+//! use tracing_subscriber::layer::SubscriberExt;
+//!
+//! let tracer = opentelemetry_sdk::trace::SdkTracerProvider::builder().build().tracer("rig");
+//! tracing::subscriber::set_global_default(
+//!     tracing_subscriber::registry().with(tracing_opentelemetry::layer().with_tracer(tracer)),
+//! )?;
+//!
+//! let openai = openai::Client::from_env();
+//! let model = OtelInstrumented::new(
+//!     openai.completion_model(openai::GPT_4O),
+//!     "openai",
+//!     openai::GPT_4O,
+//! );
+//!
+//! let agent = rig::agent::AgentBuilder::new(model).build();
+//! ```
+use futures::StreamExt;
+
+use crate::{
+    completion::{CompletionError, CompletionModel, CompletionRequest, CompletionResponse},
+    message::Message,
+    streaming::{RawStreamingChoice, StreamingCompletionResponse, StreamingResult},
+    tokens::{message_text, TokenCounter},
+};
+
+/// A completion model wrapper creating a span for each request sent to the model.
+#[derive(Clone, Debug)]
+pub struct OtelInstrumented<M: CompletionModel> {
+    model: M,
+    provider: String,
+    model_name: String,
+}
+
+impl<M: CompletionModel> OtelInstrumented<M> {
+    /// Wrap `model`, whose provider (e.g.: `openai`) and name (e.g.: `gpt-4o`) are recorded
+    /// in the spans.
+    pub fn new(model: M, provider: &str, model_name: &str) -> Self {
+        Self {
+            model,
+            provider: provider.to_string(),
+            model_name: model_name.to_string(),
+        }
+    }
+
+    fn span(&self, operation: &str, request: &CompletionRequest) -> tracing::Span {
+        let messages = request
+            .normalized_documents()
+            .into_iter()
+            .chain(request.chat_history.iter().cloned())
+            .collect::<Vec<_>>();
+        let prompt_tokens = TokenCounter::count(
+            &self.model_name,
+            &messages,
+            request.preamble.as_deref().unwrap_or_default(),
+        );
+
+        tracing::info_span!(
+            target: "rig",
+            parent: tracing::Span::current(),
+            "completion",
+            otel.name = %format!("{operation} {}", self.model_name),
+            otel.kind = "client",
+            otel.status_code = tracing::field::Empty,
+            provider = %self.provider,
+            model_name = %self.model_name,
+            temperature = request.temperature,
+            prompt_tokens,
+            completion_tokens = tracing::field::Empty,
+        )
+    }
+}
+
+impl<M> CompletionModel for OtelInstrumented<M>
+where
+    M: CompletionModel,
+    M::StreamingResponse: 'static,
+{
+    type Response = M::Response;
+    type StreamingResponse = M::StreamingResponse;
+
+    async fn completion(
+        &self,
+        request: CompletionRequest,
+    ) -> Result<CompletionResponse<Self::Response>, CompletionError> {
+        let span = self.span("chat", &request);
+
+        let response =
+            tracing::Instrument::instrument(self.model.completion(request), span.clone())
+                .await
+                .inspect_err(|_| {
+                    span.record("otel.status_code", "ERROR");
+                })?;
+
+        let text = message_text(&Message::Assistant {
+            content: response.choice.clone(),
+        });
+        span.record(
+            "completion_tokens",
+            TokenCounter::count_text(&self.model_name, &text),
+        );
+
+        Ok(response)
+    }
+
+    async fn stream(
+        &self,
+        request: CompletionRequest,
+    ) -> Result<StreamingCompletionResponse<Self::StreamingResponse>, CompletionError> {
+        let span = self.span("stream", &request);
+
+        let response = tracing::Instrument::instrument(self.model.stream(request), span.clone())
+            .await
+            .inspect_err(|_| {
+                span.record("otel.status_code", "ERROR");
+            })?;
+
+        // The span is kept open until the stream is dropped, and the completion tokens are
+        // updated with each chunk
+        let model_name = self.model_name.clone();
+        let mut text = String::new();
+        let inner: StreamingResult<Self::StreamingResponse> =
+            Box::pin(response.inner.inspect(move |chunk| match chunk {
+                Ok(RawStreamingChoice::Message(chunk)) => {
+                    text.push_str(chunk);
+                    span.record(
+                        "completion_tokens",
+                        TokenCounter::count_text(&model_name, &text),
+                    );
+                }
+                Err(_) => {
+                    span.record("otel.status_code", "ERROR");
+                }
+                _ => {}
+            }));
+
+        Ok(StreamingCompletionResponse::stream(inner))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::MockCompletionModel;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_otel_instrumented() {
+        let mock = MockCompletionModel::new("Paris");
+        let model = OtelInstrumented::new(mock.clone(), "mock", "mock-model");
+
+        let response =
+            CompletionModel::completion_request(&model, "What is the capital of France?")
+                .temperature(0.5)
+                .send()
+                .await
+                .unwrap();
+
+        assert_eq!(
+            response.choice.first(),
+            crate::message::AssistantContent::text("Paris")
+        );
+        assert_eq!(mock.requests().len(), 1);
+        assert_eq!(mock.requests()[0].temperature, Some(0.5));
+    }
+}
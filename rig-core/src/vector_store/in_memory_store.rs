@@ -175,6 +175,24 @@ impl<D: Serialize + Eq> PartialOrd for RankingItem<'_, D> {
 
 type EmbeddingRanking<'a, D> = BinaryHeap<Reverse<RankingItem<'a, D>>>;
 
+impl InMemoryVectorStore<serde_json::Value> {
+    /// Create a new [InMemoryVectorStore] from precomputed `(id, embedding, payload)` tuples
+    /// (e.g.: embeddings loaded from a file, or hand-written in tests), without calling an
+    /// embedding model.
+    pub fn from_embeddings(
+        embeddings: impl IntoIterator<Item = (impl ToString, Vec<f32>, serde_json::Value)>,
+    ) -> Self {
+        Self::from_documents_with_ids(embeddings.into_iter().map(|(id, vec, payload)| {
+            let id = id.to_string();
+            let embedding = Embedding {
+                document: id.clone(),
+                vec: vec.into_iter().map(f64::from).collect(),
+            };
+            (id, payload, OneOrMany::one(embedding))
+        }))
+    }
+}
+
 impl<D: Serialize> InMemoryVectorStore<D> {
     pub fn index<M: EmbeddingModel>(self, model: M) -> InMemoryVectorIndex<M, D> {
         InMemoryVectorIndex::new(model, self)
@@ -216,6 +234,18 @@ impl<M: EmbeddingModel, D: Serialize> InMemoryVectorIndex<M, D> {
     }
 }
 
+impl<M: EmbeddingModel> InMemoryVectorIndex<M, serde_json::Value> {
+    /// Create a new [InMemoryVectorIndex] from precomputed `(id, embedding, payload)` tuples,
+    /// searched by cosine similarity. `model` is only used to embed the queries, and must produce
+    /// embeddings of the same dimension.
+    pub fn from_embeddings(
+        model: M,
+        embeddings: impl IntoIterator<Item = (impl ToString, Vec<f32>, serde_json::Value)>,
+    ) -> Self {
+        Self::new(model, InMemoryVectorStore::from_embeddings(embeddings))
+    }
+}
+
 impl<M: EmbeddingModel + Sync, D: Serialize + Sync + Send + Eq> VectorStoreIndex
     for InMemoryVectorIndex<M, D>
 {
@@ -502,4 +532,36 @@ mod tests {
             )]
         )
     }
+
+    #[test]
+    fn test_from_embeddings() {
+        let vector_store = InMemoryVectorStore::from_embeddings(vec![
+            (
+                "doc1",
+                vec![0.1, 0.1, 0.5],
+                serde_json::json!({"word": "glarb-garb"}),
+            ),
+            (
+                "doc2",
+                vec![0.7, -0.3, 0.0],
+                serde_json::json!({"word": "marble-marble"}),
+            ),
+        ]);
+
+        let ranking = vector_store.vector_search(
+            &Embedding {
+                document: "glarby-glarble".to_string(),
+                vec: vec![0.0, 0.1, 0.6],
+            },
+            1,
+        );
+
+        assert_eq!(
+            ranking
+                .into_iter()
+                .map(|Reverse(RankingItem(_, id, doc, _))| (id.clone(), doc["word"].clone()))
+                .collect::<Vec<_>>(),
+            vec![("doc1".to_string(), serde_json::json!("glarb-garb"))]
+        );
+    }
 }
@@ -6,6 +6,14 @@ use serde_json::Value;
 use crate::embeddings::EmbeddingError;
 
 pub mod in_memory_store;
+#[cfg(feature = "redis-vector")]
+pub mod redis;
+#[cfg(feature = "bm25")]
+pub mod sparse;
+#[cfg(feature = "weaviate")]
+pub mod weaviate;
+
+pub use in_memory_store as in_memory;
 
 #[derive(Debug, thiserror::Error)]
 pub enum VectorStoreError {
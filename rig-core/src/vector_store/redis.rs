@@ -0,0 +1,308 @@
+//! Redis Stack (RediSearch) implementation of a vector store index.
+//!
+//! Documents are stored as Redis hashes (with the fields `id`, `document` and `embedding`) under
+//! the key prefix of the index, and searched with `FT.SEARCH` KNN or vector range queries.
+//!
+//! Note: requires the `redis-vector` feature.
+//!
+//! # Example
+//! ```rust
+//! use rig::{
+//!     providers::openai,
+//!     vector_store::{redis::{DistanceMetric, RedisVectorIndex}, VectorStoreIndex},
+//! };
+//!
+//! let connection = redis::Client::open("redis://127.0.0.1/")?
+//!     .get_connection_manager()
+//!     .await?;
+//! let model = openai::Client::from_env().embedding_model(openai::TEXT_EMBEDDING_3_SMALL);
+//!
+//! let index = RedisVectorIndex::new(connection, model, "definitions", 1536);
+//! index.create_index(DistanceMetric::Cosine).await?;
+//! index.insert_documents(documents).await?;
+//!
+//! let results = index.top_n::<Definition>("What is a flurbo?", 1).await?;
+//! ```
+use std::collections::HashMap;
+
+use redis::aio::ConnectionManager;
+use serde::{Deserialize, Serialize};
+
+use super::{VectorStoreError, VectorStoreIndex};
+use crate::{
+    embeddings::{Embedding, EmbeddingModel},
+    OneOrMany,
+};
+
+/// Distance metric of the vectors of a Redis index.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DistanceMetric {
+    #[default]
+    Cosine,
+    L2,
+    InnerProduct,
+}
+
+impl DistanceMetric {
+    fn as_str(&self) -> &'static str {
+        match self {
+            DistanceMetric::Cosine => "COSINE",
+            DistanceMetric::L2 => "L2",
+            DistanceMetric::InnerProduct => "IP",
+        }
+    }
+}
+
+fn datastore_error(error: redis::RedisError) -> VectorStoreError {
+    VectorStoreError::DatastoreError(Box::new(error))
+}
+
+/// A vector store index backed by a Redis Stack (i.e.: Redis with the RediSearch module) index.
+///
+/// The score of the results is the distance reported by Redis (i.e.: the lower, the closer).
+pub struct RedisVectorIndex<M: EmbeddingModel> {
+    connection: ConnectionManager,
+    model: M,
+    index_name: String,
+    dims: usize,
+    /// Prefix of the keys of the hashes of the index
+    prefix: String,
+    /// Maximum distance of the results (vector range query), if any
+    radius: Option<f64>,
+}
+
+impl<M: EmbeddingModel> RedisVectorIndex<M> {
+    /// Create a new index named `index_name`, of embeddings of `dims` dimensions.
+    /// The keys of its documents are prefixed by `"{index_name}:"`.
+    pub fn new(connection: ConnectionManager, model: M, index_name: &str, dims: usize) -> Self {
+        Self {
+            connection,
+            model,
+            index_name: index_name.to_string(),
+            dims,
+            prefix: format!("{index_name}:"),
+            radius: None,
+        }
+    }
+
+    /// Set the prefix of the keys of the documents of the index.
+    pub fn prefix(mut self, prefix: &str) -> Self {
+        self.prefix = prefix.to_string();
+        self
+    }
+
+    /// Only return the documents within `radius` of the query (i.e.: use vector range queries
+    /// instead of KNN queries).
+    pub fn radius(mut self, radius: f64) -> Self {
+        self.radius = Some(radius);
+        self
+    }
+
+    /// Create the RediSearch index (`FT.CREATE`), with an HNSW vector field.
+    pub async fn create_index(&self, metric: DistanceMetric) -> Result<(), VectorStoreError> {
+        redis::cmd("FT.CREATE")
+            .arg(&self.index_name)
+            .arg("ON")
+            .arg("HASH")
+            .arg("PREFIX")
+            .arg(1)
+            .arg(&self.prefix)
+            .arg("SCHEMA")
+            .arg("id")
+            .arg("TAG")
+            .arg("embedding")
+            .arg("VECTOR")
+            .arg("HNSW")
+            .arg(6)
+            .arg("TYPE")
+            .arg("FLOAT32")
+            .arg("DIM")
+            .arg(self.dims)
+            .arg("DISTANCE_METRIC")
+            .arg(metric.as_str())
+            .query_async::<()>(&mut self.connection.clone())
+            .await
+            .map_err(datastore_error)
+    }
+
+    /// Insert documents with their ids and embeddings. Each embedding of a document is stored in
+    /// its own hash, along with the (JSON serialized) document.
+    pub async fn insert_documents<Doc: Serialize>(
+        &self,
+        documents: Vec<(String, Doc, OneOrMany<Embedding>)>,
+    ) -> Result<(), VectorStoreError> {
+        let mut pipeline = redis::pipe();
+
+        for (id, document, embeddings) in documents {
+            let document = serde_json::to_string(&document)?;
+
+            for (i, embedding) in embeddings.into_iter().enumerate() {
+                pipeline
+                    .hset_multiple(
+                        format!("{}{id}:{i}", self.prefix),
+                        &[
+                            ("id", id.as_bytes().to_vec()),
+                            ("document", document.as_bytes().to_vec()),
+                            ("embedding", self.to_blob(&embedding.vec)?),
+                        ],
+                    )
+                    .ignore();
+            }
+        }
+
+        pipeline
+            .query_async::<()>(&mut self.connection.clone())
+            .await
+            .map_err(datastore_error)
+    }
+
+    /// Convert an embedding to the binary format of RediSearch vectors.
+    fn to_blob(&self, vec: &[f64]) -> Result<Vec<u8>, VectorStoreError> {
+        if vec.len() != self.dims {
+            return Err(VectorStoreError::DatastoreError(
+                format!(
+                    "Embedding has {} dimensions, expected {}",
+                    vec.len(),
+                    self.dims
+                )
+                .into(),
+            ));
+        }
+
+        Ok(to_blob(vec))
+    }
+
+    /// Search the documents closest to `query`, returning the requested fields of each one
+    /// along with its distance (in the `score` field).
+    async fn search(
+        &self,
+        query: &str,
+        n: usize,
+        fields: &[&str],
+    ) -> Result<Vec<(f64, HashMap<String, String>)>, VectorStoreError> {
+        let embedding = self.model.embed_text(query).await?;
+        let blob = self.to_blob(&embedding.vec)?;
+
+        let mut cmd = redis::cmd("FT.SEARCH");
+        cmd.arg(&self.index_name)
+            .arg(search_query(self.radius))
+            .arg("PARAMS")
+            .arg(4)
+            .arg("vec")
+            .arg(blob);
+        match self.radius {
+            Some(radius) => cmd.arg("radius").arg(radius),
+            None => cmd.arg("n").arg(n),
+        };
+        cmd.arg("SORTBY")
+            .arg("score")
+            .arg("RETURN")
+            .arg(fields.len() + 1)
+            .arg(fields)
+            .arg("score")
+            .arg("LIMIT")
+            .arg(0)
+            .arg(n)
+            .arg("DIALECT")
+            .arg(2);
+
+        // The response is: [total, key_1, [field, value, ...], key_2, [field, value, ...], ...]
+        let response: Vec<redis::Value> = cmd
+            .query_async(&mut self.connection.clone())
+            .await
+            .map_err(datastore_error)?;
+
+        response
+            .get(1..)
+            .unwrap_or_default()
+            .chunks(2)
+            .filter_map(|chunk| chunk.get(1))
+            .map(|fields| {
+                let fields: HashMap<String, String> =
+                    redis::from_redis_value(fields).map_err(datastore_error)?;
+                let score = fields
+                    .get("score")
+                    .and_then(|score| score.parse().ok())
+                    .ok_or_else(|| VectorStoreError::DatastoreError("Missing score".into()))?;
+                Ok((score, fields))
+            })
+            .collect()
+    }
+}
+
+/// Returns the RediSearch query of the `vec` parameter: a KNN query of the `n` nearest
+/// neighbors, or a range query of the vectors within `radius` if any.
+fn search_query(radius: Option<f64>) -> &'static str {
+    match radius {
+        Some(_) => "@embedding:[VECTOR_RANGE $radius $vec]=>{$YIELD_DISTANCE_AS: score}",
+        None => "*=>[KNN $n @embedding $vec AS score]",
+    }
+}
+
+/// Convert an embedding to the binary format of RediSearch vectors (little-endian `FLOAT32`).
+fn to_blob(vec: &[f64]) -> Vec<u8> {
+    vec.iter()
+        .flat_map(|value| (*value as f32).to_le_bytes())
+        .collect()
+}
+
+fn missing_field(field: &str) -> VectorStoreError {
+    VectorStoreError::DatastoreError(format!("Missing field {field} in Redis document").into())
+}
+
+impl<M: EmbeddingModel + Sync + Send> VectorStoreIndex for RedisVectorIndex<M> {
+    async fn top_n<T: for<'a> Deserialize<'a> + Send>(
+        &self,
+        query: &str,
+        n: usize,
+    ) -> Result<Vec<(f64, String, T)>, VectorStoreError> {
+        self.search(query, n, &["id", "document"])
+            .await?
+            .into_iter()
+            .map(|(score, mut fields)| {
+                let id = fields.remove("id").ok_or_else(|| missing_field("id"))?;
+                let document = fields
+                    .remove("document")
+                    .ok_or_else(|| missing_field("document"))?;
+                Ok((score, id, serde_json::from_str(&document)?))
+            })
+            .collect()
+    }
+
+    async fn top_n_ids(
+        &self,
+        query: &str,
+        n: usize,
+    ) -> Result<Vec<(f64, String)>, VectorStoreError> {
+        self.search(query, n, &["id"])
+            .await?
+            .into_iter()
+            .map(|(score, mut fields)| {
+                let id = fields.remove("id").ok_or_else(|| missing_field("id"))?;
+                Ok((score, id))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_blob() {
+        assert_eq!(
+            to_blob(&[1.0, -0.5]),
+            [1.0f32.to_le_bytes(), (-0.5f32).to_le_bytes()].concat()
+        );
+    }
+
+    #[test]
+    fn test_search_query() {
+        assert_eq!(search_query(None), "*=>[KNN $n @embedding $vec AS score]");
+        assert_eq!(
+            search_query(Some(0.2)),
+            "@embedding:[VECTOR_RANGE $radius $vec]=>{$YIELD_DISTANCE_AS: score}"
+        );
+    }
+}
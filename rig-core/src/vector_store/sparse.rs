@@ -0,0 +1,305 @@
+//! Sparse (i.e.: keyword based) retrieval, complementing the dense vector retrieval.
+//!
+//! [BM25Index] ranks documents with the BM25 algorithm, which is well suited to exact-match
+//! lookups (e.g.: names, identifiers or error codes) that embeddings tend to miss. A
+//! [HybridIndex] combines it with a dense index using reciprocal rank fusion.
+//!
+//! Note: requires the `bm25` feature.
+//!
+//! # Example
+//! ```rust
+//! use rig::{
+//!     providers::openai,
+//!     vector_store::{in_memory_store::InMemoryVectorStore, sparse::{BM25Index, HybridIndex}},
+//! };
+//!
+//! let sparse = BM25Index::from_documents(documents.iter().map(|doc| (doc.clone(), doc.text.clone())));
+//! let dense = InMemoryVectorStore::from_documents(embeddings).index(model);
+//!
+//! let agent = openai.agent("gpt-4o")
+//!     .dynamic_context(2, HybridIndex::new(sparse, dense).alpha(0.3))
+//!     .build();
+//! ```
+use std::collections::HashMap;
+
+use bm25::{Document, Language, SearchEngine, SearchEngineBuilder};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::{VectorStoreError, VectorStoreIndex, VectorStoreIndexDyn};
+
+/// A keyword index ranking documents with the BM25 algorithm.
+///
+/// The score of the results is their BM25 score (i.e.: the higher, the more relevant).
+pub struct BM25Index<D: Serialize> {
+    search_engine: SearchEngine<String>,
+    documents: HashMap<String, D>,
+}
+
+impl<D: Serialize> BM25Index<D> {
+    /// Create a new [BM25Index] from documents and their text (i.e.: the indexed keywords).
+    /// Ids are automatically generated have will have the form `"doc{n}"` where `n`
+    /// is the index of the document.
+    pub fn from_documents(documents: impl IntoIterator<Item = (D, String)>) -> Self {
+        Self::from_documents_with_ids(
+            documents
+                .into_iter()
+                .enumerate()
+                .map(|(i, (doc, text))| (format!("doc{i}"), doc, text)),
+        )
+    }
+
+    /// Create a new [BM25Index] from documents and their text, with ids.
+    pub fn from_documents_with_ids(
+        documents: impl IntoIterator<Item = (impl ToString, D, String)>,
+    ) -> Self {
+        let (corpus, documents): (Vec<_>, HashMap<_, _>) = documents
+            .into_iter()
+            .map(|(id, doc, text)| {
+                let id = id.to_string();
+                (Document::new(id.clone(), text), (id, doc))
+            })
+            .unzip();
+
+        Self {
+            search_engine: SearchEngineBuilder::<String>::with_documents(Language::English, corpus)
+                .build(),
+            documents,
+        }
+    }
+
+    /// Returns the ids of the `n` most relevant documents for `query`, with their score.
+    fn search(&self, query: &str, n: usize) -> Vec<(f64, String)> {
+        self.search_engine
+            .search(query, n)
+            .into_iter()
+            .map(|result| (result.score as f64, result.document.id))
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.documents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.documents.is_empty()
+    }
+}
+
+impl<D: Serialize + Send + Sync> VectorStoreIndex for BM25Index<D> {
+    async fn top_n<T: for<'a> Deserialize<'a> + Send>(
+        &self,
+        query: &str,
+        n: usize,
+    ) -> Result<Vec<(f64, String, T)>, VectorStoreError> {
+        self.search(query, n)
+            .into_iter()
+            .map(|(score, id)| {
+                let doc = self
+                    .documents
+                    .get(&id)
+                    .ok_or_else(|| VectorStoreError::MissingIdError(id.clone()))?;
+                let doc = serde_json::from_value(serde_json::to_value(doc)?)?;
+
+                Ok((score, id, doc))
+            })
+            .collect()
+    }
+
+    async fn top_n_ids(
+        &self,
+        query: &str,
+        n: usize,
+    ) -> Result<Vec<(f64, String)>, VectorStoreError> {
+        Ok(self.search(query, n))
+    }
+}
+
+/// Default constant of the reciprocal rank fusion (see [HybridIndex::rrf_k]).
+const DEFAULT_RRF_K: f64 = 60.0;
+
+/// An index combining the results of a [BM25Index] and of a dense index with reciprocal rank
+/// fusion: the score of a document is `alpha / (k + dense_rank) + (1 - alpha) / (k + sparse_rank)`
+/// (a term being 0 when the document is not retrieved by the corresponding index).
+///
+/// Both indexes must use the same ids for the same documents.
+pub struct HybridIndex<D: Serialize> {
+    sparse: BM25Index<D>,
+    dense: Box<dyn VectorStoreIndexDyn>,
+    alpha: f64,
+    rrf_k: f64,
+}
+
+impl<D: Serialize> HybridIndex<D> {
+    /// Create a hybrid index, weighting both indexes equally (i.e.: `alpha` of 0.5).
+    pub fn new(sparse: BM25Index<D>, dense: impl VectorStoreIndexDyn + 'static) -> Self {
+        Self {
+            sparse,
+            dense: Box::new(dense),
+            alpha: 0.5,
+            rrf_k: DEFAULT_RRF_K,
+        }
+    }
+
+    /// Set the weight (between 0 and 1) of the dense index, the sparse index being weighted
+    /// `1 - alpha`.
+    pub fn alpha(mut self, alpha: f64) -> Self {
+        self.alpha = alpha.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Set the constant `k` of the reciprocal rank fusion (60 by default). The greater, the less
+    /// the top ranks dominate.
+    pub fn rrf_k(mut self, k: f64) -> Self {
+        self.rrf_k = k;
+        self
+    }
+
+    /// Fuse the ranked ids of the dense and sparse indexes, returning the `n` best.
+    fn fuse(&self, dense: Vec<String>, sparse: Vec<String>, n: usize) -> Vec<(f64, String)> {
+        let mut scores = HashMap::<String, f64>::new();
+        for (weight, ids) in [(self.alpha, dense), (1.0 - self.alpha, sparse)] {
+            for (rank, id) in ids.into_iter().enumerate() {
+                *scores.entry(id).or_default() += weight / (self.rrf_k + rank as f64 + 1.0);
+            }
+        }
+
+        let mut results = scores
+            .into_iter()
+            .map(|(id, score)| (score, id))
+            .collect::<Vec<_>>();
+        results.sort_by(|(a, _), (b, _)| b.total_cmp(a));
+        results.truncate(n);
+        results
+    }
+}
+
+impl<D: Serialize + Send + Sync> VectorStoreIndex for HybridIndex<D> {
+    async fn top_n<T: for<'a> Deserialize<'a> + Send>(
+        &self,
+        query: &str,
+        n: usize,
+    ) -> Result<Vec<(f64, String, T)>, VectorStoreError> {
+        let mut documents = HashMap::<String, Value>::new();
+
+        let dense = VectorStoreIndexDyn::top_n(self.dense.as_ref(), query, n)
+            .await?
+            .into_iter()
+            .map(|(_, id, doc)| {
+                documents.insert(id.clone(), doc);
+                id
+            })
+            .collect();
+        let sparse = VectorStoreIndex::top_n::<Value>(&self.sparse, query, n)
+            .await?
+            .into_iter()
+            .map(|(_, id, doc)| {
+                documents.entry(id.clone()).or_insert(doc);
+                id
+            })
+            .collect();
+
+        self.fuse(dense, sparse, n)
+            .into_iter()
+            .map(|(score, id)| {
+                let doc = documents
+                    .remove(&id)
+                    .ok_or_else(|| VectorStoreError::MissingIdError(id.clone()))?;
+                Ok((score, id, serde_json::from_value(doc)?))
+            })
+            .collect()
+    }
+
+    async fn top_n_ids(
+        &self,
+        query: &str,
+        n: usize,
+    ) -> Result<Vec<(f64, String)>, VectorStoreError> {
+        let dense = self
+            .dense
+            .top_n_ids(query, n)
+            .await?
+            .into_iter()
+            .map(|(_, id)| id)
+            .collect();
+        let sparse = self
+            .sparse
+            .search(query, n)
+            .into_iter()
+            .map(|(_, id)| id)
+            .collect();
+
+        Ok(self.fuse(dense, sparse, n))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn sparse_index() -> BM25Index<Value> {
+        BM25Index::from_documents_with_ids([
+            (
+                "flurbo",
+                json!({"word": "flurbo"}),
+                "A flurbo is a green alien that lives on cold planets".to_string(),
+            ),
+            (
+                "glarb",
+                json!({"word": "glarb-glarb"}),
+                "A glarb-glarb is an ancient tool used by the ancestors".to_string(),
+            ),
+            (
+                "linglingdong",
+                json!({"word": "linglingdong"}),
+                "A linglingdong is a term used by inhabitants of the far side of the moon"
+                    .to_string(),
+            ),
+        ])
+    }
+
+    #[tokio::test]
+    async fn test_bm25_top_n() {
+        let index = sparse_index();
+
+        let results = VectorStoreIndex::top_n::<Value>(&index, "What is a flurbo?", 1)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].1, "flurbo");
+        assert_eq!(results[0].2, json!({"word": "flurbo"}));
+
+        let ids = VectorStoreIndex::top_n_ids(&index, "ancient tool", 3)
+            .await
+            .unwrap();
+        assert_eq!(ids[0].1, "glarb");
+    }
+
+    #[test]
+    fn test_reciprocal_rank_fusion() {
+        let ids = |ids: &[&str]| ids.iter().map(|id| id.to_string()).collect::<Vec<_>>();
+        let hybrid = HybridIndex::new(sparse_index(), sparse_index());
+
+        let results = hybrid.fuse(ids(&["a", "b"]), ids(&["b", "c"]), 3);
+        assert_eq!(
+            results
+                .iter()
+                .map(|(_, id)| id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["b", "a", "c"]
+        );
+        assert_eq!(results[0].0, 0.5 / 61.0 + 0.5 / 62.0);
+
+        let hybrid = hybrid.alpha(1.0);
+        let results = hybrid.fuse(ids(&["a", "b"]), ids(&["b", "c"]), 2);
+        assert_eq!(
+            results
+                .iter()
+                .map(|(_, id)| id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["a", "b"]
+        );
+    }
+}
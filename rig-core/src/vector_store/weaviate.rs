@@ -0,0 +1,336 @@
+//! Weaviate implementation of a vector store index.
+//!
+//! Objects are searched with the `nearVector` operator of Weaviate's GraphQL API (the query being
+//! embedded with the index's embedding model), or with the `nearText` operator for classes with a
+//! vectorizer module (see [WeaviateIndex::top_n_near_text]).
+//!
+//! Note: requires the `weaviate` feature.
+//!
+//! # Example
+//! ```rust
+//! use rig::{
+//!     providers::openai,
+//!     vector_store::{weaviate::{WeaviateClient, WeaviateIndex}, VectorStoreIndex},
+//! };
+//!
+//! let client = WeaviateClient::new("http://localhost:8080", None);
+//! let model = openai::Client::from_env().embedding_model(openai::TEXT_EMBEDDING_3_SMALL);
+//!
+//! let index = WeaviateIndex::new(client, model, "Definition", "text");
+//! index.insert_documents(documents).await?;
+//!
+//! let results = index.top_n::<Definition>("What is a flurbo?", 1).await?;
+//! ```
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+use super::{VectorStoreError, VectorStoreIndex};
+use crate::{
+    embeddings::{Embedding, EmbeddingModel},
+    OneOrMany,
+};
+
+/// A search result: the distance to the query, the id and the properties of the object.
+type SearchResult = (f64, String, Map<String, Value>);
+
+/// A client of a Weaviate instance.
+#[derive(Clone, Debug)]
+pub struct WeaviateClient {
+    /// Base URL of the instance (e.g.: `http://localhost:8080`)
+    pub url: String,
+    api_key: Option<String>,
+    http_client: reqwest::Client,
+}
+
+impl WeaviateClient {
+    pub fn new(url: &str, api_key: Option<&str>) -> Self {
+        Self {
+            url: url.trim_end_matches('/').to_string(),
+            api_key: api_key.map(str::to_string),
+            http_client: reqwest::Client::new(),
+        }
+    }
+
+    /// Use your own `reqwest::Client`.
+    pub fn with_custom_client(mut self, client: reqwest::Client) -> Self {
+        self.http_client = client;
+        self
+    }
+
+    fn post(&self, path: &str) -> reqwest::RequestBuilder {
+        let request = self.http_client.post(format!("{}{path}", self.url));
+        match &self.api_key {
+            Some(api_key) => request.bearer_auth(api_key),
+            None => request,
+        }
+    }
+
+    /// Send `request` and return its JSON response, failing on non-success statuses.
+    async fn send(&self, request: reqwest::RequestBuilder) -> Result<Value, VectorStoreError> {
+        let response = request.send().await?;
+        let status = response.status();
+
+        if status.is_success() {
+            Ok(response.json().await?)
+        } else {
+            Err(VectorStoreError::ExternalAPIError(
+                status,
+                response.text().await?,
+            ))
+        }
+    }
+}
+
+/// A vector store index backed by a Weaviate class.
+///
+/// The score of the results is the distance reported by Weaviate (i.e.: the lower, the closer),
+/// and their id is the UUID of the Weaviate object.
+pub struct WeaviateIndex<M: EmbeddingModel> {
+    client: WeaviateClient,
+    model: M,
+    class_name: String,
+    /// Property of the objects holding the embedded text
+    text_property: String,
+    /// Properties of the objects returned by `top_n`
+    properties: Vec<String>,
+}
+
+impl<M: EmbeddingModel> WeaviateIndex<M> {
+    /// Create an index of the objects of the class `class_name`, whose text is held by the
+    /// `text_property` property.
+    pub fn new(client: WeaviateClient, model: M, class_name: &str, text_property: &str) -> Self {
+        Self {
+            client,
+            model,
+            class_name: class_name.to_string(),
+            text_property: text_property.to_string(),
+            properties: vec![text_property.to_string()],
+        }
+    }
+
+    /// Set the properties of the objects returned by `top_n` (by default, only the text
+    /// property). The documents are deserialized from these properties.
+    pub fn properties(mut self, properties: &[&str]) -> Self {
+        self.properties = properties
+            .iter()
+            .map(|property| property.to_string())
+            .collect();
+        self
+    }
+
+    /// Insert documents with their embeddings, with the batch API of Weaviate. Each embedding of
+    /// a document is stored in its own object, along with the properties of the document (which
+    /// must serialize to a JSON object). The text of each embedding is stored in the text
+    /// property.
+    pub async fn insert_documents<Doc: Serialize>(
+        &self,
+        documents: Vec<(Doc, OneOrMany<Embedding>)>,
+    ) -> Result<(), VectorStoreError> {
+        let objects = documents
+            .into_iter()
+            .map(|(document, embeddings)| {
+                let properties = serde_json::to_value(document)?;
+                if !properties.is_object() {
+                    return Err(VectorStoreError::DatastoreError(
+                        "Weaviate documents must serialize to JSON objects".into(),
+                    ));
+                }
+
+                Ok(embeddings
+                    .into_iter()
+                    .map(|embedding| {
+                        let mut properties = properties.clone();
+                        properties[&self.text_property] = json!(embedding.document);
+                        json!({
+                            "class": self.class_name,
+                            "properties": properties,
+                            "vector": embedding.vec,
+                        })
+                    })
+                    .collect::<Vec<_>>())
+            })
+            .collect::<Result<Vec<_>, VectorStoreError>>()?
+            .concat();
+
+        let results = self
+            .client
+            .send(
+                self.client
+                    .post("/v1/batch/objects")
+                    .json(&json!({ "objects": objects })),
+            )
+            .await?;
+
+        // The batch API reports the errors of each object in a successful response
+        let errors = results
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|result| result.pointer("/result/errors"))
+            .map(Value::to_string)
+            .collect::<Vec<_>>();
+        if !errors.is_empty() {
+            return Err(VectorStoreError::DatastoreError(errors.join("\n").into()));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the GraphQL query of the `limit` objects nearest to `near`, with `properties`.
+    fn query(&self, near: String, limit: usize, properties: &[String]) -> String {
+        format!(
+            "{{ Get {{ {}({near}, limit: {limit}) {{ {} _additional {{ id distance }} }} }} }}",
+            self.class_name,
+            properties.join(" ")
+        )
+    }
+
+    /// Run the GraphQL `query` and return the distance, id and properties of each object.
+    async fn search(&self, query: String) -> Result<Vec<SearchResult>, VectorStoreError> {
+        let response = self
+            .client
+            .send(
+                self.client
+                    .post("/v1/graphql")
+                    .json(&json!({ "query": query })),
+            )
+            .await?;
+
+        parse_results(&self.class_name, response)
+    }
+
+    async fn near_vector(
+        &self,
+        query: &str,
+        n: usize,
+        properties: &[String],
+    ) -> Result<Vec<SearchResult>, VectorStoreError> {
+        let embedding = self.model.embed_text(query).await?;
+        let near = format!("nearVector: {{ vector: {} }}", json!(embedding.vec));
+
+        self.search(self.query(near, n, properties)).await
+    }
+
+    /// Same as `top_n`, but the query is vectorized by Weaviate (`nearText` operator) instead of
+    /// the embedding model of the index. Requires the class to have a vectorizer module.
+    pub async fn top_n_near_text<T: for<'a> Deserialize<'a>>(
+        &self,
+        query: &str,
+        n: usize,
+    ) -> Result<Vec<(f64, String, T)>, VectorStoreError> {
+        let near = format!("nearText: {{ concepts: [{}] }}", json!(query));
+
+        self.search(self.query(near, n, &self.properties))
+            .await?
+            .into_iter()
+            .map(|(distance, id, properties)| {
+                Ok((distance, id, serde_json::from_value(properties.into())?))
+            })
+            .collect()
+    }
+}
+
+/// Parse the GraphQL response of a search on `class_name`.
+fn parse_results(
+    class_name: &str,
+    mut response: Value,
+) -> Result<Vec<SearchResult>, VectorStoreError> {
+    // GraphQL errors are returned with a 200 status
+    if let Some(errors) = response.get("errors").filter(|errors| !errors.is_null()) {
+        return Err(VectorStoreError::DatastoreError(errors.to_string().into()));
+    }
+
+    let objects = match response
+        .pointer_mut(&format!("/data/Get/{class_name}"))
+        .map(Value::take)
+    {
+        Some(Value::Array(objects)) => objects,
+        _ => return Ok(vec![]),
+    };
+
+    objects
+        .into_iter()
+        .map(|object| {
+            let Value::Object(mut properties) = object else {
+                return Err(VectorStoreError::DatastoreError(
+                    "Weaviate object is not a JSON object".into(),
+                ));
+            };
+            let additional = properties.remove("_additional").unwrap_or_default();
+
+            let id = additional["id"]
+                .as_str()
+                .ok_or_else(|| VectorStoreError::MissingIdError(additional.to_string()))?
+                .to_string();
+            let distance = additional["distance"].as_f64().unwrap_or_default();
+
+            Ok((distance, id, properties))
+        })
+        .collect()
+}
+
+impl<M: EmbeddingModel + Sync + Send> VectorStoreIndex for WeaviateIndex<M> {
+    async fn top_n<T: for<'a> Deserialize<'a> + Send>(
+        &self,
+        query: &str,
+        n: usize,
+    ) -> Result<Vec<(f64, String, T)>, VectorStoreError> {
+        self.near_vector(query, n, &self.properties)
+            .await?
+            .into_iter()
+            .map(|(distance, id, properties)| {
+                Ok((distance, id, serde_json::from_value(properties.into())?))
+            })
+            .collect()
+    }
+
+    async fn top_n_ids(
+        &self,
+        query: &str,
+        n: usize,
+    ) -> Result<Vec<(f64, String)>, VectorStoreError> {
+        Ok(self
+            .near_vector(query, n, &[])
+            .await?
+            .into_iter()
+            .map(|(distance, id, _)| (distance, id))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_results() {
+        let response = json!({
+            "data": {
+                "Get": {
+                    "Definition": [
+                        {"text": "A flurbo is a green alien", "_additional": {"id": "5f8a1b", "distance": 0.12}},
+                        {"text": "A glarb-glarb is an ancient tool", "_additional": {"id": "9c2d4e", "distance": 0.48}}
+                    ]
+                }
+            }
+        });
+
+        let results = parse_results("Definition", response).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].0, 0.12);
+        assert_eq!(results[0].1, "5f8a1b");
+        assert_eq!(
+            Value::from(results[0].2.clone()),
+            json!({"text": "A flurbo is a green alien"})
+        );
+
+        let response = json!({
+            "data": {"Get": {"Definition": null}},
+            "errors": [{"message": "Cannot query field \"Definition\""}]
+        });
+        assert!(matches!(
+            parse_results("Definition", response),
+            Err(VectorStoreError::DatastoreError(_))
+        ));
+    }
+}
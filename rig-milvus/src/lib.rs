@@ -1,3 +1,9 @@
+//! Milvus vector store implementation for the rig framework.
+//!
+//! The store is a client of the [RESTful API (v2)](https://milvus.io/api-reference/restful/v2.5.x/About.md)
+//! of Milvus rather than of its gRPC API (e.g.: with `milvus-sdk-rust`): the crate was already
+//! built on it, it supports the same operations (collection creation, insertion, filtered search
+//! and deletion), and it does not add the protobuf toolchain to the build of users.
 use reqwest::StatusCode;
 use rig::{
     embeddings::{Embedding, EmbeddingModel},
@@ -5,6 +11,135 @@ use rig::{
     Embed, OneOrMany,
 };
 use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+/// Default port of the Milvus server
+pub const DEFAULT_MILVUS_PORT: u16 = 19530;
+
+/// Connection configuration for a [MilvusVectorStore].
+///
+/// # Example
+/// ```
+/// use rig_milvus::MilvusConfig;
+///
+/// let config = MilvusConfig::new("localhost")
+///     .port(19530)
+///     .tls(true)
+///     .credentials("root", "Milvus")
+///     .database_name("default")
+///     .collection_name("documents");
+/// ```
+#[derive(Clone, Debug)]
+pub struct MilvusConfig {
+    host: String,
+    port: u16,
+    tls: bool,
+    token: Option<String>,
+    database_name: String,
+    collection_name: String,
+}
+
+impl MilvusConfig {
+    /// Create a new configuration for the Milvus instance at `host`.
+    /// Defaults to port [DEFAULT_MILVUS_PORT], no TLS, no credentials and the `default` database.
+    pub fn new(host: impl Into<String>) -> Self {
+        Self {
+            host: host.into(),
+            port: DEFAULT_MILVUS_PORT,
+            tls: false,
+            token: None,
+            database_name: "default".to_string(),
+            collection_name: String::new(),
+        }
+    }
+
+    /// Set the port of the Milvus instance.
+    pub fn port(mut self, port: u16) -> Self {
+        self.port = port;
+        self
+    }
+
+    /// Enable/disable TLS (i.e.: `https`) when connecting to the Milvus instance.
+    pub fn tls(mut self, tls: bool) -> Self {
+        self.tls = tls;
+        self
+    }
+
+    /// Authenticate with a username and password.
+    pub fn credentials(mut self, username: &str, password: &str) -> Self {
+        self.token = Some(format!("{username}:{password}"));
+        self
+    }
+
+    /// Authenticate with an API key (e.g.: Zilliz Cloud).
+    pub fn token(mut self, token: &str) -> Self {
+        self.token = Some(token.to_string());
+        self
+    }
+
+    /// Set the name of the database.
+    pub fn database_name(mut self, database_name: &str) -> Self {
+        self.database_name = database_name.to_string();
+        self
+    }
+
+    /// Set the name of the collection.
+    pub fn collection_name(mut self, collection_name: &str) -> Self {
+        self.collection_name = collection_name.to_string();
+        self
+    }
+
+    /// The base URL of the Milvus instance, derived from the host, port and TLS settings.
+    pub fn base_url(&self) -> String {
+        let scheme = if self.tls { "https" } else { "http" };
+        format!("{scheme}://{}:{}", self.host, self.port)
+    }
+}
+
+/// Type of the index built on the `embedding` field when creating a collection.
+#[derive(Clone, Debug)]
+pub enum IndexType {
+    /// Hierarchical Navigable Small World graph index
+    Hnsw {
+        /// Maximum number of outgoing connections of each node
+        m: usize,
+        /// Number of candidate neighbors considered while building the graph
+        ef_construction: usize,
+    },
+    /// Inverted file index with uncompressed vectors
+    IvfFlat {
+        /// Number of clusters
+        nlist: usize,
+    },
+}
+
+impl Default for IndexType {
+    fn default() -> Self {
+        IndexType::Hnsw {
+            m: 16,
+            ef_construction: 200,
+        }
+    }
+}
+
+impl IndexType {
+    fn index_params(&self) -> serde_json::Value {
+        let (index_type, params) = match self {
+            IndexType::Hnsw { m, ef_construction } => {
+                ("HNSW", json!({ "M": m, "efConstruction": ef_construction }))
+            }
+            IndexType::IvfFlat { nlist } => ("IVF_FLAT", json!({ "nlist": nlist })),
+        };
+
+        json!({
+            "fieldName": "embedding",
+            "indexName": "embedding_index",
+            "metricType": "COSINE",
+            "indexType": index_type,
+            "params": params,
+        })
+    }
+}
 
 /// Represents a vector store implementation using Milvus - <https://milvus.io/> as the backend.
 pub struct MilvusVectorStore<M> {
@@ -18,6 +153,7 @@ pub struct MilvusVectorStore<M> {
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct CreateRecord {
     document: String,
     embedded_text: String,
@@ -41,6 +177,16 @@ struct SearchRequest<'a> {
     anns_field: &'a str,
     limit: usize,
     output_fields: Vec<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    filter: Option<&'a str>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DeleteRequest<'a> {
+    collection_name: &'a str,
+    db_name: &'a str,
+    filter: String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -92,6 +238,18 @@ impl<M: EmbeddingModel> MilvusVectorStore<M> {
         }
     }
 
+    /// Creates a new instance of `MilvusVectorStore` from a [MilvusConfig].
+    pub fn from_config(model: M, config: MilvusConfig) -> Self {
+        Self {
+            model,
+            base_url: config.base_url(),
+            client: reqwest::Client::new(),
+            database_name: config.database_name,
+            collection_name: config.collection_name,
+            token: config.token,
+        }
+    }
+
     /// Forms the auth token for Milvus from your username and password. Required if using a Milvus instance that requires authentication.
     pub fn auth(mut self, username: String, password: String) -> Self {
         let str = format!("{username}:{password}");
@@ -100,6 +258,154 @@ impl<M: EmbeddingModel> MilvusVectorStore<M> {
         self
     }
 
+    /// Creates a POST request to the given Milvus REST API path, with authentication if configured.
+    fn post(&self, path: &str) -> reqwest::RequestBuilder {
+        let url = format!("{base_url}{path}", base_url = self.base_url);
+
+        let client = self.client.post(url);
+        match self.token {
+            Some(ref token) => client.header("Authentication", format!("Bearer {token}")),
+            None => client,
+        }
+    }
+
+    /// Sends a request body to the given Milvus REST API path and returns the response.
+    async fn send<T: Serialize>(
+        &self,
+        path: &str,
+        body: &T,
+    ) -> Result<reqwest::Response, VectorStoreError> {
+        let body = serde_json::to_string(body)?;
+
+        let res = self.post(path).body(body).send().await?;
+
+        if res.status() != StatusCode::OK {
+            let status = res.status();
+            let text = res.text().await?;
+
+            return Err(VectorStoreError::ExternalAPIError(status, text));
+        }
+
+        Ok(res)
+    }
+
+    /// Creates a collection compliant with `rig-milvus` with an index on the `embedding` field
+    /// using cosine similarity. IDs are generated automatically by Milvus on insertion.
+    ///
+    /// # Arguments
+    /// * `dims` - The number of dimensions of the embeddings (e.g.: 1536 for `text-embedding-3-small`)
+    /// * `index_type` - The type of index to build on the `embedding` field
+    pub async fn create_collection(
+        &self,
+        dims: usize,
+        index_type: IndexType,
+    ) -> Result<(), VectorStoreError> {
+        let body = json!({
+            "collectionName": self.collection_name,
+            "dbName": self.database_name,
+            "schema": {
+                "autoId": true,
+                "enabledDynamicField": false,
+                "fields": [
+                    {
+                        "fieldName": "id",
+                        "dataType": "Int64",
+                        "isPrimary": true
+                    },
+                    {
+                        "fieldName": "embedding",
+                        "dataType": "FloatVector",
+                        "elementTypeParams": {
+                            "dim": dims.to_string()
+                        }
+                    },
+                    {
+                        "fieldName": "document",
+                        "dataType": "JSON"
+                    },
+                    {
+                        "fieldName": "embeddedText",
+                        "dataType": "VarChar",
+                        "elementTypeParams": {
+                            "max_length": 65535
+                        }
+                    }
+                ]
+            },
+            "indexParams": [index_type.index_params()]
+        });
+
+        self.send("/v2/vectordb/collections/create", &body).await?;
+
+        Ok(())
+    }
+
+    /// Deletes the entities with the given IDs (as returned by `top_n` and `top_n_ids`).
+    pub async fn delete(&self, ids: &[String]) -> Result<(), VectorStoreError> {
+        let ids = ids
+            .iter()
+            .map(|id| {
+                id.parse::<i64>()
+                    .map_err(|e| VectorStoreError::DatastoreError(Box::new(e)))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let body = DeleteRequest {
+            collection_name: &self.collection_name,
+            db_name: &self.database_name,
+            filter: format!(
+                "id in [{}]",
+                ids.iter()
+                    .map(|id| id.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        };
+
+        self.send("/v2/vectordb/entities/delete", &body).await?;
+
+        Ok(())
+    }
+
+    /// Search for the top `n` nearest neighbors to the given query, only considering the entities
+    /// matching the given [filter expression](https://milvus.io/docs/boolean.md)
+    /// (e.g.: `document["category"] == "fruit"`).
+    /// Returns a vector of tuples containing the score, ID, and payload of the nearest neighbors.
+    pub async fn top_n_with_filter<T: for<'a> Deserialize<'a> + Send>(
+        &self,
+        query: &str,
+        n: usize,
+        filter: &str,
+    ) -> Result<Vec<(f64, String, T)>, VectorStoreError> {
+        self.search(query, n, Some(filter)).await
+    }
+
+    async fn search<T: for<'a> Deserialize<'a> + Send>(
+        &self,
+        query: &str,
+        n: usize,
+        filter: Option<&str>,
+    ) -> Result<Vec<(f64, String, T)>, VectorStoreError> {
+        let embedding = self.model.embed_text(query).await?;
+
+        let mut body = self.create_search_request(embedding.vec, n);
+        body.filter = filter;
+
+        let json: SearchResult<T> = self
+            .send("/v2/vectordb/entities/search", &body)
+            .await?
+            .json()
+            .await?;
+
+        let res = json
+            .data
+            .into_iter()
+            .map(|x| (x.distance, x.id.to_string(), x.document))
+            .collect();
+
+        Ok(res)
+    }
+
     /// Creates a Milvus insertion request.
     fn create_insert_request(&self, data: Vec<CreateRecord>) -> InsertRequest {
         InsertRequest {
@@ -118,6 +424,7 @@ impl<M: EmbeddingModel> MilvusVectorStore<M> {
             anns_field: "embedding",
             limit,
             output_fields: vec!["id", "distance", "document", "embeddedText"],
+            filter: None,
         }
     }
 
@@ -130,6 +437,7 @@ impl<M: EmbeddingModel> MilvusVectorStore<M> {
             anns_field: "embedding",
             limit,
             output_fields: vec!["id", "distance"],
+            filter: None,
         }
     }
 
@@ -138,11 +446,6 @@ impl<M: EmbeddingModel> MilvusVectorStore<M> {
         &self,
         documents: Vec<(Doc, OneOrMany<Embedding>)>,
     ) -> Result<(), VectorStoreError> {
-        let url = format!(
-            "{base_url}/v2/vectordb/entities/insert",
-            base_url = self.base_url
-        );
-
         let data = documents
             .into_iter()
             .map(|(document, embeddings)| {
@@ -169,23 +472,10 @@ impl<M: EmbeddingModel> MilvusVectorStore<M> {
             .flatten()
             .collect::<Vec<CreateRecord>>();
 
-        let mut client = self.client.post(url);
-        if let Some(ref token) = self.token {
-            client = client.header("Authentication", format!("Bearer {token}"));
-        }
-
         let insert_request = self.create_insert_request(data);
 
-        let body = serde_json::to_string(&insert_request).unwrap();
-
-        let res = client.body(body).send().await?;
-
-        if res.status() != StatusCode::OK {
-            let status = res.status();
-            let text = res.text().await?;
-
-            return Err(VectorStoreError::ExternalAPIError(status, text));
-        }
+        self.send("/v2/vectordb/entities/insert", &insert_request)
+            .await?;
 
         Ok(())
     }
@@ -199,80 +489,193 @@ impl<M: EmbeddingModel> VectorStoreIndex for MilvusVectorStore<M> {
         query: &str,
         n: usize,
     ) -> Result<Vec<(f64, String, T)>, VectorStoreError> {
-        let embedding = self.model.embed_text(query).await?;
-        let url = format!(
-            "{base_url}/v2/vectordb/entities/search",
-            base_url = self.base_url
-        );
-
-        let body = self.create_search_request(embedding.vec, n);
-
-        let mut client = self.client.post(url);
-        if let Some(ref token) = self.token {
-            client = client.header("Authentication", format!("Bearer {token}"));
-        }
-
-        let body = serde_json::to_string(&body)?;
-
-        let res = client.body(body).send().await?;
+        self.search(query, n, None).await
+    }
 
-        if res.status() != StatusCode::OK {
-            let status = res.status();
-            let text = res.text().await?;
+    /// Search for the top `n` nearest neighbors to the given query within the Milvus vector store.
+    /// Returns a vector of tuples containing the score and ID of the nearest neighbors.
+    async fn top_n_ids(
+        &self,
+        query: &str,
+        n: usize,
+    ) -> Result<Vec<(f64, String)>, VectorStoreError> {
+        let embedding = self.model.embed_text(query).await?;
 
-            return Err(VectorStoreError::ExternalAPIError(status, text));
-        }
+        let body = self.create_search_request_id_only(embedding.vec, n);
 
-        let json: SearchResult<T> = res.json().await?;
+        let json: SearchResultOnlyId = self
+            .send("/v2/vectordb/entities/search", &body)
+            .await?
+            .json()
+            .await?;
 
         let res = json
             .data
             .into_iter()
-            .map(|x| (x.distance, x.id.to_string(), x.document))
+            .map(|x| (x.distance, x.id.to_string()))
             .collect();
 
         Ok(res)
     }
+}
 
-    /// Search for the top `n` nearest neighbors to the given query within the Milvus vector store.
-    /// Returns a vector of tuples containing the score and ID of the nearest neighbors.
-    async fn top_n_ids(
-        &self,
-        query: &str,
-        n: usize,
-    ) -> Result<Vec<(f64, String)>, VectorStoreError> {
-        let embedding = self.model.embed_text(query).await?;
-        let url = format!(
-            "{base_url}/v2/vectordb/entities/search",
-            base_url = self.base_url
-        );
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rig::embeddings::EmbeddingError;
+    use serde_json::json;
 
-        let body = self.create_search_request_id_only(embedding.vec, n);
+    #[derive(Clone)]
+    struct MockEmbeddingModel;
+
+    impl EmbeddingModel for MockEmbeddingModel {
+        const MAX_DOCUMENTS: usize = 1;
 
-        let mut client = self.client.post(url);
-        if let Some(ref token) = self.token {
-            client = client.header("Authentication", format!("Bearer {token}"));
+        fn ndims(&self) -> usize {
+            2
         }
 
-        let body = serde_json::to_string(&body)?;
+        async fn embed_texts(
+            &self,
+            texts: impl IntoIterator<Item = String> + Send,
+        ) -> Result<Vec<Embedding>, EmbeddingError> {
+            Ok(texts
+                .into_iter()
+                .map(|document| Embedding {
+                    document,
+                    vec: vec![0.5, 0.5],
+                })
+                .collect())
+        }
+    }
 
-        let res = client.body(body).send().await?;
+    fn store() -> MilvusVectorStore<MockEmbeddingModel> {
+        MilvusVectorStore::from_config(
+            MockEmbeddingModel,
+            MilvusConfig::new("localhost")
+                .credentials("root", "Milvus")
+                .collection_name("documents"),
+        )
+    }
 
-        if res.status() != StatusCode::OK {
-            let status = res.status();
-            let text = res.text().await?;
+    #[test]
+    fn test_config() {
+        let config = MilvusConfig::new("milvus.example.com").port(443).tls(true);
+        assert_eq!(config.base_url(), "https://milvus.example.com:443");
+        assert_eq!(
+            MilvusConfig::new("localhost").base_url(),
+            "http://localhost:19530"
+        );
 
-            return Err(VectorStoreError::ExternalAPIError(status, text));
-        }
+        let request = store()
+            .post("/v2/vectordb/entities/search")
+            .build()
+            .unwrap();
+        assert_eq!(
+            request.url().as_str(),
+            "http://localhost:19530/v2/vectordb/entities/search"
+        );
+        assert_eq!(request.headers()["Authentication"], "Bearer root:Milvus");
+    }
 
-        let json: SearchResultOnlyId = res.json().await?;
+    #[test]
+    fn test_index_params() {
+        assert_eq!(
+            IndexType::default().index_params(),
+            json!({
+                "fieldName": "embedding",
+                "indexName": "embedding_index",
+                "metricType": "COSINE",
+                "indexType": "HNSW",
+                "params": {"M": 16, "efConstruction": 200},
+            })
+        );
+        assert_eq!(
+            IndexType::IvfFlat { nlist: 128 }.index_params()["params"],
+            json!({"nlist": 128})
+        );
+    }
 
-        let res = json
-            .data
-            .into_iter()
-            .map(|x| (x.distance, x.id.to_string()))
-            .collect();
+    #[test]
+    fn test_serialize_insert_request() {
+        let store = store();
+        let request = store.create_insert_request(vec![CreateRecord {
+            document: "{\"id\":\"doc0\"}".to_string(),
+            embedded_text: "Hello".to_string(),
+            embedding: vec![0.5, 0.5],
+        }]);
+
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            json!({
+                "data": [{
+                    "document": "{\"id\":\"doc0\"}",
+                    "embeddedText": "Hello",
+                    "embedding": [0.5, 0.5],
+                }],
+                "collectionName": "documents",
+                "dbName": "default",
+            })
+        );
+    }
 
-        Ok(res)
+    #[test]
+    fn test_serialize_search_request() {
+        let store = store();
+        let mut request = store.create_search_request(vec![0.5, 0.5], 3);
+
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            json!({
+                "collectionName": "documents",
+                "dbName": "default",
+                "data": [0.5, 0.5],
+                "annsField": "embedding",
+                "limit": 3,
+                "outputFields": ["id", "distance", "document", "embeddedText"],
+            })
+        );
+
+        request.filter = Some("document[\"category\"] == \"fruit\"");
+        assert_eq!(
+            serde_json::to_value(&request).unwrap()["filter"],
+            "document[\"category\"] == \"fruit\""
+        );
+    }
+
+    #[test]
+    fn test_serialize_delete_request() {
+        let request = DeleteRequest {
+            collection_name: "documents",
+            db_name: "default",
+            filter: "id in [1, 2]".to_string(),
+        };
+
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            json!({
+                "collectionName": "documents",
+                "dbName": "default",
+                "filter": "id in [1, 2]",
+            })
+        );
+    }
+
+    #[test]
+    fn test_deserialize_search_result() {
+        let result: SearchResult<serde_json::Value> = serde_json::from_value(json!({
+            "code": 0,
+            "data": [{
+                "id": 42,
+                "distance": 0.9,
+                "document": {"id": "doc0"},
+                "embeddedText": "Hello",
+            }],
+        }))
+        .unwrap();
+
+        assert_eq!(result.data[0].id, 42);
+        assert_eq!(result.data[0].document, json!({"id": "doc0"}));
+        assert_eq!(result.data[0].embedded_text, "Hello");
     }
 }
@@ -173,6 +173,32 @@ impl<M: EmbeddingModel, C: Send + Sync> MongoDbVectorIndex<M, C> {
             search_params,
         })
     }
+
+    /// Create a new `MongoDbVectorIndex` searching the embeddings of the `vector_field` field
+    /// with the (Atlas Vector Search) index `index_name`, using default search params.
+    ///
+    /// Unlike [MongoDbVectorIndex::new], the index is not looked up, so its existence (and
+    /// whether it is queryable) is only checked when searching.
+    pub fn from_vector_field(
+        collection: mongodb::Collection<C>,
+        index_name: &str,
+        vector_field: &str,
+        model: M,
+    ) -> Self {
+        Self {
+            collection,
+            model,
+            index_name: index_name.to_string(),
+            embedded_field: vector_field.to_string(),
+            search_params: SearchParams::new(),
+        }
+    }
+
+    /// Sets the search params of the index.
+    pub fn search_params(mut self, search_params: SearchParams) -> Self {
+        self.search_params = search_params;
+        self
+    }
 }
 
 /// See [MongoDB Vector Search](`https://www.mongodb.com/docs/atlas/atlas-vector-search/vector-search-stage/`) for more information
@@ -88,6 +88,37 @@ impl<Model: EmbeddingModel> PostgresVectorStore<Model> {
         Self::new(model, pg_pool, None, PgVectorDistanceFunction::Cosine)
     }
 
+    /// Creates the pgvector extension and the documents table (with the dimensions of the
+    /// embedding model) if they don't exist, so that the store can be used without running
+    /// migrations first.
+    /// Note: The table is created without index, which is fine for small datasets. For larger
+    /// ones, an index matching the distance function should be created (see the README).
+    pub async fn create_table_if_not_exists(&self) -> Result<(), VectorStoreError> {
+        sqlx::query("CREATE EXTENSION IF NOT EXISTS vector")
+            .execute(&self.pg_pool)
+            .await
+            .map_err(|e| VectorStoreError::DatastoreError(Box::new(e)))?;
+
+        sqlx::query(
+            format!(
+                "CREATE TABLE IF NOT EXISTS {} ( \
+                  id uuid DEFAULT gen_random_uuid(), \
+                  document jsonb NOT NULL, \
+                  embedded_text text NOT NULL, \
+                  embedding vector({}) \
+                )",
+                self.documents_table,
+                self.model.ndims()
+            )
+            .as_str(),
+        )
+        .execute(&self.pg_pool)
+        .await
+        .map_err(|e| VectorStoreError::DatastoreError(Box::new(e)))?;
+
+        Ok(())
+    }
+
     fn search_query_full(&self) -> String {
         self.search_query(true)
     }
@@ -1,6 +1,7 @@
 use qdrant_client::{
     qdrant::{
-        point_id::PointIdOptions, PointId, PointStruct, Query, QueryPoints, UpsertPointsBuilder,
+        point_id::PointIdOptions, PointId, PointStruct, Query, QueryPoints, QueryPointsBuilder,
+        UpsertPointsBuilder,
     },
     Payload, Qdrant,
 };
@@ -38,6 +39,20 @@ impl<M: EmbeddingModel> QdrantVectorStore<M> {
         }
     }
 
+    /// Creates a new instance of `QdrantVectorStore` searching the given collection with the
+    /// default search parameters (i.e.: nearest neighbors of the query embedding, with payloads).
+    ///
+    /// # Arguments
+    /// * `client` - Qdrant client instance
+    /// * `model` - Embedding model instance
+    /// * `collection_name` - Name of the collection to search
+    pub fn from_collection(client: Qdrant, model: M, collection_name: &str) -> Self {
+        let query_params = QueryPointsBuilder::new(collection_name)
+            .with_payload(true)
+            .build();
+        Self::new(client, model, query_params)
+    }
+
     pub fn client(&self) -> &Qdrant {
         &self.client
     }
@@ -167,3 +182,26 @@ impl<M: EmbeddingModel + std::marker::Sync + Send> VectorStoreIndex for QdrantVe
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use qdrant_client::qdrant::{Query, VectorInput};
+    use rig::{client::EmbeddingsClient, providers::openai};
+
+    use super::*;
+
+    #[test]
+    fn test_from_collection() {
+        let client = Qdrant::from_url("http://localhost:6334").build().unwrap();
+        let model = openai::Client::new("TEST").embedding_model(openai::TEXT_EMBEDDING_ADA_002);
+        let vector_store = QdrantVectorStore::from_collection(client, model, "words");
+
+        let query = Query::new_nearest(VectorInput::new_dense(vec![0.1, 0.2]));
+        let params = vector_store.prepare_query_params(Some(query.clone()), 3);
+
+        assert_eq!(params.collection_name, "words");
+        assert_eq!(params.query, Some(query));
+        assert_eq!(params.limit, Some(3));
+        assert!(params.with_payload.is_some());
+    }
+}